@@ -0,0 +1,70 @@
+use std::{
+  env,
+  path::Path,
+  process::Command,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+fn git(args: &[&str]) -> Option<String> {
+  let output = Command::new("git").args(args).output().ok()?;
+
+  if !output.status.success() {
+    return None;
+  }
+
+  let stdout = String::from_utf8(output.stdout).ok()?;
+  let stdout = stdout.trim();
+
+  if stdout.is_empty() {
+    None
+  } else {
+    Some(stdout.into())
+  }
+}
+
+fn main() {
+  println!("cargo:rerun-if-changed=build.rs");
+  println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+  // Only consult git when building from a checkout. Packages from crates.io
+  // have no `.git`, and may be unpacked inside some unrelated repository.
+  let git_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(".git");
+
+  if git_dir.exists() {
+    for path in ["HEAD", "index", "refs"] {
+      println!("cargo:rerun-if-changed={}", git_dir.join(path).display());
+    }
+
+    if let Some(commit) = git(&["rev-parse", "HEAD"]) {
+      println!("cargo:rustc-env=ORD_GIT_COMMIT={commit}");
+    }
+
+    if let Some(describe) = git(&["describe", "--tags", "--always", "--dirty"]) {
+      println!("cargo:rustc-env=ORD_GIT_DESCRIBE={describe}");
+    }
+  }
+
+  let timestamp = env::var("SOURCE_DATE_EPOCH")
+    .ok()
+    .and_then(|epoch| epoch.parse::<u64>().ok())
+    .unwrap_or_else(|| {
+      SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+    });
+
+  println!("cargo:rustc-env=ORD_BUILD_TIMESTAMP={timestamp}");
+
+  let mut features = env::vars()
+    .filter_map(|(key, _)| {
+      key
+        .strip_prefix("CARGO_FEATURE_")
+        .map(|feature| feature.to_lowercase().replace('_', "-"))
+    })
+    .collect::<Vec<String>>();
+
+  features.sort();
+
+  println!("cargo:rustc-env=ORD_FEATURES={}", features.join(","));
+}
@@ -1,16 +1,46 @@
-use super::*;
+use {
+  super::*,
+  clap::{CommandFactory, ErrorKind},
+};
 
 #[derive(Debug, Parser)]
-#[clap(version)]
+#[clap(version, disable_version_flag = true)]
 pub(crate) struct Arguments {
   #[clap(flatten)]
   pub(crate) options: Options,
+  #[clap(long, short = 'V', help = "Print version.")]
+  version: bool,
+  #[clap(
+    long,
+    requires = "version",
+    help = "With `--version`, also print commit, build date, features, and index schema version."
+  )]
+  verbose: bool,
   #[clap(subcommand)]
-  pub(crate) subcommand: Subcommand,
+  pub(crate) subcommand: Option<Subcommand>,
 }
 
 impl Arguments {
   pub(crate) fn run(self) -> Result {
-    self.subcommand.run(self.options)
+    let version = Version::current();
+
+    if self.version {
+      if self.verbose {
+        print!("{}", version.long());
+      } else {
+        println!("ord {}", version.version);
+      }
+      return Ok(());
+    }
+
+    let subcommand = match self.subcommand {
+      Some(subcommand) => subcommand,
+      None => Self::command()
+        .error(ErrorKind::MissingSubcommand, "a subcommand is required")
+        .exit(),
+    };
+
+    self.options.schedule()?.install()?;
+    subcommand.run(Settings::new(self.options))
   }
 }
@@ -7,26 +7,54 @@ pub(crate) enum Blocktime {
 }
 
 impl Blocktime {
-  fn timestamp(self) -> i64 {
+  /// Number of recent blocks used to extrapolate the time of future blocks.
+  pub(crate) const SAMPLE_BLOCKS: u64 = DIFFCHANGE_INTERVAL;
+
+  const TARGET_INTERVAL: i64 = 10 * 60;
+
+  pub(crate) fn timestamp(self) -> i64 {
     match self {
       Self::Confirmed(timestamp) | Self::Expected(timestamp) => timestamp,
     }
   }
+
+  /// Expected time of `height`, given the heights and timestamps of the
+  /// first and last blocks of a recent sample.
+  pub(crate) fn extrapolate(start: (Height, i64), end: (Height, i64), height: Height) -> Self {
+    Self::Expected(
+      end.1
+        + Self::average_interval(start, end)
+          * i64::try_from(height.n().saturating_sub(end.0.n())).unwrap(),
+    )
+  }
+
+  /// Block timestamps are only loosely ordered, so fall back to the target
+  /// interval when there are too few blocks or the average isn't positive.
+  fn average_interval(start: (Height, i64), end: (Height, i64)) -> i64 {
+    let blocks = end.0.n().saturating_sub(start.0.n());
+
+    if blocks == 0 {
+      return Self::TARGET_INTERVAL;
+    }
+
+    let interval = (end.1 - start.1) / i64::try_from(blocks).unwrap();
+
+    if interval > 0 {
+      interval
+    } else {
+      Self::TARGET_INTERVAL
+    }
+  }
 }
 
 impl Display for Blocktime {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    write!(
-      f,
-      "{}",
-      NaiveDateTime::from_timestamp_opt(self.timestamp(), 0).unwrap()
-    )?;
-
-    if let Self::Expected(_) = self {
-      write!(f, " (expected)")?;
-    }
+    let datetime = NaiveDateTime::from_timestamp_opt(self.timestamp(), 0).unwrap();
 
-    Ok(())
+    match self {
+      Self::Confirmed(_) => write!(f, "{datetime}"),
+      Self::Expected(_) => write!(f, "expected circa {}", datetime.date()),
+    }
   }
 }
 
@@ -38,8 +66,49 @@ mod tests {
   fn display() {
     assert_eq!(Blocktime::Confirmed(0).to_string(), "1970-01-01 00:00:00");
     assert_eq!(
-      Blocktime::Expected(0).to_string(),
-      "1970-01-01 00:00:00 (expected)"
+      Blocktime::Expected(2035000000).to_string(),
+      "expected circa 2034-06-27"
+    );
+  }
+
+  #[test]
+  fn average_interval() {
+    assert_eq!(
+      Blocktime::average_interval((Height(0), 0), (Height(10), 6000)),
+      600
+    );
+    assert_eq!(
+      Blocktime::average_interval((Height(0), 0), (Height(10), 3000)),
+      300
+    );
+    assert_eq!(
+      Blocktime::average_interval((Height(5), 100), (Height(5), 100)),
+      600
+    );
+    assert_eq!(
+      Blocktime::average_interval((Height(0), 100), (Height(10), 0)),
+      600
+    );
+  }
+
+  #[test]
+  fn extrapolate() {
+    assert_eq!(
+      Blocktime::extrapolate(
+        (Height(0), 1000),
+        (Height(2016), 1000 + 2016 * 500),
+        Height(2026)
+      )
+      .timestamp(),
+      1000 + 2026 * 500
+    );
+    assert_eq!(
+      Blocktime::extrapolate((Height(100), 0), (Height(100), 0), Height(110)).timestamp(),
+      6000
     );
+    assert!(matches!(
+      Blocktime::extrapolate((Height(0), 0), (Height(10), 0), Height(10)),
+      Blocktime::Expected(0)
+    ));
   }
 }
@@ -9,6 +9,7 @@ pub(crate) enum Chain {
   Testnet,
   Signet,
   Regtest,
+  Custom,
 }
 
 impl Chain {
@@ -18,6 +19,7 @@ impl Chain {
       Self::Testnet => bitcoin::Network::Testnet,
       Self::Signet => bitcoin::Network::Signet,
       Self::Regtest => bitcoin::Network::Regtest,
+      Self::Custom => bitcoin::Network::Signet,
     }
   }
 
@@ -25,7 +27,7 @@ impl Chain {
     match self {
       Self::Mainnet => 8332,
       Self::Regtest => 18443,
-      Self::Signet => 38332,
+      Self::Signet | Self::Custom => 38332,
       Self::Testnet => 18332,
     }
   }
@@ -33,7 +35,7 @@ impl Chain {
   pub(crate) fn inscription_content_size_limit(self) -> Option<usize> {
     match self {
       Self::Mainnet | Self::Regtest => None,
-      Self::Testnet | Self::Signet => Some(1024),
+      Self::Testnet | Self::Signet | Self::Custom => Some(1024),
     }
   }
 
@@ -54,6 +56,7 @@ impl Chain {
       Self::Testnet => data_dir.join("testnet3"),
       Self::Signet => data_dir.join("signet"),
       Self::Regtest => data_dir.join("regtest"),
+      Self::Custom => data_dir.join("custom"),
     }
   }
 }
@@ -68,6 +71,7 @@ impl Display for Chain {
         Self::Regtest => "regtest",
         Self::Signet => "signet",
         Self::Testnet => "testnet",
+        Self::Custom => "custom",
       }
     )
   }
@@ -0,0 +1,91 @@
+use super::*;
+
+/// Identifies the collection an inscription belongs to, taken from the
+/// `collection` field of its metadata. Slugs are 1 to 64 ASCII letters,
+/// digits, and hyphens, with letters lowercased, so `Rare-Pepes` and
+/// `rare-pepes` name the same collection.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub(crate) struct CollectionSlug(String);
+
+impl CollectionSlug {
+  pub(crate) const MAX_LEN: usize = 64;
+
+  /// The slug named by `metadata`, if it is a JSON object whose `collection`
+  /// field is a valid slug.
+  pub(crate) fn from_metadata(metadata: &serde_json::Value) -> Option<Self> {
+    metadata.get("collection")?.as_str()?.parse().ok()
+  }
+
+  pub(crate) fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl FromStr for CollectionSlug {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if !(1..=Self::MAX_LEN).contains(&s.len())
+      || !s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+      bail!(
+        "invalid collection slug `{s}`: expected 1 to {} ASCII letters, digits, and hyphens",
+        Self::MAX_LEN
+      );
+    }
+
+    Ok(Self(s.to_ascii_lowercase()))
+  }
+}
+
+impl Display for CollectionSlug {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "Rare-Pepes-2"
+        .parse::<CollectionSlug>()
+        .unwrap()
+        .to_string(),
+      "rare-pepes-2"
+    );
+    assert_eq!(
+      "a".repeat(64).parse::<CollectionSlug>().unwrap().as_str(),
+      "a".repeat(64)
+    );
+
+    for invalid in ["", "rare pepes", "rare_pepes", "pépé", &"a".repeat(65)] {
+      assert_eq!(
+        invalid.parse::<CollectionSlug>().unwrap_err().to_string(),
+        format!(
+          "invalid collection slug `{invalid}`: expected 1 to 64 ASCII letters, digits, and hyphens"
+        ),
+      );
+    }
+  }
+
+  #[test]
+  fn from_metadata() {
+    let slug = |metadata: serde_json::Value| {
+      CollectionSlug::from_metadata(&metadata).map(|slug| slug.to_string())
+    };
+
+    assert_eq!(
+      slug(serde_json::json!({"collection": "Pepes", "name": "Pepe #1"})),
+      Some("pepes".into())
+    );
+    assert_eq!(slug(serde_json::json!({"name": "Pepe #1"})), None);
+    assert_eq!(slug(serde_json::json!({"collection": 1})), None);
+    assert_eq!(slug(serde_json::json!({"collection": "rare pepes"})), None);
+    assert_eq!(slug(serde_json::json!(["collection", "pepes"])), None);
+    assert_eq!(slug(serde_json::json!("pepes")), None);
+  }
+}
@@ -0,0 +1,7 @@
+use super::*;
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Config {
+  pub(crate) schedule: Option<Schedule>,
+}
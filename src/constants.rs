@@ -0,0 +1,39 @@
+//! Protocol constants. Everything else derives from these, so that the CLI,
+//! index, and server agree on a single definition of each.
+//!
+//! These describe Bitcoin's subsidy schedule, which every chain uses unless
+//! it is overridden in the config file with `--chain custom`. Under an
+//! override, [`Sat::supply`](crate::Sat::supply) and the epoch and height
+//! arithmetic follow the override, and `SUBSIDY_HALVING_INTERVAL` and `SUPPLY`
+//! no longer describe the chain.
+
+pub use bitcoin::blockdata::constants::COIN_VALUE;
+
+/// Number of blocks between difficulty adjustments.
+pub const DIFFCHANGE_INTERVAL: u64 = bitcoin::blockdata::constants::DIFFCHANGE_INTERVAL as u64;
+
+/// Number of blocks between subsidy halvings.
+pub const SUBSIDY_HALVING_INTERVAL: u64 =
+  bitcoin::blockdata::constants::SUBSIDY_HALVING_INTERVAL as u64;
+
+/// Number of epochs in a cycle, after which a halving coincides with a
+/// difficulty adjustment.
+pub const CYCLE_EPOCHS: u64 = 6;
+
+/// Total number of sats that will ever be mined under Bitcoin's subsidy
+/// schedule.
+pub const SUPPLY: u64 = 2099999997690000;
+
+const fn total_subsidy() -> u64 {
+  let mut total = 0;
+  let mut epoch = 0;
+  while (50 * COIN_VALUE) >> epoch > 0 {
+    total += ((50 * COIN_VALUE) >> epoch) * SUBSIDY_HALVING_INTERVAL;
+    epoch += 1;
+  }
+  total
+}
+
+const _: () = assert!(SUPPLY == total_subsidy());
+
+const _: () = assert!(SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS % DIFFCHANGE_INTERVAL == 0);
@@ -2,4 +2,10 @@
 pub(crate) enum Content<'a> {
   Text(&'a str),
   Png(&'a [u8]),
+  /// Content which declares itself as passive media, but looks like
+  /// something that a browser might execute.
+  Spoofed {
+    declared: &'a str,
+    sniffed: &'static str,
+  },
 }
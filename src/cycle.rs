@@ -0,0 +1,132 @@
+use super::*;
+
+/// A cycle is the period of six halvings between conjunctions, when the
+/// halving and the difficulty adjustment coincide.
+#[derive(Copy, Clone, Debug, Display, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Cycle(pub(crate) u64);
+
+impl Cycle {
+  /// The cycle numbered `n`.
+  #[cfg_attr(not(feature = "library"), allow(dead_code))]
+  pub fn new(n: u64) -> Self {
+    Self(n)
+  }
+
+  /// The cycle number.
+  #[cfg_attr(not(feature = "library"), allow(dead_code))]
+  pub fn n(self) -> u64 {
+    self.0
+  }
+
+  /// The first epoch of this cycle, or `None` if it would overflow.
+  pub fn checked_epoch(self) -> Option<Epoch> {
+    Some(Epoch(self.0.checked_mul(CYCLE_EPOCHS)?))
+  }
+}
+
+impl PartialEq<u64> for Cycle {
+  fn eq(&self, other: &u64) -> bool {
+    self.0 == *other
+  }
+}
+
+impl From<Epoch> for Cycle {
+  fn from(epoch: Epoch) -> Self {
+    Self(epoch.0 / CYCLE_EPOCHS)
+  }
+}
+
+impl From<Height> for Cycle {
+  fn from(height: Height) -> Self {
+    Epoch::from(height).into()
+  }
+}
+
+impl From<Sat> for Cycle {
+  fn from(sat: Sat) -> Self {
+    Epoch::from(sat).into()
+  }
+}
+
+impl From<Cycle> for Epoch {
+  fn from(cycle: Cycle) -> Self {
+    Self(cycle.0 * CYCLE_EPOCHS)
+  }
+}
+
+impl From<Cycle> for Height {
+  fn from(cycle: Cycle) -> Self {
+    Epoch::from(cycle).starting_height()
+  }
+}
+
+impl From<Cycle> for Sat {
+  fn from(cycle: Cycle) -> Self {
+    Epoch::from(cycle).starting_sat()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn into_epoch() {
+    assert_eq!(Epoch::from(Cycle(0)), 0);
+    assert_eq!(Epoch::from(Cycle(1)), 6);
+    assert_eq!(Epoch::from(Cycle(5)), 30);
+  }
+
+  #[test]
+  fn checked_epoch() {
+    assert_eq!(Cycle(1).checked_epoch(), Some(Epoch(6)));
+    assert_eq!(Cycle(u64::MAX).checked_epoch(), None);
+  }
+
+  #[test]
+  fn into_height() {
+    assert_eq!(Height::from(Cycle(0)), 0);
+    assert_eq!(Height::from(Cycle(1)), 1260000);
+    assert_eq!(Height::from(Cycle(5)), 6300000);
+  }
+
+  #[test]
+  fn into_sat() {
+    assert_eq!(Sat::from(Cycle(0)), 0);
+    assert_eq!(Sat::from(Cycle(1)), 2067187500000000);
+    assert_eq!(Sat::from(Cycle(1)).degree().to_string(), "1°0′0″0‴");
+    assert_eq!(Sat::from(Cycle(6)), Sat::supply());
+  }
+
+  #[test]
+  fn from_epoch() {
+    assert_eq!(Cycle::from(Epoch(0)), 0);
+    assert_eq!(Cycle::from(Epoch(5)), 0);
+    assert_eq!(Cycle::from(Epoch(6)), 1);
+    assert_eq!(Cycle::from(Epoch(32)), 5);
+    assert_eq!(Cycle::from(Epoch(33)), 5);
+  }
+
+  #[test]
+  fn from_height() {
+    assert_eq!(Cycle::from(Height(0)), 0);
+    assert_eq!(Cycle::from(Height(1259999)), 0);
+    assert_eq!(Cycle::from(Height(1260000)), 1);
+    assert_eq!(Cycle::from(Height(6929999)), 5);
+  }
+
+  #[test]
+  fn from_sat() {
+    assert_eq!(Cycle::from(Sat(0)), 0);
+    assert_eq!(Cycle::from(Sat(2067187500000000 - 1)), 0);
+    assert_eq!(Cycle::from(Sat(2067187500000000)), 1);
+    assert_eq!(Cycle::from(Sat::last()), 5);
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(serde_json::to_string(&Cycle(3)).unwrap(), "3");
+    assert_eq!(serde_json::from_str::<Cycle>("3").unwrap(), Cycle(3));
+  }
+}
@@ -1,7 +1,8 @@
 use super::*;
 
+/// A sat's position in decimal notation. See `Sat::decimal`.
 #[derive(PartialEq, Debug)]
-pub(crate) struct Decimal {
+pub struct Decimal {
   height: Height,
   offset: u64,
 }
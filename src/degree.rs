@@ -1,7 +1,8 @@
 use super::*;
 
+/// A sat's position in degree notation. See `Sat::degree`.
 #[derive(PartialEq, Debug)]
-pub(crate) struct Degree {
+pub struct Degree {
   pub(crate) hour: u64,
   pub(crate) minute: u64,
   pub(crate) second: u64,
@@ -21,9 +22,10 @@ impl Display for Degree {
 impl From<Sat> for Degree {
   fn from(sat: Sat) -> Self {
     let height = sat.height().n();
+    let halving_interval = Schedule::current().halving_interval;
     Degree {
-      hour: height / (CYCLE_EPOCHS * SUBSIDY_HALVING_INTERVAL),
-      minute: height % SUBSIDY_HALVING_INTERVAL,
+      hour: height / (CYCLE_EPOCHS * halving_interval),
+      minute: height % halving_interval,
       second: height % DIFFCHANGE_INTERVAL,
       third: sat.third(),
     }
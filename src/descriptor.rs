@@ -0,0 +1,472 @@
+use {
+  super::*,
+  bitcoin::{
+    secp256k1::{Parity, Secp256k1, Verification, XOnlyPublicKey},
+    util::bip32::{ChildNumber, DerivationPath, ExtendedPubKey},
+    Network, PrivateKey, PublicKey,
+  },
+};
+
+/// A watch-only output descriptor. Only single-key `pkh`, `wpkh`,
+/// `sh(wpkh)`, and key-path-only `tr` descriptors are supported, and keys
+/// must be public.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Descriptor {
+  body: String,
+  key: Key,
+  kind: Kind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+  Pkh,
+  ShWpkh,
+  Tr,
+  Wpkh,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Key {
+  Single(PublicKey),
+  Extended {
+    xpub: ExtendedPubKey,
+    path: Vec<ChildNumber>,
+    wildcard: bool,
+  },
+}
+
+impl Descriptor {
+  /// Whether the descriptor ends in `/*`, and so describes a sequence of
+  /// scripts rather than just one.
+  pub(crate) fn is_ranged(&self) -> bool {
+    matches!(self.key, Key::Extended { wildcard: true, .. })
+  }
+
+  /// The network of the descriptor's extended key, if it has one. Testnet,
+  /// signet, and regtest keys are indistinguishable, and all report
+  /// `Network::Testnet`.
+  pub(crate) fn network(&self) -> Option<Network> {
+    match self.key {
+      Key::Single(_) => None,
+      Key::Extended { xpub, .. } => Some(xpub.network),
+    }
+  }
+
+  /// The script at `index`, which is ignored unless the descriptor is
+  /// ranged.
+  pub(crate) fn script_pubkey<C: Verification>(
+    &self,
+    secp: &Secp256k1<C>,
+    index: u32,
+  ) -> Result<Script> {
+    let public_key = match &self.key {
+      Key::Single(public_key) => *public_key,
+      Key::Extended {
+        xpub,
+        path,
+        wildcard,
+      } => {
+        let mut path = path.clone();
+        if *wildcard {
+          path.push(ChildNumber::from_normal_idx(index)?);
+        }
+        PublicKey::new(xpub.derive_pub(secp, &path)?.public_key)
+      }
+    };
+
+    let wpubkey_hash = || {
+      public_key
+        .wpubkey_hash()
+        .ok_or_else(|| anyhow!("segwit descriptors require compressed keys"))
+    };
+
+    Ok(match self.kind {
+      Kind::Pkh => Script::new_p2pkh(&public_key.pubkey_hash()),
+      Kind::ShWpkh => Script::new_p2sh(&Script::new_v0_p2wpkh(&wpubkey_hash()?).script_hash()),
+      Kind::Tr => Script::new_v1_p2tr(secp, public_key.inner.x_only_public_key().0, None),
+      Kind::Wpkh => Script::new_v0_p2wpkh(&wpubkey_hash()?),
+    })
+  }
+
+  /// The BIP 380 checksum of `body`, the part of a descriptor before `#`.
+  fn checksum(body: &str) -> Result<String> {
+    const INPUT_CHARSET: &str =
+      "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+    const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+    fn polymod(c: u64, value: u64) -> u64 {
+      let c0 = c >> 35;
+      let mut c = ((c & 0x7ffffffff) << 5) ^ value;
+      for (i, generator) in [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+      ]
+      .into_iter()
+      .enumerate()
+      {
+        if c0 & (1 << i) != 0 {
+          c ^= generator;
+        }
+      }
+      c
+    }
+
+    let mut c = 1;
+    let mut class = 0;
+    let mut class_count = 0;
+
+    for (i, character) in body.chars().enumerate() {
+      let position = u64::try_from(
+        INPUT_CHARSET
+          .find(character)
+          .ok_or_else(|| sat::invalid_character(character, i, " in descriptor"))?,
+      )
+      .unwrap();
+      c = polymod(c, position & 31);
+      class = class * 3 + (position >> 5);
+      class_count += 1;
+      if class_count == 3 {
+        c = polymod(c, class);
+        class = 0;
+        class_count = 0;
+      }
+    }
+
+    if class_count > 0 {
+      c = polymod(c, class);
+    }
+
+    for _ in 0..8 {
+      c = polymod(c, 0);
+    }
+
+    c ^= 1;
+
+    Ok(
+      (0..8)
+        .map(|i| char::from(CHECKSUM_CHARSET[usize::try_from((c >> (5 * (7 - i))) & 31).unwrap()]))
+        .collect(),
+    )
+  }
+}
+
+impl FromStr for Descriptor {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let body = match s.split_once('#') {
+      Some((body, checksum)) => {
+        let expected = Self::checksum(body)?;
+        if checksum != expected {
+          bail!("descriptor checksum `{checksum}` does not match `{expected}`");
+        }
+        body
+      }
+      None => {
+        Self::checksum(s)?;
+        s
+      }
+    };
+
+    let unwrap = |prefix: &str, suffix: &str| body.strip_prefix(prefix)?.strip_suffix(suffix);
+
+    let (kind, key) = if let Some(key) = unwrap("sh(wpkh(", "))") {
+      (Kind::ShWpkh, key)
+    } else if let Some(key) = unwrap("wpkh(", ")") {
+      (Kind::Wpkh, key)
+    } else if let Some(key) = unwrap("pkh(", ")") {
+      (Kind::Pkh, key)
+    } else if let Some(key) = unwrap("tr(", ")") {
+      if key.contains(',') {
+        bail!("taproot descriptors with script paths are not supported: {body}");
+      }
+      (Kind::Tr, key)
+    } else {
+      bail!(
+        "only `pkh`, `wpkh`, `sh(wpkh)`, and `tr` descriptors with a single key are supported: {body}"
+      );
+    };
+
+    Ok(Self {
+      body: body.into(),
+      key: Self::parse_key(key, kind)?,
+      kind,
+    })
+  }
+}
+
+impl Descriptor {
+  fn parse_key(s: &str, kind: Kind) -> Result<Key> {
+    let s = match s.strip_prefix('[') {
+      Some(rest) => {
+        let (origin, key) = rest
+          .split_once(']')
+          .ok_or_else(|| anyhow!("unclosed key origin in descriptor: [{rest}"))?;
+
+        let (fingerprint, path) = origin.split_once('/').unwrap_or((origin, ""));
+
+        if fingerprint.len() != 8 || hex::decode(fingerprint).is_err() {
+          bail!("invalid key origin fingerprint in descriptor: {fingerprint}");
+        }
+
+        if !path.is_empty() {
+          format!("m/{path}")
+            .parse::<DerivationPath>()
+            .with_context(|| format!("invalid key origin path in descriptor: {path}"))?;
+        }
+
+        key
+      }
+      None => s,
+    };
+
+    let mut components = s.split('/');
+    let key = components.next().unwrap_or_default();
+
+    if key.starts_with("xprv") || key.starts_with("tprv") || PrivateKey::from_wif(key).is_ok() {
+      bail!("descriptors with private keys are not supported");
+    }
+
+    if let Ok(xpub) = key.parse::<ExtendedPubKey>() {
+      let mut path = Vec::new();
+      let mut wildcard = false;
+
+      for component in components {
+        if wildcard {
+          bail!("`*` must be the last component of a descriptor key path");
+        }
+
+        match component {
+          "*" => wildcard = true,
+          "*'" | "*h" => bail!("hardened wildcards cannot be derived from a public key"),
+          _ => {
+            let child = component
+              .parse::<ChildNumber>()
+              .with_context(|| format!("invalid descriptor key path component: {component}"))?;
+            if child.is_hardened() {
+              bail!(
+                "hardened key path component `{component}` cannot be derived from a public key"
+              );
+            }
+            path.push(child);
+          }
+        }
+      }
+
+      return Ok(Key::Extended {
+        xpub,
+        path,
+        wildcard,
+      });
+    }
+
+    if components.next().is_some() {
+      bail!("only extended keys may be followed by a derivation path: {s}");
+    }
+
+    if kind == Kind::Tr && key.len() == 64 {
+      return Ok(Key::Single(PublicKey::new(
+        key
+          .parse::<XOnlyPublicKey>()
+          .with_context(|| format!("invalid key in descriptor: {key}"))?
+          .public_key(Parity::Even),
+      )));
+    }
+
+    Ok(Key::Single(key.parse().with_context(|| {
+      format!("invalid key in descriptor: {key}")
+    })?))
+  }
+}
+
+impl Display for Descriptor {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}#{}", self.body, Self::checksum(&self.body).unwrap())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{secp256k1::SecretKey, util::bip32::ExtendedPrivKey},
+  };
+
+  const XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+
+  fn script(descriptor: &str, index: u32) -> Script {
+    descriptor
+      .parse::<Descriptor>()
+      .unwrap()
+      .script_pubkey(&Secp256k1::verification_only(), index)
+      .unwrap()
+  }
+
+  fn address(descriptor: &str, index: u32) -> String {
+    Address::from_script(&script(descriptor, index), Network::Bitcoin)
+      .unwrap()
+      .to_string()
+  }
+
+  #[test]
+  fn checksum() {
+    assert_eq!(Descriptor::checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+  }
+
+  #[test]
+  fn checksum_is_verified() {
+    let body = "pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)";
+    let checksum = Descriptor::checksum(body).unwrap();
+
+    assert_eq!(
+      format!("{body}#{checksum}")
+        .parse::<Descriptor>()
+        .unwrap()
+        .to_string(),
+      format!("{body}#{checksum}"),
+    );
+
+    assert_eq!(
+      format!("{body}#qqqqqqqq")
+        .parse::<Descriptor>()
+        .unwrap_err()
+        .to_string(),
+      format!("descriptor checksum `qqqqqqqq` does not match `{checksum}`"),
+    );
+  }
+
+  #[test]
+  fn display_adds_checksum() {
+    let body = format!("tr({XPUB}/0/*)");
+    assert_eq!(
+      body.parse::<Descriptor>().unwrap().to_string(),
+      format!("{body}#{}", Descriptor::checksum(&body).unwrap()),
+    );
+  }
+
+  #[test]
+  fn bip84_addresses() {
+    let xpub = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+    assert_eq!(
+      address(&format!("wpkh({xpub}/0/*)"), 0),
+      "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+    assert_eq!(
+      address(&format!("wpkh({xpub}/0/*)"), 1),
+      "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g"
+    );
+    assert_eq!(
+      address(&format!("wpkh({xpub}/1/*)"), 0),
+      "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"
+    );
+  }
+
+  #[test]
+  fn bip86_addresses() {
+    assert_eq!(
+      address(&format!("tr([73c5da0a/86'/0'/0']{XPUB}/0/*)"), 0),
+      "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+    assert_eq!(
+      address(&format!("tr({XPUB}/0/*)"), 1),
+      "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh"
+    );
+  }
+
+  #[test]
+  fn single_keys() {
+    let key = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+
+    assert_eq!(
+      script(&format!("pkh({key})"), 0),
+      script(&format!("pkh({key})"), 1),
+    );
+    assert!(script(&format!("wpkh({key})"), 0).is_v0_p2wpkh());
+    assert!(script(&format!("sh(wpkh({key}))"), 0).is_p2sh());
+    assert_eq!(
+      script(&format!("tr({key})"), 0),
+      script(&format!("tr({})", &key[2..]), 0)
+    );
+    assert!(!format!("wpkh({key})")
+      .parse::<Descriptor>()
+      .unwrap()
+      .is_ranged());
+    assert!(format!("wpkh({XPUB}/*)")
+      .parse::<Descriptor>()
+      .unwrap()
+      .is_ranged());
+  }
+
+  #[test]
+  fn private_keys_are_rejected() {
+    let xprv = ExtendedPrivKey::new_master(Network::Bitcoin, &[0; 32]).unwrap();
+    let tprv = ExtendedPrivKey::new_master(Network::Testnet, &[0; 32]).unwrap();
+    let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+
+    for descriptor in [
+      format!("wpkh({xprv}/0/*)"),
+      format!("tr([73c5da0a/86'/1'/0']{tprv}/0/*)"),
+      format!(
+        "pkh({})",
+        PrivateKey::new(secret_key, Network::Bitcoin).to_wif()
+      ),
+      format!(
+        "tr({})",
+        PrivateKey::new(secret_key, Network::Regtest).to_wif()
+      ),
+    ] {
+      assert_eq!(
+        descriptor.parse::<Descriptor>().unwrap_err().to_string(),
+        "descriptors with private keys are not supported",
+        "{descriptor}"
+      );
+    }
+  }
+
+  #[test]
+  fn unsupported_descriptors_are_rejected() {
+    for (descriptor, error) in [
+      (
+        format!("wsh(pk({XPUB}))"),
+        format!("only `pkh`, `wpkh`, `sh(wpkh)`, and `tr` descriptors with a single key are supported: wsh(pk({XPUB}))"),
+      ),
+      (
+        format!("tr({XPUB},pk({XPUB}))"),
+        format!("taproot descriptors with script paths are not supported: tr({XPUB},pk({XPUB}))"),
+      ),
+      (
+        format!("wpkh({XPUB}/0h/*)"),
+        "hardened key path component `0h` cannot be derived from a public key".into(),
+      ),
+      (
+        format!("wpkh({XPUB}/*')"),
+        "hardened wildcards cannot be derived from a public key".into(),
+      ),
+      (
+        format!("wpkh({XPUB}/*/0)"),
+        "`*` must be the last component of a descriptor key path".into(),
+      ),
+      (
+        format!("wpkh([73c5da0/84'/0'/0']{XPUB}/*)"),
+        "invalid key origin fingerprint in descriptor: 73c5da0".into(),
+      ),
+      (
+        "wpkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5/0)".into(),
+        "only extended keys may be followed by a derivation path: 02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5/0".into(),
+      ),
+      (
+        "wpkh(é)".into(),
+        "invalid character `é` (U+00E9) at position 6 in descriptor".into(),
+      ),
+    ] {
+      assert_eq!(
+        descriptor.parse::<Descriptor>().unwrap_err().to_string(),
+        error,
+        "{descriptor}"
+      );
+    }
+  }
+}
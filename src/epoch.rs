@@ -1,10 +1,14 @@
 use super::*;
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, PartialOrd)]
-pub(crate) struct Epoch(pub(crate) u64);
+/// A halving epoch of blocks sharing one subsidy.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, PartialOrd, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Epoch(pub(crate) u64);
 
 impl Epoch {
-  pub(crate) const STARTING_SATS: [Sat; 34] = [
+  /// First sat of each mainnet epoch. The last entry is `Sat::supply()`,
+  /// the start of the first epoch without a subsidy.
+  pub const STARTING_SATS: [Sat; 34] = [
     Sat(0),
     Sat(1050000000000000),
     Sat(1575000000000000),
@@ -38,26 +42,49 @@ impl Epoch {
     Sat(2099999996220000),
     Sat(2099999997060000),
     Sat(2099999997480000),
-    Sat(Sat::SUPPLY),
+    Sat(SUPPLY),
   ];
-  pub(crate) const FIRST_POST_SUBSIDY: Epoch = Self(33);
 
-  pub(crate) fn subsidy(self) -> u64 {
-    if self < Self::FIRST_POST_SUBSIDY {
-      (50 * COIN_VALUE) >> self.0
-    } else {
-      0
-    }
+  /// The epoch numbered `n`. Every epoch is valid, although those from
+  /// `Epoch::first_post_subsidy()` on mint no sats.
+  #[cfg_attr(not(feature = "library"), allow(dead_code))]
+  pub fn new(n: u64) -> Self {
+    Self(n)
+  }
+
+  /// The epoch number.
+  #[cfg_attr(not(feature = "library"), allow(dead_code))]
+  pub fn n(self) -> u64 {
+    self.0
+  }
+
+  /// The first epoch whose blocks mint no sats.
+  pub fn first_post_subsidy() -> Self {
+    Self(Schedule::current().first_post_subsidy())
+  }
+
+  /// Sats minted by each block of this epoch.
+  pub fn subsidy(self) -> u64 {
+    Schedule::current().subsidy(self.0)
   }
 
-  pub(crate) fn starting_sat(self) -> Sat {
+  /// First sat minted in this epoch, or `Sat::supply()` once the subsidy
+  /// has run out.
+  pub fn starting_sat(self) -> Sat {
+    let schedule = Schedule::current();
+
+    if schedule != Schedule::MAINNET {
+      return Sat(schedule.starting_sat(self.0));
+    }
+
     *Self::STARTING_SATS
       .get(usize::try_from(self.0).unwrap())
       .unwrap_or_else(|| Self::STARTING_SATS.last().unwrap())
   }
 
-  pub(crate) fn starting_height(self) -> Height {
-    Height(self.0 * SUBSIDY_HALVING_INTERVAL)
+  /// Height of the first block of this epoch.
+  pub fn starting_height(self) -> Height {
+    Height(self.0 * Schedule::current().halving_interval)
   }
 }
 
@@ -69,7 +96,11 @@ impl PartialEq<u64> for Epoch {
 
 impl From<Sat> for Epoch {
   fn from(sat: Sat) -> Self {
-    if sat < Self::STARTING_SATS[1] {
+    let schedule = Schedule::current();
+
+    if schedule != Schedule::MAINNET {
+      Epoch(schedule.epoch(sat.n()))
+    } else if sat < Self::STARTING_SATS[1] {
       Epoch(0)
     } else if sat < Self::STARTING_SATS[2] {
       Epoch(1)
@@ -143,7 +174,7 @@ impl From<Sat> for Epoch {
 
 impl From<Height> for Epoch {
   fn from(height: Height) -> Self {
-    Self(height.0 / SUBSIDY_HALVING_INTERVAL)
+    Self(height.0 / Schedule::current().halving_interval)
   }
 }
 
@@ -162,8 +193,8 @@ mod tests {
       Epoch(2).starting_sat(),
       (Epoch(0).subsidy() + Epoch(1).subsidy()) * SUBSIDY_HALVING_INTERVAL
     );
-    assert_eq!(Epoch(33).starting_sat(), Sat(Sat::SUPPLY));
-    assert_eq!(Epoch(34).starting_sat(), Sat(Sat::SUPPLY));
+    assert_eq!(Epoch(33).starting_sat(), Sat(Sat::supply()));
+    assert_eq!(Epoch(34).starting_sat(), Sat(Sat::supply()));
   }
 
   #[test]
@@ -213,13 +244,13 @@ mod tests {
         );
       }
       assert_eq!(Epoch::from(starting_sat), Epoch(epoch as u64));
-      assert_eq!(Epoch::from(starting_sat + 1), Epoch(epoch as u64));
+      assert_eq!(Epoch::from(Sat(starting_sat.n() + 1)), Epoch(epoch as u64));
     }
     assert_eq!(Epoch::from(Sat(0)), 0);
     assert_eq!(Epoch::from(Sat(1)), 0);
     assert_eq!(Epoch::from(Epoch(1).starting_sat()), 1);
     assert_eq!(Epoch::from(Epoch(1).starting_sat() + 1), 1);
-    assert_eq!(Epoch::from(Sat(u64::max_value())), 33);
+    assert_eq!(Epoch::from(Sat(u64::MAX)), 33);
   }
 
   #[test]
@@ -230,7 +261,51 @@ mod tests {
 
   #[test]
   fn first_post_subsidy() {
-    assert_eq!(Epoch::FIRST_POST_SUBSIDY.subsidy(), 0);
-    assert!((Epoch(Epoch::FIRST_POST_SUBSIDY.0 - 1)).subsidy() > 0);
+    assert_eq!(Epoch::first_post_subsidy().subsidy(), 0);
+    assert!((Epoch(Epoch::first_post_subsidy().0 - 1)).subsidy() > 0);
+  }
+
+  #[test]
+  fn boundaries() {
+    for epoch in (0..4).chain([32]).map(Epoch) {
+      let first = epoch.starting_height();
+      let last = Epoch(epoch.0 + 1).starting_height() - 1;
+
+      assert_eq!(Epoch::from(first), epoch);
+      assert_eq!(Epoch::from(last), epoch);
+      assert_eq!(Epoch::from(last + 1), epoch.0 + 1);
+
+      assert_eq!(first.subsidy(), epoch.subsidy());
+      assert_eq!(last.subsidy(), epoch.subsidy());
+
+      assert_eq!(first.starting_sat(), epoch.starting_sat());
+      assert_eq!(
+        last.starting_sat().n() + last.subsidy(),
+        Epoch(epoch.0 + 1).starting_sat().n()
+      );
+
+      assert_eq!(Epoch::from(epoch.starting_sat()), epoch);
+      assert_eq!(
+        Epoch::from(Sat(Epoch(epoch.0 + 1).starting_sat().n() - 1)),
+        epoch
+      );
+    }
+  }
+
+  #[test]
+  fn last_sat() {
+    assert_eq!(Sat::last().epoch(), 32);
+    assert_eq!(Sat::last().height(), Epoch(33).starting_height() - 1);
+    assert_eq!(Epoch(33).starting_sat(), Sat::supply());
+    assert_eq!(
+      Epoch::from(Sat::last().height() + 1),
+      Epoch::first_post_subsidy()
+    );
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(serde_json::to_string(&Epoch(3)).unwrap(), "3");
+    assert_eq!(serde_json::from_str::<Epoch>("3").unwrap(), Epoch(3));
   }
 }
@@ -1,42 +1,80 @@
 use super::*;
 
-#[derive(Copy, Clone, Debug, Display, FromStr, Ord, Eq, PartialEq, PartialOrd)]
-pub(crate) struct Height(pub(crate) u64);
+/// A block height, which determines the subsidy and the sats it mints.
+#[derive(
+  Copy, Clone, Debug, Display, FromStr, Ord, Eq, PartialEq, PartialOrd, Deserialize, Serialize,
+)]
+#[serde(transparent)]
+pub struct Height(pub(crate) u64);
 
 impl Height {
-  pub(crate) fn n(self) -> u64 {
+  /// The block at height `n`. Every height is valid, although blocks past
+  /// the last subsidy mint no sats.
+  #[cfg_attr(not(feature = "library"), allow(dead_code))]
+  pub fn new(n: u64) -> Self {
+    Self(n)
+  }
+
+  /// The height as a number.
+  pub fn n(self) -> u64 {
     self.0
   }
 
-  pub(crate) fn subsidy(self) -> u64 {
+  /// Sats minted by the block at this height.
+  pub fn subsidy(self) -> u64 {
     Epoch::from(self).subsidy()
   }
 
-  pub(crate) fn starting_sat(self) -> Sat {
+  /// Returns `None` rather than a height below zero.
+  pub fn checked_sub(self, n: u64) -> Option<Self> {
+    Some(Self(self.0.checked_sub(n)?))
+  }
+
+  /// Confirmed time for indexed blocks, extrapolated otherwise.
+  pub(crate) fn expected_timestamp(self, index: &Index) -> Result<Blocktime> {
+    index.blocktime(self)
+  }
+
+  /// First sat minted by the block at this height. Once the subsidy has run
+  /// out this is `Sat::supply()`, one past `Sat::last()`.
+  pub fn starting_sat(self) -> Sat {
     let epoch = Epoch::from(self);
     let epoch_starting_sat = epoch.starting_sat();
     let epoch_starting_height = epoch.starting_height();
-    epoch_starting_sat + (self - epoch_starting_height.n()).n() * epoch.subsidy()
+    Sat(epoch_starting_sat.n() + (self - epoch_starting_height.n()).n() * epoch.subsidy())
   }
 
-  pub(crate) fn period_offset(self) -> u64 {
+  /// Offset of this height within its difficulty adjustment period.
+  pub fn period_offset(self) -> u64 {
     self.0 % DIFFCHANGE_INTERVAL
   }
+
+  /// Height of the first block of the next epoch.
+  pub fn next_halving(self) -> Height {
+    Epoch::from(self).starting_height() + Schedule::current().halving_interval
+  }
+
+  /// Height of the first block of the next difficulty adjustment period.
+  pub fn next_difficulty_adjustment(self) -> Height {
+    Self(self.0 - self.period_offset() + DIFFCHANGE_INTERVAL)
+  }
 }
 
+/// Panics on overflow.
 impl Add<u64> for Height {
   type Output = Self;
 
   fn add(self, other: u64) -> Height {
-    Self(self.0 + other)
+    Self(self.0.checked_add(other).expect("height out of range"))
   }
 }
 
+/// Panics below zero. Use `Height::checked_sub` where that can happen.
 impl Sub<u64> for Height {
   type Output = Self;
 
   fn sub(self, other: u64) -> Height {
-    Self(self.0 - other)
+    self.checked_sub(other).expect("height out of range")
   }
 }
 
@@ -106,7 +144,7 @@ mod tests {
       SUBSIDY_HALVING_INTERVAL * 5000000000 + 2500000000
     );
     assert_eq!(
-      Height(u64::max_value()).starting_sat(),
+      Height(u64::MAX).starting_sat(),
       *Epoch::STARTING_SATS.last().unwrap()
     );
   }
@@ -119,4 +157,61 @@ mod tests {
     assert_eq!(Height(DIFFCHANGE_INTERVAL).period_offset(), 0);
     assert_eq!(Height(DIFFCHANGE_INTERVAL + 1).period_offset(), 1);
   }
+
+  #[test]
+  fn next_halving() {
+    assert_eq!(Height(0).next_halving(), SUBSIDY_HALVING_INTERVAL);
+    assert_eq!(
+      Height(SUBSIDY_HALVING_INTERVAL - 1).next_halving(),
+      SUBSIDY_HALVING_INTERVAL
+    );
+    assert_eq!(
+      Height(SUBSIDY_HALVING_INTERVAL).next_halving(),
+      SUBSIDY_HALVING_INTERVAL * 2
+    );
+    assert_eq!(
+      Height(SUBSIDY_HALVING_INTERVAL + 1).next_halving(),
+      SUBSIDY_HALVING_INTERVAL * 2
+    );
+  }
+
+  #[test]
+  fn next_difficulty_adjustment() {
+    assert_eq!(Height(0).next_difficulty_adjustment(), DIFFCHANGE_INTERVAL);
+    assert_eq!(
+      Height(DIFFCHANGE_INTERVAL - 1).next_difficulty_adjustment(),
+      DIFFCHANGE_INTERVAL
+    );
+    assert_eq!(
+      Height(DIFFCHANGE_INTERVAL).next_difficulty_adjustment(),
+      DIFFCHANGE_INTERVAL * 2
+    );
+    assert_eq!(
+      Height(DIFFCHANGE_INTERVAL + 1).next_difficulty_adjustment(),
+      DIFFCHANGE_INTERVAL * 2
+    );
+  }
+
+  #[test]
+  fn checked_sub() {
+    assert_eq!(Height(1).checked_sub(1), Some(Height(0)));
+    assert_eq!(Height(0).checked_sub(1), None);
+  }
+
+  #[test]
+  fn final_subsidy() {
+    assert_eq!(Height(6929999).subsidy(), 1);
+    assert_eq!(Height(6930000).subsidy(), 0);
+    assert_eq!(Height(6929999).starting_sat(), Sat::last());
+    assert_eq!(Height(6930000).starting_sat(), Sat::supply());
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(serde_json::to_string(&Height(840000)).unwrap(), "840000");
+    assert_eq!(
+      serde_json::from_str::<Height>("840000").unwrap(),
+      Height(840000)
+    );
+  }
 }
@@ -1,62 +1,179 @@
 use {
-  self::updater::Updater,
+  self::{
+    key::{InscriptionIdKey, OutPointKey, SatPointKey},
+    updater::Updater,
+  },
   super::*,
   bitcoin::BlockHeader,
-  bitcoincore_rpc::{json::GetBlockHeaderResult, Auth, Client},
+  bitcoincore_rpc::Client,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
-  redb::{Database, ReadableTable, Table, TableDefinition, WriteStrategy, WriteTransaction},
+  redb::{
+    Database, MultimapTable, MultimapTableDefinition, ReadableMultimapTable, ReadableTable, Table,
+    TableDefinition, WriteStrategy, WriteTransaction,
+  },
   std::collections::HashMap,
-  std::sync::atomic::{AtomicBool, Ordering},
+  std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    OnceLock,
+  },
+  tokio::sync::broadcast,
 };
 
+mod benchmark;
+mod compact;
+mod integrity;
+mod key;
 mod rtx;
+#[cfg(test)]
+mod test_fixtures;
 mod updater;
 
 type BlockHashArray = [u8; 32];
+type BlockInscriptionArray = [u8; 36];
+type CollectionMemberArray = [u8; 44];
 type InscriptionIdArray = [u8; 32];
-type SatRangeArray = [u8; 11];
 type OutPointArray = [u8; 36];
 type SatPointArray = [u8; 44];
-
+type SatRangeArray = [u8; 11];
+type SatHistoryArray = [u8; 56];
+type FeeProvenanceArray = [u8; 48];
+type EpochStatisticsArray = [u8; 32];
+type TxidArray = [u8; 32];
+
+const BLOCK_HASH_TO_HEIGHT: TableDefinition<&BlockHashArray, u64> =
+  TableDefinition::new("BLOCK_HASH_TO_HEIGHT");
+const COLLECTION_TO_INSCRIPTION_IDS: MultimapTableDefinition<str, &CollectionMemberArray> =
+  MultimapTableDefinition::new("COLLECTION_TO_INSCRIPTION_IDS");
+const EPOCH_TO_STATISTICS: TableDefinition<u64, &EpochStatisticsArray> =
+  TableDefinition::new("EPOCH_TO_STATISTICS");
+const FEE_RANGE_START_TO_PROVENANCE: MultimapTableDefinition<u64, &FeeProvenanceArray> =
+  MultimapTableDefinition::new("FEE_RANGE_START_TO_PROVENANCE");
+const FEE_TXID_TO_FIRST_SAT: TableDefinition<&TxidArray, u64> =
+  TableDefinition::new("FEE_TXID_TO_FIRST_SAT");
 const HEIGHT_TO_BLOCK_HASH: TableDefinition<u64, &BlockHashArray> =
   TableDefinition::new("HEIGHT_TO_BLOCK_HASH");
+const HEIGHT_TO_INSCRIPTION_IDS: MultimapTableDefinition<u64, &BlockInscriptionArray> =
+  MultimapTableDefinition::new("HEIGHT_TO_INSCRIPTION_IDS");
+const HEIGHT_TO_REWARD_BREAKDOWN: TableDefinition<u64, [u8]> =
+  TableDefinition::new("HEIGHT_TO_REWARD_BREAKDOWN");
+const INDEX_OPTIONS: TableDefinition<u64, ()> = TableDefinition::new("INDEX_OPTIONS");
 const SAT_TO_INSCRIPTION_ID: TableDefinition<u64, &InscriptionIdArray> =
   TableDefinition::new("SAT_TO_INSCRIPTION_ID");
 const SAT_TO_SATPOINT: TableDefinition<u64, &SatPointArray> =
   TableDefinition::new("SAT_TO_SATPOINT");
+const SAT_TO_SATPOINT_HISTORY: MultimapTableDefinition<u64, &SatHistoryArray> =
+  MultimapTableDefinition::new("SAT_TO_SATPOINT_HISTORY");
 const OUTPOINT_TO_SAT_RANGES: TableDefinition<&OutPointArray, [u8]> =
   TableDefinition::new("OUTPOINT_TO_SAT_RANGES");
+const OUTPOINT_TO_SCRIPT_PUBKEY: TableDefinition<&OutPointArray, [u8]> =
+  TableDefinition::new("OUTPOINT_TO_SCRIPT_PUBKEY");
+const SPENT_OUTPOINT_TO_SAT_RANGES: TableDefinition<&OutPointArray, [u8]> =
+  TableDefinition::new("SPENT_OUTPOINT_TO_SAT_RANGES");
+const SCRIPT_PUBKEY_TO_OUTPOINTS: MultimapTableDefinition<[u8], &OutPointArray> =
+  MultimapTableDefinition::new("SCRIPT_PUBKEY_TO_OUTPOINTS");
 const STATISTIC_TO_COUNT: TableDefinition<u64, u64> = TableDefinition::new("STATISTIC_TO_COUNT");
 const WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP: TableDefinition<u64, u128> =
   TableDefinition::new("WRITE_TRANSACTION_START_BLOCK_COUNT_TO_TIMESTAMP");
+const INSCRIPTION_ID_TO_BURNED: TableDefinition<&InscriptionIdArray, ()> =
+  TableDefinition::new("INSCRIPTION_ID_TO_BURNED");
 const INSCRIPTION_ID_TO_SATPOINT: TableDefinition<&InscriptionIdArray, &SatPointArray> =
   TableDefinition::new("INSCRIPTION_ID_TO_SATPOINT");
+const INSCRIPTION_ID_TO_GENESIS_HEIGHT: TableDefinition<&InscriptionIdArray, u64> =
+  TableDefinition::new("INSCRIPTION_ID_TO_GENESIS_HEIGHT");
+const INSCRIPTION_ID_TO_INSCRIPTION_NUMBER: TableDefinition<&InscriptionIdArray, u64> =
+  TableDefinition::new("INSCRIPTION_ID_TO_INSCRIPTION_NUMBER");
+const INSCRIPTION_NUMBER_TO_INSCRIPTION_ID: TableDefinition<u64, &InscriptionIdArray> =
+  TableDefinition::new("INSCRIPTION_NUMBER_TO_INSCRIPTION_ID");
+const INSCRIPTION_ID_TO_SATPOINT_HISTORY: MultimapTableDefinition<
+  &InscriptionIdArray,
+  &SatHistoryArray,
+> = MultimapTableDefinition::new("INSCRIPTION_ID_TO_SATPOINT_HISTORY");
 const SATPOINT_TO_INSCRIPTION_ID: TableDefinition<&SatPointArray, &InscriptionIdArray> =
   TableDefinition::new("SATPOINT_TO_INSCRIPTION_ID");
 
-fn encode_outpoint(outpoint: OutPoint) -> OutPointArray {
+/// History entries sort by height, then by the order in which the block's
+/// transactions were indexed, which puts the coinbase last. Sat and
+/// inscription history share this encoding.
+fn encode_sat_history(height: u64, sequence: u32, satpoint: SatPoint) -> SatHistoryArray {
+  let mut array = [0; 56];
+  array[..8].copy_from_slice(&height.to_be_bytes());
+  array[8..12].copy_from_slice(&sequence.to_be_bytes());
+  array[12..].copy_from_slice(SatPointKey::new(satpoint).as_array());
+  array
+}
+
+fn decode_sat_history(array: SatHistoryArray) -> (Height, SatPoint) {
+  (
+    Height(u64::from_be_bytes(array[..8].try_into().unwrap())),
+    SatPointKey::from_array(array[12..].try_into().unwrap()).satpoint(),
+  )
+}
+
+/// Provenance entries for a fee range sort by the height at which the range
+/// was paid as a fee.
+fn encode_fee_provenance(height: u64, end: u64, txid: Txid) -> FeeProvenanceArray {
+  let mut array = [0; 48];
+  array[..8].copy_from_slice(&height.to_be_bytes());
+  array[8..16].copy_from_slice(&end.to_be_bytes());
+  array[16..].copy_from_slice(txid.as_inner());
+  array
+}
+
+fn decode_fee_provenance(array: FeeProvenanceArray) -> (Height, u64, Txid) {
+  (
+    Height(u64::from_be_bytes(array[..8].try_into().unwrap())),
+    u64::from_be_bytes(array[8..16].try_into().unwrap()),
+    Txid::from_inner(array[16..].try_into().unwrap()),
+  )
+}
+
+/// Prefixed with the inscription's position within the block, so that the
+/// inscriptions revealed in a block are listed in order.
+fn encode_block_inscription(sequence: u32, inscription_id: InscriptionId) -> BlockInscriptionArray {
   let mut array = [0; 36];
-  outpoint
-    .consensus_encode(&mut array.as_mut_slice())
-    .unwrap();
+  array[..4].copy_from_slice(&sequence.to_be_bytes());
+  array[4..].copy_from_slice(inscription_id.as_inner());
   array
 }
 
-fn encode_satpoint(satpoint: SatPoint) -> SatPointArray {
+fn decode_block_inscription(array: BlockInscriptionArray) -> InscriptionId {
+  decode_inscription_id(array[4..].try_into().unwrap())
+}
+
+/// Collection members sort by the height at which they were inscribed, then
+/// by their order within the block.
+fn encode_collection_member(
+  height: u64,
+  sequence: u32,
+  inscription_id: InscriptionId,
+) -> CollectionMemberArray {
   let mut array = [0; 44];
-  satpoint
-    .consensus_encode(&mut array.as_mut_slice())
-    .unwrap();
+  array[..8].copy_from_slice(&height.to_be_bytes());
+  array[8..12].copy_from_slice(&sequence.to_be_bytes());
+  array[12..].copy_from_slice(inscription_id.as_inner());
   array
 }
 
-fn decode_satpoint(array: SatPointArray) -> SatPoint {
-  Decodable::consensus_decode(&mut io::Cursor::new(array)).unwrap()
+fn decode_collection_member(array: CollectionMemberArray) -> InscriptionId {
+  decode_inscription_id(array[12..].try_into().unwrap())
 }
 
-fn decode_outpoint(array: OutPointArray) -> OutPoint {
-  Decodable::consensus_decode(&mut io::Cursor::new(array)).unwrap()
+/// Blocks, subsidy, fees, and inscriptions.
+fn encode_epoch_statistics(statistics: [u64; 4]) -> EpochStatisticsArray {
+  let mut array = [0; 32];
+  for (chunk, n) in array.chunks_exact_mut(8).zip(statistics) {
+    chunk.copy_from_slice(&n.to_le_bytes());
+  }
+  array
+}
+
+fn decode_epoch_statistics(array: EpochStatisticsArray) -> [u64; 4] {
+  let mut statistics = [0; 4];
+  for (n, chunk) in statistics.iter_mut().zip(array.chunks_exact(8)) {
+    *n = u64::from_le_bytes(chunk.try_into().unwrap());
+  }
+  statistics
 }
 
 fn decode_inscription_id(array: InscriptionIdArray) -> InscriptionId {
@@ -64,30 +181,118 @@ fn decode_inscription_id(array: InscriptionIdArray) -> InscriptionId {
 }
 
 pub(crate) struct Index {
-  auth: Auth,
   chain: Chain,
-  client: Client,
   database: Database,
   database_path: PathBuf,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
+  block_events: broadcast::Sender<BlockEvent>,
   height_limit: Option<u64>,
+  inscription_events: broadcast::Sender<InscriptionEvent>,
+  node_block_count: AtomicU64,
   reorged: AtomicBool,
-  rpc_url: String,
+  rpc: Arc<RpcPool>,
+  webhooks: OnceLock<Webhooks>,
+}
+
+/// A newly indexed block, published once it has been committed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BlockEvent {
+  pub(crate) height: u64,
+  pub(crate) hash: BlockHash,
+}
+
+/// A newly indexed inscription, published once the block revealing it has
+/// been committed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct InscriptionEvent {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) number: u64,
+  pub(crate) satpoint: SatPoint,
+  pub(crate) height: u64,
+}
+
+/// An inscription returned by `get_inscriptions_by_inscription_ids`, with
+/// every field read from the same snapshot of the index.
+#[derive(Debug, PartialEq)]
+pub(crate) struct InscriptionEntry {
+  pub(crate) inscription: Inscription,
+  pub(crate) satpoint: SatPoint,
+  pub(crate) sat: Option<Sat>,
+  pub(crate) number: u64,
+  pub(crate) burned: bool,
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) enum List {
+pub(crate) enum List<T = Vec<(u64, u64)>> {
   Spent,
-  Unspent(Vec<(u64, u64)>),
+  Unspent(T),
 }
 
+/// An output's sat ranges as stored in the index, which are only decoded when
+/// read, so that a page of an output's ranges can be shown without decoding
+/// the rest.
+#[derive(Debug, PartialEq)]
+pub(crate) struct SatRanges(Vec<u8>);
+
+impl SatRanges {
+  pub(crate) fn len(&self) -> usize {
+    self.0.len() / 11
+  }
+
+  pub(crate) fn iter(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+    self.page(0, self.len())
+  }
+
+  /// The ranges from `start` up to `start + count`.
+  pub(crate) fn page(&self, start: usize, count: usize) -> impl Iterator<Item = (u64, u64)> + '_ {
+    let start = start.min(self.len());
+    let end = start.saturating_add(count).min(self.len());
+
+    self.0[start * 11..end * 11]
+      .chunks_exact(11)
+      .map(|chunk| Index::decode_sat_range(chunk.try_into().unwrap()))
+  }
+}
+
+#[cfg(test)]
+impl From<Vec<(u64, u64)>> for SatRanges {
+  fn from(ranges: Vec<(u64, u64)>) -> Self {
+    let mut sats = Vec::new();
+
+    for (start, end) in ranges {
+      let n = u128::from(start) | u128::from(end - start) << 51;
+      sats.extend_from_slice(&n.to_le_bytes()[0..11]);
+    }
+
+    Self(sats)
+  }
+}
+
+/// Part of a block's reward. Segments are in the order that the reward is
+/// assigned to the coinbase outputs: the subsidy, then the fees of each
+/// transaction in the block.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct RewardSegment {
+  /// Position in the block of the transaction which paid these sats as fees,
+  /// or `None` for the subsidy.
+  pub(crate) fee_transaction: Option<usize>,
+  pub(crate) sats: u64,
+}
+
+/// Version of the database layout. Indexes with a different version must be
+/// rebuilt.
+pub(crate) const SCHEMA_VERSION: u64 = 11;
+
 #[derive(Copy, Clone)]
 #[repr(u64)]
 pub(crate) enum Statistic {
   OutputsTraversed = 0,
   Commits = 1,
   SatRanges = 2,
+  Schema = 3,
+  InitialSubsidy = 4,
+  HalvingInterval = 5,
 }
 
 impl Statistic {
@@ -96,12 +301,71 @@ impl Statistic {
   }
 }
 
+/// Optional tables, chosen when the index is created. Rare sats, inscribed
+/// sats, and fee provenance are found while walking sat ranges, so all imply
+/// `SatRanges`. Inscription history does not need sat ranges.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u64)]
+pub(crate) enum IndexOption {
+  SatRanges = 0,
+  RareSats = 1,
+  InscribedSats = 2,
+  SatHistory = 3,
+  FeeProvenance = 4,
+  InscriptionHistory = 5,
+  Addresses = 6,
+  SpentSatRanges = 7,
+}
+
+impl IndexOption {
+  fn key(self) -> u64 {
+    self as u64
+  }
+
+  fn flag(self) -> &'static str {
+    match self {
+      Self::SatRanges => "--index-sat-ranges",
+      Self::RareSats => "--index-rare-sats",
+      Self::InscribedSats => "--index-inscribed-sats",
+      Self::SatHistory => "--index-sat-history",
+      Self::FeeProvenance => "--index-fee-provenance",
+      Self::InscriptionHistory => "--index-inscription-history",
+      Self::Addresses => "--index-addresses",
+      Self::SpentSatRanges => "--index-spent-sat-ranges",
+    }
+  }
+}
+
 impl From<Statistic> for u64 {
   fn from(statistic: Statistic) -> Self {
     statistic as u64
   }
 }
 
+/// Totals over the indexed blocks of an epoch. Fees are only known if the
+/// index tracks sat ranges.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct EpochStatistics {
+  pub(crate) epoch: Epoch,
+  pub(crate) blocks: u64,
+  pub(crate) subsidy: u64,
+  pub(crate) fees: Option<u64>,
+  pub(crate) inscriptions: u64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct RarityStatistics {
+  pub(crate) blocks: u64,
+  pub(crate) rarities: Vec<RarityStatistic>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct RarityStatistic {
+  pub(crate) rarity: Rarity,
+  pub(crate) supply: u64,
+  pub(crate) inscribed: Option<u64>,
+}
+
 #[derive(Serialize)]
 pub(crate) struct Info {
   pub(crate) blocks_indexed: u64,
@@ -149,10 +413,19 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 impl Index {
+  /// Block events buffered for each subscriber.
+  const BLOCK_EVENT_CAPACITY: usize = 1024;
+  /// Inscription events buffered for each subscriber.
+  const INSCRIPTION_EVENT_CAPACITY: usize = 1024;
+
+  /// Open the index with an RPC pool of its own.
+  #[cfg(test)]
   pub(crate) fn open(options: &Options) -> Result<Self> {
-    let rpc_url = options.rpc_url();
-    let cookie_file = options.cookie_file()?;
+    Self::open_with_rpc(options, RpcPool::new(options)?)
+  }
 
+  /// Open the index, making RPC calls with clients from `rpc`.
+  pub(crate) fn open_with_rpc(options: &Options, rpc: Arc<RpcPool>) -> Result<Self> {
     if cfg!(test) {
       // The default max database size is 10 MiB for Regtest and 1 TiB
       // for all other networks. A larger database takes longer to
@@ -160,39 +433,18 @@ impl Index {
       assert_eq!(options.chain(), Chain::Regtest);
     }
 
-    log::info!(
-      "Connecting to Bitcoin Core RPC server at {rpc_url} using credentials from `{}`",
-      cookie_file.display()
-    );
-
-    let auth = Auth::CookieFile(cookie_file);
-
-    let client = Client::new(&rpc_url, auth.clone()).context("failed to connect to RPC URL")?;
-
     let data_dir = options.data_dir()?;
 
     if let Err(err) = fs::create_dir_all(&data_dir) {
       bail!("failed to create data dir `{}`: {err}", data_dir.display());
     }
 
-    let database_path = if let Some(database_path) = &options.index {
-      database_path.clone()
-    } else {
-      data_dir.join("index.redb")
-    };
+    let database_path = Self::database_path(options)?;
 
     let database = match unsafe { redb::Database::open(&database_path) } {
       Ok(database) => database,
       Err(redb::Error::Io(error)) if error.kind() == io::ErrorKind::NotFound => {
-        let database = unsafe {
-          Database::builder()
-            .set_write_strategy(if cfg!(test) {
-              WriteStrategy::Checksum
-            } else {
-              WriteStrategy::TwoPhase
-            })
-            .create(&database_path)?
-        };
+        let database = Self::create_database(&database_path)?;
         let tx = database.begin_write()?;
 
         #[cfg(test)]
@@ -202,18 +454,69 @@ impl Index {
           tx
         };
 
+        tx.open_table(BLOCK_HASH_TO_HEIGHT)?;
+        tx.open_multimap_table(COLLECTION_TO_INSCRIPTION_IDS)?;
+        tx.open_table(EPOCH_TO_STATISTICS)?;
         tx.open_table(HEIGHT_TO_BLOCK_HASH)?;
+        tx.open_multimap_table(HEIGHT_TO_INSCRIPTION_IDS)?;
+        tx.open_table(INSCRIPTION_ID_TO_BURNED)?;
+        tx.open_table(INSCRIPTION_ID_TO_GENESIS_HEIGHT)?;
+        tx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_NUMBER)?;
         tx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
+        tx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
         tx.open_table(SAT_TO_INSCRIPTION_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
         tx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
         tx.open_table(STATISTIC_TO_COUNT)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
-        if options.index_satoshis {
+        let index_options = options.index_options();
+
+        if index_options.contains(&IndexOption::SatRanges) {
+          tx.open_table(HEIGHT_TO_REWARD_BREAKDOWN)?;
           tx.open_table(OUTPOINT_TO_SAT_RANGES)?;
         }
 
+        if index_options.contains(&IndexOption::SatHistory) {
+          tx.open_multimap_table(SAT_TO_SATPOINT_HISTORY)?;
+        }
+
+        if index_options.contains(&IndexOption::FeeProvenance) {
+          tx.open_multimap_table(FEE_RANGE_START_TO_PROVENANCE)?;
+          tx.open_table(FEE_TXID_TO_FIRST_SAT)?;
+        }
+
+        if index_options.contains(&IndexOption::InscriptionHistory) {
+          tx.open_multimap_table(INSCRIPTION_ID_TO_SATPOINT_HISTORY)?;
+        }
+
+        if index_options.contains(&IndexOption::Addresses) {
+          tx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
+          tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?;
+        }
+
+        if index_options.contains(&IndexOption::SpentSatRanges) {
+          tx.open_table(SPENT_OUTPOINT_TO_SAT_RANGES)?;
+        }
+
+        {
+          let mut index_options_table = tx.open_table(INDEX_OPTIONS)?;
+          for option in index_options {
+            index_options_table.insert(&option.key(), &())?;
+          }
+        }
+
+        {
+          let schedule = Schedule::current();
+          let mut statistic_to_count = tx.open_table(STATISTIC_TO_COUNT)?;
+          statistic_to_count.insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
+          statistic_to_count.insert(&Statistic::InitialSubsidy.key(), &schedule.initial_subsidy)?;
+          statistic_to_count.insert(
+            &Statistic::HalvingInterval.key(),
+            &schedule.halving_interval,
+          )?;
+        }
+
         tx.commit()?;
 
         database
@@ -221,34 +524,149 @@ impl Index {
       Err(error) => return Err(error.into()),
     };
 
+    let schema_version = database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .unwrap_or(0);
+
+    if schema_version != SCHEMA_VERSION {
+      bail!(
+        "index at `{}` has schema version {schema_version}, but this version of ord requires schema version {SCHEMA_VERSION}, so the index must be deleted and rebuilt",
+        database_path.display()
+      );
+    }
+
+    if !options.skip_integrity_check {
+      if let Some(violation) = integrity::check(&database)? {
+        bail!(
+          "index at `{}` failed integrity check: {violation}, so the index must be deleted and rebuilt",
+          database_path.display(),
+        );
+      }
+    }
+
+    // Sat numbers depend on the subsidy schedule, so an index can only be
+    // used with the schedule it was built with. Indexes without a recorded
+    // schedule predate overrides, and so were built with the mainnet one.
+    let schedule = {
+      let rtx = database.begin_read()?;
+      let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
+      Schedule {
+        initial_subsidy: statistic_to_count
+          .get(&Statistic::InitialSubsidy.key())?
+          .unwrap_or(Schedule::MAINNET.initial_subsidy),
+        halving_interval: statistic_to_count
+          .get(&Statistic::HalvingInterval.key())?
+          .unwrap_or(Schedule::MAINNET.halving_interval),
+      }
+    };
+
+    if schedule != Schedule::current() {
+      bail!(
+        "index at `{}` was built with {schedule}, but the current subsidy schedule has {}, so the index must be deleted and rebuilt",
+        database_path.display(),
+        Schedule::current(),
+      );
+    }
+
     let genesis_block_coinbase_transaction =
       options.chain().genesis_block().coinbase().unwrap().clone();
 
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
-      auth,
       chain: options.chain(),
-      client,
       database,
       database_path,
       genesis_block_coinbase_transaction,
+      block_events: broadcast::channel(Self::BLOCK_EVENT_CAPACITY).0,
       height_limit: options.height_limit,
+      inscription_events: broadcast::channel(Self::INSCRIPTION_EVENT_CAPACITY).0,
+      node_block_count: AtomicU64::new(0),
       reorged: AtomicBool::new(false),
-      rpc_url,
+      rpc,
+      webhooks: OnceLock::new(),
+    })
+  }
+
+  pub(crate) fn rpc(&self) -> Arc<RpcPool> {
+    self.rpc.clone()
+  }
+
+  pub(crate) fn path(&self) -> &Path {
+    &self.database_path
+  }
+
+  /// `--index`, or `index.redb` in the data dir.
+  pub(crate) fn database_path(options: &Options) -> Result<PathBuf> {
+    match &options.index {
+      Some(database_path) => Ok(database_path.clone()),
+      None => Ok(options.data_dir()?.join("index.redb")),
+    }
+  }
+
+  fn create_database(path: &Path) -> Result<Database> {
+    Ok(unsafe {
+      Database::builder()
+        .set_write_strategy(if cfg!(test) {
+          WriteStrategy::Checksum
+        } else {
+          WriteStrategy::TwoPhase
+        })
+        .create(path)?
     })
   }
 
-  pub(crate) fn has_satoshi_index(&self) -> Result<bool> {
-    match self.begin_read()?.0.open_table(OUTPOINT_TO_SAT_RANGES) {
-      Ok(_) => Ok(true),
+  fn has(&self, option: IndexOption) -> Result<bool> {
+    Self::has_in(&self.begin_read()?.0, option)
+  }
+
+  fn has_in(rtx: &redb::ReadTransaction, option: IndexOption) -> Result<bool> {
+    match rtx.open_table(INDEX_OPTIONS) {
+      Ok(table) => Ok(table.get(&option.key())?.is_some()),
       Err(redb::Error::TableDoesNotExist(_)) => Ok(false),
       Err(err) => Err(err.into()),
     }
   }
 
-  fn require_satoshi_index(&self, feature: &str) -> Result {
-    if !self.has_satoshi_index()? {
-      bail!("{feature} requires index created with `--index-satoshis` flag")
+  pub(crate) fn has_sat_ranges(&self) -> Result<bool> {
+    self.has(IndexOption::SatRanges)
+  }
+
+  pub(crate) fn has_rare_sats(&self) -> Result<bool> {
+    self.has(IndexOption::RareSats)
+  }
+
+  pub(crate) fn has_inscribed_sats(&self) -> Result<bool> {
+    self.has(IndexOption::InscribedSats)
+  }
+
+  pub(crate) fn has_sat_history(&self) -> Result<bool> {
+    self.has(IndexOption::SatHistory)
+  }
+
+  pub(crate) fn has_fee_provenance(&self) -> Result<bool> {
+    self.has(IndexOption::FeeProvenance)
+  }
+
+  pub(crate) fn has_inscription_history(&self) -> Result<bool> {
+    self.has(IndexOption::InscriptionHistory)
+  }
+
+  pub(crate) fn has_addresses(&self) -> Result<bool> {
+    self.has(IndexOption::Addresses)
+  }
+
+  pub(crate) fn has_spent_sat_ranges(&self) -> Result<bool> {
+    self.has(IndexOption::SpentSatRanges)
+  }
+
+  pub(crate) fn require(&self, option: IndexOption, feature: &str) -> Result {
+    if !self.has(option)? {
+      bail!(
+        "{feature} requires index created with `{}` flag",
+        option.flag()
+      )
     }
 
     Ok(())
@@ -265,8 +683,7 @@ impl Index {
         blocks_indexed: wtx
           .open_table(HEIGHT_TO_BLOCK_HASH)?
           .range(0..)?
-          .rev()
-          .next()
+          .next_back()
           .map(|(height, _hash)| height + 1)
           .unwrap_or(0),
         branch_pages: stats.branch_pages(),
@@ -318,18 +735,56 @@ impl Index {
   }
 
   pub(crate) fn update(&self) -> Result {
-    Updater::update(self)
+    self.update_until(&AtomicBool::new(false))
+  }
+
+  /// Like `update`, but stops after the block being indexed once `stop` is
+  /// set, committing the blocks indexed so far.
+  pub(crate) fn update_until(&self, stop: &AtomicBool) -> Result {
+    Updater::update(self, stop)?;
+    Ok(())
+  }
+
+  /// Notify `webhooks` of the new inscriptions and transfers in each block
+  /// once it has been committed.
+  pub(crate) fn set_webhooks(&self, webhooks: Webhooks) -> Result {
+    self
+      .webhooks
+      .set(webhooks)
+      .map_err(|_| anyhow!("index webhooks may only be set once"))
+  }
+
+  /// Receive an event for each block indexed from now on. Like inscription
+  /// events, receivers which fall behind miss events.
+  pub(crate) fn subscribe_block_events(&self) -> broadcast::Receiver<BlockEvent> {
+    self.block_events.subscribe()
+  }
+
+  /// Receive an event for each inscription indexed from now on. Receivers
+  /// which fall more than `INSCRIPTION_EVENT_CAPACITY` events behind miss
+  /// events, rather than holding up indexing.
+  pub(crate) fn subscribe_inscription_events(&self) -> broadcast::Receiver<InscriptionEvent> {
+    self.inscription_events.subscribe()
+  }
+
+  /// The number of blocks in the node's chain, up to `--height-limit`, when
+  /// the last update started, or `None` before the first update.
+  pub(crate) fn node_block_count(&self) -> Option<u64> {
+    match self.node_block_count.load(Ordering::Relaxed) {
+      0 => None,
+      count => Some(self.height_limit.map_or(count, |limit| count.min(limit))),
+    }
   }
 
   pub(crate) fn is_reorged(&self) -> bool {
     self.reorged.load(Ordering::Relaxed)
   }
 
-  fn begin_read(&self) -> Result<rtx::Rtx> {
+  fn begin_read(&self) -> Result<rtx::Rtx<'_>> {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
 
-  fn begin_write(&self) -> Result<WriteTransaction> {
+  fn begin_write(&self) -> Result<WriteTransaction<'_>> {
     if cfg!(test) {
       let mut tx = self.database.begin_write()?;
       tx.set_durability(redb::Durability::None);
@@ -348,7 +803,6 @@ impl Index {
     Ok(())
   }
 
-  #[cfg(test)]
   pub(crate) fn statistic(&self, statistic: Statistic) -> Result<u64> {
     Ok(
       self
@@ -360,14 +814,138 @@ impl Index {
     )
   }
 
+  /// Record `inscription_id` at `satpoint` without the transaction that
+  /// created it, so tests can use IDs with chosen prefixes, or place
+  /// inscriptions at arbitrary offsets.
+  #[cfg(test)]
+  pub(crate) fn insert_inscription_id(&self, inscription_id: InscriptionId, satpoint: SatPoint) {
+    let wtx = self.database.begin_write().unwrap();
+    wtx
+      .open_table(INSCRIPTION_ID_TO_SATPOINT)
+      .unwrap()
+      .insert(
+        InscriptionIdKey::new(inscription_id).as_array(),
+        SatPointKey::new(satpoint).as_array(),
+      )
+      .unwrap();
+    wtx
+      .open_table(SATPOINT_TO_INSCRIPTION_ID)
+      .unwrap()
+      .insert(
+        SatPointKey::new(satpoint).as_array(),
+        inscription_id.as_inner(),
+      )
+      .unwrap();
+    wtx.commit().unwrap();
+  }
+
+  /// Give `inscription_id` inscription number `number`, so tests can use
+  /// numbers too large to reach by inscribing.
+  #[cfg(test)]
+  pub(crate) fn insert_inscription_number(&self, number: u64, inscription_id: InscriptionId) {
+    let wtx = self.database.begin_write().unwrap();
+    wtx
+      .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
+      .unwrap()
+      .insert(&number, inscription_id.as_inner())
+      .unwrap();
+    wtx.commit().unwrap();
+  }
+
   pub(crate) fn height(&self) -> Result<Option<Height>> {
     self.begin_read()?.height()
   }
 
+  pub(crate) fn rarity_statistics(&self) -> Result<RarityStatistics> {
+    let rtx = self.begin_read()?;
+
+    let blocks = rtx.block_count()?;
+
+    let inscribed = if self.has_inscribed_sats()? {
+      let mut inscribed = [0; Rarity::ALL.len()];
+
+      for (sat, _inscription_id) in rtx.0.open_table(SAT_TO_INSCRIPTION_ID)?.range(0..)? {
+        let rarity = Sat(sat).rarity();
+        inscribed[Rarity::ALL.iter().position(|r| *r == rarity).unwrap()] += 1;
+      }
+
+      Some(inscribed)
+    } else {
+      None
+    };
+
+    Ok(RarityStatistics {
+      blocks,
+      rarities: Rarity::ALL
+        .into_iter()
+        .enumerate()
+        .map(|(i, rarity)| RarityStatistic {
+          rarity,
+          supply: rarity.supply(blocks),
+          inscribed: inscribed.map(|inscribed| inscribed[i]),
+        })
+        .collect(),
+    })
+  }
+
+  pub(crate) fn epoch_statistics(&self) -> Result<Vec<EpochStatistics>> {
+    let sat_ranges = self.has_sat_ranges()?;
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(EPOCH_TO_STATISTICS)?
+        .range(0..)?
+        .map(|(epoch, statistics)| {
+          let [blocks, subsidy, fees, inscriptions] = decode_epoch_statistics(*statistics);
+          EpochStatistics {
+            epoch: Epoch(epoch),
+            blocks,
+            subsidy,
+            fees: sat_ranges.then_some(fees),
+            inscriptions,
+          }
+        })
+        .collect(),
+    )
+  }
+
   pub(crate) fn block_count(&self) -> Result<u64> {
     self.begin_read()?.block_count()
   }
 
+  /// Hash of the indexed block at `height`, or of the latest indexed block
+  /// if `height` is `None`.
+  pub(crate) fn block_hash(&self, height: Option<u64>) -> Result<Option<BlockHash>> {
+    let rtx = self.begin_read()?;
+
+    let height_to_block_hash = rtx.0.open_table(HEIGHT_TO_BLOCK_HASH)?;
+
+    Ok(match height {
+      Some(height) => height_to_block_hash
+        .get(&height)?
+        .map(|hash| BlockHash::from_inner(*hash)),
+      None => height_to_block_hash
+        .range(0..)?
+        .next_back()
+        .map(|(_height, hash)| BlockHash::from_inner(*hash)),
+    })
+  }
+
+  /// Height of the indexed block with hash `hash`, or `None` if no such
+  /// block has been indexed.
+  pub(crate) fn block_height(&self, hash: BlockHash) -> Result<Option<Height>> {
+    Ok(
+      self
+        .begin_read()?
+        .0
+        .open_table(BLOCK_HASH_TO_HEIGHT)?
+        .get(&hash.into_inner())?
+        .map(Height),
+    )
+  }
+
   pub(crate) fn blocks(&self, take: usize) -> Result<Vec<(u64, BlockHash)>> {
     let mut blocks = Vec::new();
 
@@ -384,579 +962,2410 @@ impl Index {
     Ok(blocks)
   }
 
-  pub(crate) fn rare_sat_satpoints(&self) -> Result<Option<Vec<(Sat, SatPoint)>>> {
-    if self.has_satoshi_index()? {
-      let mut result = Vec::new();
+  /// Calls `f` with each tracked rare sat and its location, in sat order,
+  /// until `f` returns false. All calls see the same snapshot of the index.
+  pub(crate) fn rare_sat_satpoints(&self, mut f: impl FnMut(Sat, SatPoint) -> bool) -> Result {
+    self.require(IndexOption::RareSats, "tracking rare sats")?;
 
-      let rtx = self.database.begin_read()?;
+    let rtx = self.database.begin_read()?;
 
-      let sat_to_satpoint = rtx.open_table(SAT_TO_SATPOINT)?;
+    let sat_to_satpoint = rtx.open_table(SAT_TO_SATPOINT)?;
 
-      for (sat, satpoint) in sat_to_satpoint.range(0..)? {
-        result.push((Sat(sat), decode_satpoint(*satpoint)));
+    for (sat, satpoint) in sat_to_satpoint.range(0..)? {
+      if !f(Sat(sat), SatPointKey::from_array(*satpoint).satpoint()) {
+        break;
       }
-
-      Ok(Some(result))
-    } else {
-      Ok(None)
     }
-  }
 
-  pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<BlockHeader>> {
-    self.client.get_block_header(&hash).into_option()
+    Ok(())
   }
 
-  pub(crate) fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
-    self.client.get_block_header_info(&hash).into_option()
-  }
+  /// Calls `f` with the ID of each inscription numbered within `numbers`, in
+  /// order, until `f` returns false. All calls see the same snapshot of the
+  /// index.
+  pub(crate) fn inscription_ids_by_number(
+    &self,
+    numbers: Range<u64>,
+    mut f: impl FnMut(InscriptionId) -> bool,
+  ) -> Result {
+    let rtx = self.database.begin_read()?;
 
-  pub(crate) fn get_block_by_height(&self, height: u64) -> Result<Option<Block>> {
-    Ok(
-      self
-        .client
-        .get_block_hash(height)
-        .into_option()?
-        .map(|hash| self.client.get_block(&hash))
-        .transpose()?,
-    )
-  }
+    let inscription_number_to_inscription_id =
+      rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
 
-  pub(crate) fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
-    self.client.get_block(&hash).into_option()
-  }
+    for (_number, id) in inscription_number_to_inscription_id.range(numbers)? {
+      if !f(Txid::from_inner(*id)) {
+        break;
+      }
+    }
 
-  pub(crate) fn get_inscription_by_sat(&self, sat: Sat) -> Result<Option<Inscription>> {
-    let db = self.database.begin_read()?;
-    let table = db.open_table(SAT_TO_INSCRIPTION_ID)?;
+    Ok(())
+  }
 
-    let Some(txid) = table.get(&sat.n())? else {
-      return Ok(None);
-    };
+  /// Every location that `sat` has occupied, oldest first. Only recorded for
+  /// non-common sats.
+  pub(crate) fn sat_history(&self, sat: Sat) -> Result<Vec<(Height, SatPoint)>> {
+    self.require(IndexOption::SatHistory, "sat history")?;
 
     Ok(
       self
-        .get_inscription_by_inscription_id(Txid::from_inner(*txid))?
-        .map(|(inscription, _)| inscription),
+        .database
+        .begin_read()?
+        .open_multimap_table(SAT_TO_SATPOINT_HISTORY)?
+        .get(&sat.n())?
+        .map(|entry| decode_sat_history(*entry))
+        .collect(),
     )
   }
 
-  pub(crate) fn get_inscription_by_inscription_id(
+  /// The locations that `sat` had occupied as of the block at `height`,
+  /// oldest first, so the last is where it was at that height.
+  pub(crate) fn sat_history_at_height(
     &self,
-    txid: Txid,
-  ) -> Result<Option<(Inscription, SatPoint)>> {
-    let Some(inscription) = self.get_transaction(txid)?.and_then(|tx| Inscription::from_transaction(&tx)) else {
-      return Ok(None);
-    };
+    sat: Sat,
+    height: Height,
+  ) -> Result<Vec<(Height, SatPoint)>> {
+    self.require(IndexOption::SatHistory, "sat history")?;
 
-    let satpoint = decode_satpoint(
-      *self
-        .database
-        .begin_read()?
-        .open_table(INSCRIPTION_ID_TO_SATPOINT)?
-        .get(txid.as_inner())?
-        .ok_or_else(|| anyhow!("no satpoint for inscription"))?,
-    );
+    let rtx = self.begin_read()?;
 
-    Ok(Some((inscription, satpoint)))
-  }
+    Self::require_indexed_height(&rtx, height)?;
 
-  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
-    if txid == self.genesis_block_coinbase_txid {
-      Ok(Some(self.genesis_block_coinbase_transaction.clone()))
-    } else {
-      self.client.get_raw_transaction(&txid, None).into_option()
-    }
-  }
+    let history = rtx
+      .0
+      .open_multimap_table(SAT_TO_SATPOINT_HISTORY)?
+      .get(&sat.n())?
+      .map(|entry| decode_sat_history(*entry))
+      .take_while(|(entry_height, _satpoint)| *entry_height <= height)
+      .collect();
 
-  pub(crate) fn is_transaction_in_active_chain(&self, txid: Txid) -> Result<bool> {
-    Ok(
-      self
-        .client
-        .get_raw_transaction_info(&txid, None)
-        .into_option()?
-        .and_then(|transaction_info| {
-          transaction_info
-            .confirmations
-            .map(|confirmations| confirmations > 0)
-        })
-        .unwrap_or(false),
-    )
+    Ok(history)
   }
 
-  pub(crate) fn find(&self, sat: u64) -> Result<Option<SatPoint>> {
-    self.require_satoshi_index("find")?;
+  /// Where `inscription_id` was as of the block at `height`, or `None` if it
+  /// had not been inscribed yet.
+  pub(crate) fn get_inscription_satpoint_at_height(
+    &self,
+    inscription_id: InscriptionId,
+    height: Height,
+  ) -> Result<Option<SatPoint>> {
+    self.require(IndexOption::InscriptionHistory, "inscription history")?;
 
     let rtx = self.begin_read()?;
 
-    if rtx.block_count()? <= Sat(sat).height().n() {
-      return Ok(None);
+    Self::require_indexed_height(&rtx, height)?;
+
+    let satpoint = rtx
+      .0
+      .open_multimap_table(INSCRIPTION_ID_TO_SATPOINT_HISTORY)?
+      .get(InscriptionIdKey::new(inscription_id).as_array())?
+      .map(|entry| decode_sat_history(*entry))
+      .take_while(|(entry_height, _satpoint)| *entry_height <= height)
+      .last()
+      .map(|(_height, satpoint)| satpoint);
+
+    Ok(satpoint)
+  }
+
+  /// Blocks above the tip may yet move things, so history can't answer for
+  /// them.
+  fn require_indexed_height(rtx: &rtx::Rtx, height: Height) -> Result {
+    match rtx.block_count()?.checked_sub(1) {
+      Some(tip) if height.n() <= tip => Ok(()),
+      Some(tip) => bail!("height {height} is above index height {tip}"),
+      None => bail!("height {height} is above index height, since no blocks have been indexed"),
     }
+  }
 
-    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
+  /// Every transaction which paid `sat` as a fee, along with the height of
+  /// the block that collected it, oldest first.
+  pub(crate) fn fee_provenance(&self, sat: Sat) -> Result<Vec<(Height, Txid)>> {
+    self.require(IndexOption::FeeProvenance, "fee provenance")?;
 
-    for (key, value) in outpoint_to_sat_ranges.range([0; 36]..)? {
-      let mut offset = 0;
-      for chunk in value.chunks_exact(11) {
-        let (start, end) = Index::decode_sat_range(chunk.try_into().unwrap());
-        if start <= sat && sat < end {
-          let outpoint = decode_outpoint(*key);
-          return Ok(Some(SatPoint {
-            outpoint,
-            offset: offset + sat - start,
-          }));
+    let rtx = self.database.begin_read()?;
+
+    let table = rtx.open_multimap_table(FEE_RANGE_START_TO_PROVENANCE)?;
+
+    // Sat ranges are only ever split, never joined, so every fee range that
+    // contains `sat` starts within the subsidy of the block that mined it.
+    let start = sat.height().starting_sat().n();
+
+    let mut provenance = Vec::new();
+    for (_start, entries) in table.range(&start..=&sat.n())? {
+      for entry in entries {
+        let (height, end, txid) = decode_fee_provenance(*entry);
+        if sat.n() < end {
+          provenance.push((height, txid));
         }
-        offset += end - start;
       }
     }
 
-    Ok(None)
+    provenance.sort();
+
+    Ok(provenance)
   }
 
-  fn list_inner(&self, outpoint: OutPointArray) -> Result<Option<Vec<u8>>> {
+  /// First sat that `txid` paid as a fee.
+  pub(crate) fn fee_sat(&self, txid: Txid) -> Result<Option<Sat>> {
+    self.require(IndexOption::FeeProvenance, "fee provenance")?;
+
     Ok(
       self
-        .database
         .begin_read()?
-        .open_table(OUTPOINT_TO_SAT_RANGES)?
-        .get(&outpoint)?
-        .map(|outpoint| outpoint.to_vec()),
+        .0
+        .open_table(FEE_TXID_TO_FIRST_SAT)?
+        .get(txid.as_inner())?
+        .map(Sat),
     )
   }
 
-  pub(crate) fn list(&self, outpoint: OutPoint) -> Result<Option<List>> {
-    self.require_satoshi_index("list")?;
-
-    let outpoint_encoded = encode_outpoint(outpoint);
+  /// Current location of the first sat that `txid` paid as a fee. This scans
+  /// the sat ranges of every unspent output, so it is only done on request.
+  pub(crate) fn fee_satpoint(&self, txid: Txid) -> Result<Option<SatPoint>> {
+    let Some(sat) = self.fee_sat(txid)? else {
+      return Ok(None);
+    };
 
-    let sat_ranges = self.list_inner(outpoint_encoded)?;
+    self.find(sat.n())
+  }
 
-    match sat_ranges {
-      Some(sat_ranges) => Ok(Some(List::Unspent(
-        sat_ranges
-          .chunks_exact(11)
-          .map(|chunk| Self::decode_sat_range(chunk.try_into().unwrap()))
-          .collect(),
-      ))),
-      None => {
-        if self.is_transaction_in_active_chain(outpoint.txid)? {
-          Ok(Some(List::Spent))
-        } else {
-          Ok(None)
-        }
-      }
+  /// Only the first sat of each block's subsidy can be uncommon or rarer, so
+  /// this is at most one sat, along with its location if it's being tracked.
+  pub(crate) fn block_rare_sats(&self, height: Height) -> Result<Vec<(Sat, Option<SatPoint>)>> {
+    if height.subsidy() == 0 {
+      return Ok(Vec::new());
     }
+
+    let sat = height.starting_sat();
+
+    let satpoint = self
+      .begin_read()?
+      .0
+      .open_table(SAT_TO_SATPOINT)?
+      .get(&sat.n())?
+      .map(|satpoint| SatPointKey::from_array(*satpoint).satpoint());
+
+    Ok(vec![(sat, satpoint)])
   }
 
-  pub(crate) fn blocktime(&self, height: Height) -> Result<Blocktime> {
-    let height = height.n();
+  pub(crate) fn reward_breakdown(&self, height: Height) -> Result<Option<Vec<RewardSegment>>> {
+    if !self.has_sat_ranges()? {
+      return Ok(None);
+    }
 
-    match self.get_block_by_height(height)? {
-      Some(block) => Ok(Blocktime::Confirmed(block.header.time.into())),
-      None => {
-        let tx = self.database.begin_read()?;
+    let rtx = self.begin_read()?;
 
-        let current = tx
-          .open_table(HEIGHT_TO_BLOCK_HASH)?
-          .range(0..)?
-          .rev()
-          .next()
-          .map(|(height, _hash)| height)
-          .unwrap_or(0);
-
-        let expected_blocks = height.checked_sub(current).with_context(|| {
-          format!("current {current} height is greater than sat height {height}")
-        })?;
-
-        Ok(Blocktime::Expected(
-          Utc::now().timestamp() + 10 * 60 * i64::try_from(expected_blocks).unwrap(),
-        ))
-      }
+    let table = rtx.0.open_table(HEIGHT_TO_REWARD_BREAKDOWN)?;
+
+    let Some(breakdown) = table.get(&height.n())? else {
+      return Ok(None);
+    };
+
+    let (subsidy, fees) = breakdown.split_at(8);
+
+    let subsidy = u64::from_le_bytes(subsidy.try_into().unwrap());
+
+    let mut segments = Vec::new();
+
+    if subsidy > 0 {
+      segments.push(RewardSegment {
+        fee_transaction: None,
+        sats: subsidy,
+      });
     }
+
+    for chunk in fees.chunks_exact(12) {
+      let (tx_offset, fee) = chunk.split_at(4);
+      segments.push(RewardSegment {
+        fee_transaction: Some(
+          u32::from_le_bytes(tx_offset.try_into().unwrap())
+            .try_into()
+            .unwrap(),
+        ),
+        sats: u64::from_le_bytes(fee.try_into().unwrap()),
+      });
+    }
+
+    Ok(Some(segments))
   }
 
-  pub(crate) fn get_inscriptions(&self) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+  pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<BlockHeader>> {
+    self.rpc.get()?.get_block_header(&hash).into_option()
+  }
+
+  pub(crate) fn header_timestamp(&self, height: Height) -> Result<Option<i64>> {
+    let Some(hash) = self
+      .begin_read()?
+      .0
+      .open_table(HEIGHT_TO_BLOCK_HASH)?
+      .get(&height.n())?
+      .map(|hash| BlockHash::from_inner(*hash))
+    else {
+      return Ok(None);
+    };
+
+    Ok(self.block_header(hash)?.map(|header| header.time.into()))
+  }
+
+  pub(crate) fn get_block_by_height(&self, height: u64) -> Result<Option<Block>> {
+    let client = self.rpc.get()?;
+
+    Ok(
+      client
+        .get_block_hash(height)
+        .into_option()?
+        .map(|hash| client.get_block(&hash))
+        .transpose()?,
+    )
+  }
+
+  pub(crate) fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
+    self.rpc.get()?.get_block(&hash).into_option()
+  }
+
+  pub(crate) fn get_inscription_id_by_sat(&self, sat: Sat) -> Result<Option<InscriptionId>> {
     Ok(
       self
         .database
         .begin_read()?
-        .open_table(SATPOINT_TO_INSCRIPTION_ID)?
-        .range([0; 44]..)?
-        .map(|(satpoint, id)| (decode_satpoint(*satpoint), decode_inscription_id(*id)))
-        .collect(),
+        .open_table(SAT_TO_INSCRIPTION_ID)?
+        .get(&sat.n())?
+        .map(|txid| Txid::from_inner(*txid)),
     )
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  /// The inscription revealed by `txid`, and its location, or `None` if the
+  /// index hasn't seen it.
+  pub(crate) fn get_inscription_by_inscription_id(
+    &self,
+    txid: Txid,
+  ) -> Result<Option<(Inscription, SatPoint)>> {
+    let Some(satpoint) = self
+      .database
+      .begin_read()?
+      .open_table(INSCRIPTION_ID_TO_SATPOINT)?
+      .get(InscriptionIdKey::new(txid).as_array())?
+      .map(|satpoint| SatPointKey::from_array(*satpoint).satpoint())
+    else {
+      return Ok(None);
+    };
 
-  struct Context {
-    rpc_server: test_bitcoincore_rpc::Handle,
-    #[allow(unused)]
-    tempdir: TempDir,
-    index: Index,
+    Ok(
+      self
+        .get_transaction(txid)?
+        .and_then(|tx| Inscription::from_transaction(&tx))
+        .map(|inscription| (inscription, satpoint)),
+    )
   }
 
-  impl Context {
-    fn with_args(args: &str) -> Self {
-      let rpc_server = test_bitcoincore_rpc::spawn();
-
-      let tempdir = TempDir::new().unwrap();
-      let cookie_file = tempdir.path().join("cookie");
-      fs::write(&cookie_file, "username:password").unwrap();
-      let options = Options::try_parse_from(
-        format!(
-          "
-          ord
-          --rpc-url {}
-          --data-dir {}
-          --cookie-file {}
-          --chain regtest
-          {args}
-        ",
-          rpc_server.url(),
-          tempdir.path().display(),
-          cookie_file.display(),
-        )
-        .split_whitespace(),
-      )
-      .unwrap();
-      let index = Index::open(&options).unwrap();
-      index.update().unwrap();
+  /// Look up each of `inscription_ids`, along with its sat if the index tracks
+  /// sat ranges. Locations are all read in one transaction, so they are
+  /// consistent with each other even if blocks are indexed meanwhile.
+  pub(crate) fn get_inscriptions_by_inscription_ids(
+    &self,
+    inscription_ids: &[InscriptionId],
+  ) -> Result<Vec<Option<InscriptionEntry>>> {
+    let rtx = self.database.begin_read()?;
+    let inscription_id_to_satpoint = rtx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
+    let inscription_id_to_inscription_number =
+      rtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_NUMBER)?;
+    let inscription_id_to_burned = rtx.open_table(INSCRIPTION_ID_TO_BURNED)?;
+    let outpoint_to_sat_ranges = if Self::has_in(&rtx, IndexOption::SatRanges)? {
+      Some(rtx.open_table(OUTPOINT_TO_SAT_RANGES)?)
+    } else {
+      None
+    };
 
-      Self {
-        rpc_server,
-        tempdir,
-        index,
-      }
+    let mut inscriptions = Vec::with_capacity(inscription_ids.len());
+
+    for inscription_id in inscription_ids {
+      let key = InscriptionIdKey::new(*inscription_id);
+
+      let Some(satpoint) = inscription_id_to_satpoint
+        .get(key.as_array())?
+        .map(|satpoint| SatPointKey::from_array(*satpoint).satpoint())
+      else {
+        inscriptions.push(None);
+        continue;
+      };
+
+      let Some(inscription) = self
+        .get_transaction(*inscription_id)?
+        .and_then(|tx| Inscription::from_transaction(&tx))
+      else {
+        inscriptions.push(None);
+        continue;
+      };
+
+      let sat = match &outpoint_to_sat_ranges {
+        Some(outpoint_to_sat_ranges) => outpoint_to_sat_ranges
+          .get(OutPointKey::new(satpoint.outpoint).as_array())?
+          .and_then(|sat_ranges| Self::sat_at_offset(sat_ranges, satpoint.offset)),
+        None => None,
+      };
+
+      let number = inscription_id_to_inscription_number
+        .get(key.as_array())?
+        .ok_or_else(|| anyhow!("inscription {inscription_id} has no number"))?;
+
+      inscriptions.push(Some(InscriptionEntry {
+        inscription,
+        satpoint,
+        sat,
+        number,
+        burned: inscription_id_to_burned.get(key.as_array())?.is_some(),
+      }));
     }
+
+    Ok(inscriptions)
   }
 
-  #[test]
-  fn height_limit() {
-    {
-      let context = Context::with_args("--height-limit 0");
-      context.rpc_server.mine_blocks(1);
-      context.index.update().unwrap();
-      assert_eq!(context.index.height().unwrap(), None);
-      assert_eq!(context.index.block_count().unwrap(), 0);
-    }
+  /// The inscription IDs that start with `prefix`, in hex order. Keys sort
+  /// like the hex IDs, so this only reads the range of keys that share it.
+  pub(crate) fn find_inscriptions_by_prefix(
+    &self,
+    prefix: &InscriptionIdPrefix,
+  ) -> Result<Vec<InscriptionId>> {
+    let (start, end) = prefix.bounds();
 
-    {
-      let context = Context::with_args("--height-limit 1");
-      context.rpc_server.mine_blocks(1);
-      context.index.update().unwrap();
-      assert_eq!(context.index.height().unwrap(), Some(Height(0)));
-      assert_eq!(context.index.block_count().unwrap(), 1);
-    }
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_SATPOINT)?
+        .range(*InscriptionIdKey::new(start).as_array()..=*InscriptionIdKey::new(end).as_array())?
+        .map(|(inscription_id, _satpoint)| {
+          InscriptionIdKey::from_array(*inscription_id).inscription_id()
+        })
+        .collect(),
+    )
+  }
 
-    {
-      let context = Context::with_args("--height-limit 2");
-      context.rpc_server.mine_blocks(2);
-      context.index.update().unwrap();
-      assert_eq!(context.index.height().unwrap(), Some(Height(1)));
-      assert_eq!(context.index.block_count().unwrap(), 2);
-    }
+  pub(crate) fn inscription_count(&self) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?
+        .iter()?
+        .next_back()
+        .map(|(number, _id)| number + 1)
+        .unwrap_or_default(),
+    )
   }
 
-  #[test]
-  fn list_first_coinbase_transaction() {
-    let context = Context::with_args("--index-satoshis");
-    assert_eq!(
-      context
-        .index
-        .list(
-          "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
-            .parse()
-            .unwrap()
-        )
-        .unwrap()
-        .unwrap(),
-      List::Unspent(vec![(0, 50 * COIN_VALUE)])
+  /// The number of the inscription, counting from zero in the order that
+  /// inscriptions were indexed.
+  pub(crate) fn get_inscription_number(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<u64>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_INSCRIPTION_NUMBER)?
+        .get(InscriptionIdKey::new(inscription_id).as_array())?,
     )
   }
 
-  #[test]
-  fn list_second_coinbase_transaction() {
-    let context = Context::with_args("--index-satoshis");
-    let txid = context.rpc_server.mine_blocks(1)[0].txdata[0].txid();
-    context.index.update().unwrap();
-    assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Unspent(vec![(50 * COIN_VALUE, 100 * COIN_VALUE)])
+  /// The height of the block that revealed the inscription.
+  pub(crate) fn get_genesis_height(&self, inscription_id: InscriptionId) -> Result<Option<u64>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_GENESIS_HEIGHT)?
+        .get(InscriptionIdKey::new(inscription_id).as_array())?,
     )
   }
 
-  #[test]
-  fn list_split_ranges_are_tracked_correctly() {
-    let context = Context::with_args("--index-satoshis");
+  pub(crate) fn get_inscription_id_by_inscription_number(
+    &self,
+    number: u64,
+  ) -> Result<Option<InscriptionId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?
+        .get(&number)?
+        .map(|id| Txid::from_inner(*id)),
+    )
+  }
 
-    context.rpc_server.mine_blocks(1);
-    let split_coinbase_output = TransactionTemplate {
-      input_slots: &[(1, 0, 0)],
-      output_count: 2,
-      fee: 0,
+  /// The `n` most recently revealed inscriptions, newest first.
+  pub(crate) fn get_latest_inscriptions(&self, n: usize) -> Result<Vec<InscriptionId>> {
+    Ok(self.get_latest_inscriptions_with_prev_and_next(n, None)?.0)
+  }
+
+  /// Up to `n` inscriptions, newest first, starting with inscription number
+  /// `from`, or the latest inscription if `from` is `None`. Also returns the
+  /// number to start the page of older inscriptions at, and the one to start
+  /// the page of newer inscriptions at, if there are any.
+  pub(crate) fn get_latest_inscriptions_with_prev_and_next(
+    &self,
+    n: usize,
+    from: Option<u64>,
+  ) -> Result<(Vec<InscriptionId>, Option<u64>, Option<u64>)> {
+    let rtx = self.database.begin_read()?;
+
+    let inscription_number_to_inscription_id =
+      rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let Some(latest) = inscription_number_to_inscription_id
+      .iter()?
+      .next_back()
+      .map(|(number, _id)| number)
+    else {
+      return Ok((Vec::new(), None, None));
     };
-    let txid = context.rpc_server.broadcast_tx(split_coinbase_output);
 
-    context.rpc_server.mine_blocks(1);
-    context.index.update().unwrap();
+    let from = from.unwrap_or(latest).min(latest);
 
-    assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Unspent(vec![(50 * COIN_VALUE, 75 * COIN_VALUE)])
-    );
+    let page_size = u64::try_from(n)?;
 
-    assert_eq!(
-      context.index.list(OutPoint::new(txid, 1)).unwrap().unwrap(),
-      List::Unspent(vec![(75 * COIN_VALUE, 100 * COIN_VALUE)])
-    );
-  }
+    let inscriptions = inscription_number_to_inscription_id
+      .range(..=from)?
+      .rev()
+      .take(n)
+      .map(|(_number, id)| Txid::from_inner(*id))
+      .collect();
 
-  #[test]
-  fn list_merge_ranges_are_tracked_correctly() {
-    let context = Context::with_args("--index-satoshis");
+    let prev = from.checked_sub(page_size);
 
-    context.rpc_server.mine_blocks(2);
-    let merge_coinbase_outputs = TransactionTemplate {
-      input_slots: &[(1, 0, 0), (2, 0, 0)],
-      output_count: 1,
-      fee: 0,
-    };
+    let next = (from < latest).then(|| from.saturating_add(page_size).min(latest));
 
-    let txid = context.rpc_server.broadcast_tx(merge_coinbase_outputs);
-    context.rpc_server.mine_blocks(1);
-    context.index.update().unwrap();
+    Ok((inscriptions, prev, next))
+  }
 
-    assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Unspent(vec![
-        (50 * COIN_VALUE, 100 * COIN_VALUE),
-        (100 * COIN_VALUE, 150 * COIN_VALUE)
-      ]),
+  /// The inscriptions in `collection`, oldest first.
+  pub(crate) fn get_collection_inscriptions(
+    &self,
+    collection: &CollectionSlug,
+  ) -> Result<Vec<InscriptionId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_multimap_table(COLLECTION_TO_INSCRIPTION_IDS)?
+        .get(collection.as_str())?
+        .map(|member| decode_collection_member(*member))
+        .collect(),
+    )
+  }
+
+  /// Page `page_index` of the inscriptions in `collection`, oldest first,
+  /// and whether there are more after it.
+  pub(crate) fn get_collection_inscriptions_paginated(
+    &self,
+    collection: &CollectionSlug,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let mut inscriptions = self
+      .database
+      .begin_read()?
+      .open_multimap_table(COLLECTION_TO_INSCRIPTION_IDS)?
+      .get(collection.as_str())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|member| decode_collection_member(*member))
+      .collect::<Vec<InscriptionId>>();
+
+    let more = inscriptions.len() > page_size;
+
+    inscriptions.truncate(page_size);
+
+    Ok((inscriptions, more))
+  }
+
+  /// Page `page_index` of the inscriptions revealed in the block at
+  /// `height`, in the order they were revealed, and whether there are more
+  /// after it.
+  pub(crate) fn get_block_inscriptions_paginated(
+    &self,
+    height: u64,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let mut inscriptions = self
+      .database
+      .begin_read()?
+      .open_multimap_table(HEIGHT_TO_INSCRIPTION_IDS)?
+      .get(&height)?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|inscription| decode_block_inscription(*inscription))
+      .collect::<Vec<InscriptionId>>();
+
+    let more = inscriptions.len() > page_size;
+
+    inscriptions.truncate(page_size);
+
+    Ok((inscriptions, more))
+  }
+
+  /// Whether the inscription has been sent to an output that can never be
+  /// spent. Its sat is tracked as usual.
+  pub(crate) fn is_inscription_burned(&self, inscription_id: InscriptionId) -> Result<bool> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_BURNED)?
+        .get(InscriptionIdKey::new(inscription_id).as_array())?
+        .is_some(),
+    )
+  }
+
+  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    if txid == self.genesis_block_coinbase_txid {
+      Ok(Some(self.genesis_block_coinbase_transaction.clone()))
+    } else {
+      self
+        .rpc
+        .get()?
+        .get_raw_transaction(&txid, None)
+        .into_option()
+    }
+  }
+
+  pub(crate) fn is_transaction_in_active_chain(&self, txid: Txid) -> Result<bool> {
+    Ok(
+      self
+        .rpc
+        .get()?
+        .get_raw_transaction_info(&txid, None)
+        .into_option()?
+        .and_then(|transaction_info| {
+          transaction_info
+            .confirmations
+            .map(|confirmations| confirmations > 0)
+        })
+        .unwrap_or(false),
+    )
+  }
+
+  pub(crate) fn find(&self, sat: u64) -> Result<Option<SatPoint>> {
+    Ok(self.find_many(&[Sat(sat)])?.remove(&Sat(sat)))
+  }
+
+  /// Locate several sats with a single scan of the sat ranges table.
+  pub(crate) fn find_many(&self, sats: &[Sat]) -> Result<BTreeMap<Sat, SatPoint>> {
+    self.require(IndexOption::SatRanges, "find")?;
+
+    let rtx = self.begin_read()?;
+
+    let block_count = rtx.block_count()?;
+
+    let mut remaining = sats
+      .iter()
+      .copied()
+      .filter(|sat| sat.height().n() < block_count)
+      .collect::<BTreeSet<Sat>>();
+
+    let mut satpoints = BTreeMap::new();
+
+    if remaining.is_empty() {
+      return Ok(satpoints);
+    }
+
+    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
+
+    for (key, value) in outpoint_to_sat_ranges.iter()? {
+      let mut offset = 0;
+      for chunk in value.chunks_exact(11) {
+        let (start, end) = Index::decode_sat_range(chunk.try_into().unwrap());
+
+        let found = remaining
+          .range(Sat(start)..Sat(end))
+          .copied()
+          .collect::<Vec<Sat>>();
+
+        for sat in found {
+          remaining.remove(&sat);
+          satpoints.insert(
+            sat,
+            SatPoint {
+              outpoint: OutPointKey::from_array(*key).outpoint(),
+              offset: offset + sat.n() - start,
+            },
+          );
+        }
+
+        offset += end - start;
+      }
+
+      if remaining.is_empty() {
+        break;
+      }
+    }
+
+    Ok(satpoints)
+  }
+
+  pub(crate) fn find_next_rare(&self, sat: Sat, rarity: Rarity) -> Result<Option<(Sat, SatPoint)>> {
+    self.require(IndexOption::RareSats, "find")?;
+
+    let rtx = self.begin_read()?;
+
+    let sat_to_satpoint = rtx.0.open_table(SAT_TO_SATPOINT)?;
+
+    for (next, satpoint) in sat_to_satpoint.range(sat.n()..)? {
+      if Sat(next).rarity() >= rarity {
+        return Ok(Some((
+          Sat(next),
+          SatPointKey::from_array(*satpoint).satpoint(),
+        )));
+      }
+    }
+
+    Ok(None)
+  }
+
+  fn list_inner(&self, outpoint: OutPointKey) -> Result<Option<Vec<u8>>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(OUTPOINT_TO_SAT_RANGES)?
+        .get(outpoint.as_array())?
+        .map(|outpoint| outpoint.to_vec()),
+    )
+  }
+
+  pub(crate) fn list(&self, outpoint: OutPoint) -> Result<Option<List>> {
+    Ok(self.list_sat_ranges(outpoint)?.map(|list| match list {
+      List::Spent => List::Spent,
+      List::Unspent(sat_ranges) => List::Unspent(sat_ranges.iter().collect()),
+    }))
+  }
+
+  /// Like `list`, but leaves the ranges of unspent outputs undecoded.
+  pub(crate) fn list_sat_ranges(&self, outpoint: OutPoint) -> Result<Option<List<SatRanges>>> {
+    self.require(IndexOption::SatRanges, "list")?;
+
+    match self.list_inner(OutPointKey::new(outpoint))? {
+      Some(sat_ranges) => Ok(Some(List::Unspent(SatRanges(sat_ranges)))),
+      None => {
+        if self.is_transaction_in_active_chain(outpoint.txid)? {
+          Ok(Some(List::Spent))
+        } else {
+          Ok(None)
+        }
+      }
+    }
+  }
+
+  /// The sat ranges of `outpoint` when it was spent, or `None` if it hasn't
+  /// been spent.
+  pub(crate) fn spent_sat_ranges(&self, outpoint: OutPoint) -> Result<Option<Vec<(u64, u64)>>> {
+    self.require(IndexOption::SpentSatRanges, "spent sat ranges")?;
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SPENT_OUTPOINT_TO_SAT_RANGES)?
+        .get(OutPointKey::new(outpoint).as_array())?
+        .map(|sat_ranges| {
+          sat_ranges
+            .chunks_exact(11)
+            .map(|chunk| Self::decode_sat_range(chunk.try_into().unwrap()))
+            .collect()
+        }),
+    )
+  }
+
+  /// The sat at `satpoint`, or `None` if its output isn't unspent.
+  pub(crate) fn sat_at_satpoint(&self, satpoint: SatPoint) -> Result<Option<Sat>> {
+    self.require(IndexOption::SatRanges, "sat lookup")?;
+
+    Ok(
+      self
+        .list_inner(OutPointKey::new(satpoint.outpoint))?
+        .and_then(|sat_ranges| Self::sat_at_offset(&sat_ranges, satpoint.offset)),
+    )
+  }
+
+  fn sat_at_offset(sat_ranges: &[u8], mut offset: u64) -> Option<Sat> {
+    for chunk in sat_ranges.chunks_exact(11) {
+      let (start, end) = Self::decode_sat_range(chunk.try_into().unwrap());
+
+      if offset < end - start {
+        return Some(Sat(start + offset));
+      }
+
+      offset -= end - start;
+    }
+
+    None
+  }
+
+  pub(crate) fn blocktime(&self, height: Height) -> Result<Blocktime> {
+    if let Some(timestamp) = self.header_timestamp(height)? {
+      return Ok(Blocktime::Confirmed(timestamp));
+    }
+
+    let Some(tip) = self.height()? else {
+      return Ok(Blocktime::extrapolate(
+        (Height(0), Utc::now().timestamp()),
+        (Height(0), Utc::now().timestamp()),
+        height,
+      ));
+    };
+
+    let timestamp = |height: Height| {
+      self
+        .header_timestamp(height)?
+        .ok_or_else(|| anyhow!("block {height} has not been indexed"))
+    };
+
+    let start = tip
+      .checked_sub(Blocktime::SAMPLE_BLOCKS)
+      .unwrap_or(Height(0));
+
+    Ok(Blocktime::extrapolate(
+      (start, timestamp(start)?),
+      (tip, timestamp(tip)?),
+      height,
+    ))
+  }
+
+  /// Inscriptions on `outpoint`, ordered by offset. Satpoint keys sort by
+  /// outpoint and then offset, so this is the range of keys from offset 0 to
+  /// `u64::MAX` within `outpoint`.
+  fn inscriptions_on_output<'a, 'tx: 'a>(
+    satpoint_to_inscription_id: &'a impl ReadableTable<&'tx SatPointArray, &'tx InscriptionIdArray>,
+    outpoint: OutPoint,
+  ) -> Result<impl Iterator<Item = (SatPoint, InscriptionId)> + 'a> {
+    let start = SatPointKey::new(SatPoint {
+      outpoint,
+      offset: 0,
+    });
+
+    let end = SatPointKey::new(SatPoint {
+      outpoint,
+      offset: u64::MAX,
+    });
+
+    Ok(
+      satpoint_to_inscription_id
+        .range(*start.as_array()..=*end.as_array())?
+        .map(|(satpoint, id)| {
+          (
+            SatPointKey::from_array(*satpoint).satpoint(),
+            decode_inscription_id(*id),
+          )
+        }),
+    )
+  }
+
+  /// The unspent outputs paying to `script_pubkey`, in outpoint order.
+  pub(crate) fn get_address_outpoints(&self, script_pubkey: &Script) -> Result<Vec<OutPoint>> {
+    self.require(IndexOption::Addresses, "address index")?;
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?
+        .get(script_pubkey.as_bytes())?
+        .map(|outpoint| OutPointKey::from_array(*outpoint).outpoint())
+        .collect(),
+    )
+  }
+
+  /// Page `page_index` of the outputs paying to `script_pubkey`, in outpoint
+  /// order, and whether there are more.
+  pub(crate) fn get_address_outpoints_paginated(
+    &self,
+    script_pubkey: &Script,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<OutPoint>, bool)> {
+    self.require(IndexOption::Addresses, "address index")?;
+
+    let mut outpoints = self
+      .database
+      .begin_read()?
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?
+      .get(script_pubkey.as_bytes())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|outpoint| OutPointKey::from_array(*outpoint).outpoint())
+      .collect::<Vec<OutPoint>>();
+
+    let more = outpoints.len() > page_size;
+
+    outpoints.truncate(page_size);
+
+    Ok((outpoints, more))
+  }
+
+  pub(crate) fn get_inscriptions_on_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    Ok(
+      Self::inscriptions_on_output(
+        &self
+          .database
+          .begin_read()?
+          .open_table(SATPOINT_TO_INSCRIPTION_ID)?,
+        outpoint,
+      )?
+      .collect(),
+    )
+  }
+
+  pub(crate) fn get_inscriptions(&self) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SATPOINT_TO_INSCRIPTION_ID)?
+        .range([0; 44]..)?
+        .map(|(satpoint, id)| {
+          (
+            SatPointKey::from_array(*satpoint).satpoint(),
+            decode_inscription_id(*id),
+          )
+        })
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{
+      secp256k1::rand::{rngs::StdRng, Rng, SeedableRng},
+      PackedLockTime,
+    },
+    bitcoincore_rpc::Auth,
+  };
+
+  struct Context {
+    options: Options,
+    rpc_server: test_bitcoincore_rpc::Handle,
+    #[allow(unused)]
+    tempdir: TempDir,
+    index: Index,
+  }
+
+  impl Context {
+    fn with_args(args: &str) -> Self {
+      let rpc_server = test_bitcoincore_rpc::spawn();
+
+      let tempdir = TempDir::new().unwrap();
+      let cookie_file = tempdir.path().join("cookie");
+      fs::write(&cookie_file, "username:password").unwrap();
+      let options = Options::try_parse_from(
+        format!(
+          "
+          ord
+          --rpc-url {}
+          --data-dir {}
+          --cookie-file {}
+          --chain regtest
+          {args}
+        ",
+          rpc_server.url(),
+          tempdir.path().display(),
+          cookie_file.display(),
+        )
+        .split_whitespace(),
+      )
+      .unwrap();
+      let index = Index::open(&options).unwrap();
+      index.update().unwrap();
+
+      Self {
+        options,
+        rpc_server,
+        tempdir,
+        index,
+      }
+    }
+  }
+
+  #[test]
+  fn headers_are_fetched_in_batches() {
+    let context = Context::with_args("");
+    let blocks = context.rpc_server.mine_blocks(3);
+
+    let client = Client::new(&context.rpc_server.url(), Auth::None).unwrap();
+
+    let batches = context.rpc_server.batch_count();
+
+    assert_eq!(
+      Updater::get_headers_batched(&client, 1, 2).unwrap(),
+      [blocks[0].header, blocks[1].header]
+    );
+    assert_eq!(
+      Updater::get_headers_batched(&client, 2, 1000).unwrap(),
+      [blocks[1].header, blocks[2].header]
+    );
+    assert!(Updater::get_headers_batched(&client, 4, 1000)
+      .unwrap()
+      .is_empty());
+
+    assert_eq!(context.rpc_server.batch_count() - batches, 5);
+
+    context.rpc_server.reject_batches();
+
+    assert!(Updater::get_headers_batched(&client, 1, 2).is_err());
+  }
+
+  #[test]
+  fn schema_version_mismatch() {
+    let Context {
+      options,
+      rpc_server: _rpc_server,
+      tempdir: _tempdir,
+      index,
+    } = Context::with_args("");
+
+    {
+      let wtx = index.database.begin_write().unwrap();
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &0)
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    drop(index);
+
+    assert_eq!(
+      Index::open(&options).err().unwrap().to_string(),
+      format!(
+        "index at `{}` has schema version 0, but this version of ord requires schema version {SCHEMA_VERSION}, so the index must be deleted and rebuilt",
+        options.data_dir().unwrap().join("index.redb").display(),
+      )
+    );
+  }
+
+  #[test]
+  fn subsidy_schedule_mismatch() {
+    let Context {
+      options,
+      rpc_server: _rpc_server,
+      tempdir: _tempdir,
+      index,
+    } = Context::with_args("");
+
+    drop(index);
+
+    // The schedule is fixed once used, so open the index with another one on
+    // a fresh thread.
+    let error = thread::spawn({
+      let options = options.clone();
+      move || {
+        Schedule {
+          initial_subsidy: 1000,
+          halving_interval: 10,
+        }
+        .install()
+        .unwrap();
+
+        Index::open(&options).err().unwrap().to_string()
+      }
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(
+      error,
+      format!(
+        "index at `{}` was built with initial subsidy 5000000000 and halving interval 210000, but the current subsidy schedule has initial subsidy 1000 and halving interval 10, so the index must be deleted and rebuilt",
+        options.data_dir().unwrap().join("index.redb").display(),
+      )
+    );
+  }
+
+  fn corrupt(
+    context: Context,
+    f: impl FnOnce(&WriteTransaction),
+  ) -> (Options, test_bitcoincore_rpc::Handle, TempDir) {
+    let Context {
+      options,
+      rpc_server,
+      tempdir,
+      index,
+    } = context;
+
+    let wtx = index.database.begin_write().unwrap();
+    f(&wtx);
+    wtx.commit().unwrap();
+
+    (options, rpc_server, tempdir)
+  }
+
+  fn integrity_check_failure(options: &Options, message: &str) {
+    assert_eq!(
+      Index::open(options).err().unwrap().to_string(),
+      format!(
+        "index at `{}` failed integrity check: {message}",
+        options.data_dir().unwrap().join("index.redb").display(),
+      )
+    );
+  }
+
+  #[test]
+  fn schema_version_is_checked_before_integrity() {
+    let (options, _rpc_server, _tempdir) = corrupt(Context::with_args(""), |wtx| {
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &0)
+        .unwrap();
+      assert!(wtx.delete_table(INDEX_OPTIONS).unwrap());
+    });
+
+    assert_eq!(
+      Index::open(&options).err().unwrap().to_string(),
+      format!(
+        "index at `{}` has schema version 0, but this version of ord requires schema version {SCHEMA_VERSION}, so the index must be deleted and rebuilt",
+        options.data_dir().unwrap().join("index.redb").display(),
+      )
+    );
+  }
+
+  #[test]
+  fn integrity_check_detects_missing_index_options() {
+    let (options, _rpc_server, _tempdir) = corrupt(Context::with_args(""), |wtx| {
+      assert!(wtx.delete_table(INDEX_OPTIONS).unwrap());
+    });
+
+    integrity_check_failure(
+      &options,
+      "index options table is missing, so the index must be deleted and rebuilt",
+    );
+  }
+
+  #[test]
+  fn integrity_check_detects_missing_block() {
+    let context = Context::with_args("");
+    context.rpc_server.mine_blocks(3);
+    context.index.update().unwrap();
+
+    let (options, _rpc_server, _tempdir) = corrupt(context, |wtx| {
+      wtx
+        .open_table(HEIGHT_TO_BLOCK_HASH)
+        .unwrap()
+        .remove(&1)
+        .unwrap();
+    });
+
+    integrity_check_failure(
+      &options,
+      "block 1 is missing, but blocks through height 3 are indexed, so the index must be deleted and rebuilt",
+    );
+  }
+
+  #[test]
+  fn integrity_check_detects_tip_behind_statistics() {
+    let context = Context::with_args("");
+    context.rpc_server.mine_blocks(3);
+    context.index.update().unwrap();
+
+    let (options, _rpc_server, _tempdir) = corrupt(context, |wtx| {
+      wtx
+        .open_table(HEIGHT_TO_BLOCK_HASH)
+        .unwrap()
+        .remove(&3)
+        .unwrap();
+    });
+
+    integrity_check_failure(
+      &options,
+      "epoch statistics count 4 blocks, but 3 blocks are indexed, so the index must be deleted and rebuilt",
+    );
+  }
+
+  #[test]
+  fn integrity_check_can_be_skipped() {
+    let (mut options, _rpc_server, _tempdir) = corrupt(Context::with_args(""), |wtx| {
+      assert!(wtx.delete_table(INDEX_OPTIONS).unwrap());
+    });
+
+    assert!(Index::open(&options).is_err());
+
+    options.skip_integrity_check = true;
+
+    Index::open(&options).unwrap();
+  }
+
+  #[test]
+  fn inscriptions_on_output() {
+    let context = Context::with_args("");
+
+    let txid = outpoint(1).txid;
+
+    let satpoint = |vout, offset| SatPoint {
+      outpoint: OutPoint { txid, vout },
+      offset,
+    };
+
+    let inscriptions = [
+      (satpoint(0, 0), outpoint(2).txid),
+      (satpoint(0, u64::MAX), outpoint(3).txid),
+      (satpoint(1, 0), outpoint(4).txid),
+      (satpoint(256, 7), outpoint(5).txid),
+      (
+        SatPoint {
+          outpoint: OutPoint::new(outpoint(6).txid, 0),
+          offset: 0,
+        },
+        outpoint(7).txid,
+      ),
+    ];
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      {
+        let mut table = wtx.open_table(SATPOINT_TO_INSCRIPTION_ID).unwrap();
+        for (satpoint, inscription_id) in inscriptions {
+          table
+            .insert(
+              SatPointKey::new(satpoint).as_array(),
+              inscription_id.as_inner(),
+            )
+            .unwrap();
+        }
+      }
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_on_output(OutPoint { txid, vout: 0 })
+        .unwrap(),
+      [inscriptions[0], inscriptions[1]],
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_on_output(OutPoint { txid, vout: 1 })
+        .unwrap(),
+      [inscriptions[2]],
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_on_output(OutPoint { txid, vout: 256 })
+        .unwrap(),
+      [inscriptions[3]],
+    );
+
+    assert!(context
+      .index
+      .get_inscriptions_on_output(OutPoint { txid, vout: 2 })
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn find_inscriptions_by_prefix() {
+    let context = Context::with_args("");
+
+    let id = |hex: &str| -> InscriptionId { format!("{hex:0<64}").parse().unwrap() };
+
+    let inscription_ids = [
+      id(&format!("0123abcc{}", "f".repeat(56))),
+      id("0123abcd"),
+      id("0123abcd1"),
+      id(&format!("0123abcd{}", "f".repeat(56))),
+      id("0123abce"),
+      id("ff"),
+    ];
+
+    for inscription_id in inscription_ids {
+      context
+        .index
+        .insert_inscription_id(inscription_id, satpoint(1, 0));
+    }
+
+    let find = |prefix: &str| {
+      context
+        .index
+        .find_inscriptions_by_prefix(&prefix.parse().unwrap())
+        .unwrap()
+    };
+
+    assert_eq!(
+      find("0123abcd"),
+      [inscription_ids[1], inscription_ids[2], inscription_ids[3]]
+    );
+    assert_eq!(find("0123ABCD1"), [inscription_ids[2]]);
+    assert_eq!(find("0123abcc"), [inscription_ids[0]]);
+    assert_eq!(find("ff000000"), [inscription_ids[5]]);
+    assert_eq!(find(&inscription_ids[3].to_string()), [inscription_ids[3]]);
+    assert!(find("0123abcf").is_empty());
+  }
+
+  #[test]
+  fn updates_reuse_rpc_connections() {
+    let context = Context::with_args("");
+
+    let connections = context.rpc_server.connections();
+
+    for _ in 0..10 {
+      context.rpc_server.mine_blocks(1);
+      context.index.update().unwrap();
+      context.index.block_header(BlockHash::all_zeros()).unwrap();
+    }
+
+    // The block fetching thread may not have returned its client by the time
+    // the next update starts, so up to two are in use.
+    assert!(context.rpc_server.connections() <= connections + 2);
+  }
+
+  #[test]
+  fn node_block_count_is_recorded_by_updates() {
+    let context = Context::with_args("");
+    assert_eq!(context.index.node_block_count(), Some(1));
+    context.rpc_server.mine_blocks(2);
+    assert_eq!(context.index.node_block_count(), Some(1));
+    context.index.update().unwrap();
+    assert_eq!(context.index.node_block_count(), Some(3));
+  }
+
+  #[test]
+  fn node_block_count_is_limited_by_height_limit() {
+    let context = Context::with_args("--height-limit 1");
+    context.rpc_server.mine_blocks(2);
+    context.index.update().unwrap();
+    assert_eq!(context.index.node_block_count(), Some(1));
+  }
+
+  #[test]
+  fn height_limit() {
+    {
+      let context = Context::with_args("--height-limit 0");
+      context.rpc_server.mine_blocks(1);
+      context.index.update().unwrap();
+      assert_eq!(context.index.height().unwrap(), None);
+      assert_eq!(context.index.block_count().unwrap(), 0);
+    }
+
+    {
+      let context = Context::with_args("--height-limit 1");
+      context.rpc_server.mine_blocks(1);
+      context.index.update().unwrap();
+      assert_eq!(context.index.height().unwrap(), Some(Height(0)));
+      assert_eq!(context.index.block_count().unwrap(), 1);
+    }
+
+    {
+      let context = Context::with_args("--height-limit 2");
+      context.rpc_server.mine_blocks(2);
+      context.index.update().unwrap();
+      assert_eq!(context.index.height().unwrap(), Some(Height(1)));
+      assert_eq!(context.index.block_count().unwrap(), 2);
+    }
+  }
+
+  #[test]
+  fn update_until_stops_after_one_block_once_stopped() {
+    let context = Context::with_args("");
+    context.rpc_server.mine_blocks(2);
+
+    context.index.update_until(&AtomicBool::new(true)).unwrap();
+    assert_eq!(context.index.block_count().unwrap(), 2);
+
+    context.index.update().unwrap();
+    assert_eq!(context.index.block_count().unwrap(), 3);
+  }
+
+  #[test]
+  fn list_first_coinbase_transaction() {
+    let context = Context::with_args("--index-satoshis");
+    assert_eq!(
+      context
+        .index
+        .list(
+          "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
+            .parse()
+            .unwrap()
+        )
+        .unwrap()
+        .unwrap(),
+      List::Unspent(vec![(0, 50 * COIN_VALUE)])
+    )
+  }
+
+  #[test]
+  fn list_second_coinbase_transaction() {
+    let context = Context::with_args("--index-satoshis");
+    let txid = context.rpc_server.mine_blocks(1)[0].txdata[0].txid();
+    context.index.update().unwrap();
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![(50 * COIN_VALUE, 100 * COIN_VALUE)])
+    )
+  }
+
+  #[test]
+  fn list_split_ranges_are_tracked_correctly() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(1);
+    let split_coinbase_output = TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 2,
+      fee: 0,
+    };
+    let txid = context.rpc_server.broadcast_tx(split_coinbase_output);
+
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![(50 * COIN_VALUE, 75 * COIN_VALUE)])
+    );
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 1)).unwrap().unwrap(),
+      List::Unspent(vec![(75 * COIN_VALUE, 100 * COIN_VALUE)])
+    );
+  }
+
+  #[test]
+  fn list_merge_ranges_are_tracked_correctly() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(2);
+    let merge_coinbase_outputs = TransactionTemplate {
+      input_slots: &[(1, 0, 0), (2, 0, 0)],
+      output_count: 1,
+      fee: 0,
+    };
+
+    let txid = context.rpc_server.broadcast_tx(merge_coinbase_outputs);
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![
+        (50 * COIN_VALUE, 100 * COIN_VALUE),
+        (100 * COIN_VALUE, 150 * COIN_VALUE)
+      ]),
+    );
+  }
+
+  #[test]
+  fn sat_at_satpoint_walks_merged_ranges() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(2);
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(1, 0, 0), (2, 0, 0)],
+      output_count: 1,
+      fee: 0,
+    });
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    let sat = |offset| {
+      context
+        .index
+        .sat_at_satpoint(SatPoint {
+          outpoint: OutPoint::new(txid, 0),
+          offset,
+        })
+        .unwrap()
+    };
+
+    assert_eq!(sat(0), Some(Sat(50 * COIN_VALUE)));
+    assert_eq!(sat(50 * COIN_VALUE + 1), Some(Sat(100 * COIN_VALUE + 1)));
+    assert_eq!(sat(100 * COIN_VALUE), None);
+  }
+
+  #[test]
+  fn list_fee_paying_transaction_range() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(1);
+    let fee_paying_tx = TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 2,
+      fee: 10,
+    };
+    let txid = context.rpc_server.broadcast_tx(fee_paying_tx);
+    let coinbase_txid = context.rpc_server.mine_blocks(1)[0].txdata[0].txid();
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![(50 * COIN_VALUE, 7499999995)]),
+    );
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 1)).unwrap().unwrap(),
+      List::Unspent(vec![(7499999995, 9999999990)]),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(coinbase_txid, 0))
+        .unwrap()
+        .unwrap(),
+      List::Unspent(vec![(10000000000, 15000000000), (9999999990, 10000000000)])
+    );
+  }
+
+  #[test]
+  fn list_two_fee_paying_transaction_range() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(2);
+    let first_fee_paying_tx = TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 10,
+    };
+    let second_fee_paying_tx = TransactionTemplate {
+      input_slots: &[(2, 0, 0)],
+      output_count: 1,
+      fee: 10,
+    };
+    context.rpc_server.broadcast_tx(first_fee_paying_tx);
+    context.rpc_server.broadcast_tx(second_fee_paying_tx);
+
+    let coinbase_txid = context.rpc_server.mine_blocks(1)[0].txdata[0].txid();
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(coinbase_txid, 0))
+        .unwrap()
+        .unwrap(),
+      List::Unspent(vec![
+        (15000000000, 20000000000),
+        (9999999990, 10000000000),
+        (14999999990, 15000000000)
+      ])
+    );
+  }
+
+  #[test]
+  fn list_null_output() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(1);
+    let no_value_output = TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 50 * COIN_VALUE,
+    };
+    let txid = context.rpc_server.broadcast_tx(no_value_output);
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![])
+    );
+  }
+
+  #[test]
+  fn list_null_input() {
+    let context = Context::with_args("--index-satoshis");
+
+    context.rpc_server.mine_blocks(1);
+    let no_value_output = TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 50 * COIN_VALUE,
+    };
+    context.rpc_server.broadcast_tx(no_value_output);
+    context.rpc_server.mine_blocks(1);
+
+    let no_value_input = TransactionTemplate {
+      input_slots: &[(2, 1, 0)],
+      output_count: 1,
+      fee: 0,
+    };
+    let txid = context.rpc_server.broadcast_tx(no_value_input);
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![])
+    );
+  }
+
+  #[test]
+  fn list_spent_output() {
+    let context = Context::with_args("--index-satoshis");
+    context.rpc_server.mine_blocks(1);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 0,
+    });
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+    let txid = context.rpc_server.tx(1, 0).txid();
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Spent,
+    );
+  }
+
+  #[test]
+  fn list_unknown_output() {
+    let context = Context::with_args("--index-satoshis");
+
+    assert_eq!(
+      context
+        .index
+        .list(
+          "0000000000000000000000000000000000000000000000000000000000000000:0"
+            .parse()
+            .unwrap()
+        )
+        .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn find_first_sat() {
+    let context = Context::with_args("--index-satoshis");
+    assert_eq!(
+      context.index.find(0).unwrap().unwrap(),
+      SatPoint {
+        outpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
+          .parse()
+          .unwrap(),
+        offset: 0,
+      }
+    )
+  }
+
+  #[test]
+  fn find_second_sat() {
+    let context = Context::with_args("--index-satoshis");
+    assert_eq!(
+      context.index.find(1).unwrap().unwrap(),
+      SatPoint {
+        outpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
+          .parse()
+          .unwrap(),
+        offset: 1,
+      }
+    )
+  }
+
+  #[test]
+  fn find_first_sat_of_second_block() {
+    let context = Context::with_args("--index-satoshis");
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+    assert_eq!(
+      context.index.find(50 * COIN_VALUE).unwrap().unwrap(),
+      SatPoint {
+        outpoint: "0c4eb1fa83a7d6ce0e21e5e616a96e83a7b1658170fb544acf6f5c6a2d4b3f90:0"
+          .parse()
+          .unwrap(),
+        offset: 0,
+      }
+    )
+  }
+
+  #[test]
+  fn find_unmined_sat() {
+    let context = Context::with_args("--index-satoshis");
+    assert_eq!(context.index.find(50 * COIN_VALUE).unwrap(), None);
+  }
+
+  #[test]
+  fn find_first_satoshi_spent_in_second_block() {
+    let context = Context::with_args("--index-satoshis");
+    context.rpc_server.mine_blocks(1);
+    let spend_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 0,
+    });
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+    assert_eq!(
+      context.index.find(50 * COIN_VALUE).unwrap().unwrap(),
+      SatPoint {
+        outpoint: OutPoint::new(spend_txid, 0),
+        offset: 0,
+      }
+    )
+  }
+
+  #[test]
+  fn find_next_rare() {
+    let context = Context::with_args("--index-satoshis");
+    context.rpc_server.mine_blocks(2);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .find_next_rare(Sat(0), Rarity::Uncommon)
+        .unwrap()
+        .unwrap()
+        .0,
+      Sat(0)
+    );
+
+    let (sat, satpoint) = context
+      .index
+      .find_next_rare(Sat(1), Rarity::Uncommon)
+      .unwrap()
+      .unwrap();
+    assert_eq!(sat, Sat(50 * COIN_VALUE));
+    assert_eq!(satpoint, context.index.find(sat.n()).unwrap().unwrap());
+
+    assert_eq!(
+      context
+        .index
+        .find_next_rare(Sat(50 * COIN_VALUE + 1), Rarity::Uncommon)
+        .unwrap()
+        .unwrap()
+        .0,
+      Sat(100 * COIN_VALUE)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .find_next_rare(Sat(100 * COIN_VALUE + 1), Rarity::Uncommon)
+        .unwrap(),
+      None
+    );
+
+    assert_eq!(
+      context.index.find_next_rare(Sat(1), Rarity::Rare).unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn find_many() {
+    let context = Context::with_args("--index-sat-ranges");
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    let sats = [Sat(0), Sat(50 * COIN_VALUE + 7), Sat(100 * COIN_VALUE)];
+
+    let satpoints = context.index.find_many(&sats).unwrap();
+
+    assert_eq!(satpoints.len(), 2);
+
+    for sat in sats {
+      assert_eq!(
+        satpoints.get(&sat).copied(),
+        context.index.find(sat.n()).unwrap()
+      );
+    }
+
+    assert_eq!(satpoints[&Sat(50 * COIN_VALUE + 7)].offset, 7);
+  }
+
+  #[test]
+  fn find_next_rare_requires_satoshi_index() {
+    let context = Context::with_args("");
+    assert_eq!(
+      context
+        .index
+        .find_next_rare(Sat(0), Rarity::Uncommon)
+        .unwrap_err()
+        .to_string(),
+      "find requires index created with `--index-rare-sats` flag"
+    );
+  }
+
+  #[test]
+  fn sat_history() {
+    let context = Context::with_args("--index-sat-history");
+    context.rpc_server.mine_blocks(1);
+    let first = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 2,
+      fee: 0,
+    });
+    context.rpc_server.mine_blocks(1);
+    let second = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(2, 1, 0)],
+      output_count: 1,
+      fee: 0,
+    });
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    let coinbase = context.rpc_server.tx(1, 0).txid();
+
+    assert_eq!(
+      context.index.sat_history(Sat(50 * COIN_VALUE)).unwrap(),
+      [
+        (
+          Height(1),
+          SatPoint {
+            outpoint: OutPoint::new(coinbase, 0),
+            offset: 0
+          }
+        ),
+        (
+          Height(2),
+          SatPoint {
+            outpoint: OutPoint::new(first, 0),
+            offset: 0
+          }
+        ),
+        (
+          Height(3),
+          SatPoint {
+            outpoint: OutPoint::new(second, 0),
+            offset: 0
+          }
+        ),
+      ]
+    );
+    assert!(context
+      .index
+      .sat_history(Sat(50 * COIN_VALUE + 1))
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn sat_history_at_height() {
+    let context = Context::with_args("--index-sat-history");
+    context.rpc_server.mine_blocks(1);
+    let first = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 0,
+    });
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    let coinbase = context.rpc_server.tx(1, 0).txid();
+
+    let at = |height| {
+      context
+        .index
+        .sat_history_at_height(Sat(50 * COIN_VALUE), Height(height))
+    };
+
+    assert!(at(0).unwrap().is_empty());
+    assert_eq!(
+      at(1).unwrap(),
+      [(
+        Height(1),
+        SatPoint {
+          outpoint: OutPoint::new(coinbase, 0),
+          offset: 0
+        }
+      )]
+    );
+    assert_eq!(at(2).unwrap().last().unwrap().1.outpoint.txid, first);
+    assert_eq!(
+      at(3).unwrap_err().to_string(),
+      "height 3 is above index height 2"
+    );
+  }
+
+  #[test]
+  fn sat_history_requires_index_option() {
+    let context = Context::with_args("--index-satoshis");
+    assert_eq!(
+      context.index.sat_history(Sat(0)).unwrap_err().to_string(),
+      "sat history requires index created with `--index-sat-history` flag"
+    );
+  }
+
+  #[test]
+  fn fee_provenance() {
+    let context = Context::with_args("--index-fee-provenance");
+    context.rpc_server.mine_blocks(1);
+    let first = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(1, 0, 0)],
+      output_count: 1,
+      fee: 10,
+    });
+    context.rpc_server.mine_blocks(1);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .fee_provenance(Sat(100 * COIN_VALUE - 10))
+        .unwrap(),
+      [(Height(2), first)]
+    );
+    assert_eq!(
+      context
+        .index
+        .fee_provenance(Sat(100 * COIN_VALUE - 1))
+        .unwrap(),
+      [(Height(2), first)]
     );
-  }
+    assert!(context
+      .index
+      .fee_provenance(Sat(100 * COIN_VALUE - 11))
+      .unwrap()
+      .is_empty());
+    assert!(context
+      .index
+      .fee_provenance(Sat(100 * COIN_VALUE))
+      .unwrap()
+      .is_empty());
 
-  #[test]
-  fn list_fee_paying_transaction_range() {
-    let context = Context::with_args("--index-satoshis");
+    assert_eq!(
+      context.index.fee_satpoint(first).unwrap(),
+      Some(SatPoint {
+        outpoint: OutPoint::new(context.rpc_server.tx(2, 0).txid(), 0),
+        offset: 50 * COIN_VALUE,
+      })
+    );
 
-    context.rpc_server.mine_blocks(1);
-    let fee_paying_tx = TransactionTemplate {
-      input_slots: &[(1, 0, 0)],
-      output_count: 2,
+    let second = context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(2, 0, 0)],
+      output_count: 1,
       fee: 10,
-    };
-    let txid = context.rpc_server.broadcast_tx(fee_paying_tx);
-    let coinbase_txid = context.rpc_server.mine_blocks(1)[0].txdata[0].txid();
+    });
+    context.rpc_server.mine_blocks(1);
     context.index.update().unwrap();
 
     assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Unspent(vec![(50 * COIN_VALUE, 7499999995)]),
+      context
+        .index
+        .fee_provenance(Sat(100 * COIN_VALUE - 10))
+        .unwrap(),
+      [(Height(2), first), (Height(3), second)]
     );
 
+    let coinbase = OutPoint::new(context.rpc_server.tx(3, 0).txid(), 0);
+
     assert_eq!(
-      context.index.list(OutPoint::new(txid, 1)).unwrap().unwrap(),
-      List::Unspent(vec![(7499999995, 9999999990)]),
+      context.index.fee_satpoint(first).unwrap(),
+      Some(SatPoint {
+        outpoint: coinbase,
+        offset: 50 * COIN_VALUE,
+      })
+    );
+    assert_eq!(
+      context.index.fee_satpoint(second).unwrap(),
+      Some(SatPoint {
+        outpoint: coinbase,
+        offset: 50 * COIN_VALUE,
+      })
     );
-
     assert_eq!(
       context
         .index
-        .list(OutPoint::new(coinbase_txid, 0))
-        .unwrap()
+        .fee_satpoint(context.rpc_server.tx(3, 0).txid())
         .unwrap(),
-      List::Unspent(vec![(10000000000, 15000000000), (9999999990, 10000000000)])
+      None
     );
   }
 
   #[test]
-  fn list_two_fee_paying_transaction_range() {
-    let context = Context::with_args("--index-satoshis");
-
-    context.rpc_server.mine_blocks(2);
-    let first_fee_paying_tx = TransactionTemplate {
-      input_slots: &[(1, 0, 0)],
-      output_count: 1,
-      fee: 10,
-    };
-    let second_fee_paying_tx = TransactionTemplate {
-      input_slots: &[(2, 0, 0)],
-      output_count: 1,
-      fee: 10,
-    };
-    context.rpc_server.broadcast_tx(first_fee_paying_tx);
-    context.rpc_server.broadcast_tx(second_fee_paying_tx);
-
-    let coinbase_txid = context.rpc_server.mine_blocks(1)[0].txdata[0].txid();
-    context.index.update().unwrap();
-
+  fn fee_provenance_requires_index_option() {
+    let context = Context::with_args("--index-sat-ranges");
     assert_eq!(
       context
         .index
-        .list(OutPoint::new(coinbase_txid, 0))
-        .unwrap()
-        .unwrap(),
-      List::Unspent(vec![
-        (15000000000, 20000000000),
-        (9999999990, 10000000000),
-        (14999999990, 15000000000)
-      ])
+        .fee_provenance(Sat(0))
+        .unwrap_err()
+        .to_string(),
+      "fee provenance requires index created with `--index-fee-provenance` flag"
+    );
+    assert_eq!(
+      context
+        .index
+        .fee_satpoint(Txid::all_zeros())
+        .unwrap_err()
+        .to_string(),
+      "fee provenance requires index created with `--index-fee-provenance` flag"
     );
   }
 
   #[test]
-  fn list_null_output() {
-    let context = Context::with_args("--index-satoshis");
+  fn sat_ranges_pages_are_decoded_lazily() {
+    let sat_ranges = SatRanges::from(vec![(0, 1), (1, 3), (3, 4)]);
+    assert_eq!(sat_ranges.len(), 3);
+    assert_eq!(
+      sat_ranges.iter().collect::<Vec<(u64, u64)>>(),
+      [(0, 1), (1, 3), (3, 4)]
+    );
+    assert_eq!(sat_ranges.page(1, 1).collect::<Vec<(u64, u64)>>(), [(1, 3)]);
+    assert_eq!(sat_ranges.page(2, 2).collect::<Vec<(u64, u64)>>(), [(3, 4)]);
+    assert_eq!(sat_ranges.page(3, 2).count(), 0);
+    assert_eq!(sat_ranges.page(usize::MAX, usize::MAX).count(), 0);
+  }
 
+  #[test]
+  fn spent_sat_ranges_are_kept() {
+    let context = Context::with_args("--index-spent-sat-ranges");
     context.rpc_server.mine_blocks(1);
-    let no_value_output = TransactionTemplate {
+    let outpoint = OutPoint::new(context.rpc_server.tx(1, 0).txid(), 0);
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.spent_sat_ranges(outpoint).unwrap(), None);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
       input_slots: &[(1, 0, 0)],
       output_count: 1,
-      fee: 50 * COIN_VALUE,
-    };
-    let txid = context.rpc_server.broadcast_tx(no_value_output);
+      fee: 0,
+    });
     context.rpc_server.mine_blocks(1);
     context.index.update().unwrap();
 
     assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Unspent(vec![])
+      context.index.spent_sat_ranges(outpoint).unwrap(),
+      Some(vec![(50 * COIN_VALUE, 100 * COIN_VALUE)])
     );
+    assert_eq!(context.index.list(outpoint).unwrap(), Some(List::Spent));
   }
 
   #[test]
-  fn list_null_input() {
-    let context = Context::with_args("--index-satoshis");
+  fn spent_sat_ranges_requires_index_option() {
+    let context = Context::with_args("--index-sat-ranges");
+    assert_eq!(
+      context
+        .index
+        .spent_sat_ranges(OutPoint::null())
+        .unwrap_err()
+        .to_string(),
+      "spent sat ranges requires index created with `--index-spent-sat-ranges` flag"
+    );
+  }
 
+  #[test]
+  fn epoch_statistics() {
+    let context = Context::with_args("--index-sat-ranges");
     context.rpc_server.mine_blocks(1);
-    let no_value_output = TransactionTemplate {
+    context.rpc_server.broadcast_tx(TransactionTemplate {
       input_slots: &[(1, 0, 0)],
       output_count: 1,
-      fee: 50 * COIN_VALUE,
-    };
-    context.rpc_server.broadcast_tx(no_value_output);
+      fee: 10,
+    });
     context.rpc_server.mine_blocks(1);
-
-    let no_value_input = TransactionTemplate {
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      input_slots: &[(2, 0, 0)],
+      output_count: 2,
+      fee: 8,
+    });
+    context.rpc_server.broadcast_tx(TransactionTemplate {
       input_slots: &[(2, 1, 0)],
       output_count: 1,
-      fee: 0,
-    };
-    let txid = context.rpc_server.broadcast_tx(no_value_input);
+      fee: 5,
+    });
     context.rpc_server.mine_blocks(1);
     context.index.update().unwrap();
 
     assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Unspent(vec![])
+      context.index.epoch_statistics().unwrap(),
+      [EpochStatistics {
+        epoch: Epoch(0),
+        blocks: 4,
+        subsidy: 200 * COIN_VALUE,
+        fees: Some(23),
+        inscriptions: 0,
+      }]
     );
   }
 
   #[test]
-  fn list_spent_output() {
-    let context = Context::with_args("--index-satoshis");
+  fn epoch_statistics_without_sat_ranges_omit_fees() {
+    let context = Context::with_args("");
     context.rpc_server.mine_blocks(1);
     context.rpc_server.broadcast_tx(TransactionTemplate {
       input_slots: &[(1, 0, 0)],
       output_count: 1,
-      fee: 0,
+      fee: 10,
     });
     context.rpc_server.mine_blocks(1);
     context.index.update().unwrap();
-    let txid = context.rpc_server.tx(1, 0).txid();
+
     assert_eq!(
-      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
-      List::Spent,
+      context.index.epoch_statistics().unwrap(),
+      [EpochStatistics {
+        epoch: Epoch(0),
+        blocks: 3,
+        subsidy: 150 * COIN_VALUE,
+        fees: None,
+        inscriptions: 0,
+      }]
     );
   }
 
   #[test]
-  fn list_unknown_output() {
+  fn rarity_statistics() {
     let context = Context::with_args("--index-satoshis");
+    context.rpc_server.mine_blocks(2);
+    context.index.update().unwrap();
+
+    let statistics = context.index.rarity_statistics().unwrap();
+
+    assert_eq!(statistics.blocks, 3);
+
+    let mut supply = [0; Rarity::ALL.len()];
+    for height in 0..3 {
+      let rarity = Height(height).starting_sat().rarity();
+      supply[Rarity::ALL.iter().position(|r| *r == rarity).unwrap()] += 1;
+    }
+    supply[0] = 150 * COIN_VALUE - 3;
 
     assert_eq!(
-      context
-        .index
-        .list(
-          "0000000000000000000000000000000000000000000000000000000000000000:0"
-            .parse()
-            .unwrap()
-        )
-        .unwrap(),
-      None
+      statistics.rarities,
+      Rarity::ALL
+        .into_iter()
+        .zip(supply)
+        .map(|(rarity, supply)| RarityStatistic {
+          rarity,
+          supply,
+          inscribed: Some(0),
+        })
+        .collect::<Vec<RarityStatistic>>()
     );
   }
 
   #[test]
-  fn find_first_sat() {
-    let context = Context::with_args("--index-satoshis");
-    assert_eq!(
-      context.index.find(0).unwrap().unwrap(),
-      SatPoint {
-        outpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
-          .parse()
-          .unwrap(),
-        offset: 0,
-      }
-    )
+  fn rarity_statistics_without_satoshi_index() {
+    let context = Context::with_args("");
+    assert!(context
+      .index
+      .rarity_statistics()
+      .unwrap()
+      .rarities
+      .iter()
+      .all(|statistic| statistic.inscribed.is_none()));
   }
 
   #[test]
-  fn find_second_sat() {
-    let context = Context::with_args("--index-satoshis");
-    assert_eq!(
-      context.index.find(1).unwrap().unwrap(),
-      SatPoint {
-        outpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
-          .parse()
-          .unwrap(),
-        offset: 1,
+  fn blocktime() {
+    let context = Context::with_args("");
+
+    assert!(matches!(
+      context.index.blocktime(Height(0)).unwrap(),
+      Blocktime::Confirmed(1231006505)
+    ));
+
+    assert!(matches!(
+      context.index.blocktime(Height(10)).unwrap(),
+      Blocktime::Expected(timestamp) if timestamp == 1231006505 + 10 * 600
+    ));
+
+    context.rpc_server.mine_blocks(2);
+    context.index.update().unwrap();
+
+    assert!(matches!(
+      context.index.blocktime(Height(2)).unwrap(),
+      Blocktime::Confirmed(0)
+    ));
+
+    assert!(matches!(
+      context.index.blocktime(Height(5)).unwrap(),
+      Blocktime::Expected(1800)
+    ));
+  }
+
+  /// The transfer loop as it was before inscriptions were moved in batches,
+  /// kept to check that batching doesn't change the result.
+  fn transfer_inscriptions_one_at_a_time(
+    tx: &Transaction,
+    txid: Txid,
+    inscription_id_to_satpoint: &mut Table<&InscriptionIdArray, &SatPointArray>,
+    satpoint_to_inscription_id: &mut Table<&SatPointArray, &InscriptionIdArray>,
+  ) {
+    for tx_in in &tx.input {
+      let inscriptions: Vec<(SatPoint, InscriptionId)> =
+        Index::inscriptions_on_output(satpoint_to_inscription_id, tx_in.previous_output)
+          .unwrap()
+          .collect();
+
+      for (old_satpoint, inscription_id) in inscriptions {
+        let new_satpoint = SatPointKey::new(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        });
+
+        satpoint_to_inscription_id
+          .remove(SatPointKey::new(old_satpoint).as_array())
+          .unwrap();
+        satpoint_to_inscription_id
+          .insert(new_satpoint.as_array(), inscription_id.as_inner())
+          .unwrap();
+        inscription_id_to_satpoint
+          .insert(
+            InscriptionIdKey::new(inscription_id).as_array(),
+            new_satpoint.as_array(),
+          )
+          .unwrap();
       }
-    )
+    }
   }
 
-  #[test]
-  fn find_first_sat_of_second_block() {
-    let context = Context::with_args("--index-satoshis");
-    context.rpc_server.mine_blocks(1);
-    context.index.update().unwrap();
-    assert_eq!(
-      context.index.find(50 * COIN_VALUE).unwrap().unwrap(),
-      SatPoint {
-        outpoint: "0c4eb1fa83a7d6ce0e21e5e616a96e83a7b1658170fb544acf6f5c6a2d4b3f90:0"
-          .parse()
-          .unwrap(),
-        offset: 0,
+  struct TransferTables {
+    inscription_id_to_satpoint:
+      TableDefinition<'static, &'static InscriptionIdArray, &'static SatPointArray>,
+    satpoint_to_inscription_id:
+      TableDefinition<'static, &'static SatPointArray, &'static InscriptionIdArray>,
+  }
+
+  const BATCHED: TransferTables = TransferTables {
+    inscription_id_to_satpoint: TableDefinition::new("BATCHED_INSCRIPTION_ID_TO_SATPOINT"),
+    satpoint_to_inscription_id: TableDefinition::new("BATCHED_SATPOINT_TO_INSCRIPTION_ID"),
+  };
+
+  const ONE_AT_A_TIME: TransferTables = TransferTables {
+    inscription_id_to_satpoint: TableDefinition::new("ONE_AT_A_TIME_INSCRIPTION_ID_TO_SATPOINT"),
+    satpoint_to_inscription_id: TableDefinition::new("ONE_AT_A_TIME_SATPOINT_TO_INSCRIPTION_ID"),
+  };
+
+  fn random_txid(rng: &mut StdRng) -> Txid {
+    Txid::from_inner(rng.gen())
+  }
+
+  fn spend(outpoints: &[OutPoint]) -> Transaction {
+    Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: outpoints
+        .iter()
+        .map(|outpoint| TxIn {
+          previous_output: *outpoint,
+          ..Default::default()
+        })
+        .collect(),
+      output: Vec::new(),
+    }
+  }
+
+  /// Put `inscriptions` in both sets of tables, spend `inputs` with each
+  /// implementation, and return the elapsed time for each.
+  fn transfer(
+    database: &Database,
+    inscriptions: &[(SatPoint, InscriptionId)],
+    inputs: &[OutPoint],
+    txid: Txid,
+  ) -> (Duration, Duration) {
+    let wtx = database.begin_write().unwrap();
+
+    for tables in [BATCHED, ONE_AT_A_TIME] {
+      let mut inscription_id_to_satpoint =
+        wtx.open_table(tables.inscription_id_to_satpoint).unwrap();
+      let mut satpoint_to_inscription_id =
+        wtx.open_table(tables.satpoint_to_inscription_id).unwrap();
+      for (satpoint, inscription_id) in inscriptions {
+        let satpoint = SatPointKey::new(*satpoint);
+        satpoint_to_inscription_id
+          .insert(satpoint.as_array(), inscription_id.as_inner())
+          .unwrap();
+        inscription_id_to_satpoint
+          .insert(
+            InscriptionIdKey::new(*inscription_id).as_array(),
+            satpoint.as_array(),
+          )
+          .unwrap();
       }
+    }
+
+    let tx = spend(inputs);
+
+    let batched = {
+      let mut inscription_id_to_satpoint =
+        wtx.open_table(BATCHED.inscription_id_to_satpoint).unwrap();
+      let mut satpoint_to_inscription_id =
+        wtx.open_table(BATCHED.satpoint_to_inscription_id).unwrap();
+      let mut buffer = Vec::new();
+      let start = Instant::now();
+      Updater::transfer_inscriptions(
+        &mut buffer,
+        &tx,
+        txid,
+        &mut inscription_id_to_satpoint,
+        &mut satpoint_to_inscription_id,
+        None,
+        None,
+      )
+      .unwrap();
+      start.elapsed()
+    };
+
+    let one_at_a_time = {
+      let mut inscription_id_to_satpoint = wtx
+        .open_table(ONE_AT_A_TIME.inscription_id_to_satpoint)
+        .unwrap();
+      let mut satpoint_to_inscription_id = wtx
+        .open_table(ONE_AT_A_TIME.satpoint_to_inscription_id)
+        .unwrap();
+      let start = Instant::now();
+      transfer_inscriptions_one_at_a_time(
+        &tx,
+        txid,
+        &mut inscription_id_to_satpoint,
+        &mut satpoint_to_inscription_id,
+      );
+      start.elapsed()
+    };
+
+    wtx.commit().unwrap();
+
+    (batched, one_at_a_time)
+  }
+
+  fn contents(
+    database: &Database,
+    tables: TransferTables,
+  ) -> (
+    Vec<(InscriptionIdArray, SatPointArray)>,
+    Vec<(SatPointArray, InscriptionIdArray)>,
+  ) {
+    let rtx = database.begin_read().unwrap();
+    (
+      rtx
+        .open_table(tables.inscription_id_to_satpoint)
+        .unwrap()
+        .range([0; 32]..)
+        .unwrap()
+        .map(|(inscription_id, satpoint)| (*inscription_id, *satpoint))
+        .collect(),
+      rtx
+        .open_table(tables.satpoint_to_inscription_id)
+        .unwrap()
+        .range([0; 44]..)
+        .unwrap()
+        .map(|(satpoint, inscription_id)| (*satpoint, *inscription_id))
+        .collect(),
     )
   }
 
+  fn transfer_database() -> (TempDir, Database) {
+    let tempdir = TempDir::new().unwrap();
+    let database = unsafe { Database::create(tempdir.path().join("index.redb")).unwrap() };
+    (tempdir, database)
+  }
+
   #[test]
-  fn find_unmined_sat() {
-    let context = Context::with_args("--index-satoshis");
-    assert_eq!(context.index.find(50 * COIN_VALUE).unwrap(), None);
+  fn batched_transfer_matches_one_at_a_time_transfer() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let (_tempdir, database) = transfer_database();
+
+    for _ in 0..20 {
+      let outpoints = (0..rng.gen_range(1..5))
+        .map(|_| OutPoint {
+          txid: random_txid(&mut rng),
+          vout: rng.gen_range(0..3),
+        })
+        .collect::<Vec<OutPoint>>();
+
+      let inscriptions = (0..rng.gen_range(0..2500))
+        .map(|_| {
+          (
+            SatPoint {
+              outpoint: outpoints[rng.gen_range(0..outpoints.len())],
+              offset: if rng.gen() {
+                rng.gen_range(0..10)
+              } else {
+                rng.gen()
+              },
+            },
+            random_txid(&mut rng),
+          )
+        })
+        .collect::<Vec<(SatPoint, InscriptionId)>>();
+
+      let unspent = OutPoint {
+        txid: random_txid(&mut rng),
+        vout: 0,
+      };
+
+      let inputs = outpoints
+        .iter()
+        .filter(|_| rng.gen_ratio(3, 4))
+        .copied()
+        .chain([unspent])
+        .collect::<Vec<OutPoint>>();
+
+      transfer(&database, &inscriptions, &inputs, random_txid(&mut rng));
+
+      assert_eq!(
+        contents(&database, BATCHED),
+        contents(&database, ONE_AT_A_TIME)
+      );
+    }
   }
 
   #[test]
-  fn find_first_satoshi_spent_in_second_block() {
-    let context = Context::with_args("--index-satoshis");
-    context.rpc_server.mine_blocks(1);
-    let spend_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-      input_slots: &[(1, 0, 0)],
-      output_count: 1,
-      fee: 0,
-    });
-    context.rpc_server.mine_blocks(1);
-    context.index.update().unwrap();
+  fn batched_transfer_matches_one_at_a_time_transfer_for_output_with_many_inscriptions() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let outpoint = OutPoint {
+      txid: random_txid(&mut rng),
+      vout: 0,
+    };
+
+    let inscriptions = (0..5000)
+      .map(|offset| (SatPoint { outpoint, offset }, random_txid(&mut rng)))
+      .collect::<Vec<(SatPoint, InscriptionId)>>();
+
+    let (_tempdir, database) = transfer_database();
+
+    transfer(&database, &inscriptions, &[outpoint], random_txid(&mut rng));
+
     assert_eq!(
-      context.index.find(50 * COIN_VALUE).unwrap().unwrap(),
-      SatPoint {
-        outpoint: OutPoint::new(spend_txid, 0),
-        offset: 0,
-      }
-    )
+      contents(&database, BATCHED),
+      contents(&database, ONE_AT_A_TIME)
+    );
+  }
+
+  /// Run with `cargo test -- --ignored --nocapture
+  /// batched_transfer_is_faster` to compare the two implementations on a
+  /// single output holding many inscriptions.
+  #[test]
+  #[ignore]
+  fn batched_transfer_is_faster_for_output_with_many_inscriptions() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let outpoint = OutPoint {
+      txid: random_txid(&mut rng),
+      vout: 0,
+    };
+
+    let inscriptions = (0..5000)
+      .map(|offset| (SatPoint { outpoint, offset }, random_txid(&mut rng)))
+      .collect::<Vec<(SatPoint, InscriptionId)>>();
+
+    let mut batched = Duration::MAX;
+    let mut one_at_a_time = Duration::MAX;
+
+    for _ in 0..5 {
+      let (_tempdir, database) = transfer_database();
+      let elapsed = transfer(&database, &inscriptions, &[outpoint], random_txid(&mut rng));
+      batched = batched.min(elapsed.0);
+      one_at_a_time = one_at_a_time.min(elapsed.1);
+    }
+
+    eprintln!("batched: {batched:?}, one at a time: {one_at_a_time:?}");
+
+    assert!(batched < one_at_a_time);
   }
 }
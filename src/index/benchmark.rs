@@ -0,0 +1,148 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct BenchmarkReport {
+  pub(crate) start_height: u64,
+  pub(crate) blocks: u64,
+  pub(crate) wall_time_secs: f64,
+  pub(crate) blocks_per_sec: f64,
+  pub(crate) sat_ranges: u64,
+  pub(crate) sat_ranges_per_sec: f64,
+  pub(crate) peak_cache_size: usize,
+  pub(crate) rpc_time_secs: f64,
+  pub(crate) index_time_secs: f64,
+}
+
+impl Index {
+  /// Index up to `blocks` blocks into a throwaway database beside the index,
+  /// and report how long it took, leaving the index itself untouched.
+  ///
+  /// Without `start_height`, the throwaway database is a copy of the index,
+  /// or a fresh one if there is no index yet, and the benchmark indexes the
+  /// blocks after its tip. With `start_height`, the throwaway database is a
+  /// fresh one created with the index options in `options`, which starts at
+  /// that height. Such a database doesn't know about earlier outputs, so it
+  /// can't track sat ranges, and misses transfers of earlier inscriptions.
+  pub(crate) fn benchmark(
+    options: &Options,
+    rpc: Arc<RpcPool>,
+    start_height: Option<u64>,
+    blocks: u64,
+  ) -> Result<BenchmarkReport> {
+    let source = Self::database_path(options)?;
+
+    let mut path = source.as_os_str().to_owned();
+    path.push(".benchmark");
+    let path = PathBuf::from(path);
+
+    match fs::remove_file(&path) {
+      Ok(()) => log::info!(
+        "Removed database left by interrupted benchmark at `{}`",
+        path.display()
+      ),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+      Err(err) => return Err(err.into()),
+    }
+
+    let result = Self::run_benchmark(options, rpc, &source, &path, start_height, blocks);
+
+    fs::remove_file(&path).ok();
+
+    result
+  }
+
+  fn run_benchmark(
+    options: &Options,
+    rpc: Arc<RpcPool>,
+    source: &Path,
+    path: &Path,
+    start_height: Option<u64>,
+    blocks: u64,
+  ) -> Result<BenchmarkReport> {
+    match start_height {
+      None => match unsafe { Database::open(source) } {
+        // Holding the index open keeps other processes from writing to it
+        // while it is copied.
+        Ok(database) => {
+          log::info!(
+            "Copying index at `{}` to `{}`…",
+            source.display(),
+            path.display()
+          );
+          fs::copy(source, path)?;
+          drop(database);
+        }
+        Err(redb::Error::DatabaseAlreadyOpen) => bail!(
+          "index at `{}` is in use by another process, which must be stopped before benchmarking",
+          source.display()
+        ),
+        Err(redb::Error::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+      },
+      Some(start_height) => {
+        if start_height > 0 && options.index_options().contains(&IndexOption::SatRanges) {
+          bail!(
+            "cannot benchmark sat ranges from height {start_height}, since a fresh index doesn't know the sat ranges of earlier outputs"
+          );
+        }
+      }
+    }
+
+    let mut options = options.clone();
+    options.index = Some(path.into());
+
+    let index = Self::open_with_rpc(&options, rpc.clone())?;
+
+    let start_height = match start_height {
+      Some(start_height) => {
+        if let Some(previous) = start_height.checked_sub(1) {
+          // Blocks are checked against the hash of their parent, so the
+          // parent must look indexed.
+          let hash = rpc
+            .get()?
+            .get_block_hash(previous)
+            .into_option()?
+            .ok_or_else(|| anyhow!("no block at height {previous}"))?;
+
+          let wtx = index.begin_write()?;
+          wtx
+            .open_table(HEIGHT_TO_BLOCK_HASH)?
+            .insert(&previous, &hash.as_hash().into_inner())?;
+          wtx.commit()?;
+        }
+
+        start_height
+      }
+      None => index.block_count()?,
+    };
+
+    let index = Self {
+      height_limit: Some(start_height.saturating_add(blocks)),
+      ..index
+    };
+
+    let start = Instant::now();
+
+    let statistics = Updater::update(&index, &AtomicBool::new(false))?;
+
+    let wall_time = start.elapsed();
+
+    if statistics.blocks == 0 {
+      bail!("no blocks to benchmark at height {start_height}");
+    }
+
+    let per_sec = |n: u64| n as f64 / wall_time.as_secs_f64();
+
+    Ok(BenchmarkReport {
+      start_height,
+      blocks: statistics.blocks,
+      wall_time_secs: wall_time.as_secs_f64(),
+      blocks_per_sec: per_sec(statistics.blocks),
+      sat_ranges: statistics.sat_ranges,
+      sat_ranges_per_sec: per_sec(statistics.sat_ranges),
+      peak_cache_size: statistics.peak_cache_size,
+      rpc_time_secs: statistics.rpc_time.as_secs_f64(),
+      index_time_secs: statistics.index_time.as_secs_f64(),
+    })
+  }
+}
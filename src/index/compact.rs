@@ -0,0 +1,321 @@
+use {super::*, redb::ReadTransaction, std::collections::BTreeSet};
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Compaction {
+  pub(crate) path: PathBuf,
+  pub(crate) size_before: u64,
+  pub(crate) size_after: u64,
+}
+
+impl Index {
+  /// Rewrite the index at `source` into a fresh file at `destination`, which
+  /// may be `source` itself, leaving behind the pages freed by deletes.
+  ///
+  /// The copy is built beside `destination` and renamed over it only once
+  /// committed, so an interrupted compaction never leaves a partial file at
+  /// `destination`. `source` stays open, and thus locked, until the rename
+  /// is done, so no other process can write to it in the meantime.
+  pub(crate) fn compact(source: &Path, destination: &Path) -> Result<Compaction> {
+    let database = match unsafe { Database::open(source) } {
+      Ok(database) => database,
+      Err(redb::Error::DatabaseAlreadyOpen) => bail!(
+        "index at `{}` is in use by another process, which must be stopped before compacting",
+        source.display()
+      ),
+      Err(redb::Error::Io(error)) if error.kind() == io::ErrorKind::NotFound => {
+        bail!("no index at `{}`", source.display())
+      }
+      Err(err) => return Err(err.into()),
+    };
+
+    let size_before = fs::metadata(source)?.len();
+
+    let mut partial = destination.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    match fs::remove_file(&partial) {
+      Ok(()) => log::info!(
+        "Removed partial index left by interrupted compaction at `{}`",
+        partial.display()
+      ),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+      Err(err) => return Err(err.into()),
+    }
+
+    let result = Self::create_database(&partial).and_then(|compacted| {
+      let wtx = compacted.begin_write()?;
+      copy_tables(&database.begin_read()?, &wtx)?;
+      Ok(wtx.commit()?)
+    });
+
+    if let Err(err) = result {
+      fs::remove_file(&partial).ok();
+      return Err(err);
+    }
+
+    fs::rename(&partial, destination)?;
+
+    drop(database);
+
+    Ok(Compaction {
+      path: destination.into(),
+      size_before,
+      size_after: fs::metadata(destination)?.len(),
+    })
+  }
+}
+
+/// Copy every table in `rtx` into `wtx`, refusing to drop any that this
+/// version of ord does not know about.
+fn copy_tables(rtx: &ReadTransaction, wtx: &WriteTransaction) -> Result {
+  let mut tables = rtx.list_tables()?.collect::<BTreeSet<String>>();
+  let mut multimap_tables = rtx.list_multimap_tables()?.collect::<BTreeSet<String>>();
+
+  macro_rules! copy {
+    ($($definition:ident),* $(,)?) => {
+      $(
+        if tables.remove($definition.name()) {
+          let mut table = wtx.open_table($definition)?;
+          for (key, value) in rtx.open_table($definition)?.iter()? {
+            table.insert(&key, &value)?;
+          }
+        }
+      )*
+    };
+  }
+
+  macro_rules! copy_multimap {
+    ($($definition:ident),* $(,)?) => {
+      $(
+        if multimap_tables.remove($definition.name()) {
+          let mut table = wtx.open_multimap_table($definition)?;
+          for (key, values) in rtx.open_multimap_table($definition)?.iter()? {
+            for value in values {
+              table.insert(&key, &value)?;
+            }
+          }
+        }
+      )*
+    };
+  }
+
+  copy! {
+    BLOCK_HASH_TO_HEIGHT,
+    EPOCH_TO_STATISTICS,
+    FEE_TXID_TO_FIRST_SAT,
+    HEIGHT_TO_BLOCK_HASH,
+    HEIGHT_TO_REWARD_BREAKDOWN,
+    INDEX_OPTIONS,
+    INSCRIPTION_ID_TO_BURNED,
+    INSCRIPTION_ID_TO_GENESIS_HEIGHT,
+    INSCRIPTION_ID_TO_INSCRIPTION_NUMBER,
+    INSCRIPTION_ID_TO_SATPOINT,
+    INSCRIPTION_NUMBER_TO_INSCRIPTION_ID,
+    OUTPOINT_TO_SAT_RANGES,
+    OUTPOINT_TO_SCRIPT_PUBKEY,
+    SAT_TO_INSCRIPTION_ID,
+    SAT_TO_SATPOINT,
+    SATPOINT_TO_INSCRIPTION_ID,
+    SPENT_OUTPOINT_TO_SAT_RANGES,
+    STATISTIC_TO_COUNT,
+    WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
+  }
+
+  copy_multimap! {
+    COLLECTION_TO_INSCRIPTION_IDS,
+    FEE_RANGE_START_TO_PROVENANCE,
+    HEIGHT_TO_INSCRIPTION_IDS,
+    INSCRIPTION_ID_TO_SATPOINT_HISTORY,
+    SAT_TO_SATPOINT_HISTORY,
+    SCRIPT_PUBKEY_TO_OUTPOINTS,
+  }
+
+  if let Some(name) = tables.iter().chain(&multimap_tables).next() {
+    bail!("index contains unknown table `{name}`");
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::test_fixtures::{spend, TestChain},
+  };
+
+  fn queries(index: &Index, outpoints: &[OutPoint]) -> impl PartialEq + fmt::Debug {
+    (
+      outpoints
+        .iter()
+        .map(|outpoint| index.list_inner(OutPointKey::new(*outpoint)).unwrap())
+        .collect::<Vec<_>>(),
+      index.info().unwrap().utxos_indexed,
+      index.blocks(usize::MAX).unwrap(),
+      index.epoch_statistics().unwrap(),
+      serde_json::to_value(index.rarity_statistics().unwrap()).unwrap(),
+      index.statistic(Statistic::SatRanges).unwrap(),
+    )
+  }
+
+  #[test]
+  fn compaction_reclaims_space_and_preserves_queries() {
+    const COINBASES: usize = 10;
+    const OUTPUTS: usize = 1000;
+
+    let mut chain = TestChain::new("--index-sat-ranges");
+
+    for _ in 0..COINBASES {
+      chain.mine(Vec::new());
+    }
+
+    // Split each coinbase into many outputs and merge them all back, so that
+    // most of the sat ranges ever indexed have since been deleted.
+    let splits = (1..=COINBASES)
+      .map(|height| {
+        spend(
+          &[chain.coinbase(height)],
+          &[50 * COIN_VALUE / u64::try_from(OUTPUTS).unwrap(); OUTPUTS],
+        )
+      })
+      .collect::<Vec<Transaction>>();
+
+    let merges = splits
+      .iter()
+      .map(|split| {
+        spend(
+          &(0..OUTPUTS)
+            .map(|vout| OutPoint::new(split.txid(), vout.try_into().unwrap()))
+            .collect::<Vec<OutPoint>>(),
+          &[50 * COIN_VALUE],
+        )
+      })
+      .collect::<Vec<Transaction>>();
+
+    chain.mine(splits.clone());
+    chain.mine(merges.clone());
+
+    let outpoints = splits
+      .iter()
+      .chain(&merges)
+      .map(|tx| OutPoint::new(tx.txid(), 0))
+      .chain((0..=COINBASES + 2).map(|height| chain.coinbase(height)))
+      .collect::<Vec<OutPoint>>();
+
+    let expected = queries(&chain.index, &outpoints);
+
+    let source = chain.index.path().to_owned();
+    let output = source.with_file_name("compacted.redb");
+
+    assert_eq!(
+      Index::compact(&source, &output).unwrap_err().to_string(),
+      format!(
+        "index at `{}` is in use by another process, which must be stopped before compacting",
+        source.display()
+      ),
+    );
+    assert!(!output.exists());
+
+    let (mut options, _tempdir) = chain.close();
+
+    let size = fs::metadata(&source).unwrap().len();
+
+    let compaction = Index::compact(&source, &output).unwrap();
+
+    assert_eq!(
+      compaction,
+      Compaction {
+        path: output.clone(),
+        size_before: size,
+        size_after: fs::metadata(&output).unwrap().len(),
+      }
+    );
+    assert!(compaction.size_after < compaction.size_before);
+    assert_eq!(fs::metadata(&source).unwrap().len(), size);
+
+    options.index = Some(output);
+    assert_eq!(
+      queries(&Index::open(&options).unwrap(), &outpoints),
+      expected
+    );
+
+    assert_eq!(
+      Index::compact(&source, &source).unwrap().size_after,
+      compaction.size_after
+    );
+
+    options.index = Some(source);
+    assert_eq!(
+      queries(&Index::open(&options).unwrap(), &outpoints),
+      expected
+    );
+  }
+
+  #[test]
+  fn compaction_preserves_spent_sat_ranges() {
+    let mut chain = TestChain::new("--index-spent-sat-ranges");
+
+    chain.mine(Vec::new());
+
+    let coinbase = chain.coinbase(1);
+
+    chain.mine(vec![spend(&[coinbase], &[50 * COIN_VALUE])]);
+
+    let expected = chain.index.spent_sat_ranges(coinbase).unwrap();
+    assert!(expected.is_some());
+
+    let source = chain.index.path().to_owned();
+    let (mut options, _tempdir) = chain.close();
+
+    Index::compact(&source, &source).unwrap();
+
+    options.index = Some(source);
+    assert_eq!(
+      Index::open(&options)
+        .unwrap()
+        .spent_sat_ranges(coinbase)
+        .unwrap(),
+      expected
+    );
+  }
+
+  #[test]
+  fn stale_partial_file_is_replaced() {
+    let chain = TestChain::new("");
+    let source = chain.index.path().to_owned();
+    let (_options, _tempdir) = chain.close();
+
+    let partial = source.with_file_name("index.redb.partial");
+    fs::write(&partial, "garbage").unwrap();
+
+    Index::compact(&source, &source).unwrap();
+
+    assert!(!partial.exists());
+    unsafe { Database::open(&source) }.unwrap();
+  }
+
+  #[test]
+  fn unknown_tables_are_not_dropped() {
+    let chain = TestChain::new("");
+    let source = chain.index.path().to_owned();
+    let (_options, _tempdir) = chain.close();
+
+    {
+      const UNKNOWN: TableDefinition<u64, u64> = TableDefinition::new("UNKNOWN");
+      let database = unsafe { Database::open(&source) }.unwrap();
+      let wtx = database.begin_write().unwrap();
+      wtx.open_table(UNKNOWN).unwrap().insert(&0, &0).unwrap();
+      wtx.commit().unwrap();
+    }
+
+    let size = fs::metadata(&source).unwrap().len();
+
+    assert_eq!(
+      Index::compact(&source, &source).unwrap_err().to_string(),
+      "index contains unknown table `UNKNOWN`"
+    );
+    assert_eq!(fs::metadata(&source).unwrap().len(), size);
+    assert!(!source.with_file_name("index.redb.partial").exists());
+  }
+}
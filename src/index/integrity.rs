@@ -0,0 +1,68 @@
+use super::*;
+
+/// An invariant that a freshly opened index is expected to uphold, but does
+/// not. The schema version is checked before these, since the layout of
+/// other versions is unknown.
+#[derive(Debug, PartialEq)]
+pub(super) enum Violation {
+  EpochStatistics { counted: u64, blocks: u64 },
+  MissingBlock { height: u64, tip: u64 },
+  MissingIndexOptions,
+}
+
+impl Display for Violation {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::EpochStatistics { counted, blocks } => write!(
+        f,
+        "epoch statistics count {counted} blocks, but {blocks} blocks are indexed"
+      ),
+      Self::MissingBlock { height, tip } => write!(
+        f,
+        "block {height} is missing, but blocks through height {tip} are indexed"
+      ),
+      Self::MissingIndexOptions => write!(f, "index options table is missing"),
+    }
+  }
+}
+
+pub(super) fn check(database: &Database) -> Result<Option<Violation>> {
+  let rtx = database.begin_read()?;
+
+  match rtx.open_table(INDEX_OPTIONS) {
+    Ok(_) => {}
+    Err(redb::Error::TableDoesNotExist(_)) => return Ok(Some(Violation::MissingIndexOptions)),
+    Err(err) => return Err(err.into()),
+  }
+
+  let height_to_block_hash = rtx.open_table(HEIGHT_TO_BLOCK_HASH)?;
+
+  let blocks = height_to_block_hash
+    .range(0..)?
+    .next_back()
+    .map(|(height, _hash)| height + 1)
+    .unwrap_or(0);
+
+  if u64::try_from(height_to_block_hash.len()?).unwrap() != blocks {
+    for (expected, (height, _hash)) in (0..).zip(height_to_block_hash.range(0..)?) {
+      if height != expected {
+        return Ok(Some(Violation::MissingBlock {
+          height: expected,
+          tip: blocks - 1,
+        }));
+      }
+    }
+  }
+
+  let counted = rtx
+    .open_table(EPOCH_TO_STATISTICS)?
+    .range(0..)?
+    .map(|(_epoch, statistics)| decode_epoch_statistics(*statistics)[0])
+    .sum::<u64>();
+
+  if counted != blocks {
+    return Ok(Some(Violation::EpochStatistics { counted, blocks }));
+  }
+
+  Ok(None)
+}
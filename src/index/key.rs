@@ -0,0 +1,241 @@
+use super::*;
+
+/// Database key for an outpoint: the txid's 32 bytes, in the same order as
+/// `Txid`'s `Ord` compares them, followed by the vout as a big-endian `u32`.
+/// redb compares array keys bytewise, so keys sort in the same order as
+/// `OutPoint`s.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) struct OutPointKey(OutPointArray);
+
+impl OutPointKey {
+  pub(super) fn new(outpoint: OutPoint) -> Self {
+    let mut array = [0; 36];
+    array[..32].copy_from_slice(outpoint.txid.as_inner());
+    array[32..].copy_from_slice(&outpoint.vout.to_be_bytes());
+    Self(array)
+  }
+
+  pub(super) fn outpoint(self) -> OutPoint {
+    OutPoint {
+      txid: Txid::from_inner(self.0[..32].try_into().unwrap()),
+      vout: u32::from_be_bytes(self.0[32..].try_into().unwrap()),
+    }
+  }
+
+  pub(super) fn as_array(&self) -> &OutPointArray {
+    &self.0
+  }
+
+  pub(super) fn from_array(array: OutPointArray) -> Self {
+    Self(array)
+  }
+}
+
+/// Database key for a satpoint: an `OutPointKey` followed by the offset as a
+/// big-endian `u64`. Keys sort by outpoint and then by offset, so all
+/// satpoints in an outpoint form a contiguous range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) struct SatPointKey(SatPointArray);
+
+impl SatPointKey {
+  pub(super) fn new(satpoint: SatPoint) -> Self {
+    let mut array = [0; 44];
+    array[..36].copy_from_slice(&OutPointKey::new(satpoint.outpoint).0);
+    array[36..].copy_from_slice(&satpoint.offset.to_be_bytes());
+    Self(array)
+  }
+
+  pub(super) fn satpoint(self) -> SatPoint {
+    SatPoint {
+      outpoint: OutPointKey(self.0[..36].try_into().unwrap()).outpoint(),
+      offset: u64::from_be_bytes(self.0[36..].try_into().unwrap()),
+    }
+  }
+
+  pub(super) fn as_array(&self) -> &SatPointArray {
+    &self.0
+  }
+
+  pub(super) fn from_array(array: SatPointArray) -> Self {
+    Self(array)
+  }
+}
+
+/// Database key for an inscription ID: the txid's 32 bytes in the order in
+/// which they are displayed, which is the reverse of `Txid`'s. Keys sort like
+/// the hex IDs, so all IDs with a given hex prefix form a contiguous range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) struct InscriptionIdKey(InscriptionIdArray);
+
+impl InscriptionIdKey {
+  pub(super) fn new(inscription_id: InscriptionId) -> Self {
+    let mut array = inscription_id.into_inner();
+    array.reverse();
+    Self(array)
+  }
+
+  pub(super) fn inscription_id(self) -> InscriptionId {
+    let mut array = self.0;
+    array.reverse();
+    InscriptionId::from_inner(array)
+  }
+
+  pub(super) fn as_array(&self) -> &InscriptionIdArray {
+    &self.0
+  }
+
+  pub(super) fn from_array(array: InscriptionIdArray) -> Self {
+    Self(array)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn txids() -> Vec<Txid> {
+    let mut txids = vec![Txid::all_zeros()];
+
+    for i in 0..32 {
+      for byte in [0x01, 0x7f, 0x80, 0xff] {
+        let mut array = [0; 32];
+        array[i] = byte;
+        txids.push(Txid::from_inner(array));
+      }
+    }
+
+    txids.push(Txid::from_inner([0xff; 32]));
+
+    txids
+  }
+
+  fn outpoints() -> Vec<OutPoint> {
+    let mut outpoints = Vec::new();
+    for txid in txids() {
+      for vout in [0, 1, 255, 256, 65_535, 65_536, u32::MAX - 1, u32::MAX] {
+        outpoints.push(OutPoint { txid, vout });
+      }
+    }
+    outpoints
+  }
+
+  fn satpoints() -> Vec<SatPoint> {
+    let mut satpoints = Vec::new();
+    for outpoint in outpoints().into_iter().step_by(3) {
+      for offset in [0, 1, 255, 256, u64::from(u32::MAX) + 1, u64::MAX] {
+        satpoints.push(SatPoint { outpoint, offset });
+      }
+    }
+    satpoints
+  }
+
+  #[test]
+  fn outpoint_round_trip() {
+    for outpoint in outpoints() {
+      assert_eq!(OutPointKey::new(outpoint).outpoint(), outpoint);
+      assert_eq!(
+        OutPointKey::from_array(*OutPointKey::new(outpoint).as_array()).outpoint(),
+        outpoint
+      );
+    }
+  }
+
+  #[test]
+  fn satpoint_round_trip() {
+    for satpoint in satpoints() {
+      assert_eq!(SatPointKey::new(satpoint).satpoint(), satpoint);
+      assert_eq!(
+        SatPointKey::from_array(*SatPointKey::new(satpoint).as_array()).satpoint(),
+        satpoint
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_id_round_trip() {
+    for txid in txids() {
+      assert_eq!(InscriptionIdKey::new(txid).inscription_id(), txid);
+      assert_eq!(
+        InscriptionIdKey::from_array(*InscriptionIdKey::new(txid).as_array()).inscription_id(),
+        txid
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_id_key_order_matches_hex_order() {
+    let txids = txids();
+    for a in &txids {
+      for b in &txids {
+        assert_eq!(
+          InscriptionIdKey::new(*a)
+            .as_array()
+            .cmp(InscriptionIdKey::new(*b).as_array()),
+          a.to_string().cmp(&b.to_string()),
+          "{a} {b}",
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn outpoint_key_order_matches_outpoint_order() {
+    let outpoints = outpoints();
+    for a in &outpoints {
+      for b in &outpoints {
+        assert_eq!(
+          OutPointKey::new(*a)
+            .as_array()
+            .cmp(OutPointKey::new(*b).as_array()),
+          (a.txid, a.vout).cmp(&(b.txid, b.vout)),
+          "{a} {b}",
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn satpoint_key_order_matches_satpoint_order() {
+    let satpoints = satpoints();
+    for a in &satpoints {
+      for b in &satpoints {
+        assert_eq!(
+          SatPointKey::new(*a)
+            .as_array()
+            .cmp(SatPointKey::new(*b).as_array()),
+          (a.outpoint.txid, a.outpoint.vout, a.offset).cmp(&(
+            b.outpoint.txid,
+            b.outpoint.vout,
+            b.offset
+          )),
+          "{a} {b}",
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn satpoint_range_covers_exactly_one_outpoint() {
+    let satpoints = satpoints();
+    let outpoint = satpoints[100].outpoint;
+
+    let start = SatPointKey::new(SatPoint {
+      outpoint,
+      offset: 0,
+    });
+
+    let end = SatPointKey::new(SatPoint {
+      outpoint,
+      offset: u64::MAX,
+    });
+
+    for satpoint in satpoints {
+      let key = SatPointKey::new(satpoint);
+      assert_eq!(
+        (start.as_array()..=end.as_array()).contains(&key.as_array()),
+        satpoint.outpoint == outpoint,
+        "{satpoint}",
+      );
+    }
+  }
+}
@@ -9,8 +9,7 @@ impl Rtx<'_> {
         .0
         .open_table(HEIGHT_TO_BLOCK_HASH)?
         .range(0..)?
-        .rev()
-        .next()
+        .next_back()
         .map(|(height, _hash)| Height(height)),
     )
   }
@@ -21,8 +20,7 @@ impl Rtx<'_> {
         .0
         .open_table(HEIGHT_TO_BLOCK_HASH)?
         .range(0..)?
-        .rev()
-        .next()
+        .next_back()
         .map(|(height, _hash)| height + 1)
         .unwrap_or(0),
     )
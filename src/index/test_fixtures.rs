@@ -0,0 +1,227 @@
+use {
+  super::*,
+  bitcoin::{blockdata::script, hashes::Hash, PackedLockTime, TxMerkleNode, Witness},
+};
+
+/// A regtest chain built in memory and handed straight to
+/// `Updater::index_block`, so indexing can be tested without a bitcoind.
+/// Blocks are only as valid as the updater requires: there is no proof of
+/// work, and scripts are never checked.
+pub(super) struct TestChain {
+  blocks: Vec<Block>,
+  pub(super) index: Index,
+  nonce: u32,
+  options: Options,
+  tempdir: TempDir,
+  transactions: HashMap<Txid, Transaction>,
+  updater: Updater,
+}
+
+impl TestChain {
+  /// A chain containing only the regtest genesis block, indexed with
+  /// options `args`. The RPC URL is never connected to.
+  pub(super) fn new(args: &str) -> Self {
+    let tempdir = TempDir::new().unwrap();
+    let cookie_file = tempdir.path().join("cookie");
+    fs::write(&cookie_file, "username:password").unwrap();
+
+    let options = Options::try_parse_from(
+      format!(
+        "
+        ord
+        --rpc-url 127.0.0.1:9
+        --data-dir {}
+        --cookie-file {}
+        --chain regtest
+        {args}
+      ",
+        tempdir.path().display(),
+        cookie_file.display(),
+      )
+      .split_whitespace(),
+    )
+    .unwrap();
+
+    let index = Index::open(&options).unwrap();
+
+    let mut chain = Self {
+      blocks: Vec::new(),
+      nonce: 0,
+      options: options.clone(),
+      tempdir,
+      transactions: HashMap::new(),
+      updater: Updater::new(&index, 0).unwrap(),
+      index,
+    };
+
+    chain.index(options.chain().genesis_block()).unwrap();
+
+    chain
+  }
+
+  /// Build and index a block on top of the tip, containing a coinbase that
+  /// collects the subsidy and fees, followed by `transactions`.
+  pub(super) fn mine(&mut self, transactions: Vec<Transaction>) -> Block {
+    let block = self.block(self.tip().block_hash(), transactions);
+    self.index(block.clone()).unwrap();
+    block
+  }
+
+  /// Build a block on top of `prev_blockhash` without indexing it.
+  pub(super) fn block(
+    &mut self,
+    prev_blockhash: BlockHash,
+    transactions: Vec<Transaction>,
+  ) -> Block {
+    let height = self
+      .blocks
+      .iter()
+      .position(|block| block.block_hash() == prev_blockhash)
+      .expect("unknown previous block")
+      + 1;
+
+    let fees = transactions
+      .iter()
+      .enumerate()
+      .map(|(i, tx)| {
+        tx.input
+          .iter()
+          .map(|input| {
+            let spent = self
+              .transactions
+              .get(&input.previous_output.txid)
+              .or_else(|| {
+                transactions[..i]
+                  .iter()
+                  .find(|earlier| earlier.txid() == input.previous_output.txid)
+              })
+              .expect("unknown previous output");
+
+            spent.output[usize::try_from(input.previous_output.vout).unwrap()].value
+          })
+          .sum::<u64>()
+          - tx.output.iter().map(|output| output.value).sum::<u64>()
+      })
+      .sum::<u64>();
+
+    let coinbase = Transaction {
+      version: 0,
+      lock_time: PackedLockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: script::Builder::new()
+          .push_int(height.try_into().unwrap())
+          .into_script(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: Height(height.try_into().unwrap()).subsidy() + fees,
+        script_pubkey: Script::new(),
+      }],
+    };
+
+    self.nonce += 1;
+
+    Block {
+      header: BlockHeader {
+        version: 0,
+        prev_blockhash,
+        merkle_root: TxMerkleNode::all_zeros(),
+        time: self.tip().header.time + 600,
+        bits: 0,
+        nonce: self.nonce,
+      },
+      txdata: std::iter::once(coinbase).chain(transactions).collect(),
+    }
+  }
+
+  /// Index `block` in a write transaction of its own, and make it the tip if
+  /// that succeeds.
+  pub(super) fn index(&mut self, block: Block) -> Result {
+    let mut wtx = self.index.begin_write()?;
+    self
+      .updater
+      .index_block(&self.index, &mut wtx, block.clone().into())?;
+    self.updater.commit(wtx)?;
+
+    for tx in &block.txdata {
+      self.transactions.insert(tx.txid(), tx.clone());
+    }
+
+    self.blocks.push(block);
+
+    Ok(())
+  }
+
+  /// Notify `webhooks` of blocks indexed from now on.
+  pub(super) fn set_webhooks(&mut self, webhooks: Webhooks) {
+    self.index.set_webhooks(webhooks).unwrap();
+    self.updater = Updater::new(&self.index, self.blocks.len().try_into().unwrap()).unwrap();
+  }
+
+  /// Close the index, returning the options it was opened with, and the
+  /// directory that contains it.
+  pub(super) fn close(self) -> (Options, TempDir) {
+    (self.options, self.tempdir)
+  }
+
+  pub(super) fn tip(&self) -> &Block {
+    self.blocks.last().unwrap()
+  }
+
+  /// The coinbase output of the block at `height`.
+  pub(super) fn coinbase(&self, height: usize) -> OutPoint {
+    OutPoint::new(self.blocks[height].txdata[0].txid(), 0)
+  }
+
+  pub(super) fn satpoint(&self, inscription_id: InscriptionId) -> Option<SatPoint> {
+    self
+      .index
+      .database
+      .begin_read()
+      .unwrap()
+      .open_table(INSCRIPTION_ID_TO_SATPOINT)
+      .unwrap()
+      .get(InscriptionIdKey::new(inscription_id).as_array())
+      .unwrap()
+      .map(|satpoint| SatPointKey::from_array(*satpoint).satpoint())
+  }
+}
+
+/// A transaction spending `inputs` into outputs of `values`.
+pub(super) fn spend(inputs: &[OutPoint], values: &[u64]) -> Transaction {
+  Transaction {
+    version: 0,
+    lock_time: PackedLockTime::ZERO,
+    input: inputs
+      .iter()
+      .map(|previous_output| TxIn {
+        previous_output: *previous_output,
+        script_sig: Script::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      })
+      .collect(),
+    output: values
+      .iter()
+      .map(|value| TxOut {
+        value: *value,
+        script_pubkey: Script::new(),
+      })
+      .collect(),
+  }
+}
+
+/// Like `spend`, but revealing `inscription` in the witness of the first
+/// input.
+pub(super) fn reveal(inscription: Inscription, inputs: &[OutPoint], values: &[u64]) -> Transaction {
+  let mut tx = spend(inputs, values);
+  tx.input[0].witness = Witness::from_vec(vec![
+    inscription
+      .append_reveal_script(script::Builder::new())
+      .into_bytes(),
+    Vec::new(),
+  ]);
+  tx
+}
@@ -1,4 +1,9 @@
-use {super::*, std::sync::mpsc};
+use {
+  super::*,
+  crate::webhooks::{NewInscription, Notification, Transfer},
+  bitcoincore_rpc::jsonrpc,
+  std::sync::mpsc,
+};
 
 pub(crate) struct BlockData {
   header: BlockHeader,
@@ -21,28 +26,78 @@ impl From<Block> for BlockData {
   }
 }
 
+/// Totals over a call to `Updater::update`, reported by benchmarks.
+#[derive(Debug, Default)]
+pub(crate) struct UpdateStatistics {
+  pub(crate) blocks: u64,
+  pub(crate) sat_ranges: u64,
+  pub(crate) peak_cache_size: usize,
+  /// Time spent waiting for blocks to be fetched from Bitcoin Core.
+  pub(crate) rpc_time: Duration,
+  /// Time spent indexing blocks and committing them.
+  pub(crate) index_time: Duration,
+}
+
+/// Inscriptions read from a spent output at a time when transferring them.
+const TRANSFER_BATCH_SIZE: usize = 1024;
+
+/// Heights per batch request when fetching headers without transactions.
+const HEADER_BATCH_SIZE: u64 = 1000;
+
 pub struct Updater {
-  cache: HashMap<OutPointArray, Vec<u8>>,
+  block_events: Vec<BlockEvent>,
+  block_events_sender: broadcast::Sender<BlockEvent>,
+  cache: HashMap<OutPointKey, Vec<u8>>,
+  collection_members: Vec<(CollectionSlug, Txid)>,
+  new_inscriptions: Vec<InscriptionId>,
   height: u64,
-  index_satoshis: bool,
+  inscription_events: Vec<InscriptionEvent>,
+  inscription_events_sender: broadcast::Sender<InscriptionEvent>,
+  inscription_history: Vec<(InscriptionId, SatPoint)>,
+  index_addresses: bool,
+  index_spent_sat_ranges: bool,
+  index_fee_provenance: bool,
+  index_inscribed_sats: bool,
+  index_inscription_history: bool,
+  index_rare_sats: bool,
+  index_sat_history: bool,
+  index_sat_ranges: bool,
+  notification: Option<Notification>,
+  notifications: Vec<Notification>,
   sat_ranges_since_flush: u64,
+  statistics: UpdateStatistics,
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
   outputs_traversed: u64,
+  transfer_buffer: Vec<(SatPointArray, InscriptionIdArray)>,
+  webhooks: Option<Webhooks>,
 }
 
 impl Updater {
-  pub(crate) fn update(index: &Index) -> Result {
+  pub(crate) fn update(index: &Index, stop: &AtomicBool) -> Result<UpdateStatistics> {
     let wtx = index.begin_write()?;
 
     let height = wtx
       .open_table(HEIGHT_TO_BLOCK_HASH)?
       .range(0..)?
-      .rev()
-      .next()
+      .next_back()
       .map(|(height, _hash)| height + 1)
       .unwrap_or(0);
 
+    {
+      let mut write_transactions =
+        wtx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+
+      let interrupted = write_transactions
+        .range(height + 1..)?
+        .map(|(started, _timestamp)| started)
+        .collect::<Vec<u64>>();
+
+      for started in interrupted {
+        write_transactions.remove(&started)?;
+      }
+    }
+
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
       .insert(
@@ -53,31 +108,62 @@ impl Updater {
           .unwrap_or(0),
       )?;
 
-    let mut updater = Self {
+    let mut updater = Self::new(index, height)?;
+
+    updater.update_index(index, wtx, stop)?;
+
+    Ok(updater.statistics)
+  }
+
+  /// An updater that will index the block at `height` next.
+  pub(crate) fn new(index: &Index, height: u64) -> Result<Self> {
+    Ok(Self {
+      block_events: Vec::new(),
+      block_events_sender: index.block_events.clone(),
       cache: HashMap::new(),
+      collection_members: Vec::new(),
+      new_inscriptions: Vec::new(),
       height,
-      index_satoshis: index.has_satoshi_index()?,
+      inscription_events: Vec::new(),
+      inscription_events_sender: index.inscription_events.clone(),
+      inscription_history: Vec::new(),
+      index_addresses: index.has_addresses()?,
+      index_spent_sat_ranges: index.has_spent_sat_ranges()?,
+      index_fee_provenance: index.has_fee_provenance()?,
+      index_inscribed_sats: index.has_inscribed_sats()?,
+      index_inscription_history: index.has_inscription_history()?,
+      index_rare_sats: index.has_rare_sats()?,
+      index_sat_history: index.has_sat_history()?,
+      index_sat_ranges: index.has_sat_ranges()?,
+      notification: None,
+      notifications: Vec::new(),
       sat_ranges_since_flush: 0,
+      statistics: UpdateStatistics::default(),
       outputs_cached: 0,
       outputs_inserted_since_flush: 0,
       outputs_traversed: 0,
-    };
-
-    updater.update_index(index, wtx)
+      transfer_buffer: Vec::with_capacity(TRANSFER_BATCH_SIZE),
+      webhooks: index.webhooks.get().cloned(),
+    })
   }
 
   pub(crate) fn update_index<'index>(
     &mut self,
     index: &'index Index,
     mut wtx: WriteTransaction<'index>,
+    stop: &AtomicBool,
   ) -> Result {
-    let starting_height = index.client.get_block_count()? + 1;
+    let starting_height = index.rpc.get()?.get_block_count()? + 1;
+
+    index
+      .node_block_count
+      .store(starting_height, atomic::Ordering::Relaxed);
 
     let mut progress_bar = if cfg!(test)
       || log_enabled!(log::Level::Info)
       || starting_height <= self.height
       || env::var_os("ORD_DISABLE_PROGRESS_BAR")
-        .map(|value| value.len() > 0)
+        .map(|value| !value.is_empty())
         .unwrap_or(false)
     {
       None
@@ -90,14 +176,17 @@ impl Updater {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(index, self.height, self.index_satoshis)?;
+    let rx = Self::fetch_blocks_from(
+      index,
+      self.height,
+      self.index_sat_ranges || self.index_addresses,
+    )?;
 
     let mut uncommitted = 0;
-    loop {
-      let block = match rx.recv() {
-        Ok(block) => block,
-        Err(mpsc::RecvError) => break,
-      };
+    let mut waiting = Instant::now();
+    while let Ok(block) = rx.recv() {
+      let indexing = Instant::now();
+      self.statistics.rpc_time += indexing - waiting;
 
       self.index_block(index, &mut wtx, block)?;
 
@@ -105,7 +194,7 @@ impl Updater {
         progress_bar.inc(1);
 
         if progress_bar.position() > progress_bar.length().unwrap() {
-          progress_bar.set_length(index.client.get_block_count()? + 1);
+          progress_bar.set_length(index.rpc.get()?.get_block_count()? + 1);
         }
       }
 
@@ -118,8 +207,7 @@ impl Updater {
         let height = wtx
           .open_table(HEIGHT_TO_BLOCK_HASH)?
           .range(0..)?
-          .rev()
-          .next()
+          .next_back()
           .map(|(height, _hash)| height + 1)
           .unwrap_or(0);
         if height != self.height {
@@ -138,13 +226,18 @@ impl Updater {
           )?;
       }
 
-      if INTERRUPTS.load(atomic::Ordering::Relaxed) > 0 {
+      waiting = Instant::now();
+      self.statistics.index_time += waiting - indexing;
+
+      if INTERRUPTS.load(atomic::Ordering::Relaxed) > 0 || stop.load(atomic::Ordering::Relaxed) {
         break;
       }
     }
 
     if uncommitted > 0 {
+      let committing = Instant::now();
       self.commit(wtx)?;
+      self.statistics.index_time += committing.elapsed();
     }
 
     if let Some(progress_bar) = &mut progress_bar {
@@ -157,36 +250,76 @@ impl Updater {
   fn fetch_blocks_from(
     index: &Index,
     mut height: u64,
-    index_satoshis: bool,
+    require_transactions: bool,
   ) -> Result<mpsc::Receiver<BlockData>> {
     let (tx, rx) = mpsc::sync_channel(32);
 
     let height_limit = index.height_limit;
 
-    let client =
-      Client::new(&index.rpc_url, index.auth.clone()).context("failed to connect to RPC URL")?;
+    let client = index.rpc.get()?;
 
-    let with_transactions = index_satoshis || index.chain != Chain::Mainnet;
+    let with_transactions = require_transactions || index.chain != Chain::Mainnet;
 
-    thread::spawn(move || loop {
-      if let Some(height_limit) = height_limit {
-        if height >= height_limit {
-          break;
-        }
-      }
+    thread::spawn(move || {
+      // Headers are fetched in batches until the node rejects a batch, after
+      // which every block is fetched individually.
+      let mut batch = !with_transactions;
 
-      match Self::get_block_with_retries(&client, height, with_transactions) {
-        Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
-            log::info!("Block receiver disconnected: {err}");
+      loop {
+        if let Some(height_limit) = height_limit {
+          if height >= height_limit {
             break;
           }
-          height += 1;
         }
-        Ok(None) => break,
-        Err(err) => {
-          log::error!("Failed to fetch block {height}: {err}");
-          break;
+
+        if batch {
+          let count = height_limit
+            .map(|height_limit| (height_limit - height).min(HEADER_BATCH_SIZE))
+            .unwrap_or(HEADER_BATCH_SIZE);
+
+          match Self::get_headers_batched(&client, height, count) {
+            Ok(headers) => {
+              let end = u64::try_from(headers.len()).unwrap() < count;
+
+              for header in headers {
+                if let Err(err) = tx.send(BlockData {
+                  header,
+                  txdata: Vec::new(),
+                }) {
+                  log::info!("Block receiver disconnected: {err}");
+                  return;
+                }
+                height += 1;
+              }
+
+              if end {
+                break;
+              }
+            }
+            Err(err) => {
+              log::warn!(
+                "Failed to fetch headers in batch, falling back to serial requests: {err}"
+              );
+              batch = false;
+            }
+          }
+
+          continue;
+        }
+
+        match Self::get_block_with_retries(&client, height, with_transactions) {
+          Ok(Some(block)) => {
+            if let Err(err) = tx.send(block.into()) {
+              log::info!("Block receiver disconnected: {err}");
+              break;
+            }
+            height += 1;
+          }
+          Ok(None) => break,
+          Err(err) => {
+            log::error!("Failed to fetch block {height}: {err}");
+            break;
+          }
         }
       }
     });
@@ -194,6 +327,65 @@ impl Updater {
     Ok(rx)
   }
 
+  /// Fetch the headers of up to `count` blocks starting at `start`, with one
+  /// batch request for their hashes and another for the headers themselves.
+  /// Stops early at the chain tip.
+  pub(crate) fn get_headers_batched(
+    client: &Client,
+    start: u64,
+    count: u64,
+  ) -> Result<Vec<BlockHeader>> {
+    let client = client.get_jsonrpc_client();
+
+    let params = (start..start + count)
+      .map(|height| [jsonrpc::arg(height)])
+      .collect::<Vec<_>>();
+
+    let requests = params
+      .iter()
+      .map(|params| client.build_request("getblockhash", params))
+      .collect::<Vec<_>>();
+
+    let mut hashes = Vec::new();
+    for response in client.send_batch(&requests)? {
+      let response = response.ok_or_else(|| anyhow!("missing response to getblockhash"))?;
+
+      match response
+        .result::<BlockHash>()
+        .map_err(bitcoincore_rpc::Error::from)
+        .into_option()?
+      {
+        Some(hash) => hashes.push(hash),
+        None => break,
+      }
+    }
+
+    if hashes.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let params = hashes
+      .iter()
+      .map(|hash| [jsonrpc::arg(hash), jsonrpc::arg(false)])
+      .collect::<Vec<_>>();
+
+    let requests = params
+      .iter()
+      .map(|params| client.build_request("getblockheader", params))
+      .collect::<Vec<_>>();
+
+    client
+      .send_batch(&requests)?
+      .into_iter()
+      .map(|response| {
+        let hex = response
+          .ok_or_else(|| anyhow!("missing response to getblockheader"))?
+          .result::<String>()?;
+        Ok(bitcoin::consensus::encode::deserialize(&hex::decode(hex)?)?)
+      })
+      .collect()
+  }
+
   pub(crate) fn get_block_with_retries(
     client: &Client,
     height: u64,
@@ -245,11 +437,14 @@ impl Updater {
     wtx: &mut WriteTransaction,
     block: BlockData,
   ) -> Result<()> {
+    let mut block_hash_to_height = wtx.open_table(BLOCK_HASH_TO_HEIGHT)?;
     let mut height_to_block_hash = wtx.open_table(HEIGHT_TO_BLOCK_HASH)?;
 
     let start = Instant::now();
     let mut sat_ranges_written = 0;
     let mut outputs_in_block = 0;
+    let mut fees = 0;
+    let mut inscriptions = 0;
 
     let time = Utc.timestamp_opt(block.header.time.into(), 0).unwrap();
 
@@ -269,17 +464,44 @@ impl Updater {
       }
     }
 
+    if self.webhooks.is_some() {
+      self.notification = Some(Notification {
+        height: self.height,
+        hash: block.header.block_hash(),
+        inscriptions: Vec::new(),
+        transfers: Vec::new(),
+      });
+    }
+
     let mut inscription_id_to_satpoint = wtx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
     let mut satpoint_to_inscription_id = wtx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
+    let mut inscription_id_to_burned = wtx.open_table(INSCRIPTION_ID_TO_BURNED)?;
 
-    if self.index_satoshis {
+    if self.index_sat_ranges {
       let mut sat_to_inscription_id = wtx.open_table(SAT_TO_INSCRIPTION_ID)?;
       let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
+      let mut sat_to_satpoint_history = wtx.open_multimap_table(SAT_TO_SATPOINT_HISTORY)?;
       let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
+      let mut spent_outpoint_to_sat_ranges = if self.index_spent_sat_ranges {
+        Some(wtx.open_table(SPENT_OUTPOINT_TO_SAT_RANGES)?)
+      } else {
+        None
+      };
+      let mut fee_provenance_tables = if self.index_fee_provenance {
+        Some((
+          wtx.open_multimap_table(FEE_RANGE_START_TO_PROVENANCE)?,
+          wtx.open_table(FEE_TXID_TO_FIRST_SAT)?,
+        ))
+      } else {
+        None
+      };
 
       let mut coinbase_inputs = VecDeque::new();
 
       let h = Height(self.height);
+
+      let mut reward_breakdown = h.subsidy().to_le_bytes().to_vec();
+
       if h.subsidy() > 0 {
         let start = h.starting_sat();
         coinbase_inputs.push_front((start.n(), (start + h.subsidy()).n()));
@@ -292,7 +514,7 @@ impl Updater {
         let mut input_sat_ranges = VecDeque::new();
 
         for input in &tx.input {
-          let key = encode_outpoint(input.previous_output);
+          let key = OutPointKey::new(input.previous_output);
 
           let sat_ranges = match self.cache.remove(&key) {
             Some(sat_ranges) => {
@@ -300,60 +522,227 @@ impl Updater {
               sat_ranges
             }
             None => outpoint_to_sat_ranges
-              .remove(&key)?
+              .remove(key.as_array())?
               .ok_or_else(|| anyhow!("Could not find outpoint {} in index", input.previous_output))?
               .to_value()
               .to_vec(),
           };
 
+          if let Some(spent_outpoint_to_sat_ranges) = &mut spent_outpoint_to_sat_ranges {
+            spent_outpoint_to_sat_ranges.insert(key.as_array(), sat_ranges.as_slice())?;
+          }
+
           for chunk in sat_ranges.chunks_exact(11) {
             input_sat_ranges.push_back(Index::decode_sat_range(chunk.try_into().unwrap()));
           }
         }
 
-        self.index_transaction_sats(
+        if self.index_transaction_sats(
           tx,
           *txid,
+          tx_offset.try_into().unwrap(),
           &mut sat_to_satpoint,
+          &mut sat_to_satpoint_history,
           &mut sat_to_inscription_id,
           &mut inscription_id_to_satpoint,
           &mut satpoint_to_inscription_id,
+          &mut inscription_id_to_burned,
           &mut input_sat_ranges,
           &mut sat_ranges_written,
           &mut outputs_in_block,
-        )?;
+        )? {
+          inscriptions += 1;
+        }
+
+        let fee = input_sat_ranges
+          .iter()
+          .map(|(start, end)| end - start)
+          .sum::<u64>();
+
+        fees += fee;
+
+        if fee > 0 {
+          reward_breakdown.extend_from_slice(&u32::try_from(tx_offset).unwrap().to_le_bytes());
+          reward_breakdown.extend_from_slice(&fee.to_le_bytes());
+
+          if let Some((fee_range_start_to_provenance, fee_txid_to_first_sat)) =
+            &mut fee_provenance_tables
+          {
+            for (start, end) in &input_sat_ranges {
+              fee_range_start_to_provenance
+                .insert(start, &encode_fee_provenance(self.height, *end, *txid))?;
+            }
+
+            if let Some((start, _end)) = input_sat_ranges.front() {
+              fee_txid_to_first_sat.insert(txid.as_inner(), start)?;
+            }
+          }
+        }
 
         coinbase_inputs.extend(input_sat_ranges);
       }
 
-      if let Some((tx, txid)) = block.txdata.get(0) {
-        self.index_transaction_sats(
+      wtx
+        .open_table(HEIGHT_TO_REWARD_BREAKDOWN)?
+        .insert(&self.height, reward_breakdown.as_slice())?;
+
+      if let Some((tx, txid)) = block.txdata.first() {
+        if self.index_transaction_sats(
           tx,
           *txid,
+          block.txdata.len().try_into().unwrap(),
           &mut sat_to_satpoint,
+          &mut sat_to_satpoint_history,
           &mut sat_to_inscription_id,
           &mut inscription_id_to_satpoint,
           &mut satpoint_to_inscription_id,
+          &mut inscription_id_to_burned,
           &mut coinbase_inputs,
           &mut sat_ranges_written,
           &mut outputs_in_block,
-        )?;
+        )? {
+          inscriptions += 1;
+        }
       }
     } else {
       for (tx, txid) in &block.txdata {
-        self.index_transaction_inscriptions(
+        if self.index_transaction_inscriptions(
           tx,
           *txid,
           &mut inscription_id_to_satpoint,
           &mut satpoint_to_inscription_id,
+          &mut inscription_id_to_burned,
+        )? {
+          inscriptions += 1;
+        }
+      }
+    }
+
+    if self.index_addresses {
+      let mut outpoint_to_script_pubkey = wtx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
+      let mut script_pubkey_to_outpoints = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?;
+
+      for (tx, txid) in &block.txdata {
+        Self::index_transaction_addresses(
+          tx,
+          *txid,
+          &mut outpoint_to_script_pubkey,
+          &mut script_pubkey_to_outpoints,
+        )?;
+      }
+    }
+
+    let mut inscription_events = Vec::new();
+
+    if !self.new_inscriptions.is_empty() {
+      let mut height_to_inscription_ids = wtx.open_multimap_table(HEIGHT_TO_INSCRIPTION_IDS)?;
+      let mut inscription_id_to_genesis_height =
+        wtx.open_table(INSCRIPTION_ID_TO_GENESIS_HEIGHT)?;
+      let mut inscription_id_to_inscription_number =
+        wtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_NUMBER)?;
+      let mut inscription_number_to_inscription_id =
+        wtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+      let next_number = inscription_number_to_inscription_id
+        .iter()?
+        .next_back()
+        .map(|(number, _id)| number + 1)
+        .unwrap_or_default();
+
+      for (sequence, (number, inscription_id)) in (next_number..)
+        .zip(self.new_inscriptions.drain(..))
+        .enumerate()
+      {
+        height_to_inscription_ids.insert(
+          &self.height,
+          &encode_block_inscription(sequence.try_into().unwrap(), inscription_id),
+        )?;
+        inscription_id_to_genesis_height.insert(
+          InscriptionIdKey::new(inscription_id).as_array(),
+          &self.height,
+        )?;
+        inscription_id_to_inscription_number
+          .insert(InscriptionIdKey::new(inscription_id).as_array(), &number)?;
+        inscription_number_to_inscription_id.insert(&number, inscription_id.as_inner())?;
+
+        inscription_events.push(InscriptionEvent {
+          inscription_id,
+          number,
+          satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: inscription_id,
+              vout: 0,
+            },
+            offset: 0,
+          },
+          height: self.height,
+        });
+      }
+    }
+
+    if !self.collection_members.is_empty() {
+      let mut collection_to_inscription_ids =
+        wtx.open_multimap_table(COLLECTION_TO_INSCRIPTION_IDS)?;
+
+      for (sequence, (collection, inscription_id)) in self.collection_members.drain(..).enumerate()
+      {
+        collection_to_inscription_ids.insert(
+          collection.as_str(),
+          &encode_collection_member(self.height, sequence.try_into().unwrap(), inscription_id),
         )?;
       }
     }
 
+    if !self.inscription_history.is_empty() {
+      let mut inscription_id_to_satpoint_history =
+        wtx.open_multimap_table(INSCRIPTION_ID_TO_SATPOINT_HISTORY)?;
+
+      for (sequence, (inscription_id, satpoint)) in self.inscription_history.drain(..).enumerate() {
+        inscription_id_to_satpoint_history.insert(
+          InscriptionIdKey::new(inscription_id).as_array(),
+          &encode_sat_history(self.height, sequence.try_into().unwrap(), satpoint),
+        )?;
+      }
+    }
+
+    {
+      let h = Height(self.height);
+      let epoch = Epoch::from(h).0;
+      let mut epoch_to_statistics = wtx.open_table(EPOCH_TO_STATISTICS)?;
+      let [blocks, subsidy, total_fees, total_inscriptions] = epoch_to_statistics
+        .get(&epoch)?
+        .map(|statistics| decode_epoch_statistics(*statistics))
+        .unwrap_or_default();
+      epoch_to_statistics.insert(
+        &epoch,
+        &encode_epoch_statistics([
+          blocks + 1,
+          subsidy + h.subsidy(),
+          total_fees + fees,
+          total_inscriptions + inscriptions,
+        ]),
+      )?;
+    }
+
     height_to_block_hash.insert(
       &self.height,
       &block.header.block_hash().as_hash().into_inner(),
     )?;
+    block_hash_to_height.insert(
+      &block.header.block_hash().as_hash().into_inner(),
+      &self.height,
+    )?;
+
+    self.notifications.extend(self.notification.take());
+    self.inscription_events.extend(inscription_events);
+    self.block_events.push(BlockEvent {
+      height: self.height,
+      hash: block.header.block_hash(),
+    });
+
+    self.statistics.blocks += 1;
+    self.statistics.sat_ranges += sat_ranges_written;
+    self.statistics.peak_cache_size = self.statistics.peak_cache_size.max(self.cache.len());
 
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
@@ -366,76 +755,225 @@ impl Updater {
     Ok(())
   }
 
+  /// Remove the outputs that `tx` spends from the address index, and add the
+  /// ones it creates. Outputs that can never be spent aren't added.
+  fn index_transaction_addresses(
+    tx: &Transaction,
+    txid: Txid,
+    outpoint_to_script_pubkey: &mut Table<&OutPointArray, [u8]>,
+    script_pubkey_to_outpoints: &mut MultimapTable<[u8], &OutPointArray>,
+  ) -> Result {
+    for input in &tx.input {
+      if input.previous_output.is_null() {
+        continue;
+      }
+
+      let key = OutPointKey::new(input.previous_output);
+
+      if let Some(script_pubkey) = outpoint_to_script_pubkey.remove(key.as_array())? {
+        script_pubkey_to_outpoints.remove(script_pubkey.to_value(), key.as_array())?;
+      }
+    }
+
+    for (vout, output) in tx.output.iter().enumerate() {
+      if output.script_pubkey.is_provably_unspendable() {
+        continue;
+      }
+
+      let key = OutPointKey::new(OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      });
+
+      outpoint_to_script_pubkey.insert(key.as_array(), output.script_pubkey.as_bytes())?;
+      script_pubkey_to_outpoints.insert(output.script_pubkey.as_bytes(), key.as_array())?;
+    }
+
+    Ok(())
+  }
+
   pub(crate) fn index_transaction_inscriptions(
     &mut self,
     tx: &Transaction,
     txid: Txid,
     inscription_id_to_satpoint: &mut Table<&InscriptionIdArray, &SatPointArray>,
     satpoint_to_inscription_id: &mut Table<&SatPointArray, &InscriptionIdArray>,
+    inscription_id_to_burned: &mut Table<&InscriptionIdArray, ()>,
   ) -> Result<bool> {
-    let inscribed = Inscription::from_transaction(tx).is_some();
+    let inscription = Inscription::from_transaction(tx);
+
+    let inscribed = inscription.is_some();
+
+    // Inscriptions always land on the first output, so they are burned if it
+    // can never be spent.
+    let burned = tx
+      .output
+      .first()
+      .map(|output| output.script_pubkey.is_provably_unspendable())
+      .unwrap_or_default();
 
     if inscribed {
-      let satpoint = encode_satpoint(SatPoint {
+      let satpoint = SatPointKey::new(SatPoint {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 0,
       });
 
-      inscription_id_to_satpoint.insert(txid.as_inner(), &satpoint)?;
-      satpoint_to_inscription_id.insert(&satpoint, txid.as_inner())?;
+      let inscription_id = InscriptionIdKey::new(txid);
+
+      inscription_id_to_satpoint.insert(inscription_id.as_array(), satpoint.as_array())?;
+      satpoint_to_inscription_id.insert(satpoint.as_array(), txid.as_inner())?;
+
+      if burned {
+        inscription_id_to_burned.insert(inscription_id.as_array(), &())?;
+      }
+
+      self.new_inscriptions.push(txid);
+
+      if let Some(collection) = inscription.and_then(|inscription| inscription.collection()) {
+        self.collection_members.push((collection, txid));
+      }
+
+      if self.index_inscription_history {
+        self.inscription_history.push((txid, satpoint.satpoint()));
+      }
+
+      if let Some(notification) = &mut self.notification {
+        notification.inscriptions.push(NewInscription {
+          inscription: txid,
+          satpoint: satpoint.satpoint(),
+        });
+      }
     };
 
+    let mut transfers = Vec::new();
+
+    Self::transfer_inscriptions(
+      &mut self.transfer_buffer,
+      tx,
+      txid,
+      inscription_id_to_satpoint,
+      satpoint_to_inscription_id,
+      (self.notification.is_some() || self.index_inscription_history).then_some(&mut transfers),
+      burned.then_some(inscription_id_to_burned),
+    )?;
+
+    if self.index_inscription_history {
+      self.inscription_history.extend(
+        transfers
+          .iter()
+          .map(|transfer| (transfer.inscription, transfer.to)),
+      );
+    }
+
+    if let Some(notification) = &mut self.notification {
+      notification.transfers.extend(transfers);
+    }
+
+    Ok(inscribed)
+  }
+
+  /// Move the inscriptions on every output spent by `tx` to its first
+  /// satpoint, reading at most `TRANSFER_BATCH_SIZE` of them at a time into
+  /// `buffer`. Inscriptions are removed as they are moved, so each batch is
+  /// the start of what remains on the output. All of them land on the same
+  /// satpoint, so only the last one needs to be written to
+  /// `satpoint_to_inscription_id`. Each move is recorded in `transfers`, and
+  /// each moved inscription is marked as burned in `inscription_id_to_burned`,
+  /// if given.
+  pub(crate) fn transfer_inscriptions(
+    buffer: &mut Vec<(SatPointArray, InscriptionIdArray)>,
+    tx: &Transaction,
+    txid: Txid,
+    inscription_id_to_satpoint: &mut Table<&InscriptionIdArray, &SatPointArray>,
+    satpoint_to_inscription_id: &mut Table<&SatPointArray, &InscriptionIdArray>,
+    mut transfers: Option<&mut Vec<Transfer>>,
+    mut inscription_id_to_burned: Option<&mut Table<&InscriptionIdArray, ()>>,
+  ) -> Result {
+    let new_satpoint = SatPointKey::new(SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 0,
+    });
+
     for tx_in in &tx.input {
-      let outpoint = tx_in.previous_output;
-      let start = encode_satpoint(SatPoint {
-        outpoint,
+      let start = SatPointKey::new(SatPoint {
+        outpoint: tx_in.previous_output,
         offset: 0,
       });
 
-      let end = encode_satpoint(SatPoint {
-        outpoint,
+      let end = SatPointKey::new(SatPoint {
+        outpoint: tx_in.previous_output,
         offset: u64::MAX,
       });
 
-      let inscription_ids: Vec<(SatPointArray, InscriptionIdArray)> = satpoint_to_inscription_id
-        .range(start..=end)?
-        .map(|(satpoint, id)| (*satpoint, *id))
-        .collect();
+      loop {
+        buffer.clear();
+        buffer.extend(
+          satpoint_to_inscription_id
+            .range(*start.as_array()..=*end.as_array())?
+            .take(TRANSFER_BATCH_SIZE)
+            .map(|(satpoint, inscription_id)| (*satpoint, *inscription_id)),
+        );
 
-      for (old_satpoint, inscription_id) in inscription_ids {
-        let new_satpoint = encode_satpoint(SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
-        });
+        let Some((_satpoint, last)) = buffer.last() else {
+          break;
+        };
 
-        satpoint_to_inscription_id.remove(&old_satpoint)?;
-        satpoint_to_inscription_id.insert(&new_satpoint, &inscription_id)?;
-        inscription_id_to_satpoint.insert(&inscription_id, &new_satpoint)?;
+        satpoint_to_inscription_id.insert(new_satpoint.as_array(), last)?;
+
+        for (old_satpoint, inscription_id) in buffer.iter() {
+          let inscription_id = decode_inscription_id(*inscription_id);
+          let key = InscriptionIdKey::new(inscription_id);
+
+          satpoint_to_inscription_id.remove(old_satpoint)?;
+          inscription_id_to_satpoint.insert(key.as_array(), new_satpoint.as_array())?;
+
+          if let Some(inscription_id_to_burned) = inscription_id_to_burned.as_deref_mut() {
+            inscription_id_to_burned.insert(key.as_array(), &())?;
+          }
+
+          if let Some(transfers) = transfers.as_deref_mut() {
+            transfers.push(Transfer {
+              inscription: inscription_id,
+              from: SatPointKey::from_array(*old_satpoint).satpoint(),
+              to: new_satpoint.satpoint(),
+            });
+          }
+        }
+
+        if buffer.len() < TRANSFER_BATCH_SIZE {
+          break;
+        }
       }
     }
 
-    Ok(inscribed)
+    Ok(())
   }
 
   pub(crate) fn index_transaction_sats(
     &mut self,
     tx: &Transaction,
     txid: Txid,
+    sequence: u32,
     sat_to_satpoint: &mut Table<u64, &SatPointArray>,
+    sat_to_satpoint_history: &mut MultimapTable<u64, &SatHistoryArray>,
     sat_to_inscription_id: &mut Table<u64, &InscriptionIdArray>,
     inscription_id_to_satpoint: &mut Table<&InscriptionIdArray, &SatPointArray>,
     satpoint_to_inscription_id: &mut Table<&SatPointArray, &InscriptionIdArray>,
+    inscription_id_to_burned: &mut Table<&InscriptionIdArray, ()>,
     input_sat_ranges: &mut VecDeque<(u64, u64)>,
     sat_ranges_written: &mut u64,
     outputs_traversed: &mut u64,
-  ) -> Result {
-    if self.index_transaction_inscriptions(
+  ) -> Result<bool> {
+    let inscribed = self.index_transaction_inscriptions(
       tx,
       txid,
       inscription_id_to_satpoint,
       satpoint_to_inscription_id,
-    )? {
-      if let Some((start, _end)) = input_sat_ranges.get(0) {
+      inscription_id_to_burned,
+    )?;
+
+    if inscribed && self.index_inscribed_sats {
+      if let Some((start, _end)) = input_sat_ranges.front() {
         sat_to_inscription_id.insert(&start, txid.as_inner())?;
       }
     }
@@ -453,14 +991,20 @@ impl Updater {
           .pop_front()
           .ok_or_else(|| anyhow!("insufficient inputs for transaction outputs"))?;
 
-        if !Sat(range.0).is_common() {
-          sat_to_satpoint.insert(
-            &range.0,
-            &encode_satpoint(SatPoint {
-              outpoint,
-              offset: output.value - remaining,
-            }),
-          )?;
+        if self.index_rare_sats && !Sat(range.0).is_common() {
+          let satpoint = SatPoint {
+            outpoint,
+            offset: output.value - remaining,
+          };
+
+          sat_to_satpoint.insert(&range.0, SatPointKey::new(satpoint).as_array())?;
+
+          if self.index_sat_history {
+            sat_to_satpoint_history.insert(
+              &range.0,
+              &encode_sat_history(self.height, sequence, satpoint),
+            )?;
+          }
         }
 
         let count = range.1 - range.0;
@@ -488,11 +1032,11 @@ impl Updater {
 
       *outputs_traversed += 1;
 
-      self.cache.insert(encode_outpoint(outpoint), sats);
+      self.cache.insert(OutPointKey::new(outpoint), sats);
       self.outputs_inserted_since_flush += 1;
     }
 
-    Ok(())
+    Ok(inscribed)
   }
 
   pub(crate) fn commit(&mut self, wtx: WriteTransaction) -> Result {
@@ -504,7 +1048,7 @@ impl Updater {
       self.outputs_cached
     );
 
-    if self.index_satoshis {
+    if self.index_sat_ranges {
       log::info!(
         "Flushing {} entries ({:.1}% resulting from {} insertions) from memory to database",
         self.cache.len(),
@@ -515,7 +1059,7 @@ impl Updater {
       let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
 
       for (k, v) in &self.cache {
-        outpoint_to_sat_ranges.insert(k, v)?;
+        outpoint_to_sat_ranges.insert(k.as_array(), v)?;
       }
 
       self.cache.clear();
@@ -529,6 +1073,848 @@ impl Updater {
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
 
     wtx.commit()?;
+
+    if let Some(webhooks) = &self.webhooks {
+      for notification in self.notifications.drain(..) {
+        webhooks.notify(notification);
+      }
+    }
+
+    // Sending only fails when there are no subscribers, in which case there
+    // is no one to tell.
+    for event in self.block_events.drain(..) {
+      self.block_events_sender.send(event).ok();
+    }
+
+    for event in self.inscription_events.drain(..) {
+      self.inscription_events_sender.send(event).ok();
+    }
+
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::test_fixtures::{reveal, spend, TestChain},
+    bitcoin::blockdata::{opcodes, script},
+  };
+
+  fn satpoint(outpoint: OutPoint, offset: u64) -> SatPoint {
+    SatPoint { outpoint, offset }
+  }
+
+  #[test]
+  fn inscription_is_created_on_first_sat_of_reveal() {
+    for args in ["", "--index-sat-ranges"] {
+      let mut chain = TestChain::new(args);
+      chain.mine(Vec::new());
+
+      let tx = reveal(
+        inscription("text/plain", "foo"),
+        &[chain.coinbase(1)],
+        &[50 * COIN_VALUE],
+      );
+      let txid = tx.txid();
+      chain.mine(vec![tx]);
+
+      assert_eq!(
+        chain.satpoint(txid),
+        Some(satpoint(OutPoint::new(txid, 0), 0))
+      );
+      assert_eq!(
+        chain.index.get_inscriptions().unwrap(),
+        [(satpoint(OutPoint::new(txid, 0), 0), txid)].into()
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_transfers_to_first_satpoint_of_spending_transaction() {
+    for args in ["", "--index-sat-ranges"] {
+      let mut chain = TestChain::new(args);
+      chain.mine(Vec::new());
+
+      let inscription = reveal(
+        inscription("text/plain", "foo"),
+        &[chain.coinbase(1)],
+        &[50 * COIN_VALUE],
+      );
+      let inscription_id = inscription.txid();
+      chain.mine(vec![inscription]);
+
+      let send = spend(
+        &[OutPoint::new(inscription_id, 0)],
+        &[10 * COIN_VALUE, 40 * COIN_VALUE],
+      );
+      let send_txid = send.txid();
+      chain.mine(vec![send]);
+
+      assert_eq!(
+        chain.satpoint(inscription_id),
+        Some(satpoint(OutPoint::new(send_txid, 0), 0))
+      );
+      assert_eq!(
+        chain.index.get_inscriptions().unwrap(),
+        [(satpoint(OutPoint::new(send_txid, 0), 0), inscription_id)].into()
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_transfers_through_transactions_in_same_block() {
+    let mut chain = TestChain::new("--index-sat-ranges");
+    chain.mine(Vec::new());
+
+    let inscription = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let inscription_id = inscription.txid();
+    let first = spend(&[OutPoint::new(inscription_id, 0)], &[50 * COIN_VALUE]);
+    let second = spend(&[OutPoint::new(first.txid(), 0)], &[50 * COIN_VALUE]);
+    let second_txid = second.txid();
+    chain.mine(vec![inscription, first, second]);
+
+    assert_eq!(
+      chain.satpoint(inscription_id),
+      Some(satpoint(OutPoint::new(second_txid, 0), 0))
+    );
+    assert_eq!(
+      chain.index.get_inscriptions().unwrap(),
+      [(satpoint(OutPoint::new(second_txid, 0), 0), inscription_id)].into()
+    );
+  }
+
+  #[test]
+  fn inscriptions_from_all_inputs_transfer_to_first_satpoint() {
+    let mut chain = TestChain::new("");
+    chain.mine(Vec::new());
+    chain.mine(Vec::new());
+
+    let a = reveal(
+      inscription("text/plain", "a"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let b = reveal(
+      inscription("text/plain", "b"),
+      &[chain.coinbase(2)],
+      &[50 * COIN_VALUE],
+    );
+    let (a_id, b_id) = (a.txid(), b.txid());
+    chain.mine(vec![a, b]);
+
+    let send = spend(
+      &[OutPoint::new(b_id, 0), OutPoint::new(a_id, 0)],
+      &[100 * COIN_VALUE],
+    );
+    let destination = satpoint(OutPoint::new(send.txid(), 0), 0);
+    chain.mine(vec![send]);
+
+    assert_eq!(chain.satpoint(a_id), Some(destination));
+    assert_eq!(chain.satpoint(b_id), Some(destination));
+
+    // Both inscriptions share a satpoint, so only the one on the last input
+    // is recorded in `SATPOINT_TO_INSCRIPTION_ID`.
+    assert_eq!(
+      chain.index.get_inscriptions().unwrap(),
+      [(destination, a_id)].into()
+    );
+  }
+
+  #[test]
+  fn reinscription_shares_satpoint_with_transferred_inscription() {
+    let mut chain = TestChain::new("");
+    chain.mine(Vec::new());
+
+    let first = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let first_id = first.txid();
+    chain.mine(vec![first]);
+
+    let second = reveal(
+      inscription("text/plain", "bar"),
+      &[OutPoint::new(first_id, 0)],
+      &[50 * COIN_VALUE],
+    );
+    let second_id = second.txid();
+    let destination = satpoint(OutPoint::new(second_id, 0), 0);
+    chain.mine(vec![second]);
+
+    assert_eq!(chain.satpoint(first_id), Some(destination));
+    assert_eq!(chain.satpoint(second_id), Some(destination));
+    assert_eq!(
+      chain.index.get_inscriptions().unwrap(),
+      [(destination, first_id)].into()
+    );
+  }
+
+  #[test]
+  fn sat_ranges_follow_spent_outputs() {
+    let mut chain = TestChain::new("--index-sat-ranges");
+    chain.mine(Vec::new());
+
+    let send = spend(&[chain.coinbase(1)], &[10 * COIN_VALUE, 39 * COIN_VALUE]);
+    let txid = send.txid();
+    chain.mine(vec![send]);
+
+    let start = 50 * COIN_VALUE;
+
+    assert_eq!(
+      chain.index.list(OutPoint::new(txid, 0)).unwrap(),
+      Some(List::Unspent(vec![(start, start + 10 * COIN_VALUE)]))
+    );
+    assert_eq!(
+      chain.index.list(OutPoint::new(txid, 1)).unwrap(),
+      Some(List::Unspent(vec![(
+        start + 10 * COIN_VALUE,
+        start + 49 * COIN_VALUE
+      )]))
+    );
+    assert_eq!(
+      chain.index.list(chain.coinbase(2)).unwrap(),
+      Some(List::Unspent(vec![
+        (100 * COIN_VALUE, 150 * COIN_VALUE),
+        (start + 49 * COIN_VALUE, start + 50 * COIN_VALUE),
+      ]))
+    );
+  }
+
+  #[test]
+  fn reorg_is_detected_when_block_does_not_extend_tip() {
+    let mut chain = TestChain::new("");
+    chain.mine(Vec::new());
+    let tip = chain.mine(Vec::new());
+
+    let fork = chain.block(tip.header.prev_blockhash, Vec::new());
+
+    assert_eq!(
+      chain.index(fork).unwrap_err().to_string(),
+      "reorg detected at or before 2"
+    );
+    assert!(chain.index.is_reorged());
+    assert_eq!(chain.index.block_count().unwrap(), 3);
+
+    chain.mine(Vec::new());
+    assert_eq!(chain.index.block_count().unwrap(), 4);
+  }
+
+  #[test]
+  fn committed_blocks_are_sent_to_webhooks() {
+    let sink = WebhookSink::spawn(&[]);
+    let mut chain = TestChain::new("");
+    let tempdir = TempDir::new().unwrap();
+    let webhooks = Webhooks::spawn(
+      vec![sink.url()],
+      Some("secret".into()),
+      tempdir.path().join(Webhooks::DEAD_LETTER_FILE),
+    )
+    .unwrap();
+    chain.set_webhooks(webhooks.clone());
+
+    let first = chain.mine(Vec::new());
+
+    let inscription = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let inscription_id = inscription.txid();
+    let send = spend(&[OutPoint::new(inscription_id, 0)], &[50 * COIN_VALUE]);
+    let send_txid = send.txid();
+    let block = chain.mine(vec![inscription, send]);
+
+    let fork = chain.block(block.header.prev_blockhash, Vec::new());
+    chain.index(fork).unwrap_err();
+
+    webhooks.flush().unwrap();
+
+    let requests = sink.requests();
+    assert_eq!(requests.len(), 2);
+
+    for request in &requests {
+      assert_eq!(
+        request.signature.as_deref(),
+        Some(crate::webhooks::signature(b"secret", request.body.as_bytes()).as_str())
+      );
+    }
+
+    assert_eq!(
+      serde_json::from_str::<serde_json::Value>(&requests[0].body).unwrap(),
+      serde_json::json!({
+        "height": 1,
+        "hash": first.block_hash(),
+        "inscriptions": [],
+        "transfers": [],
+      })
+    );
+
+    // The fork was never committed, so it is not sent.
+    assert_eq!(
+      serde_json::from_str::<serde_json::Value>(&requests[1].body).unwrap(),
+      serde_json::json!({
+        "height": 2,
+        "hash": block.block_hash(),
+        "inscriptions": [{
+          "inscription": inscription_id,
+          "satpoint": satpoint(OutPoint::new(inscription_id, 0), 0),
+        }],
+        "transfers": [{
+          "inscription": inscription_id,
+          "from": satpoint(OutPoint::new(inscription_id, 0), 0),
+          "to": satpoint(OutPoint::new(send_txid, 0), 0),
+        }],
+      })
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_numbered_in_inscription_order() {
+    let mut chain = TestChain::new("");
+
+    for _ in 0..4 {
+      chain.mine(Vec::new());
+    }
+
+    let inscribe = |chain: &TestChain, height: usize| {
+      reveal(
+        inscription("text/plain", "foo"),
+        &[chain.coinbase(height)],
+        &[50 * COIN_VALUE],
+      )
+    };
+
+    let page = |chain: &TestChain, from| {
+      chain
+        .index
+        .get_latest_inscriptions_with_prev_and_next(2, from)
+        .unwrap()
+    };
+
+    assert_eq!(page(&chain, None), (Vec::new(), None, None));
+
+    let a = inscribe(&chain, 1);
+    let a_txid = a.txid();
+    chain.mine(vec![a]);
+
+    let b = inscribe(&chain, 2);
+    let c = inscribe(&chain, 3);
+    let d = inscribe(&chain, 4);
+    let (b_txid, c_txid, d_txid) = (b.txid(), c.txid(), d.txid());
+    chain.mine(vec![b, c, d]);
+
+    assert_eq!(page(&chain, None), (vec![d_txid, c_txid], Some(1), None));
+    assert_eq!(page(&chain, Some(1)), (vec![b_txid, a_txid], None, Some(3)));
+    assert_eq!(
+      page(&chain, Some(2)),
+      (vec![c_txid, b_txid], Some(0), Some(3))
+    );
+    assert_eq!(
+      page(&chain, Some(100)),
+      (vec![d_txid, c_txid], Some(1), None)
+    );
+    assert_eq!(
+      chain.index.get_latest_inscriptions(3).unwrap(),
+      [d_txid, c_txid, b_txid]
+    );
+
+    for (number, txid) in [a_txid, b_txid, c_txid, d_txid].into_iter().enumerate() {
+      let number = u64::try_from(number).unwrap();
+      assert_eq!(
+        chain.index.get_inscription_number(txid).unwrap(),
+        Some(number)
+      );
+      assert_eq!(
+        chain
+          .index
+          .get_inscription_id_by_inscription_number(number)
+          .unwrap(),
+        Some(txid)
+      );
+    }
+
+    assert_eq!(
+      chain
+        .index
+        .get_inscription_id_by_inscription_number(4)
+        .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn address_index_tracks_unspent_outputs() {
+    let mut chain = TestChain::new("--index-addresses");
+    chain.mine(Vec::new());
+    chain.mine(Vec::new());
+
+    let outputs = |chain: &TestChain| {
+      chain
+        .index
+        .get_address_outpoints(&Script::new())
+        .unwrap()
+        .into_iter()
+        .collect::<BTreeSet<OutPoint>>()
+    };
+
+    assert!(outputs(&chain).contains(&chain.coinbase(1)));
+    assert!(outputs(&chain).contains(&chain.coinbase(2)));
+
+    let mut send = spend(&[chain.coinbase(1)], &[COIN_VALUE, COIN_VALUE, 0]);
+    send.output[2].script_pubkey = script::Builder::new()
+      .push_opcode(opcodes::all::OP_RETURN)
+      .into_script();
+    let send_txid = send.txid();
+
+    let forward = spend(&[OutPoint::new(send_txid, 1)], &[COIN_VALUE]);
+    let forward_txid = forward.txid();
+
+    chain.mine(vec![send, forward]);
+
+    let outputs = outputs(&chain);
+
+    assert!(!outputs.contains(&chain.coinbase(1)));
+    assert!(outputs.contains(&chain.coinbase(2)));
+    assert!(outputs.contains(&chain.coinbase(3)));
+    assert!(outputs.contains(&OutPoint::new(send_txid, 0)));
+    assert!(!outputs.contains(&OutPoint::new(send_txid, 1)));
+    assert!(!outputs.contains(&OutPoint::new(send_txid, 2)));
+    assert!(outputs.contains(&OutPoint::new(forward_txid, 0)));
+
+    assert_eq!(
+      chain
+        .index
+        .get_address_outpoints(&script::Builder::new().push_int(1).into_script())
+        .unwrap(),
+      Vec::new()
+    );
+  }
+
+  #[test]
+  fn address_outpoints_are_paginated() {
+    let mut chain = TestChain::new("--index-addresses");
+    chain.mine(Vec::new());
+    chain.mine(Vec::new());
+    chain.mine(Vec::new());
+
+    let all = chain.index.get_address_outpoints(&Script::new()).unwrap();
+    assert_eq!(all.len(), 3);
+
+    let page = |page_index| {
+      chain
+        .index
+        .get_address_outpoints_paginated(&Script::new(), 2, page_index)
+        .unwrap()
+    };
+
+    assert_eq!(page(0), (all[..2].to_vec(), true));
+    assert_eq!(page(1), (all[2..].to_vec(), false));
+    assert_eq!(page(2), (Vec::new(), false));
+  }
+
+  #[test]
+  fn address_index_must_be_enabled() {
+    let chain = TestChain::new("");
+
+    assert_eq!(
+      chain
+        .index
+        .get_address_outpoints(&Script::new())
+        .unwrap_err()
+        .to_string(),
+      "address index requires index created with `--index-addresses` flag"
+    );
+  }
+
+  #[test]
+  fn genesis_height_is_height_of_revealing_block() {
+    let mut chain = TestChain::new("");
+
+    chain.mine(Vec::new());
+    chain.mine(Vec::new());
+
+    let a = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let a_txid = a.txid();
+    chain.mine(vec![a]);
+    let a_height = chain.index.height().unwrap().unwrap().n();
+
+    chain.mine(Vec::new());
+
+    let b = reveal(
+      inscription("text/plain", "bar"),
+      &[chain.coinbase(2)],
+      &[50 * COIN_VALUE],
+    );
+    let b_txid = b.txid();
+    chain.mine(vec![b]);
+
+    assert_eq!(
+      chain.index.get_genesis_height(a_txid).unwrap(),
+      Some(a_height)
+    );
+    assert_eq!(
+      chain.index.get_genesis_height(b_txid).unwrap(),
+      Some(a_height + 2)
+    );
+    assert_eq!(
+      chain.index.get_genesis_height(Txid::all_zeros()).unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_listed_by_block_in_reveal_order() {
+    let mut chain = TestChain::new("");
+
+    for _ in 0..3 {
+      chain.mine(Vec::new());
+    }
+
+    let inscribe = |chain: &TestChain, height: usize| {
+      reveal(
+        inscription("text/plain", "foo"),
+        &[chain.coinbase(height)],
+        &[50 * COIN_VALUE],
+      )
+    };
+
+    let (a, b, c) = (
+      inscribe(&chain, 3),
+      inscribe(&chain, 1),
+      inscribe(&chain, 2),
+    );
+    let (a_txid, b_txid, c_txid) = (a.txid(), b.txid(), c.txid());
+    chain.mine(vec![a, b, c]);
+
+    assert_eq!(
+      chain
+        .index
+        .get_block_inscriptions_paginated(4, 2, 0)
+        .unwrap(),
+      (vec![a_txid, b_txid], true),
+    );
+    assert_eq!(
+      chain
+        .index
+        .get_block_inscriptions_paginated(4, 2, 1)
+        .unwrap(),
+      (vec![c_txid], false),
+    );
+    assert_eq!(
+      chain
+        .index
+        .get_block_inscriptions_paginated(3, 2, 0)
+        .unwrap(),
+      (Vec::new(), false),
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_grouped_by_collection_in_inscription_order() {
+    for args in ["", "--index-sat-ranges"] {
+      let mut chain = TestChain::new(args);
+
+      for _ in 0..5 {
+        chain.mine(Vec::new());
+      }
+
+      let inscribe = |chain: &TestChain, height: usize, metadata: &str| {
+        reveal(
+          inscription("text/plain", "foo")
+            .with_metadata(metadata.as_bytes())
+            .unwrap(),
+          &[chain.coinbase(height)],
+          &[50 * COIN_VALUE],
+        )
+      };
+
+      let malformed = {
+        let mut tx = spend(&[chain.coinbase(2)], &[50 * COIN_VALUE]);
+        tx.input[0].witness = Witness::from_vec(vec![
+          script::Builder::new()
+            .push_opcode(opcodes::OP_FALSE)
+            .push_opcode(opcodes::all::OP_IF)
+            .push_slice(b"ord")
+            .push_slice(&[5])
+            .push_slice(br#"{"collection":"#)
+            .push_opcode(opcodes::all::OP_ENDIF)
+            .into_script()
+            .into_bytes(),
+          Vec::new(),
+        ]);
+        tx
+      };
+      let malformed_txid = malformed.txid();
+
+      let a = inscribe(&chain, 1, r#"{"collection":"pepes"}"#);
+      let a_txid = a.txid();
+      chain.mine(vec![a, malformed]);
+
+      let b = inscribe(&chain, 3, r#"{"collection":"other"}"#);
+      let c = inscribe(&chain, 4, r#"{"collection":"Pepes"}"#);
+      let d = inscribe(&chain, 5, r#"{"collection":"pepes"}"#);
+      let (b_txid, c_txid, d_txid) = (b.txid(), c.txid(), d.txid());
+      chain.mine(vec![b, c, d]);
+
+      assert!(chain.satpoint(malformed_txid).is_some());
+
+      let pepes = "pepes".parse().unwrap();
+
+      assert_eq!(
+        chain.index.get_collection_inscriptions(&pepes).unwrap(),
+        [a_txid, c_txid, d_txid],
+      );
+      assert_eq!(
+        chain
+          .index
+          .get_collection_inscriptions(&"other".parse().unwrap())
+          .unwrap(),
+        [b_txid],
+      );
+
+      let page = |page_index| {
+        chain
+          .index
+          .get_collection_inscriptions_paginated(&pepes, 2, page_index)
+          .unwrap()
+      };
+
+      assert_eq!(page(0), (vec![a_txid, c_txid], true));
+      assert_eq!(page(1), (vec![d_txid], false));
+      assert_eq!(page(2), (Vec::new(), false));
+    }
+  }
+
+  #[test]
+  fn block_heights_are_recorded_by_hash() {
+    let mut chain = TestChain::new("");
+
+    let genesis = chain.tip().block_hash();
+    let block = chain.mine(Vec::new());
+    let unindexed = chain.block(block.block_hash(), Vec::new());
+
+    assert_eq!(chain.index.block_height(genesis).unwrap(), Some(Height(0)));
+    assert_eq!(
+      chain.index.block_height(block.block_hash()).unwrap(),
+      Some(Height(1))
+    );
+    assert_eq!(
+      chain.index.block_height(unindexed.block_hash()).unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn committed_blocks_are_published() {
+    let mut chain = TestChain::new("");
+    let mut events = chain.index.subscribe_block_events();
+
+    let block = chain.mine(Vec::new());
+
+    let fork = chain.block(block.header.prev_blockhash, Vec::new());
+    chain.index(fork).unwrap_err();
+
+    assert_eq!(
+      events.try_recv().unwrap(),
+      BlockEvent {
+        height: 1,
+        hash: block.block_hash(),
+      }
+    );
+    assert!(events.try_recv().is_err());
+  }
+
+  #[test]
+  fn committed_inscriptions_are_published() {
+    let mut chain = TestChain::new("");
+    let mut events = chain.index.subscribe_inscription_events();
+
+    chain.mine(Vec::new());
+
+    let first = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let first_id = first.txid();
+    chain.mine(vec![first]);
+
+    let second = reveal(
+      inscription("text/plain", "bar"),
+      &[chain.coinbase(2)],
+      &[50 * COIN_VALUE],
+    );
+    let prev_blockhash = chain.tip().header.prev_blockhash;
+    let fork = chain.block(prev_blockhash, vec![second]);
+    chain.index(fork).unwrap_err();
+
+    assert_eq!(
+      events.try_recv().unwrap(),
+      InscriptionEvent {
+        inscription_id: first_id,
+        number: 0,
+        satpoint: satpoint(OutPoint::new(first_id, 0), 0),
+        height: 2,
+      }
+    );
+
+    // The fork was never committed, so its inscription is not published.
+    assert!(events.try_recv().is_err());
+  }
+
+  #[test]
+  fn inscription_locations_are_recorded_at_each_height() {
+    for args in [
+      "--index-inscription-history",
+      "--index-inscription-history --index-sat-ranges",
+    ] {
+      let mut chain = TestChain::new(args);
+      chain.mine(Vec::new());
+
+      let inscription = reveal(
+        inscription("text/plain", "foo"),
+        &[chain.coinbase(1)],
+        &[50 * COIN_VALUE],
+      );
+      let inscription_id = inscription.txid();
+      chain.mine(vec![inscription]);
+
+      let first = spend(&[OutPoint::new(inscription_id, 0)], &[50 * COIN_VALUE]);
+      let first_txid = first.txid();
+      chain.mine(vec![first]);
+
+      let second = spend(&[OutPoint::new(first_txid, 0)], &[50 * COIN_VALUE]);
+      let third = spend(&[OutPoint::new(second.txid(), 0)], &[50 * COIN_VALUE]);
+      let third_txid = third.txid();
+      chain.mine(vec![second, third]);
+
+      let at = |height| {
+        chain
+          .index
+          .get_inscription_satpoint_at_height(inscription_id, Height(height))
+      };
+
+      assert_eq!(at(1).unwrap(), None);
+      assert_eq!(
+        at(2).unwrap(),
+        Some(satpoint(OutPoint::new(inscription_id, 0), 0))
+      );
+      assert_eq!(
+        at(3).unwrap(),
+        Some(satpoint(OutPoint::new(first_txid, 0), 0))
+      );
+      assert_eq!(
+        at(4).unwrap(),
+        Some(satpoint(OutPoint::new(third_txid, 0), 0))
+      );
+      assert_eq!(at(4).unwrap(), chain.satpoint(inscription_id));
+      assert_eq!(
+        at(5).unwrap_err().to_string(),
+        "height 5 is above index height 4"
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_history_requires_index_option() {
+    let mut chain = TestChain::new("--index-sat-ranges");
+    chain.mine(Vec::new());
+
+    assert_eq!(
+      chain
+        .index
+        .get_inscription_satpoint_at_height(outpoint(1).txid, Height(0))
+        .unwrap_err()
+        .to_string(),
+      "inscription history requires index created with `--index-inscription-history` flag"
+    );
+  }
+
+  fn op_return() -> Script {
+    script::Builder::new()
+      .push_opcode(opcodes::all::OP_RETURN)
+      .into_script()
+  }
+
+  #[test]
+  fn inscription_sent_to_unspendable_output_is_burned() {
+    let mut chain = TestChain::new("--index-sat-ranges");
+    chain.mine(Vec::new());
+
+    let inscription = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let inscription_id = inscription.txid();
+    chain.mine(vec![inscription]);
+
+    assert!(!chain.index.is_inscription_burned(inscription_id).unwrap());
+
+    let mut burn = spend(&[OutPoint::new(inscription_id, 0)], &[50 * COIN_VALUE]);
+    burn.output[0].script_pubkey = op_return();
+    let burn_txid = burn.txid();
+    chain.mine(vec![burn]);
+
+    assert!(chain.index.is_inscription_burned(inscription_id).unwrap());
+    assert_eq!(
+      chain.satpoint(inscription_id),
+      Some(satpoint(OutPoint::new(burn_txid, 0), 0))
+    );
+    assert_eq!(
+      chain.index.list(OutPoint::new(burn_txid, 0)).unwrap(),
+      Some(List::Unspent(vec![(50 * COIN_VALUE, 100 * COIN_VALUE)]))
+    );
+  }
+
+  #[test]
+  fn inscription_revealed_onto_unspendable_output_is_burned() {
+    let mut chain = TestChain::new("");
+    chain.mine(Vec::new());
+
+    let mut inscription = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    inscription.output[0].script_pubkey = op_return();
+    let inscription_id = inscription.txid();
+    chain.mine(vec![inscription]);
+
+    assert!(chain.index.is_inscription_burned(inscription_id).unwrap());
+  }
+
+  #[test]
+  fn inscriptions_are_not_burned_by_unspendable_outputs_after_the_first() {
+    let mut chain = TestChain::new("");
+    chain.mine(Vec::new());
+
+    let inscription = reveal(
+      inscription("text/plain", "foo"),
+      &[chain.coinbase(1)],
+      &[50 * COIN_VALUE],
+    );
+    let inscription_id = inscription.txid();
+    chain.mine(vec![inscription]);
+
+    let mut send = spend(
+      &[OutPoint::new(inscription_id, 0)],
+      &[10 * COIN_VALUE, 40 * COIN_VALUE],
+    );
+    send.output[1].script_pubkey = op_return();
+    chain.mine(vec![send]);
+
+    assert!(!chain.index.is_inscription_burned(inscription_id).unwrap());
+  }
+}
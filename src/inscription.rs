@@ -2,6 +2,7 @@ use {
   super::*,
   bitcoin::{
     blockdata::{
+      constants::MAX_SCRIPT_ELEMENT_SIZE,
       opcodes,
       script::{self, Instruction, Instructions},
     },
@@ -15,24 +16,51 @@ const PROTOCOL_ID: &[u8] = b"ord";
 
 const CONTENT_TAG: &[u8] = &[];
 const CONTENT_TYPE_TAG: &[u8] = &[1];
+const METADATA_TAG: &[u8] = &[5];
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct Inscription {
   content: Option<Vec<u8>>,
   content_type: Option<Vec<u8>>,
+  metadata: Option<Vec<u8>>,
 }
 
 impl Inscription {
+  pub(crate) const MAX_INLINE_SIZE: usize = 4 * 1024;
+
   #[cfg(test)]
   pub(crate) fn new(content_type: Option<Vec<u8>>, content: Option<Vec<u8>>) -> Self {
     Self {
       content_type,
       content,
+      metadata: None,
     }
   }
 
+  /// Attach `metadata`, which must be a JSON object small enough to fit in a
+  /// single script push.
+  pub(crate) fn with_metadata(mut self, metadata: &[u8]) -> Result<Self, Error> {
+    if metadata.len() > MAX_SCRIPT_ELEMENT_SIZE {
+      bail!(
+        "metadata of {} bytes exceeds {MAX_SCRIPT_ELEMENT_SIZE} byte limit",
+        metadata.len()
+      );
+    }
+
+    if !serde_json::from_slice::<serde_json::Value>(metadata)
+      .context("metadata must be JSON")?
+      .is_object()
+    {
+      bail!("metadata must be a JSON object");
+    }
+
+    self.metadata = Some(metadata.into());
+
+    Ok(self)
+  }
+
   pub(crate) fn from_transaction(tx: &Transaction) -> Option<Inscription> {
-    InscriptionParser::parse(&tx.input.get(0)?.witness).ok()
+    InscriptionParser::parse(&tx.input.first()?.witness).ok()
   }
 
   pub(crate) fn from_file(chain: Chain, path: impl AsRef<Path>) -> Result<Self, Error> {
@@ -65,6 +93,7 @@ impl Inscription {
     Ok(Self {
       content: Some(content),
       content_type: Some(content_type.into()),
+      metadata: None,
     })
   }
 
@@ -80,6 +109,10 @@ impl Inscription {
         .push_slice(content_type);
     }
 
+    if let Some(metadata) = &self.metadata {
+      builder = builder.push_slice(METADATA_TAG).push_slice(metadata);
+    }
+
     if let Some(content) = &self.content {
       builder = builder.push_slice(CONTENT_TAG);
       for chunk in content.chunks(520) {
@@ -90,9 +123,16 @@ impl Inscription {
     builder.push_opcode(opcodes::all::OP_ENDIF).into_script()
   }
 
-  pub(crate) fn content(&self) -> Option<Content> {
+  pub(crate) fn content(&self) -> Option<Content<'_>> {
     let content = self.content.as_ref()?;
 
+    if let Some(sniffed) = self.spoofed_content_type() {
+      return Some(Content::Spoofed {
+        declared: self.content_type()?,
+        sniffed,
+      });
+    }
+
     match self.content_type()? {
       "text/plain;charset=utf-8" => Some(Content::Text(str::from_utf8(content).ok()?)),
       "image/png" => Some(Content::Png(content)),
@@ -100,11 +140,31 @@ impl Inscription {
     }
   }
 
+  /// How the content should be shown, according to its declared type.
+  /// Content which was spoofed is still classified by its declared type, so
+  /// check `spoofed_content_type` first.
+  pub(crate) fn media(&self) -> Media {
+    match (&self.content, self.content_type()) {
+      (Some(_), Some(content_type)) => Media::from_content_type(content_type),
+      _ => Media::Unknown,
+    }
+  }
+
   pub(crate) fn content_bytes(&self) -> Option<&[u8]> {
     Some(self.content.as_ref()?)
   }
 
-  pub(crate) fn content_html(&self) -> Trusted<ContentHtml> {
+  pub(crate) fn into_content(self) -> Option<Vec<u8>> {
+    self.content
+  }
+
+  /// Whether the content is small enough to inline into pages. Larger
+  /// content is only loaded from `/content`, in a frame.
+  pub(crate) fn is_inlinable(&self) -> bool {
+    self.content_size().unwrap_or_default() <= Self::MAX_INLINE_SIZE
+  }
+
+  pub(crate) fn content_html(&self) -> Trusted<ContentHtml<'_>> {
     Trusted(ContentHtml(self.content()))
   }
 
@@ -115,6 +175,41 @@ impl Inscription {
   pub(crate) fn content_type(&self) -> Option<&str> {
     str::from_utf8(self.content_type.as_ref()?).ok()
   }
+
+  /// The metadata, if present and valid JSON.
+  pub(crate) fn metadata(&self) -> Option<serde_json::Value> {
+    serde_json::from_slice(self.metadata.as_ref()?).ok()
+  }
+
+  /// The collection named by the metadata, if any. Malformed metadata
+  /// belongs to no collection.
+  pub(crate) fn collection(&self) -> Option<CollectionSlug> {
+    CollectionSlug::from_metadata(&self.metadata()?)
+  }
+
+  /// The type of the content according to its leading bytes, if that differs
+  /// from the declared content type.
+  pub(crate) fn sniffed_content_type(&self) -> Option<&'static str> {
+    let sniffed = sniff::sniff(self.content_bytes()?)?;
+
+    if self.content_type().map(sniff::essence).as_deref() == Some(sniffed) {
+      None
+    } else {
+      Some(sniffed)
+    }
+  }
+
+  /// The sniffed content type, if the content claims to be passive media but
+  /// looks like something a browser might execute.
+  pub(crate) fn spoofed_content_type(&self) -> Option<&'static str> {
+    let sniffed = self.sniffed_content_type()?;
+
+    if sniff::is_passive(self.content_type()?) && sniff::is_active(sniffed) {
+      Some(sniffed)
+    } else {
+      None
+    }
+  }
 }
 
 #[derive(Debug, PartialEq)]
@@ -218,6 +313,7 @@ impl<'a> InscriptionParser<'a> {
       return Ok(Some(Inscription {
         content: fields.remove(CONTENT_TAG),
         content_type: fields.remove(CONTENT_TYPE_TAG),
+        metadata: fields.remove(METADATA_TAG),
       }));
     }
 
@@ -358,6 +454,7 @@ mod tests {
       Ok(Inscription {
         content_type: Some(b"text/plain;charset=utf-8".to_vec()),
         content: None,
+        metadata: None,
       }),
     );
   }
@@ -369,6 +466,7 @@ mod tests {
       Ok(Inscription {
         content_type: None,
         content: Some(b"foo".to_vec()),
+        metadata: None,
       }),
     );
   }
@@ -508,6 +606,61 @@ mod tests {
     );
   }
 
+  #[test]
+  fn sniffed_content_type() {
+    assert_eq!(
+      inscription("image/png", b"\x89PNG\r\n\x1a\n").sniffed_content_type(),
+      None
+    );
+    assert_eq!(
+      inscription("image/png", b"GIF89a").sniffed_content_type(),
+      Some("image/gif")
+    );
+    assert_eq!(
+      inscription("text/html;charset=utf-8", "<html>").sniffed_content_type(),
+      None
+    );
+    assert_eq!(
+      inscription("text/plain;charset=utf-8", "hello").sniffed_content_type(),
+      None
+    );
+    assert_eq!(
+      Inscription::new(None, Some("<html>".into())).sniffed_content_type(),
+      Some("text/html")
+    );
+  }
+
+  #[test]
+  fn spoofed_content_type() {
+    assert_eq!(
+      inscription("image/png", "<html><script>alert(1)</script></html>").spoofed_content_type(),
+      Some("text/html")
+    );
+    assert_eq!(
+      inscription("image/png", "<html><script>alert(1)</script></html>").content(),
+      Some(Content::Spoofed {
+        declared: "image/png",
+        sniffed: "text/html"
+      })
+    );
+    assert_eq!(
+      inscription("video/mp4", "<svg onload=alert(1)>").spoofed_content_type(),
+      Some("image/svg+xml")
+    );
+    assert_eq!(
+      inscription("image/png", b"GIF89a").spoofed_content_type(),
+      None
+    );
+    assert_eq!(
+      inscription("text/plain;charset=utf-8", "<html>").spoofed_content_type(),
+      None
+    );
+    assert_eq!(
+      Inscription::new(None, Some("<html>".into())).spoofed_content_type(),
+      None
+    );
+  }
+
   #[test]
   fn no_endif() {
     let script = script::Builder::new()
@@ -553,6 +706,7 @@ mod tests {
       Ok(Inscription {
         content_type: None,
         content: None,
+        metadata: None,
       }),
     );
   }
@@ -672,7 +826,7 @@ mod tests {
   fn chunked_data_is_parsable() {
     let mut witness = Witness::new();
 
-    witness.push(&inscription("foo", [1; 1040]).append_reveal_script(script::Builder::new()));
+    witness.push(inscription("foo", [1; 1040]).append_reveal_script(script::Builder::new()));
 
     witness.push([]);
 
@@ -687,9 +841,10 @@ mod tests {
     let mut witness = Witness::new();
 
     witness.push(
-      &Inscription {
+      Inscription {
         content_type: None,
         content: None,
+        metadata: None,
       }
       .append_reveal_script(script::Builder::new()),
     );
@@ -701,7 +856,79 @@ mod tests {
       Inscription {
         content_type: None,
         content: None,
+        metadata: None,
       }
     );
   }
+
+  #[test]
+  fn metadata() {
+    let metadata = br#"{"collection":"Pepes","name":"Pepe #1"}"#;
+
+    assert_eq!(
+      InscriptionParser::parse(&container(&[
+        b"ord",
+        &[1],
+        b"text/plain;charset=utf-8",
+        &[5],
+        metadata,
+        &[],
+        b"ord",
+      ])),
+      Ok(
+        inscription("text/plain;charset=utf-8", "ord")
+          .with_metadata(metadata)
+          .unwrap()
+      ),
+    );
+  }
+
+  #[test]
+  fn round_trip_with_metadata() {
+    let inscription = inscription("text/plain;charset=utf-8", "ord")
+      .with_metadata(br#"{"collection":"Pepes"}"#)
+      .unwrap();
+
+    let mut witness = Witness::new();
+    witness.push(inscription.append_reveal_script(script::Builder::new()));
+    witness.push([]);
+
+    let parsed = InscriptionParser::parse(&witness).unwrap();
+
+    assert_eq!(parsed, inscription);
+    assert_eq!(
+      parsed.metadata(),
+      Some(serde_json::json!({"collection": "Pepes"}))
+    );
+    assert_eq!(parsed.collection(), Some("pepes".parse().unwrap()));
+  }
+
+  #[test]
+  fn malformed_metadata_has_no_collection() {
+    for metadata in [&b"{\"collection\":"[..], b"\xff", b"[\"pepes\"]"] {
+      let inscription = InscriptionParser::parse(&container(&[b"ord", &[5], metadata])).unwrap();
+      assert_eq!(inscription.collection(), None);
+    }
+  }
+
+  #[test]
+  fn with_metadata_requires_json_object() {
+    let inscription = || inscription("text/plain;charset=utf-8", "ord");
+
+    assert_eq!(
+      inscription().with_metadata(b"[1]").unwrap_err().to_string(),
+      "metadata must be a JSON object"
+    );
+    assert_eq!(
+      inscription().with_metadata(b"{").unwrap_err().to_string(),
+      "metadata must be JSON"
+    );
+    assert_eq!(
+      inscription()
+        .with_metadata(format!(r#"{{"a":"{}"}}"#, "a".repeat(515)).as_bytes())
+        .unwrap_err()
+        .to_string(),
+      "metadata of 523 bytes exceeds 520 byte limit"
+    );
+  }
 }
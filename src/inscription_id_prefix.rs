@@ -0,0 +1,96 @@
+use super::*;
+
+/// The leading hex digits of an inscription ID, long enough that truncated
+/// IDs pasted from elsewhere rarely match more than one inscription.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InscriptionIdPrefix(String);
+
+impl InscriptionIdPrefix {
+  pub(crate) const MIN_LEN: usize = 8;
+
+  /// The lowest and highest inscription IDs that start with this prefix.
+  pub(crate) fn bounds(&self) -> (InscriptionId, InscriptionId) {
+    let pad = |digit: &str| {
+      format!("{}{}", self.0, digit.repeat(64 - self.0.len()))
+        .parse()
+        .unwrap()
+    };
+
+    (pad("0"), pad("f"))
+  }
+}
+
+impl FromStr for InscriptionIdPrefix {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if !(Self::MIN_LEN..=64).contains(&s.len()) || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+      bail!(
+        "invalid inscription ID prefix `{s}`: expected {} to 64 hex characters",
+        Self::MIN_LEN
+      );
+    }
+
+    Ok(Self(s.to_ascii_lowercase()))
+  }
+}
+
+impl Display for InscriptionIdPrefix {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "0123ABcd"
+        .parse::<InscriptionIdPrefix>()
+        .unwrap()
+        .to_string(),
+      "0123abcd"
+    );
+    assert_eq!(
+      "0"
+        .repeat(64)
+        .parse::<InscriptionIdPrefix>()
+        .unwrap()
+        .to_string(),
+      "0".repeat(64)
+    );
+
+    for invalid in ["0123abc", "0123abcg", "0123abcd:0", "", &"0".repeat(65)] {
+      assert_eq!(
+        invalid
+          .parse::<InscriptionIdPrefix>()
+          .unwrap_err()
+          .to_string(),
+        format!("invalid inscription ID prefix `{invalid}`: expected 8 to 64 hex characters"),
+      );
+    }
+  }
+
+  #[test]
+  fn bounds() {
+    assert_eq!(
+      "0123abcd".parse::<InscriptionIdPrefix>().unwrap().bounds(),
+      (
+        format!("0123abcd{}", "0".repeat(56)).parse().unwrap(),
+        format!("0123abcd{}", "f".repeat(56)).parse().unwrap(),
+      )
+    );
+
+    let id = outpoint(1).txid;
+    assert_eq!(
+      id.to_string()
+        .parse::<InscriptionIdPrefix>()
+        .unwrap()
+        .bounds(),
+      (id, id)
+    );
+  }
+}
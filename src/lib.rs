@@ -0,0 +1,167 @@
+#![allow(
+  clippy::too_many_arguments,
+  clippy::type_complexity,
+  clippy::result_large_err
+)]
+#![deny(
+  clippy::cast_lossless,
+  clippy::cast_possible_truncation,
+  clippy::cast_possible_wrap,
+  clippy::cast_sign_loss
+)]
+
+use {
+  self::{
+    arguments::Arguments,
+    blocktime::Blocktime,
+    collection_slug::CollectionSlug,
+    config::Config,
+    constants::{COIN_VALUE, CYCLE_EPOCHS, DIFFCHANGE_INTERVAL, SUBSIDY_HALVING_INTERVAL, SUPPLY},
+    content::Content,
+    cycle::Cycle,
+    degree::Degree,
+    epoch::Epoch,
+    height::Height,
+    index::{
+      EpochStatistics, Index, IndexOption, InscriptionEntry, InscriptionEvent, List,
+      RarityStatistics, RewardSegment, SatRanges,
+    },
+    inscription::Inscription,
+    inscription_id_prefix::InscriptionIdPrefix,
+    media::Media,
+    options::Options,
+    rarity::Rarity,
+    rpc_pool::{PooledClient, RpcPool},
+    sat::Sat,
+    sat_point::SatPoint,
+    sat_trait::SatTrait,
+    schedule::Schedule,
+    settings::Settings,
+    state_file::{StateFile, Versioned},
+    subcommand::{server::templates::ContentHtml, Subcommand},
+    tally::Tally,
+    version::Version,
+    webhooks::{WebhookOptions, Webhooks},
+  },
+  anyhow::{anyhow, bail, Context, Error},
+  bitcoin::{
+    consensus::{self, Decodable, Encodable},
+    hash_types::BlockHash,
+    hashes::Hash,
+    Address, Amount, Block, OutPoint, Script, Sequence, Transaction, TxIn, TxOut, Txid,
+  },
+  bitcoincore_rpc::RpcApi,
+  chain::Chain,
+  chrono::{DateTime, NaiveDateTime, TimeZone, Utc},
+  clap::{ArgGroup, Parser},
+  derive_more::{Display, FromStr},
+  html_escaper::{Escape, Trusted},
+  regex::Regex,
+  serde::{Deserialize, Serialize},
+  std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    env,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    net::ToSocketAddrs,
+    ops::{Add, AddAssign, Range, Sub},
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::{
+      atomic::{self, AtomicU64},
+      Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+  },
+  tokio::{runtime::Runtime, task},
+  tower_http::cors::{AllowOrigin, CorsLayer},
+};
+
+#[cfg(test)]
+#[macro_use]
+mod test;
+
+#[cfg(test)]
+use self::test::*;
+
+mod arguments;
+mod blocktime;
+mod chain;
+mod collection_slug;
+mod config;
+pub mod constants;
+mod content;
+mod cycle;
+mod decimal;
+mod degree;
+mod descriptor;
+mod epoch;
+mod height;
+mod index;
+mod inscription;
+mod inscription_id_prefix;
+mod media;
+mod options;
+pub mod policy;
+mod rarity;
+mod rpc_pool;
+#[cfg(feature = "library")]
+pub mod sat;
+#[cfg(not(feature = "library"))]
+mod sat;
+mod sat_point;
+mod sat_trait;
+mod schedule;
+mod settings;
+mod sniff;
+mod state_file;
+mod subcommand;
+mod tally;
+mod version;
+mod webhooks;
+
+type Result<T = (), E = Error> = std::result::Result<T, E>;
+
+pub(crate) type InscriptionId = Txid;
+
+static INTERRUPTS: AtomicU64 = AtomicU64::new(0);
+static LISTENERS: Mutex<Vec<axum_server::Handle>> = Mutex::new(Vec::new());
+
+pub fn main() {
+  env_logger::init();
+
+  // Handles SIGTERM as well as SIGINT, so that servers stopped by a service
+  // manager shut down gracefully.
+  ctrlc::set_handler(move || {
+    LISTENERS
+      .lock()
+      .unwrap()
+      .iter()
+      .for_each(|handle| handle.graceful_shutdown(Some(Duration::from_secs(5))));
+
+    let interrupts = INTERRUPTS.fetch_add(1, atomic::Ordering::Relaxed);
+
+    if interrupts > 5 {
+      process::exit(1);
+    }
+  })
+  .expect("Error setting ctrl-c handler");
+
+  if let Err(err) = Arguments::parse().run() {
+    eprintln!("error: {}", err);
+    err
+      .chain()
+      .skip(1)
+      .for_each(|cause| eprintln!("because: {}", cause));
+    if env::var_os("RUST_BACKTRACE")
+      .map(|val| val == "1")
+      .unwrap_or_default()
+    {
+      eprintln!("{}", err.backtrace());
+    }
+    process::exit(1);
+  }
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+/// How content is shown in a browser, according to its declared type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Media {
+  Audio,
+  Iframe,
+  Image,
+  Text,
+  Unknown,
+  Video,
+}
+
+impl Media {
+  /// Markup and SVG may contain scripts, so they are only ever shown in a
+  /// sandboxed `<iframe>`, never inline.
+  pub(crate) fn from_content_type(content_type: &str) -> Self {
+    let essence = sniff::essence(content_type);
+
+    if sniff::is_active(&essence) {
+      Self::Iframe
+    } else if essence.starts_with("audio/") {
+      Self::Audio
+    } else if essence.starts_with("image/") {
+      Self::Image
+    } else if essence.starts_with("text/") || essence == "application/json" {
+      Self::Text
+    } else if essence.starts_with("video/") {
+      Self::Video
+    } else {
+      Self::Unknown
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_content_type() {
+    #[track_caller]
+    fn case(content_type: &str, media: Media) {
+      assert_eq!(Media::from_content_type(content_type), media);
+    }
+
+    case("audio/mpeg", Media::Audio);
+    case("audio/flac", Media::Audio);
+    case("image/png", Media::Image);
+    case("image/jpeg", Media::Image);
+    case("IMAGE/GIF", Media::Image);
+    case("image/svg+xml", Media::Iframe);
+    case("text/html;charset=utf-8", Media::Iframe);
+    case("text/xml", Media::Iframe);
+    case("text/plain;charset=utf-8", Media::Text);
+    case("text/plain", Media::Text);
+    case("application/json", Media::Text);
+    case("video/webm", Media::Video);
+    case("video/mp4", Media::Video);
+    case("application/pdf", Media::Unknown);
+    case("", Media::Unknown);
+  }
+}
@@ -0,0 +1,327 @@
+use {
+  super::*,
+  bitcoin::{util::address::WitnessVersion, PubkeyHash, ScriptHash, WPubkeyHash, WScriptHash},
+};
+
+/// Fee rate, in sats per vbyte, below which Bitcoin Core won't relay
+/// transactions. The wallet pays this rate.
+pub const MIN_RELAY_FEE_RATE: u64 = 1;
+
+/// Fee rate, in sats per vbyte, that Bitcoin Core uses to determine whether
+/// an output is dust.
+pub const DUST_RELAY_FEE_RATE: u64 = 3;
+
+/// Postage used when the transfer fee at the assumed fee rate doesn't call
+/// for more.
+pub const TARGET_POSTAGE: Amount = Amount::from_sat(10_000);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptType {
+  P2pkh,
+  P2sh,
+  P2wpkh,
+  P2wsh,
+  P2tr,
+}
+
+impl ScriptType {
+  pub const ALL: [Self; 5] = [
+    Self::P2pkh,
+    Self::P2sh,
+    Self::P2wpkh,
+    Self::P2wsh,
+    Self::P2tr,
+  ];
+
+  pub fn from_script(script: &Script) -> Option<Self> {
+    Self::ALL
+      .into_iter()
+      .find(|script_type| script_type.matches(script))
+  }
+
+  fn matches(self, script: &Script) -> bool {
+    match self {
+      Self::P2pkh => script.is_p2pkh(),
+      Self::P2sh => script.is_p2sh(),
+      Self::P2wpkh => script.is_v0_p2wpkh(),
+      Self::P2wsh => script.is_v0_p2wsh(),
+      Self::P2tr => script.is_v1_p2tr(),
+    }
+  }
+
+  /// A script of this type, for size and dust calculations.
+  fn example(self) -> Script {
+    match self {
+      Self::P2pkh => Script::new_p2pkh(&PubkeyHash::from_inner([0; 20])),
+      Self::P2sh => Script::new_p2sh(&ScriptHash::from_inner([0; 20])),
+      Self::P2wpkh => Script::new_v0_p2wpkh(&WPubkeyHash::from_inner([0; 20])),
+      Self::P2wsh => Script::new_v0_p2wsh(&WScriptHash::from_inner([0; 32])),
+      Self::P2tr => Script::new_witness_program(WitnessVersion::V1, &[0; 32]),
+    }
+  }
+
+  /// Smallest output value of this type that Bitcoin Core will relay.
+  pub fn dust_limit(self) -> Amount {
+    self.example().dust_value()
+  }
+
+  /// Weight of an input spending an output of this type. P2SH is assumed to
+  /// wrap P2WPKH, P2WSH to be a single key and `OP_CHECKSIG`, and P2TR to be
+  /// a key path spend.
+  fn input_weight(self) -> u64 {
+    const OUTPOINT_AND_SEQUENCE: u64 = 36 + 4;
+    const SIGNATURE: u64 = 1 + 72;
+    const PUBLIC_KEY: u64 = 1 + 33;
+
+    let (script_sig, witness) = match self {
+      Self::P2pkh => (SIGNATURE + PUBLIC_KEY, 0),
+      Self::P2sh => (1 + 22, 1 + SIGNATURE + PUBLIC_KEY),
+      Self::P2wpkh => (0, 1 + SIGNATURE + PUBLIC_KEY),
+      Self::P2wsh => (0, 1 + SIGNATURE + 1 + PUBLIC_KEY + 1),
+      Self::P2tr => (0, 1 + 1 + 64),
+    };
+
+    (OUTPOINT_AND_SEQUENCE + 1 + script_sig) * 4 + witness
+  }
+
+  fn output_weight(self) -> u64 {
+    (8 + 1 + u64::try_from(self.example().len()).unwrap()) * 4
+  }
+
+  fn is_witness(self) -> bool {
+    self != Self::P2pkh
+  }
+
+  /// Virtual size of a transaction which transfers an output of this type to
+  /// a single output of the same type.
+  pub fn transfer_vsize(self) -> u64 {
+    const VERSION_LOCK_TIME_AND_COUNTS: u64 = (4 + 4 + 1 + 1) * 4;
+    const SEGWIT_MARKER_AND_FLAG: u64 = 2;
+
+    let weight = VERSION_LOCK_TIME_AND_COUNTS
+      + if self.is_witness() {
+        SEGWIT_MARKER_AND_FLAG
+      } else {
+        0
+      }
+      + self.input_weight()
+      + self.output_weight();
+
+    (weight + 3) / 4
+  }
+
+  /// Postage which lets an output of this type pay for its own transfer at
+  /// `fee_rate` and still be above the dust limit.
+  pub fn postage(self, fee_rate: u64) -> Amount {
+    TARGET_POSTAGE.max(self.dust_limit() + Amount::from_sat(fee_rate * self.transfer_vsize()))
+  }
+}
+
+impl Display for ScriptType {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::P2pkh => "p2pkh",
+        Self::P2sh => "p2sh",
+        Self::P2wpkh => "p2wpkh",
+        Self::P2wsh => "p2wsh",
+        Self::P2tr => "p2tr",
+      }
+    )
+  }
+}
+
+impl FromStr for ScriptType {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::ALL
+      .into_iter()
+      .find(|script_type| script_type.to_string() == s)
+      .ok_or_else(|| anyhow!("invalid script type: {s}"))
+  }
+}
+
+/// Smallest value of an output with `script` that Bitcoin Core will relay.
+pub fn dust_limit(script: &Script) -> Amount {
+  ScriptType::from_script(script)
+    .map(ScriptType::dust_limit)
+    .unwrap_or_else(|| script.dust_value())
+}
+
+/// Postage for an output with `script`, falling back to `TARGET_POSTAGE` for
+/// non-standard scripts.
+pub fn postage(script: &Script, fee_rate: u64) -> Amount {
+  ScriptType::from_script(script)
+    .map(|script_type| script_type.postage(fee_rate))
+    .unwrap_or(TARGET_POSTAGE)
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Policy {
+  pub fee_rate: u64,
+  pub min_relay_fee_rate: u64,
+  pub dust_relay_fee_rate: u64,
+  pub script_types: Vec<ScriptPolicy>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ScriptPolicy {
+  pub script_type: ScriptType,
+  pub dust_limit: u64,
+  pub transfer_vsize: u64,
+  pub postage: u64,
+}
+
+impl Policy {
+  /// Recommendations assuming that inscriptions will be transferred at
+  /// `fee_rate`.
+  pub fn new(fee_rate: u64) -> Result<Self> {
+    if fee_rate < MIN_RELAY_FEE_RATE {
+      bail!("fee rate must be at least {MIN_RELAY_FEE_RATE} sat/vB");
+    }
+
+    if fee_rate.checked_mul(COIN_VALUE).is_none() {
+      bail!("fee rate {fee_rate} sat/vB is too large");
+    }
+
+    Ok(Self {
+      fee_rate,
+      min_relay_fee_rate: MIN_RELAY_FEE_RATE,
+      dust_relay_fee_rate: DUST_RELAY_FEE_RATE,
+      script_types: ScriptType::ALL
+        .into_iter()
+        .map(|script_type| ScriptPolicy {
+          script_type,
+          dust_limit: script_type.dust_limit().to_sat(),
+          transfer_vsize: script_type.transfer_vsize(),
+          postage: script_type.postage(fee_rate).to_sat(),
+        })
+        .collect(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display_and_from_str() {
+    for script_type in ScriptType::ALL {
+      assert_eq!(
+        script_type.to_string().parse::<ScriptType>().unwrap(),
+        script_type
+      );
+    }
+    "p2ms".parse::<ScriptType>().unwrap_err();
+  }
+
+  #[test]
+  fn from_script() {
+    for script_type in ScriptType::ALL {
+      assert_eq!(
+        ScriptType::from_script(&script_type.example()),
+        Some(script_type)
+      );
+    }
+    assert_eq!(ScriptType::from_script(&Script::new_op_return(&[])), None);
+  }
+
+  #[test]
+  fn dust_limits_match_bitcoin_core() {
+    assert_eq!(ScriptType::P2pkh.dust_limit().to_sat(), 546);
+    assert_eq!(ScriptType::P2sh.dust_limit().to_sat(), 540);
+    assert_eq!(ScriptType::P2wpkh.dust_limit().to_sat(), 294);
+    assert_eq!(ScriptType::P2wsh.dust_limit().to_sat(), 330);
+    assert_eq!(ScriptType::P2tr.dust_limit().to_sat(), 330);
+  }
+
+  #[test]
+  fn transfer_vsize() {
+    assert_eq!(ScriptType::P2pkh.transfer_vsize(), 192);
+    assert_eq!(ScriptType::P2sh.transfer_vsize(), 134);
+    assert_eq!(ScriptType::P2wpkh.transfer_vsize(), 110);
+    assert_eq!(ScriptType::P2wsh.transfer_vsize(), 122);
+    assert_eq!(ScriptType::P2tr.transfer_vsize(), 111);
+  }
+
+  #[test]
+  fn postage_is_target_at_low_fee_rates() {
+    for script_type in ScriptType::ALL {
+      for fee_rate in [1, 10, 45] {
+        assert_eq!(script_type.postage(fee_rate), TARGET_POSTAGE);
+      }
+    }
+  }
+
+  #[test]
+  fn postage_covers_transfer_at_high_fee_rates() {
+    for script_type in ScriptType::ALL {
+      for fee_rate in [100, 250, 1000] {
+        let postage = script_type.postage(fee_rate);
+        assert!(postage > TARGET_POSTAGE);
+        assert_eq!(
+          postage - Amount::from_sat(fee_rate * script_type.transfer_vsize()),
+          script_type.dust_limit()
+        );
+      }
+    }
+
+    assert_eq!(ScriptType::P2tr.postage(100).to_sat(), 330 + 100 * 111);
+    assert_eq!(ScriptType::P2pkh.postage(100).to_sat(), 546 + 100 * 192);
+  }
+
+  #[test]
+  fn dust_limit_for_script() {
+    for script_type in ScriptType::ALL {
+      assert_eq!(dust_limit(&script_type.example()), script_type.dust_limit());
+    }
+    assert_eq!(
+      dust_limit(&Script::new_op_return(&[])),
+      Script::new_op_return(&[]).dust_value()
+    );
+  }
+
+  #[test]
+  fn postage_for_script() {
+    assert_eq!(
+      postage(&ScriptType::P2tr.example(), 100),
+      ScriptType::P2tr.postage(100)
+    );
+    assert_eq!(postage(&Script::new_op_return(&[]), 100), TARGET_POSTAGE);
+  }
+
+  #[test]
+  fn policy() {
+    let policy = Policy::new(100).unwrap();
+    assert_eq!(policy.fee_rate, 100);
+    assert_eq!(policy.min_relay_fee_rate, MIN_RELAY_FEE_RATE);
+    assert_eq!(policy.dust_relay_fee_rate, DUST_RELAY_FEE_RATE);
+    assert_eq!(policy.script_types.len(), ScriptType::ALL.len());
+    assert_eq!(
+      policy.script_types[4],
+      ScriptPolicy {
+        script_type: ScriptType::P2tr,
+        dust_limit: 330,
+        transfer_vsize: 111,
+        postage: 11430,
+      }
+    );
+  }
+
+  #[test]
+  fn policy_rejects_invalid_fee_rates() {
+    assert_eq!(
+      Policy::new(0).unwrap_err().to_string(),
+      "fee rate must be at least 1 sat/vB"
+    );
+    assert_eq!(
+      Policy::new(u64::MAX).unwrap_err().to_string(),
+      format!("fee rate {} sat/vB is too large", u64::MAX)
+    );
+  }
+}
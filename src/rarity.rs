@@ -1,7 +1,10 @@
 use super::*;
 
-#[derive(Debug, PartialEq, PartialOrd)]
-pub(crate) enum Rarity {
+/// How rare a sat is, by whether it starts a block, difficulty adjustment
+/// period, epoch, or cycle.
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rarity {
   Common,
   Uncommon,
   Rare,
@@ -10,6 +13,53 @@ pub(crate) enum Rarity {
   Mythic,
 }
 
+impl Rarity {
+  pub(crate) const ALL: [Self; 6] = [
+    Self::Common,
+    Self::Uncommon,
+    Self::Rare,
+    Self::Epic,
+    Self::Legendary,
+    Self::Mythic,
+  ];
+
+  /// Number of sats of this rarity mined in the first `blocks` blocks.
+  pub(crate) fn supply(self, blocks: u64) -> u64 {
+    let blocks = blocks.min(Epoch::first_post_subsidy().starting_height().n());
+
+    let multiples = |interval: u64| (blocks + interval - 1) / interval;
+
+    let halving_interval = Schedule::current().halving_interval;
+
+    let cycle = halving_interval * CYCLE_EPOCHS;
+
+    // Heights which are both a halving and a difficulty adjustment. On
+    // mainnet these are exactly the cycle boundaries.
+    let conjunction = {
+      let (mut a, mut b) = (halving_interval, DIFFCHANGE_INTERVAL);
+      while b != 0 {
+        (a, b) = (b, a % b);
+      }
+      halving_interval / a * DIFFCHANGE_INTERVAL
+    };
+
+    let mythic = (blocks.min(cycle) + conjunction - 1) / conjunction;
+
+    match self {
+      Self::Common => Height(blocks).starting_sat().n() - blocks,
+      Self::Uncommon => {
+        blocks + multiples(conjunction)
+          - multiples(DIFFCHANGE_INTERVAL)
+          - multiples(halving_interval)
+      }
+      Self::Rare => multiples(DIFFCHANGE_INTERVAL) - multiples(conjunction),
+      Self::Epic => multiples(halving_interval) - multiples(conjunction),
+      Self::Legendary => multiples(conjunction) - mythic,
+      Self::Mythic => mythic,
+    }
+  }
+}
+
 impl Display for Rarity {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     write!(
@@ -27,6 +77,22 @@ impl Display for Rarity {
   }
 }
 
+impl FromStr for Rarity {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "common" => Ok(Self::Common),
+      "uncommon" => Ok(Self::Uncommon),
+      "rare" => Ok(Self::Rare),
+      "epic" => Ok(Self::Epic),
+      "legendary" => Ok(Self::Legendary),
+      "mythic" => Ok(Self::Mythic),
+      _ => Err(anyhow!("invalid rarity: {s}")),
+    }
+  }
+}
+
 impl From<Sat> for Rarity {
   fn from(sat: Sat) -> Self {
     let Degree {
@@ -95,4 +161,95 @@ mod tests {
     assert_eq!(Sat(2067187500000000).rarity(), Rarity::Legendary);
     assert_eq!(Sat(2067187500000000 + 1).rarity(), Rarity::Common);
   }
+
+  #[test]
+  fn from_str_ok() {
+    #[track_caller]
+    fn case(s: &str, expected: Rarity) {
+      assert_eq!(s.parse::<Rarity>().unwrap(), expected);
+      assert_eq!(expected.to_string(), s);
+    }
+
+    case("common", Rarity::Common);
+    case("uncommon", Rarity::Uncommon);
+    case("rare", Rarity::Rare);
+    case("epic", Rarity::Epic);
+    case("legendary", Rarity::Legendary);
+    case("mythic", Rarity::Mythic);
+  }
+
+  #[test]
+  fn from_str_err() {
+    "foo".parse::<Rarity>().unwrap_err();
+    "Common".parse::<Rarity>().unwrap_err();
+  }
+
+  #[test]
+  fn supply_matches_enumeration() {
+    let checkpoints = [
+      0,
+      1,
+      2,
+      DIFFCHANGE_INTERVAL,
+      DIFFCHANGE_INTERVAL + 1,
+      SUBSIDY_HALVING_INTERVAL,
+      SUBSIDY_HALVING_INTERVAL + 1,
+      SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS,
+      SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS + 1,
+    ];
+
+    let mut counts = [0; 6];
+
+    for blocks in 0..=*checkpoints.last().unwrap() {
+      if checkpoints.contains(&blocks) {
+        for (rarity, count) in Rarity::ALL.into_iter().zip(counts) {
+          if rarity != Rarity::Common {
+            assert_eq!(rarity.supply(blocks), count, "{rarity} in {blocks} blocks");
+          }
+        }
+      }
+
+      let rarity = Height(blocks).starting_sat().rarity();
+      counts[Rarity::ALL.iter().position(|r| *r == rarity).unwrap()] += 1;
+    }
+  }
+
+  #[test]
+  fn supply_sums_to_mined_sats() {
+    for blocks in [
+      0,
+      1,
+      100,
+      SUBSIDY_HALVING_INTERVAL + 5,
+      6929999,
+      6930000,
+      10000000,
+    ] {
+      assert_eq!(
+        Rarity::ALL
+          .into_iter()
+          .map(|rarity| rarity.supply(blocks))
+          .sum::<u64>(),
+        Height(blocks).starting_sat().n(),
+        "{blocks}"
+      );
+    }
+
+    assert_eq!(
+      Rarity::ALL
+        .into_iter()
+        .map(|rarity| rarity.supply(u64::MAX))
+        .sum::<u64>(),
+      Sat::supply()
+    );
+  }
+
+  #[test]
+  fn supply_of_all_blocks() {
+    assert_eq!(Rarity::Mythic.supply(u64::MAX), 1);
+    assert_eq!(Rarity::Legendary.supply(u64::MAX), 5);
+    assert_eq!(Rarity::Epic.supply(u64::MAX), 27);
+    assert_eq!(Rarity::Rare.supply(u64::MAX), 3432);
+    assert_eq!(Rarity::Uncommon.supply(u64::MAX), 6926535);
+  }
 }
@@ -0,0 +1,202 @@
+use {
+  super::*,
+  bitcoincore_rpc::{jsonrpc, Auth, Client},
+  std::ops::Deref,
+};
+
+/// Bitcoin Core RPC clients kept open between calls. Each client holds a
+/// single keep-alive connection, so borrowing one from the pool instead of
+/// building a new one avoids a fresh TCP connection per client. Clients are
+/// only created when none are idle, and the pool never blocks, so up to
+/// `size` idle clients are kept, but any number may be borrowed at once.
+#[derive(Debug)]
+pub(crate) struct RpcPool {
+  cookie_file: PathBuf,
+  idle: Mutex<Vec<(Client, Instant)>>,
+  keep_alive: Duration,
+  size: usize,
+  timeout: Duration,
+  url: String,
+}
+
+impl RpcPool {
+  pub(crate) fn new(options: &Options) -> Result<Arc<Self>> {
+    Ok(Arc::new(Self {
+      cookie_file: options.cookie_file()?,
+      idle: Mutex::new(Vec::new()),
+      keep_alive: Duration::from_secs(options.rpc_keep_alive),
+      size: options.rpc_pool_size,
+      timeout: Duration::from_secs(options.rpc_timeout),
+      url: options.rpc_url(),
+    }))
+  }
+
+  /// Borrow an idle client, or create one if none are idle. Clients idle for
+  /// longer than the keep-alive are closed rather than reused, since the node
+  /// may have closed their connections.
+  pub(crate) fn get(self: &Arc<Self>) -> Result<PooledClient> {
+    let client = {
+      let mut idle = self.idle.lock().unwrap();
+      idle.retain(|(_client, since)| since.elapsed() < self.keep_alive);
+      idle.pop().map(|(client, _since)| client)
+    };
+
+    let client = match client {
+      Some(client) => client,
+      None => self.connect()?,
+    };
+
+    Ok(PooledClient {
+      client: Some(client),
+      pool: self.clone(),
+    })
+  }
+
+  fn connect(&self) -> Result<Client> {
+    log::info!(
+      "Connecting to Bitcoin Core RPC server at {} using credentials from `{}`",
+      self.url,
+      self.cookie_file.display()
+    );
+
+    let (user, pass) = Auth::CookieFile(self.cookie_file.clone()).get_user_pass()?;
+
+    let mut builder = jsonrpc::simple_http::SimpleHttpTransport::builder()
+      .url(&self.url)
+      .with_context(|| format!("failed to connect to Bitcoin Core RPC at {}", self.url))?
+      .timeout(self.timeout);
+
+    if let Some(user) = user {
+      builder = builder.auth(user, pass);
+    }
+
+    Ok(Client::from_jsonrpc(jsonrpc::Client::with_transport(
+      builder.build(),
+    )))
+  }
+
+  fn put(&self, client: Client) {
+    let mut idle = self.idle.lock().unwrap();
+    if idle.len() < self.size {
+      idle.push((client, Instant::now()));
+    }
+  }
+}
+
+/// A client borrowed from an `RpcPool`, returned to it when dropped.
+#[derive(Debug)]
+pub(crate) struct PooledClient {
+  client: Option<Client>,
+  pool: Arc<RpcPool>,
+}
+
+impl Deref for PooledClient {
+  type Target = Client;
+
+  fn deref(&self) -> &Client {
+    self.client.as_ref().unwrap()
+  }
+}
+
+impl Drop for PooledClient {
+  fn drop(&mut self) {
+    if let Some(client) = self.client.take() {
+      self.pool.put(client);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pool(rpc_url: &str, args: &[&str]) -> (Arc<RpcPool>, TempDir) {
+    let tempdir = TempDir::new().unwrap();
+    let cookie_file = tempdir.path().join("cookie");
+    fs::write(&cookie_file, "username:password").unwrap();
+
+    let pool = RpcPool::new(
+      &Options::try_parse_from(
+        [
+          "ord",
+          "--rpc-url",
+          rpc_url,
+          "--cookie-file",
+          cookie_file.to_str().unwrap(),
+        ]
+        .iter()
+        .chain(args),
+      )
+      .unwrap(),
+    )
+    .unwrap();
+
+    (pool, tempdir)
+  }
+
+  #[test]
+  fn clients_are_reused() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    let (pool, _tempdir) = pool(&rpc_server.url(), &[]);
+
+    let connections = rpc_server.connections();
+
+    for _ in 0..10 {
+      pool.get().unwrap().get_block_count().unwrap();
+    }
+
+    assert_eq!(rpc_server.connections(), connections + 1);
+  }
+
+  #[test]
+  fn concurrent_borrows_create_clients_up_to_size_idle() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    let (pool, _tempdir) = pool(&rpc_server.url(), &["--rpc-pool-size", "2"]);
+
+    let connections = rpc_server.connections();
+
+    let clients = (0..3).map(|_| pool.get().unwrap()).collect::<Vec<_>>();
+
+    for client in &clients {
+      client.get_block_count().unwrap();
+    }
+
+    drop(clients);
+
+    assert_eq!(pool.idle.lock().unwrap().len(), 2);
+
+    pool.get().unwrap().get_block_count().unwrap();
+    pool.get().unwrap().get_block_count().unwrap();
+
+    assert_eq!(rpc_server.connections(), connections + 3);
+  }
+
+  #[test]
+  fn idle_clients_expire_after_keep_alive() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    let (pool, _tempdir) = pool(&rpc_server.url(), &["--rpc-keep-alive", "0"]);
+
+    let connections = rpc_server.connections();
+
+    pool.get().unwrap().get_block_count().unwrap();
+    pool.get().unwrap().get_block_count().unwrap();
+
+    assert_eq!(rpc_server.connections(), connections + 2);
+  }
+
+  #[test]
+  fn calls_time_out() {
+    // Connections to a listener that never accepts them are queued, but
+    // never answered.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+
+    let (pool, _tempdir) = pool(
+      &listener.local_addr().unwrap().to_string(),
+      &["--rpc-timeout", "1"],
+    );
+
+    let start = Instant::now();
+    assert!(pool.get().unwrap().get_block_count().is_err());
+    assert!(start.elapsed() < Duration::from_secs(5));
+  }
+}
@@ -1,67 +1,159 @@
+//! Sat numbering: the ordinal number of each satoshi, and the block height,
+//! epoch, cycle, rarity, and names that follow from it.
+
 use super::*;
 
+pub use crate::{
+  cycle::Cycle, decimal::Decimal, degree::Degree, epoch::Epoch, height::Height, rarity::Rarity,
+  sat_trait::SatTrait,
+};
+
+/// A single satoshi, identified by its ordinal number in mining order.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Ord, PartialOrd, Deserialize, Serialize)]
 #[serde(transparent)]
-pub(crate) struct Sat(pub(crate) u64);
+pub struct Sat(pub(crate) u64);
 
 impl Sat {
-  pub(crate) const LAST: Self = Self(Self::SUPPLY - 1);
-  pub(crate) const SUPPLY: u64 = 2099999997690000;
+  /// The sat numbered `n`, or `None` if `n` is past `Sat::last()`.
+  pub fn new(n: u64) -> Option<Self> {
+    Self(0).checked_add(n)
+  }
 
-  pub(crate) fn n(self) -> u64 {
+  /// Total number of sats that will ever be mined.
+  pub fn supply() -> u64 {
+    Schedule::current().supply()
+  }
+
+  /// The last sat that will ever be mined.
+  pub fn last() -> Self {
+    Self(Self::supply() - 1)
+  }
+
+  /// The ordinal number of this sat.
+  pub fn n(self) -> u64 {
     self.0
   }
 
-  pub(crate) fn degree(self) -> Degree {
+  /// Position within the cycle, epoch, period, and block, as
+  /// `cycle°epoch-offset′period-offset″block-offset‴`.
+  pub fn degree(self) -> Degree {
     self.into()
   }
 
-  pub(crate) fn height(self) -> Height {
+  /// The height of the block which mined this sat.
+  pub fn height(self) -> Height {
     self.epoch().starting_height() + self.epoch_position() / self.epoch().subsidy()
   }
 
-  pub(crate) fn cycle(self) -> u64 {
-    Epoch::from(self).0 / CYCLE_EPOCHS
+  pub(crate) fn expected_timestamp(self, index: &Index) -> Result<Blocktime> {
+    self.height().expected_timestamp(index)
   }
 
-  pub(crate) fn percentile(self) -> String {
-    format!("{}%", (self.0 as f64 / Self::LAST.0 as f64) * 100.0)
+  /// The cycle in which this sat was mined.
+  pub fn cycle(self) -> Cycle {
+    self.into()
   }
 
-  pub(crate) fn epoch(self) -> Epoch {
+  /// Position among all sats, as a percentage of `Sat::last()`.
+  pub fn percentile(self) -> String {
+    format!("{}%", (self.0 as f64 / Self::last().0 as f64) * 100.0)
+  }
+
+  /// The halving epoch in which this sat was mined.
+  pub fn epoch(self) -> Epoch {
     self.into()
   }
 
-  pub(crate) fn period(self) -> u64 {
+  /// The difficulty adjustment period in which this sat was mined.
+  pub fn period(self) -> u64 {
     self.height().n() / DIFFCHANGE_INTERVAL
   }
 
-  pub(crate) fn third(self) -> u64 {
+  /// Offset of this sat within the subsidy of the block which mined it.
+  pub fn third(self) -> u64 {
     self.epoch_position() % self.epoch().subsidy()
   }
 
-  pub(crate) fn epoch_position(self) -> u64 {
+  /// Offset of this sat from the first sat of its epoch.
+  pub fn epoch_position(self) -> u64 {
     self.0 - self.epoch().starting_sat().0
   }
 
-  pub(crate) fn decimal(self) -> Decimal {
+  /// Returns `None` rather than a sat past `Sat::last()`.
+  pub fn checked_add(self, n: u64) -> Option<Self> {
+    let sat = Self(self.0.checked_add(n)?);
+
+    if sat > Self::last() {
+      None
+    } else {
+      Some(sat)
+    }
+  }
+
+  /// Returns `None` rather than a sat before `Sat(0)`.
+  pub fn checked_sub(self, n: u64) -> Option<Self> {
+    Some(Self(self.0.checked_sub(n)?))
+  }
+
+  /// Block height and offset within that block, as `height.third`.
+  pub fn decimal(self) -> Decimal {
     self.into()
   }
 
-  pub(crate) fn rarity(self) -> Rarity {
+  /// Rarity, determined by whether this is the first sat of a block,
+  /// difficulty adjustment period, epoch, or cycle.
+  pub fn rarity(self) -> Rarity {
     self.into()
   }
 
   /// `Sat::rarity` is expensive and is called frequently when indexing.
   /// Sat::is_common only checks if self is `Rarity::Common` but is
   /// much faster.
-  pub(crate) fn is_common(self) -> bool {
+  pub fn is_common(self) -> bool {
     let epoch = self.epoch();
     (self.0 - epoch.starting_sat().0) % epoch.subsidy() != 0
   }
 
-  pub(crate) fn name(self) -> String {
-    let mut x = Self::SUPPLY - self.0;
+  /// Whether this sat's number is a multiple of a whole bitcoin.
+  pub fn coin(self) -> bool {
+    self.0 % COIN_VALUE == 0
+  }
+
+  /// First sat of a whole bitcoin minted by its block.
+  pub fn alpha(self) -> bool {
+    let offset = self.third();
+    offset % COIN_VALUE == 0 && offset + COIN_VALUE <= self.epoch().subsidy()
+  }
+
+  /// Last sat of a whole bitcoin minted by its block.
+  pub fn omega(self) -> bool {
+    (self.third() + 1) % COIN_VALUE == 0
+  }
+
+  /// Whether this sat's number reads the same backwards.
+  pub fn decimal_palindrome(self) -> bool {
+    let n = self.0.to_string();
+    n.chars().eq(n.chars().rev())
+  }
+
+  /// Whether this sat's name reads the same backwards.
+  pub fn name_palindrome(self) -> bool {
+    let name = self.name();
+    name.chars().eq(name.chars().rev())
+  }
+
+  /// Every `SatTrait` this sat has.
+  pub fn traits(self) -> Vec<SatTrait> {
+    SatTrait::ALL
+      .into_iter()
+      .filter(|sat_trait| sat_trait.is_set(self))
+      .collect()
+  }
+
+  /// This sat's name, counting down in bijective base-26 from `a` for the
+  /// last sat.
+  pub fn name(self) -> String {
+    let mut x = Self::supply() - self.0;
     let mut name = String::new();
     while x > 0 {
       name.push(
@@ -75,20 +167,89 @@ impl Sat {
     name.chars().rev().collect()
   }
 
-  fn from_name(s: &str) -> Result<Self> {
-    let mut x = 0;
-    for c in s.chars() {
-      match c {
-        'a'..='z' => {
-          x = x * 26 + c as u64 - 'a' as u64 + 1;
-        }
-        _ => bail!("invalid character in sat name: {c}"),
-      }
+  /// Sats whose names start with `prefix`, smallest first. Names count down
+  /// from the supply in bijective base-26, so the names of each length which
+  /// share a prefix form a single contiguous range.
+  pub(crate) fn with_name_prefix(prefix: &str) -> Result<impl Iterator<Item = Self>> {
+    Ok(
+      Self::name_prefix_ranges(prefix)?
+        .into_iter()
+        .flatten()
+        .map(Self),
+    )
+  }
+
+  fn name_prefix_ranges(prefix: &str) -> Result<Vec<Range<u64>>> {
+    if prefix.is_empty() {
+      bail!("sat name prefix may not be empty");
+    }
+
+    let supply = Self::supply();
+    let mut base = supply - Self::from_name(prefix)?.0;
+    let mut shortest_suffix = 0;
+    let mut longest_suffix = 0;
+    let mut ranges = Vec::new();
+
+    while let Some(lowest) = base
+      .checked_add(shortest_suffix)
+      .filter(|lowest| *lowest <= supply)
+    {
+      let highest = base.saturating_add(longest_suffix).min(supply);
+      ranges.push(supply - highest..supply - lowest + 1);
+
+      let (Some(next), Some(shortest), Some(longest)) = (
+        base.checked_mul(26),
+        shortest_suffix.checked_mul(26).map(|n| n + 1),
+        longest_suffix.checked_mul(26).map(|n| n + 26),
+      ) else {
+        break;
+      };
+
+      base = next;
+      shortest_suffix = shortest;
+      longest_suffix = longest;
     }
-    if x > Self::SUPPLY {
-      bail!("sat name out of range");
+
+    ranges.reverse();
+
+    Ok(ranges)
+  }
+
+  fn from_name(s: &str) -> Result<Self> {
+    let mut x = 0u64;
+    for c in Self::normalize_name(s)?.chars() {
+      x = x
+        .checked_mul(26)
+        .and_then(|x| x.checked_add(c as u64 - 'a' as u64 + 1))
+        .filter(|x| *x <= Self::supply())
+        .ok_or_else(|| anyhow!("sat name out of range"))?;
     }
-    Ok(Sat(Self::SUPPLY - x))
+    Ok(Sat(Self::supply() - x))
+  }
+
+  /// Sat names are lowercase ASCII letters, but may be entered with
+  /// surrounding whitespace or in uppercase. Anything else, including
+  /// letters from other scripts that look like ASCII, such as Cyrillic `а`,
+  /// is rejected.
+  pub(crate) fn normalize_name(name: &str) -> Result<String> {
+    name
+      .trim()
+      .chars()
+      .enumerate()
+      .map(|(i, c)| {
+        if c.is_ascii_alphabetic() {
+          Ok(c.to_ascii_lowercase())
+        } else {
+          Err(invalid_character(c, i, " in sat name"))
+        }
+      })
+      .collect()
+  }
+
+  /// Whether `s` should be parsed as a name, rather than as a number,
+  /// degree, percentile, or decimal.
+  pub(crate) fn is_name(s: &str) -> bool {
+    s.chars().any(char::is_alphabetic)
   }
 
   fn from_degree(degree: &str) -> Result<Self> {
@@ -96,12 +257,16 @@ impl Sat {
       .split_once('°')
       .ok_or_else(|| anyhow!("missing degree symbol"))?;
     let cycle_number = cycle_number.parse::<u64>()?;
+    if cycle_number > Sat::last().cycle().0 {
+      bail!("invalid cycle number");
+    }
 
     let (epoch_offset, rest) = rest
       .split_once('′')
       .ok_or_else(|| anyhow!("missing minute symbol"))?;
     let epoch_offset = epoch_offset.parse::<u64>()?;
-    if epoch_offset >= SUBSIDY_HALVING_INTERVAL {
+    let halving_interval = Schedule::current().halving_interval;
+    if epoch_offset >= halving_interval {
       bail!("invalid epoch offset");
     }
 
@@ -115,21 +280,14 @@ impl Sat {
 
     let cycle_start_epoch = cycle_number * CYCLE_EPOCHS;
 
-    const HALVING_INCREMENT: u64 = SUBSIDY_HALVING_INTERVAL % DIFFCHANGE_INTERVAL;
-
-    // For valid degrees the relationship between epoch_offset and period_offset
-    // will increment by 336 every halving.
-    let relationship = period_offset + SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS - epoch_offset;
-
-    if relationship % HALVING_INCREMENT != 0 {
-      bail!("relationship between epoch offset and period offset must be multiple of 336");
-    }
-
-    let epochs_since_cycle_start = relationship % DIFFCHANGE_INTERVAL / HALVING_INCREMENT;
-
-    let epoch = cycle_start_epoch + epochs_since_cycle_start;
-
-    let height = Height(epoch * SUBSIDY_HALVING_INTERVAL + epoch_offset);
+    // The period offset of a block is determined by its epoch offset and by
+    // how many halvings into the cycle it is.
+    let height = (cycle_start_epoch..cycle_start_epoch + CYCLE_EPOCHS)
+      .map(|epoch| Height(epoch * halving_interval + epoch_offset))
+      .find(|height| height.period_offset() == period_offset)
+      .ok_or_else(|| {
+        anyhow!("relationship between epoch offset and period offset is not possible")
+      })?;
 
     let (block_offset, rest) = match rest.split_once('‴') {
       Some((block_offset, rest)) => (block_offset.parse::<u64>()?, rest),
@@ -172,7 +330,7 @@ impl Sat {
       bail!("invalid percentile: {}", percentile);
     }
 
-    let last = Sat::LAST.n() as f64;
+    let last = Sat::last().n() as f64;
 
     let n = (percentile / 100.0 * last).round();
 
@@ -185,6 +343,15 @@ impl Sat {
   }
 }
 
+pub(crate) fn invalid_character(c: char, i: usize, context: &str) -> Error {
+  anyhow!(
+    "invalid character `{}` (U+{:04X}) at position {}{context}",
+    c.escape_debug(),
+    u32::from(c),
+    i + 1,
+  )
+}
+
 impl PartialEq<u64> for Sat {
   fn eq(&self, other: &u64) -> bool {
     self.0 == *other
@@ -197,17 +364,28 @@ impl PartialOrd<u64> for Sat {
   }
 }
 
+impl TryFrom<u64> for Sat {
+  type Error = Error;
+
+  fn try_from(n: u64) -> Result<Self> {
+    Self::new(n).ok_or_else(|| anyhow!("invalid sat"))
+  }
+}
+
+/// Panics if the result would be past `Sat::last()`. Use `Sat::checked_add`
+/// where that can happen.
 impl Add<u64> for Sat {
   type Output = Self;
 
   fn add(self, other: u64) -> Sat {
-    Sat(self.0 + other)
+    self.checked_add(other).expect("sat out of range")
   }
 }
 
+/// Panics if the result would be past `Sat::last()`, like `Add`.
 impl AddAssign<u64> for Sat {
   fn add_assign(&mut self, other: u64) {
-    *self = Sat(self.0 + other);
+    *self = *self + other;
   }
 }
 
@@ -215,21 +393,28 @@ impl FromStr for Sat {
   type Err = Error;
 
   fn from_str(s: &str) -> Result<Self> {
-    if s.chars().any(|c| matches!(c, 'a'..='z')) {
-      Self::from_name(s)
-    } else if s.contains('°') {
+    let s = s.trim();
+
+    if Self::is_name(s) {
+      return Self::from_name(s);
+    }
+
+    if let Some((i, c)) = s
+      .chars()
+      .enumerate()
+      .find(|(_, c)| !c.is_ascii() && !"°′″‴".contains(*c))
+    {
+      return Err(invalid_character(c, i, ""));
+    }
+
+    if s.contains('°') {
       Self::from_degree(s)
     } else if s.contains('%') {
       Self::from_percentile(s)
     } else if s.contains('.') {
       Self::from_decimal(s)
     } else {
-      let sat = Self(s.parse()?);
-      if sat > Self::LAST {
-        Err(anyhow!("invalid sat"))
-      } else {
-        Ok(sat)
-      }
+      Self::try_from(s.parse::<u64>()?)
     }
   }
 }
@@ -397,7 +582,7 @@ mod tests {
       Sat(Epoch(1).starting_sat().n() + Epoch(1).subsidy()).third(),
       0
     );
-    assert_eq!(Sat::LAST.third(), 0);
+    assert_eq!(Sat::last().third(), 0);
   }
 
   #[test]
@@ -414,12 +599,12 @@ mod tests {
       mined += subsidy;
     }
 
-    assert_eq!(Sat::SUPPLY, mined);
+    assert_eq!(Sat::supply(), mined);
   }
 
   #[test]
   fn last() {
-    assert_eq!(Sat::LAST, Sat::SUPPLY - 1);
+    assert_eq!(Sat::last(), Sat::supply() - 1);
   }
 
   #[test]
@@ -440,6 +625,28 @@ mod tests {
     assert_eq!(Sat(1) + 100, 101);
   }
 
+  #[test]
+  #[should_panic(expected = "sat out of range")]
+  fn add_past_last_panics() {
+    let _ = Sat::last() + 1;
+  }
+
+  #[test]
+  #[should_panic(expected = "sat out of range")]
+  fn add_assign_past_last_panics() {
+    let mut sat = Sat::last();
+    sat += 1;
+  }
+
+  #[test]
+  fn new() {
+    assert_eq!(Sat::new(0), Some(Sat(0)));
+    assert_eq!(Sat::new(Sat::last().n()), Some(Sat::last()));
+    assert_eq!(Sat::new(Sat::supply()), None);
+    assert_eq!(Sat::try_from(Sat::supply() - 1).unwrap(), Sat::last());
+    assert!(Sat::try_from(Sat::supply()).is_err());
+  }
+
   #[test]
   fn add_assign() {
     let mut sat = Sat(0);
@@ -545,6 +752,109 @@ mod tests {
     assert!(parse("nvtdijuwxlq").is_err());
   }
 
+  #[test]
+  fn from_str_name_is_normalized() {
+    assert_eq!(parse("NVTDIJUWXLP").unwrap(), 0);
+    assert_eq!(parse("NvTdIjUwXlP").unwrap(), 0);
+    assert_eq!(parse(" nvtdijuwxlp\n").unwrap(), 0);
+    assert_eq!(parse("\tA ").unwrap(), 2099999997689999);
+  }
+
+  #[test]
+  fn from_str_name_rejects_non_ascii() {
+    assert_eq!(
+      parse("nvtdij\u{0430}wxlp").unwrap_err(),
+      "invalid character `\u{0430}` (U+0430) at position 7 in sat name"
+    );
+    assert_eq!(
+      parse("\u{0430}").unwrap_err(),
+      "invalid character `\u{0430}` (U+0430) at position 1 in sat name"
+    );
+    assert_eq!(
+      parse("ab\u{00e9}").unwrap_err(),
+      "invalid character `\u{00e9}` (U+00E9) at position 3 in sat name"
+    );
+    assert_eq!(
+      parse("a b").unwrap_err(),
+      "invalid character ` ` (U+0020) at position 2 in sat name"
+    );
+    assert_eq!(
+      parse("a\u{1}").unwrap_err(),
+      "invalid character `\\u{1}` (U+0001) at position 2 in sat name"
+    );
+  }
+
+  #[test]
+  fn from_str_rejects_non_ascii() {
+    assert_eq!(
+      parse("1\u{1f600}").unwrap_err(),
+      "invalid character `\u{1f600}` (U+1F600) at position 2"
+    );
+    assert_eq!(
+      parse("\u{ff11}").unwrap_err(),
+      "invalid character `\u{ff11}` (U+FF11) at position 1"
+    );
+  }
+
+  #[test]
+  fn from_str_name_out_of_range_does_not_overflow() {
+    assert_eq!(
+      parse(&"z".repeat(100)).unwrap_err(),
+      "sat name out of range"
+    );
+  }
+
+  #[test]
+  fn from_str_arbitrary_unicode() {
+    use bitcoin::secp256k1::rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const INTERESTING: &[char] = &[
+      '0', '1', '5', '9', '.', '%', '°', '′', '″', '‴', '-', '+', 'e', 'a', 'z', 'A', 'Z', ' ',
+      '\t', '\n', '\u{0430}', '\u{00a0}', '\u{200b}', '\u{ff10}',
+    ];
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..100_000 {
+      let len = rng.gen_range(0..24);
+      let s = (0..len)
+        .map(|_| {
+          if rng.gen_bool(0.8) {
+            INTERESTING[rng.gen_range(0..INTERESTING.len())]
+          } else {
+            loop {
+              if let Some(c) = char::from_u32(rng.gen_range(0..=0x10FFFF)) {
+                break c;
+              }
+            }
+          }
+        })
+        .collect::<String>();
+
+      if let Ok(sat) = s.parse::<Sat>() {
+        assert!(sat <= Sat::last(), "{s:?} parsed as {sat}");
+      }
+    }
+  }
+
+  #[test]
+  fn from_str_huge_components() {
+    for s in [
+      "18446744073709551615",
+      "18446744073709551615.0",
+      "0.18446744073709551615",
+      "18446744073709551615°0′0″0‴",
+      "3074457345618258603°0′0″0‴",
+      "0°18446744073709551615′0″0‴",
+      "0°0′18446744073709551615″0‴",
+      "0°0′0″18446744073709551615‴",
+      "1e308%",
+      "18446744073709551615%",
+    ] {
+      assert!(parse(s).is_err(), "{s}");
+    }
+  }
+
   #[test]
   fn cycle() {
     assert_eq!(
@@ -578,8 +888,8 @@ mod tests {
   #[test]
   fn percentile() {
     assert_eq!(Sat(0).percentile(), "0%");
-    assert_eq!(Sat(Sat::LAST.n() / 2).percentile(), "49.99999999999998%");
-    assert_eq!(Sat::LAST.percentile(), "100%");
+    assert_eq!(Sat(Sat::last().n() / 2).percentile(), "49.99999999999998%");
+    assert_eq!(Sat::last().percentile(), "100%");
   }
 
   #[test]
@@ -598,9 +908,9 @@ mod tests {
 
     for n in 0..1024 {
       case(n);
-      case(Sat::LAST.n() / 2 + n);
-      case(Sat::LAST.n() - n);
-      case(Sat::LAST.n() / (n + 1));
+      case(Sat::last().n() / 2 + n);
+      case(Sat::last().n() - n);
+      case(Sat::last().n() / (n + 1));
     }
   }
 
@@ -619,4 +929,145 @@ mod tests {
     case(2067187500000000);
     case(2067187500000000 + 1);
   }
+
+  #[test]
+  fn checked_add() {
+    assert_eq!(Sat(0).checked_add(1), Some(Sat(1)));
+    assert_eq!(Sat(0).checked_add(Sat::last().n()), Some(Sat::last()));
+    assert_eq!(Sat::last().checked_add(1), None);
+    assert_eq!(Sat(u64::MAX).checked_add(1), None);
+  }
+
+  #[test]
+  fn checked_sub() {
+    assert_eq!(Sat(1).checked_sub(1), Some(Sat(0)));
+    assert_eq!(Sat(0).checked_sub(1), None);
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(serde_json::to_string(&Sat(1)).unwrap(), "1");
+    assert_eq!(serde_json::from_str::<Sat>("1").unwrap(), Sat(1));
+  }
+
+  #[test]
+  fn coin() {
+    assert!(Sat(0).coin());
+    assert!(!Sat(1).coin());
+    assert!(Sat(COIN_VALUE).coin());
+    assert!(!Sat(COIN_VALUE - 1).coin());
+    assert!(Sat(1050000000000000).coin());
+  }
+
+  #[test]
+  fn alpha() {
+    assert!(Sat(0).alpha());
+    assert!(!Sat(1).alpha());
+    assert!(Sat(COIN_VALUE).alpha());
+    assert!(!Sat(49 * COIN_VALUE + 1).alpha());
+
+    let start = Height(SUBSIDY_HALVING_INTERVAL * 3 + 1).starting_sat();
+    assert!(start.alpha());
+    assert!(!start.coin());
+    assert!(!(start + 6 * COIN_VALUE).alpha());
+
+    assert!(!Height(SUBSIDY_HALVING_INTERVAL * 6).starting_sat().alpha());
+    assert!(!Sat::last().alpha());
+  }
+
+  #[test]
+  fn omega() {
+    assert!(!Sat(0).omega());
+    assert!(Sat(COIN_VALUE - 1).omega());
+    assert!(Sat(50 * COIN_VALUE - 1).omega());
+    assert!(!Sat(50 * COIN_VALUE).omega());
+    assert!(!Sat::last().omega());
+  }
+
+  #[test]
+  fn decimal_palindrome() {
+    assert!(Sat(0).decimal_palindrome());
+    assert!(Sat(7).decimal_palindrome());
+    assert!(!Sat(10).decimal_palindrome());
+    assert!(Sat(11).decimal_palindrome());
+    assert!(Sat(1234554321).decimal_palindrome());
+    assert!(!Sat(1234554320).decimal_palindrome());
+  }
+
+  #[test]
+  fn name_palindrome() {
+    assert!(Sat::last().name_palindrome());
+    assert!(Sat(2099999997689999 - 26).name_palindrome());
+    assert!(!Sat(2099999997689999 - 27).name_palindrome());
+    assert!(Sat("aba".parse::<Sat>().unwrap().n()).name_palindrome());
+    assert!(!Sat(0).name_palindrome());
+  }
+
+  #[test]
+  fn traits() {
+    assert_eq!(
+      Sat(0).traits(),
+      [SatTrait::Coin, SatTrait::Alpha, SatTrait::DecimalPalindrome]
+    );
+    assert_eq!(Sat(12345).traits(), []);
+    assert_eq!(Sat::last().traits(), [SatTrait::NamePalindrome]);
+  }
+
+  #[test]
+  fn with_name_prefix_matches_brute_force() {
+    let first = Sat::last().n() - 50_000;
+    for prefix in ["a", "b", "z", "ab", "zz", "bcd", "zzz"] {
+      let expected = (first..=Sat::last().n())
+        .map(Sat)
+        .filter(|sat| sat.name().starts_with(prefix))
+        .collect::<Vec<Sat>>();
+
+      let actual = Sat::name_prefix_ranges(prefix)
+        .unwrap()
+        .into_iter()
+        .flat_map(|range| range.start.max(first)..range.end)
+        .map(Sat)
+        .collect::<Vec<Sat>>();
+
+      assert_eq!(actual, expected, "{prefix}");
+    }
+  }
+
+  #[test]
+  fn with_name_prefix_smallest_first() {
+    assert_eq!(
+      Sat::with_name_prefix("nvtdijuwxlp").unwrap().next(),
+      Some(Sat(0))
+    );
+
+    let sats = Sat::with_name_prefix("ord")
+      .unwrap()
+      .take(1000)
+      .collect::<Vec<Sat>>();
+    assert_eq!(sats.len(), 1000);
+    assert!(sats.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(sats.iter().all(|sat| sat.name().starts_with("ord")));
+  }
+
+  #[test]
+  fn with_name_prefix_errors() {
+    assert_eq!(
+      Sat::with_name_prefix("").err().unwrap().to_string(),
+      "sat name prefix may not be empty"
+    );
+    assert_eq!(
+      Sat::with_name_prefix("o\u{0440}d")
+        .err()
+        .unwrap()
+        .to_string(),
+      "invalid character `\u{0440}` (U+0440) at position 2 in sat name"
+    );
+    assert_eq!(
+      Sat::with_name_prefix("zzzzzzzzzzzz")
+        .err()
+        .unwrap()
+        .to_string(),
+      "sat name out of range"
+    );
+  }
 }
@@ -12,6 +12,26 @@ impl Display for SatPoint {
   }
 }
 
+impl Serialize for SatPoint {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for SatPoint {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    String::deserialize(deserializer)?
+      .parse()
+      .map_err(serde::de::Error::custom)
+  }
+}
+
 impl Encodable for SatPoint {
   fn consensus_encode<S: io::Write + ?Sized>(&self, s: &mut S) -> Result<usize, io::Error> {
     let len = self.outpoint.consensus_encode(s)?;
@@ -78,4 +98,15 @@ mod tests {
       .parse::<SatPoint>()
       .unwrap_err();
   }
+
+  #[test]
+  fn serde() {
+    let satpoint = satpoint(1, 2);
+    let json = serde_json::to_string(&satpoint).unwrap();
+    assert_eq!(
+      json,
+      "\"1111111111111111111111111111111111111111111111111111111111111111:1:2\""
+    );
+    assert_eq!(serde_json::from_str::<SatPoint>(&json).unwrap(), satpoint);
+  }
 }
@@ -0,0 +1,314 @@
+use super::*;
+
+/// A property of a sat's number or name which doesn't depend on its
+/// rarity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SatTrait {
+  Coin,
+  Alpha,
+  Omega,
+  DecimalPalindrome,
+  NamePalindrome,
+}
+
+impl SatTrait {
+  pub(crate) const ALL: [Self; 5] = [
+    Self::Coin,
+    Self::Alpha,
+    Self::Omega,
+    Self::DecimalPalindrome,
+    Self::NamePalindrome,
+  ];
+
+  pub(crate) fn is_set(self, sat: Sat) -> bool {
+    match self {
+      Self::Coin => sat.coin(),
+      Self::Alpha => sat.alpha(),
+      Self::Omega => sat.omega(),
+      Self::DecimalPalindrome => sat.decimal_palindrome(),
+      Self::NamePalindrome => sat.name_palindrome(),
+    }
+  }
+
+  /// First sat at or after `sat` with this trait. Computed arithmetically,
+  /// so it's cheap to call on the large sat ranges held by a wallet.
+  pub(crate) fn next(self, sat: Sat) -> Option<Sat> {
+    let next = match self {
+      Self::Coin => Sat(sat.n().checked_add(COIN_VALUE - 1)? / COIN_VALUE * COIN_VALUE),
+      Self::Alpha => Self::next_in_block(sat, 0)?,
+      Self::Omega => Self::next_in_block(sat, COIN_VALUE - 1)?,
+      Self::DecimalPalindrome => Sat(palindrome::at_or_after(sat.n(), 10, false)?),
+      Self::NamePalindrome => {
+        Sat(Sat::supply() - palindrome::at_or_before(Sat::supply() - sat.n(), 26, true)?)
+      }
+    };
+
+    if next > Sat::last() {
+      None
+    } else {
+      Some(next)
+    }
+  }
+
+  /// Next sat at `remainder` within a whole bitcoin minted by its block.
+  fn next_in_block(sat: Sat, remainder: u64) -> Option<Sat> {
+    let mut height = sat.height();
+    let mut offset = sat.third();
+
+    loop {
+      let subsidy = height.subsidy();
+
+      if subsidy < COIN_VALUE {
+        return None;
+      }
+
+      let coin = (offset.saturating_sub(remainder) + COIN_VALUE - 1) / COIN_VALUE * COIN_VALUE;
+
+      if coin + COIN_VALUE <= subsidy {
+        return Some(height.starting_sat() + coin + remainder);
+      }
+
+      height = height + 1;
+      offset = 0;
+    }
+  }
+}
+
+impl Display for SatTrait {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Coin => "coin",
+        Self::Alpha => "alpha",
+        Self::Omega => "omega",
+        Self::DecimalPalindrome => "decimal-palindrome",
+        Self::NamePalindrome => "name-palindrome",
+      }
+    )
+  }
+}
+
+impl FromStr for SatTrait {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::ALL
+      .into_iter()
+      .find(|sat_trait| sat_trait.to_string() == s)
+      .ok_or_else(|| anyhow!("invalid trait: {s}"))
+  }
+}
+
+/// Palindrome search in standard or bijective positional notation. Sat
+/// names are bijective base-26, so name palindromes use the latter.
+mod palindrome {
+  fn digits(mut n: u64, base: u64, bijective: bool) -> Vec<u64> {
+    let mut digits = Vec::new();
+    if bijective {
+      while n > 0 {
+        digits.push((n - 1) % base + 1);
+        n = (n - 1) / base;
+      }
+    } else {
+      loop {
+        digits.push(n % base);
+        n /= base;
+        if n == 0 {
+          break;
+        }
+      }
+    }
+    digits.reverse();
+    digits
+  }
+
+  fn value(digits: &[u64], base: u64) -> Option<u64> {
+    digits.iter().try_fold(0u64, |acc, digit| {
+      acc.checked_mul(base)?.checked_add(*digit)
+    })
+  }
+
+  fn mirror(half: u64, len: usize, base: u64, bijective: bool) -> Option<u64> {
+    let mut digits = digits(half, base, bijective);
+    let reflection = digits[..len / 2]
+      .iter()
+      .rev()
+      .copied()
+      .collect::<Vec<u64>>();
+    digits.extend(reflection);
+    value(&digits, base)
+  }
+
+  pub(super) fn at_or_after(n: u64, base: u64, bijective: bool) -> Option<u64> {
+    let len = digits(n, base, bijective).len();
+    let half_len = (len + 1) / 2;
+    let half = value(&digits(n, base, bijective)[..half_len], base)?;
+
+    let palindrome = mirror(half, len, base, bijective)?;
+    if palindrome >= n {
+      return Some(palindrome);
+    }
+
+    let half = half + 1;
+    if digits(half, base, bijective).len() == half_len {
+      mirror(half, len, base, bijective)
+    } else if len % 2 == 1 {
+      mirror(half / base, len + 1, base, bijective)
+    } else {
+      mirror(half, len + 1, base, bijective)
+    }
+  }
+
+  pub(super) fn at_or_before(n: u64, base: u64, bijective: bool) -> Option<u64> {
+    let len = digits(n, base, bijective).len();
+    let half_len = (len + 1) / 2;
+    let half = value(&digits(n, base, bijective)[..half_len], base)?;
+
+    let palindrome = mirror(half, len, base, bijective)?;
+    if palindrome <= n {
+      return Some(palindrome);
+    }
+
+    let half = half - 1;
+    if half > 0 && digits(half, base, bijective).len() == half_len {
+      mirror(half, len, base, bijective)
+    } else if len == 1 {
+      None
+    } else {
+      let max = if bijective { base } else { base - 1 };
+      value(&vec![max; len - 1], base)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display_and_from_str() {
+    for sat_trait in SatTrait::ALL {
+      assert_eq!(
+        sat_trait.to_string().parse::<SatTrait>().unwrap(),
+        sat_trait
+      );
+    }
+    assert_eq!(
+      "decimal-palindrome".parse::<SatTrait>().unwrap(),
+      SatTrait::DecimalPalindrome
+    );
+    "foo".parse::<SatTrait>().unwrap_err();
+  }
+
+  #[test]
+  fn is_set() {
+    assert!(SatTrait::Coin.is_set(Sat(COIN_VALUE)));
+    assert!(!SatTrait::Omega.is_set(Sat(COIN_VALUE)));
+    assert!(SatTrait::DecimalPalindrome.is_set(Sat(12321)));
+  }
+
+  fn brute_force(sat_trait: SatTrait, sat: Sat) -> Option<Sat> {
+    (sat.n()..=Sat::last().n())
+      .map(Sat)
+      .find(|sat| sat_trait.is_set(*sat))
+  }
+
+  #[test]
+  fn next_matches_brute_force() {
+    for n in (0..2000).chain(98990..101010) {
+      assert_eq!(
+        SatTrait::DecimalPalindrome.next(Sat(n)),
+        brute_force(SatTrait::DecimalPalindrome, Sat(n)),
+        "{n}"
+      );
+    }
+
+    for n in (Sat::last().n() - 20000..=Sat::last().n()).step_by(7) {
+      assert_eq!(
+        SatTrait::NamePalindrome.next(Sat(n)),
+        brute_force(SatTrait::NamePalindrome, Sat(n)),
+        "{n}"
+      );
+    }
+
+    for n in Sat::last().n() - 2000..=Sat::last().n() {
+      assert_eq!(
+        SatTrait::NamePalindrome.next(Sat(n)),
+        brute_force(SatTrait::NamePalindrome, Sat(n)),
+        "{n}"
+      );
+    }
+  }
+
+  #[test]
+  fn next_coin() {
+    assert_eq!(SatTrait::Coin.next(Sat(0)), Some(Sat(0)));
+    assert_eq!(SatTrait::Coin.next(Sat(1)), Some(Sat(COIN_VALUE)));
+    assert_eq!(SatTrait::Coin.next(Sat(COIN_VALUE)), Some(Sat(COIN_VALUE)));
+    assert_eq!(SatTrait::Coin.next(Sat::last()), None);
+  }
+
+  #[test]
+  fn next_alpha() {
+    assert_eq!(SatTrait::Alpha.next(Sat(0)), Some(Sat(0)));
+    assert_eq!(SatTrait::Alpha.next(Sat(1)), Some(Sat(COIN_VALUE)));
+    assert_eq!(
+      SatTrait::Alpha.next(Sat(49 * COIN_VALUE + 1)),
+      Some(Sat(50 * COIN_VALUE))
+    );
+
+    let start = Height(SUBSIDY_HALVING_INTERVAL * 3 + 1).starting_sat();
+    assert_eq!(start.n() % COIN_VALUE, 25000000);
+    assert_eq!(SatTrait::Alpha.next(start + 1), Some(start + COIN_VALUE));
+    assert_eq!(SatTrait::Alpha.next(Sat(Sat::last().n() - 1)), None);
+  }
+
+  #[test]
+  fn next_omega() {
+    assert_eq!(SatTrait::Omega.next(Sat(0)), Some(Sat(COIN_VALUE - 1)));
+    assert_eq!(
+      SatTrait::Omega.next(Sat(50 * COIN_VALUE - 1)),
+      Some(Sat(50 * COIN_VALUE - 1))
+    );
+    assert_eq!(
+      SatTrait::Omega.next(Sat(50 * COIN_VALUE)),
+      Some(Sat(51 * COIN_VALUE - 1))
+    );
+    assert_eq!(
+      SatTrait::Omega.next(Height(SUBSIDY_HALVING_INTERVAL * 7).starting_sat()),
+      None
+    );
+  }
+
+  #[test]
+  fn next_decimal_palindrome() {
+    assert_eq!(
+      SatTrait::DecimalPalindrome.next(Sat(12345)),
+      Some(Sat(12421))
+    );
+    assert_eq!(
+      SatTrait::DecimalPalindrome.next(Sat(99999)),
+      Some(Sat(99999))
+    );
+    assert_eq!(
+      SatTrait::DecimalPalindrome.next(Sat(100000)),
+      Some(Sat(100001))
+    );
+    assert_eq!(
+      SatTrait::DecimalPalindrome.next(Sat(Sat::last().n() - COIN_VALUE)),
+      None
+    );
+  }
+
+  #[test]
+  fn next_name_palindrome_from_first_sat() {
+    let next = SatTrait::NamePalindrome.next(Sat(0)).unwrap();
+    assert_eq!(next.name(), "nvtdijidtvn");
+    assert_eq!(
+      SatTrait::NamePalindrome.next(next + 1).unwrap().name(),
+      "nvtdiiidtvn"
+    );
+  }
+}
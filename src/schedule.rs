@@ -0,0 +1,272 @@
+use {super::*, std::sync::OnceLock};
+
+#[cfg(not(test))]
+static SCHEDULE: Installed = Installed::new();
+
+// Unit tests run concurrently in one process, so each test thread gets its own
+// schedule instead of sharing whichever one was installed first. Only where
+// the schedule is stored differs; `Installed` is the same in both.
+#[cfg(test)]
+thread_local! {
+  static SCHEDULE: Installed = const { Installed::new() };
+}
+
+#[cfg(not(test))]
+fn installed<T>(f: impl FnOnce(&Installed) -> T) -> T {
+  f(&SCHEDULE)
+}
+
+#[cfg(test)]
+fn installed<T>(f: impl FnOnce(&Installed) -> T) -> T {
+  SCHEDULE.with(f)
+}
+
+/// The schedule in use. It is fixed by the first of `install` or `current`,
+/// so sats can never be numbered with two different schedules.
+struct Installed(OnceLock<Schedule>);
+
+impl Installed {
+  const fn new() -> Self {
+    Self(OnceLock::new())
+  }
+
+  fn current(&self) -> Schedule {
+    *self.0.get_or_init(|| Schedule::MAINNET)
+  }
+
+  fn install(&self, schedule: Schedule) -> Result {
+    let installed = *self.0.get_or_init(|| schedule);
+
+    if installed != schedule {
+      bail!("subsidy schedule already installed");
+    }
+
+    Ok(())
+  }
+}
+
+/// Subsidy schedule. Always `Schedule::MAINNET`, except on `--chain custom`,
+/// where it may be overridden by the config file.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Schedule {
+  pub(crate) initial_subsidy: u64,
+  pub(crate) halving_interval: u64,
+}
+
+impl Schedule {
+  pub(crate) const MAINNET: Self = Self {
+    initial_subsidy: 50 * COIN_VALUE,
+    halving_interval: SUBSIDY_HALVING_INTERVAL,
+  };
+
+  pub(crate) fn current() -> Self {
+    installed(Installed::current)
+  }
+
+  pub(crate) fn install(self) -> Result {
+    installed(|installed| installed.install(self))
+  }
+
+  pub(crate) fn check(self) -> Result<Self> {
+    if self.initial_subsidy == 0 {
+      bail!("initial subsidy must be greater than zero");
+    }
+
+    if self.halving_interval == 0 {
+      bail!("halving interval must be greater than zero");
+    }
+
+    (0..self.first_post_subsidy()).try_fold(0u64, |supply, epoch| {
+      self
+        .subsidy(epoch)
+        .checked_mul(self.halving_interval)
+        .and_then(|sats| supply.checked_add(sats))
+        .ok_or_else(|| anyhow!("total supply of schedule overflows"))
+    })?;
+
+    Ok(self)
+  }
+
+  pub(crate) fn subsidy(self, epoch: u64) -> u64 {
+    u32::try_from(epoch)
+      .ok()
+      .and_then(|epoch| self.initial_subsidy.checked_shr(epoch))
+      .unwrap_or(0)
+  }
+
+  pub(crate) fn first_post_subsidy(self) -> u64 {
+    u64::from(u64::BITS - self.initial_subsidy.leading_zeros())
+  }
+
+  pub(crate) fn starting_sat(self, epoch: u64) -> u64 {
+    (0..epoch.min(self.first_post_subsidy()))
+      .map(|epoch| self.subsidy(epoch) * self.halving_interval)
+      .sum()
+  }
+
+  pub(crate) fn supply(self) -> u64 {
+    self.starting_sat(self.first_post_subsidy())
+  }
+
+  pub(crate) fn epoch(self, sat: u64) -> u64 {
+    let mut epoch = 0;
+    let mut end = 0;
+    while epoch < self.first_post_subsidy() {
+      end += self.subsidy(epoch) * self.halving_interval;
+      if sat < end {
+        break;
+      }
+      epoch += 1;
+    }
+    epoch
+  }
+}
+
+impl Display for Schedule {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "initial subsidy {} and halving interval {}",
+      self.initial_subsidy, self.halving_interval
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const TOY: Schedule = Schedule {
+    initial_subsidy: 100,
+    halving_interval: 3,
+  };
+
+  #[test]
+  fn mainnet_matches_constants() {
+    assert_eq!(Schedule::MAINNET.supply(), SUPPLY);
+    assert_eq!(Schedule::MAINNET.first_post_subsidy(), 33);
+    for epoch in 0..=34 {
+      assert_eq!(
+        Schedule::MAINNET.starting_sat(epoch),
+        Epoch(epoch).starting_sat().n()
+      );
+    }
+  }
+
+  #[test]
+  fn toy_schedule_matches_brute_force() {
+    let mut sats = Vec::new();
+    let mut heights = Vec::new();
+    let mut subsidy = TOY.initial_subsidy;
+    let mut height = 0;
+    while subsidy > 0 {
+      for _ in 0..subsidy {
+        heights.push(height);
+      }
+      sats.push(subsidy);
+      height += 1;
+      if height % TOY.halving_interval == 0 {
+        subsidy /= 2;
+      }
+    }
+
+    assert_eq!(TOY.supply(), sats.iter().sum::<u64>());
+    assert_eq!(TOY.first_post_subsidy(), height / TOY.halving_interval);
+
+    for (n, height) in heights.iter().enumerate() {
+      let epoch = TOY.epoch(n as u64);
+      assert_eq!(epoch, height / TOY.halving_interval, "{n}");
+      assert!(TOY.starting_sat(epoch) <= n as u64);
+      assert!((n as u64) < TOY.starting_sat(epoch + 1));
+      assert_eq!(TOY.subsidy(epoch), sats[usize::try_from(*height).unwrap()]);
+    }
+
+    assert_eq!(TOY.epoch(TOY.supply()), TOY.first_post_subsidy());
+  }
+
+  #[test]
+  fn subsidy_past_shift_width_is_zero() {
+    assert_eq!(TOY.subsidy(64), 0);
+    assert_eq!(TOY.subsidy(u64::MAX), 0);
+  }
+
+  #[test]
+  fn installed_schedule_is_per_test_thread() {
+    assert_eq!(Schedule::current(), Schedule::MAINNET);
+
+    thread::spawn(|| {
+      TOY.install().unwrap();
+      assert_eq!(Schedule::current(), TOY);
+      assert!(Schedule::MAINNET.install().is_err());
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(Schedule::current(), Schedule::MAINNET);
+  }
+
+  #[test]
+  fn install() {
+    let installed = Installed::new();
+    installed.install(TOY).unwrap();
+    assert_eq!(installed.current(), TOY);
+    installed.install(TOY).unwrap();
+    assert_eq!(
+      installed
+        .install(Schedule::MAINNET)
+        .unwrap_err()
+        .to_string(),
+      "subsidy schedule already installed"
+    );
+    assert_eq!(installed.current(), TOY);
+  }
+
+  #[test]
+  fn install_after_first_use() {
+    let installed = Installed::new();
+    assert_eq!(installed.current(), Schedule::MAINNET);
+    installed.install(Schedule::MAINNET).unwrap();
+    assert!(installed.install(TOY).is_err());
+    assert_eq!(installed.current(), Schedule::MAINNET);
+  }
+
+  #[test]
+  fn check() {
+    assert_eq!(TOY.check().unwrap(), TOY);
+    Schedule::MAINNET.check().unwrap();
+
+    assert_eq!(
+      Schedule {
+        initial_subsidy: 0,
+        halving_interval: 1,
+      }
+      .check()
+      .unwrap_err()
+      .to_string(),
+      "initial subsidy must be greater than zero"
+    );
+
+    assert_eq!(
+      Schedule {
+        initial_subsidy: 1,
+        halving_interval: 0,
+      }
+      .check()
+      .unwrap_err()
+      .to_string(),
+      "halving interval must be greater than zero"
+    );
+
+    assert_eq!(
+      Schedule {
+        initial_subsidy: u64::MAX,
+        halving_interval: 2,
+      }
+      .check()
+      .unwrap_err()
+      .to_string(),
+      "total supply of schedule overflows"
+    );
+  }
+}
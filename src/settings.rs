@@ -0,0 +1,193 @@
+use {super::*, std::cell::OnceCell};
+
+/// `Options` resolved into the connections subcommands need. Every
+/// subcommand receives a `Settings`, so RPC clients come from one pool, which
+/// the index shares, and the index is opened at most once and shared.
+pub(crate) struct Settings {
+  pub(crate) options: Options,
+  index: OnceCell<Arc<Index>>,
+  rpc: OnceCell<Arc<RpcPool>>,
+}
+
+impl Settings {
+  pub(crate) fn new(options: Options) -> Self {
+    Self {
+      options,
+      index: OnceCell::new(),
+      rpc: OnceCell::new(),
+    }
+  }
+
+  /// Settings sharing an index that has already been opened, and its RPC
+  /// pool.
+  pub(crate) fn with_index(options: Options, index: Arc<Index>) -> Self {
+    Self {
+      options,
+      rpc: OnceCell::from(index.rpc()),
+      index: OnceCell::from(index),
+    }
+  }
+
+  /// The index, opened on first use. Callers are responsible for updating it.
+  pub(crate) fn index(&self) -> Result<Arc<Index>> {
+    if let Some(index) = self.index.get() {
+      return Ok(index.clone());
+    }
+
+    let index = Arc::new(Index::open_with_rpc(&self.options, self.rpc()?)?);
+
+    Ok(self.index.get_or_init(|| index).clone())
+  }
+
+  pub(crate) fn rpc(&self) -> Result<Arc<RpcPool>> {
+    if let Some(rpc) = self.rpc.get() {
+      return Ok(rpc.clone());
+    }
+
+    let rpc = RpcPool::new(&self.options)?;
+
+    Ok(self.rpc.get_or_init(|| rpc).clone())
+  }
+
+  pub(crate) fn bitcoin_rpc_client(&self) -> Result<PooledClient> {
+    let client = self.rpc()?.get()?;
+
+    let rpc_chain = match client.get_blockchain_info()?.chain.as_str() {
+      "main" => Chain::Mainnet,
+      "test" => Chain::Testnet,
+      "regtest" => Chain::Regtest,
+      "signet" => Chain::Signet,
+      other => bail!("Bitcoin RPC server on unknown chain: {other}"),
+    };
+
+    let ord_chain = self.options.chain();
+
+    if rpc_chain.network() != ord_chain.network() {
+      bail!("Bitcoin RPC server is on {rpc_chain} but ord is on {ord_chain}");
+    }
+
+    Ok(client)
+  }
+
+  pub(crate) fn bitcoin_rpc_client_mainnet_forbidden(&self, command: &str) -> Result<PooledClient> {
+    let client = self.bitcoin_rpc_client()?;
+
+    if self.options.chain() == Chain::Mainnet {
+      bail!("`{command}` is unstable and not yet supported on mainnet.");
+    }
+    Ok(client)
+  }
+
+  pub(crate) fn bitcoin_rpc_client_regtest_only(&self, command: &str) -> Result<PooledClient> {
+    let chain = self.options.chain();
+
+    if chain != Chain::Regtest {
+      bail!("`{command}` may only be used on regtest, not {chain}");
+    }
+
+    self.bitcoin_rpc_client()
+  }
+
+  pub(crate) fn bitcoin_rpc_client_for_wallet_command(
+    &self,
+    command: &str,
+  ) -> Result<PooledClient> {
+    let client = self.bitcoin_rpc_client()?;
+
+    if self.options.chain() == Chain::Mainnet {
+      let wallet_info = client.get_wallet_info()?;
+
+      if !(wallet_info.wallet_name == "ord" || wallet_info.wallet_name.starts_with("ord-")) {
+        bail!("`{command}` may only be used on mainnet with a wallet named `ord` or whose name starts with `ord-`");
+      }
+
+      let balances = client.get_balances()?;
+
+      let total = balances.mine.trusted + balances.mine.untrusted_pending + balances.mine.immature;
+
+      if total > Amount::from_sat(1_000_000) {
+        bail!(
+          "`{command}` may not be used on mainnet with wallets containing more than 1,000,000 sats"
+        );
+      }
+    }
+    Ok(client)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rpc_server_chain_must_match() {
+    let rpc_server = test_bitcoincore_rpc::spawn_with(bitcoin::Network::Testnet, "ord");
+
+    let tempdir = TempDir::new().unwrap();
+
+    let cookie_file = tempdir.path().join(".cookie");
+    fs::write(&cookie_file, "username:password").unwrap();
+
+    let settings = Settings::new(
+      Options::try_parse_from([
+        "ord",
+        "--cookie-file",
+        cookie_file.to_str().unwrap(),
+        "--rpc-url",
+        &rpc_server.url(),
+      ])
+      .unwrap(),
+    );
+
+    assert_eq!(
+      settings.bitcoin_rpc_client().unwrap_err().to_string(),
+      "Bitcoin RPC server is on testnet but ord is on mainnet"
+    );
+  }
+
+  fn options(rpc_server: &test_bitcoincore_rpc::Handle, tempdir: &TempDir) -> Options {
+    let cookie_file = tempdir.path().join("cookie");
+    fs::write(&cookie_file, "username:password").unwrap();
+
+    Options::try_parse_from([
+      "ord",
+      "--chain",
+      "regtest",
+      "--rpc-url",
+      &rpc_server.url(),
+      "--cookie-file",
+      cookie_file.to_str().unwrap(),
+      "--data-dir",
+      tempdir.path().to_str().unwrap(),
+    ])
+    .unwrap()
+  }
+
+  #[test]
+  fn index_is_opened_once() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    let tempdir = TempDir::new().unwrap();
+
+    let settings = Settings::new(options(&rpc_server, &tempdir));
+
+    assert!(Arc::ptr_eq(
+      &settings.index().unwrap(),
+      &settings.index().unwrap()
+    ));
+  }
+
+  #[test]
+  fn injected_index_is_used() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    let tempdir = TempDir::new().unwrap();
+
+    let index = Arc::new(Index::open(&options(&rpc_server, &tempdir)).unwrap());
+
+    let settings = Settings::with_index(
+      Options::try_parse_from(["ord", "--index", "/does/not/exist"]).unwrap(),
+      index.clone(),
+    );
+
+    assert!(Arc::ptr_eq(&settings.index().unwrap(), &index));
+  }
+}
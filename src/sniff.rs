@@ -0,0 +1,186 @@
+/// Content types that browsers may execute, and so must never be served in
+/// place of passive media.
+const ACTIVE: &[&str] = &["image/svg+xml", "text/html", "text/xml"];
+
+const SIGNATURES: &[(&[u8], &str)] = &[
+  (b"\x89PNG\r\n\x1a\n", "image/png"),
+  (b"GIF87a", "image/gif"),
+  (b"GIF89a", "image/gif"),
+  (b"\xff\xd8\xff", "image/jpeg"),
+  (b"%PDF-", "application/pdf"),
+];
+
+/// Tags which, following the WHATWG MIME sniffing standard, mark content as
+/// HTML when they open a document and are followed by a space or `>`.
+const HTML_TAGS: &[&[u8]] = &[
+  b"<!doctype html",
+  b"<html",
+  b"<head",
+  b"<script",
+  b"<iframe",
+  b"<h1",
+  b"<div",
+  b"<font",
+  b"<table",
+  b"<a",
+  b"<style",
+  b"<title",
+  b"<b",
+  b"<body",
+  b"<br",
+  b"<p",
+  b"<!--",
+];
+
+/// Identify the type of `content` from its leading bytes, the way a browser
+/// would if it ignored the declared type.
+pub(crate) fn sniff(content: &[u8]) -> Option<&'static str> {
+  for (signature, media_type) in SIGNATURES {
+    if content.starts_with(signature) {
+      return Some(media_type);
+    }
+  }
+
+  if content.len() >= 16 && content.starts_with(b"RIFF") && content[8..].starts_with(b"WEBPVP") {
+    return Some("image/webp");
+  }
+
+  let markup = content.strip_prefix(b"\xef\xbb\xbf").unwrap_or(content);
+
+  let markup = &markup[markup
+    .iter()
+    .position(|byte| !matches!(byte, b'\t' | b'\n' | b'\x0c' | b'\r' | b' '))
+    .unwrap_or(markup.len())..];
+
+  if HTML_TAGS.iter().any(|tag| opens_with_tag(markup, tag)) {
+    return Some("text/html");
+  }
+
+  if opens_with_tag(markup, b"<svg") {
+    return Some("image/svg+xml");
+  }
+
+  if starts_with_ignore_case(markup, b"<?xml") {
+    return Some("text/xml");
+  }
+
+  None
+}
+
+fn starts_with_ignore_case(content: &[u8], prefix: &[u8]) -> bool {
+  content.len() >= prefix.len() && content[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn opens_with_tag(content: &[u8], tag: &[u8]) -> bool {
+  starts_with_ignore_case(content, tag) && matches!(content.get(tag.len()), Some(b' ' | b'>'))
+}
+
+/// The type and subtype of `content_type`, without parameters, lowercased.
+pub(crate) fn essence(content_type: &str) -> String {
+  content_type
+    .split(';')
+    .next()
+    .unwrap_or_default()
+    .trim()
+    .to_ascii_lowercase()
+}
+
+pub(crate) fn is_active(content_type: &str) -> bool {
+  ACTIVE.contains(&essence(content_type).as_str())
+}
+
+/// Images, audio, and video, which browsers display without executing.
+pub(crate) fn is_passive(content_type: &str) -> bool {
+  let essence = essence(content_type);
+  !is_active(&essence)
+    && ["image/", "audio/", "video/"]
+      .iter()
+      .any(|prefix| essence.starts_with(prefix))
+}
+
+/// Content types whose encoding is already compressed, so that compressing
+/// them again would only waste time.
+pub(crate) fn is_compressed(content_type: &str) -> bool {
+  let essence = essence(content_type);
+  (is_passive(&essence) && !["audio/wav", "image/bmp"].contains(&essence.as_str()))
+    || [
+      "application/gzip",
+      "application/pdf",
+      "application/x-7z-compressed",
+      "application/x-bzip2",
+      "application/x-gzip",
+      "application/x-xz",
+      "application/zip",
+      "application/zstd",
+      "font/woff",
+      "font/woff2",
+    ]
+    .contains(&essence.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn signatures() {
+    assert_eq!(sniff(b""), None);
+    assert_eq!(sniff(b"hello"), None);
+    assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some("image/png"));
+    assert_eq!(sniff(b"\x89PNG"), None);
+    assert_eq!(sniff(b"GIF89a\x01\x00"), Some("image/gif"));
+    assert_eq!(sniff(b"GIF87a"), Some("image/gif"));
+    assert_eq!(sniff(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+    assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+    assert_eq!(sniff(b"RIFF\0\0\0\0WAVEfmt "), None);
+    assert_eq!(sniff(b"%PDF-1.7"), Some("application/pdf"));
+  }
+
+  #[test]
+  fn markup() {
+    assert_eq!(sniff(b"<!DOCTYPE html>"), Some("text/html"));
+    assert_eq!(sniff(b"<html><body>"), Some("text/html"));
+    assert_eq!(sniff(b"<SCRIPT>alert(1)</SCRIPT>"), Some("text/html"));
+    assert_eq!(sniff(b"<a href=/>"), Some("text/html"));
+    assert_eq!(sniff(b" \t\r\n<p>hello"), Some("text/html"));
+    assert_eq!(sniff(b"\xef\xbb\xbf<html>"), Some("text/html"));
+    assert_eq!(sniff(b"<!-- comment -->"), Some("text/html"));
+    assert_eq!(
+      sniff(b"<svg xmlns='http://www.w3.org/2000/svg'>"),
+      Some("image/svg+xml")
+    );
+    assert_eq!(sniff(b"<?xml version='1.0'?>"), Some("text/xml"));
+    assert_eq!(sniff(b"<abbr>"), None);
+    assert_eq!(sniff(b"<html"), None);
+    assert_eq!(sniff(b"hello <html>"), None);
+  }
+
+  #[test]
+  fn content_type_classes() {
+    assert_eq!(essence("Text/HTML; charset=utf-8"), "text/html");
+    assert_eq!(essence("image/png"), "image/png");
+
+    assert!(is_active("text/html;charset=utf-8"));
+    assert!(is_active("image/svg+xml"));
+    assert!(is_active("text/xml"));
+    assert!(!is_active("image/png"));
+    assert!(!is_active("text/plain"));
+
+    assert!(is_passive("image/png"));
+    assert!(is_passive("IMAGE/GIF"));
+    assert!(is_passive("audio/mpeg"));
+    assert!(is_passive("video/mp4"));
+    assert!(!is_passive("image/svg+xml"));
+    assert!(!is_passive("text/plain;charset=utf-8"));
+    assert!(!is_passive("text/html"));
+
+    assert!(is_compressed("image/png"));
+    assert!(is_compressed("image/jpeg"));
+    assert!(is_compressed("video/webm"));
+    assert!(is_compressed("application/zip"));
+    assert!(!is_compressed("image/svg+xml"));
+    assert!(!is_compressed("image/bmp"));
+    assert!(!is_compressed("text/plain;charset=utf-8"));
+    assert!(!is_compressed("application/json"));
+  }
+}
@@ -0,0 +1,578 @@
+use {super::*, serde::de::DeserializeOwned, std::marker::PhantomData};
+
+/// State kept in a `StateFile`. `VERSION` must be bumped whenever the
+/// serialized form changes incompatibly.
+pub(crate) trait Versioned: Default + Serialize + DeserializeOwned {
+  const VERSION: u32;
+
+  /// Apply an entry passed to `StateFile::append`. Only states that are
+  /// appended to need to override this.
+  fn apply(&mut self, entry: serde_json::Value) -> Result {
+    bail!("unexpected journal entry {entry}")
+  }
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+  version: u32,
+  journal: u64,
+  state: &'a T,
+}
+
+#[derive(Deserialize)]
+struct RawEnvelope {
+  version: u32,
+  #[serde(default)]
+  journal: u64,
+  state: serde_json::Value,
+}
+
+/// Write `contents` to a temporary file beside `path` that only the owner
+/// can read, sync it, and rename it over `path`, so that a crash leaves
+/// either the old or new contents.
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> Result {
+  fs::rename(write_temporary(path, contents)?, path)?;
+  sync_parent(path)
+}
+
+/// Write `contents` to a synced temporary file beside `path` that only the
+/// owner can read, and return its path.
+fn write_temporary(path: &Path, contents: &[u8]) -> Result<PathBuf> {
+  let mut temporary = path.as_os_str().to_owned();
+  temporary.push(".tmp");
+  let temporary = PathBuf::from(temporary);
+
+  let mut options = fs::OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+
+  #[cfg(unix)]
+  std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+  let mut file = options.open(&temporary)?;
+  io::Write::write_all(&mut file, contents)?;
+  file.sync_all()?;
+
+  Ok(temporary)
+}
+
+fn sync_parent(path: &Path) -> Result {
+  #[cfg(unix)]
+  if let Some(dir) = path.parent() {
+    fs::File::open(if dir.as_os_str().is_empty() {
+      Path::new(".")
+    } else {
+      dir
+    })?
+    .sync_all()?;
+  }
+
+  Ok(())
+}
+
+/// A JSON file holding state that must survive crashes. The state is written
+/// to a temporary file that is synced and then renamed into place, so a
+/// crash leaves either the old or new state, never a mix. The state it
+/// replaces is kept beside it, and is read instead if the current file turns
+/// out to be torn, for example because the file system reordered the rename
+/// before the data reached the disk.
+///
+/// Small changes can instead be appended to a journal beside the file, which
+/// is replayed on load and folded into the file by the next update. Each
+/// write of the file starts a new journal, so a crash after the write never
+/// replays an entry twice.
+///
+/// Updates from within one process are serialized, but nothing prevents two
+/// processes from updating the same file.
+#[derive(Debug)]
+pub(crate) struct StateFile<T> {
+  /// The current journal, once known.
+  journal: Mutex<Option<u64>>,
+  path: PathBuf,
+  state: PhantomData<T>,
+}
+
+impl<T: Versioned> StateFile<T> {
+  pub(crate) fn new(path: PathBuf) -> Self {
+    Self {
+      journal: Mutex::new(None),
+      path,
+      state: PhantomData,
+    }
+  }
+
+  pub(crate) fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// The current state, or the default if nothing has been saved yet.
+  #[cfg(test)]
+  pub(crate) fn load(&self) -> Result<T> {
+    let mut journal = self.journal.lock().unwrap();
+    Ok(self.read(&mut journal)?.state)
+  }
+
+  /// Apply `f` to the current state and save the result.
+  pub(crate) fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R> {
+    let mut journal = self.journal.lock().unwrap();
+
+    let mut read = self.read(&mut journal)?;
+
+    let result = f(&mut read.state);
+
+    self.write(&mut journal, &read)?;
+
+    Ok(result)
+  }
+
+  /// The current state, after folding in the journal if it isn't empty.
+  pub(crate) fn compact(&self) -> Result<T> {
+    let mut journal = self.journal.lock().unwrap();
+
+    let read = self.read(&mut journal)?;
+
+    if read.entries > 0 {
+      self.write(&mut journal, &read)?;
+    }
+
+    Ok(read.state)
+  }
+
+  /// Append `entry` to the journal, to be applied to the state with
+  /// `Versioned::apply` when it is next loaded. Unlike `update`, this doesn't
+  /// rewrite the state.
+  pub(crate) fn append(&self, entry: &impl Serialize) -> Result {
+    let mut journal = self.journal.lock().unwrap();
+
+    let current = match *journal {
+      Some(current) => current,
+      None => self.read(&mut journal)?.journal,
+    };
+
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+
+    let path = self.journal_path(current);
+
+    let mut file = fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&path)?;
+    io::Write::write_all(&mut file, &line)?;
+    file.sync_data()?;
+
+    Ok(())
+  }
+
+  fn sibling(&self, extension: &str) -> PathBuf {
+    let mut path = self.path.as_os_str().to_owned();
+    path.push(extension);
+    PathBuf::from(path)
+  }
+
+  fn journal_path(&self, journal: u64) -> PathBuf {
+    self.sibling(&format!(".journal-{journal}"))
+  }
+
+  /// Read the current state and replay its journal.
+  fn read(&self, journal: &mut Option<u64>) -> Result<Read<T>> {
+    let (mut state, current_is_good, current) = self.read_snapshot()?;
+
+    let entries = self.replay(&mut state, current)?;
+
+    *journal = Some(current);
+
+    Ok(Read {
+      current_is_good,
+      entries,
+      journal: current,
+      state,
+    })
+  }
+
+  /// Apply the entries in journal `journal` to `state`, returning how many
+  /// there were. A torn last entry was never fully appended, so it is ignored
+  /// and truncated away before anything else is appended.
+  fn replay(&self, state: &mut T, journal: u64) -> Result<usize> {
+    let path = self.journal_path(journal);
+
+    let contents = match fs::read(&path) {
+      Ok(contents) => contents,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut entries = 0;
+    let mut end = 0;
+
+    for line in contents.split_inclusive(|byte| *byte == b'\n') {
+      if !line.ends_with(b"\n") {
+        log::warn!("Ignoring torn entry at end of journal `{}`", path.display());
+        fs::OpenOptions::new()
+          .write(true)
+          .open(&path)?
+          .set_len(end.try_into().unwrap())?;
+        break;
+      }
+
+      end += line.len();
+
+      serde_json::from_slice(line)
+        .map_err(Error::from)
+        .and_then(|entry| state.apply(entry))
+        .with_context(|| format!("invalid entry in journal `{}`", path.display()))?;
+
+      entries += 1;
+    }
+
+    Ok(entries)
+  }
+
+  /// Read the current state, falling back to the previous one if the current
+  /// file is missing or torn. Also returns whether the current file was good,
+  /// and thus should become the previous one on the next write, and the
+  /// state's journal.
+  fn read_snapshot(&self) -> Result<(T, bool, u64)> {
+    match Self::read_file(&self.path) {
+      Ok(Some((state, journal))) => return Ok((state, true, journal)),
+      Ok(None) => {}
+      Err(ReadError::Torn(err)) => log::warn!(
+        "State file `{}` is torn, falling back to previous state: {err}",
+        self.path.display()
+      ),
+      Err(ReadError::Invalid(err)) => return Err(err),
+    }
+
+    let previous = self.sibling(".previous");
+
+    match Self::read_file(&previous) {
+      Ok(Some((state, journal))) => Ok((state, false, journal)),
+      Ok(None) => Ok((T::default(), false, 0)),
+      Err(ReadError::Torn(err) | ReadError::Invalid(err)) => Err(err.context(format!(
+        "failed to recover state from `{}`",
+        previous.display()
+      ))),
+    }
+  }
+
+  fn read_file(path: &Path) -> Result<Option<(T, u64)>, ReadError> {
+    let json = match fs::read(path) {
+      Ok(json) => json,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+      Err(err) => return Err(ReadError::Invalid(err.into())),
+    };
+
+    let envelope =
+      serde_json::from_slice::<RawEnvelope>(&json).map_err(|err| ReadError::Torn(err.into()))?;
+
+    if envelope.version != T::VERSION {
+      return Err(ReadError::Invalid(anyhow!(
+        "state file `{}` has schema version {}, but this version of ord only supports schema version {}",
+        path.display(),
+        envelope.version,
+        T::VERSION,
+      )));
+    }
+
+    serde_json::from_value(envelope.state)
+      .map(|state| Some((state, envelope.journal)))
+      .map_err(|err| {
+        ReadError::Invalid(
+          Error::from(err).context(format!("invalid state file `{}`", path.display())),
+        )
+      })
+  }
+
+  /// Save the state in `read` with a new, empty journal. The journal it
+  /// replaces is kept while the previous state might still be read.
+  fn write(&self, journal: &mut Option<u64>, read: &Read<T>) -> Result {
+    let next = read.journal + 1;
+
+    let json = serde_json::to_vec(&Envelope {
+      version: T::VERSION,
+      journal: next,
+      state: &read.state,
+    })?;
+
+    let temporary = write_temporary(&self.path, &json)?;
+
+    // A torn current file must not replace the good previous one.
+    if read.current_is_good {
+      fs::rename(&self.path, self.sibling(".previous"))?;
+    }
+
+    fs::rename(&temporary, &self.path)?;
+
+    sync_parent(&self.path)?;
+
+    *journal = Some(next);
+
+    if let Some(stale) = read.journal.checked_sub(1) {
+      match fs::remove_file(self.journal_path(stale)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+        _ => {}
+      }
+    }
+
+    Ok(())
+  }
+}
+
+struct Read<T> {
+  current_is_good: bool,
+  entries: usize,
+  journal: u64,
+  state: T,
+}
+
+enum ReadError {
+  Torn(Error),
+  Invalid(Error),
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+  struct Counter(u64);
+
+  impl Versioned for Counter {
+    const VERSION: u32 = 2;
+
+    fn apply(&mut self, entry: serde_json::Value) -> Result {
+      self.0 += serde_json::from_value::<u64>(entry)?;
+      Ok(())
+    }
+  }
+
+  fn state_file() -> (StateFile<Counter>, TempDir) {
+    let tempdir = TempDir::new().unwrap();
+    (StateFile::new(tempdir.path().join("counter.json")), tempdir)
+  }
+
+  fn increment(state_file: &StateFile<Counter>) {
+    state_file.update(|counter| counter.0 += 1).unwrap();
+  }
+
+  #[test]
+  fn missing_state_is_default() {
+    let (state_file, _tempdir) = state_file();
+    assert_eq!(state_file.load().unwrap(), Counter(0));
+  }
+
+  #[test]
+  fn updates_are_saved_with_version() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+    increment(&state_file);
+
+    assert_eq!(state_file.load().unwrap(), Counter(2));
+    assert_eq!(
+      fs::read_to_string(state_file.path()).unwrap(),
+      r#"{"version":2,"journal":2,"state":2}"#
+    );
+    assert_eq!(
+      fs::read_to_string(state_file.sibling(".previous")).unwrap(),
+      r#"{"version":2,"journal":1,"state":1}"#
+    );
+    assert!(!state_file.sibling(".tmp").exists());
+  }
+
+  #[test]
+  fn torn_write_falls_back_to_previous_state() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+    increment(&state_file);
+
+    fs::write(state_file.path(), r#"{"version":2,"st"#).unwrap();
+
+    assert_eq!(state_file.load().unwrap(), Counter(1));
+
+    // The good previous state is kept until a good current one replaces it.
+    increment(&state_file);
+    assert_eq!(state_file.load().unwrap(), Counter(2));
+    assert_eq!(
+      fs::read_to_string(state_file.sibling(".previous")).unwrap(),
+      r#"{"version":2,"journal":1,"state":1}"#
+    );
+
+    increment(&state_file);
+    assert_eq!(state_file.load().unwrap(), Counter(3));
+  }
+
+  #[test]
+  fn interrupted_write_keeps_current_state() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+
+    fs::write(state_file.sibling(".tmp"), r#"{"vers"#).unwrap();
+
+    assert_eq!(state_file.load().unwrap(), Counter(1));
+
+    increment(&state_file);
+    assert_eq!(state_file.load().unwrap(), Counter(2));
+  }
+
+  #[test]
+  fn crash_between_renames_recovers_previous_state() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+    increment(&state_file);
+
+    fs::remove_file(state_file.path()).unwrap();
+
+    assert_eq!(state_file.load().unwrap(), Counter(1));
+  }
+
+  #[test]
+  fn torn_current_and_previous_state_is_an_error() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+    increment(&state_file);
+
+    fs::write(state_file.path(), "").unwrap();
+    fs::write(state_file.sibling(".previous"), "{").unwrap();
+
+    assert_eq!(
+      state_file.load().unwrap_err().to_string(),
+      format!(
+        "failed to recover state from `{}`",
+        state_file.sibling(".previous").display()
+      )
+    );
+  }
+
+  #[test]
+  fn state_without_journal_is_read() {
+    let (state_file, _tempdir) = state_file();
+
+    fs::write(state_file.path(), r#"{"version":2,"state":5}"#).unwrap();
+
+    assert_eq!(state_file.load().unwrap(), Counter(5));
+  }
+
+  #[test]
+  fn appended_entries_are_replayed_and_compacted() {
+    let (state_file, _tempdir) = state_file();
+
+    state_file.append(&2).unwrap();
+    state_file.append(&3).unwrap();
+
+    assert!(!state_file.path().exists());
+    assert_eq!(state_file.load().unwrap(), Counter(5));
+
+    assert_eq!(state_file.compact().unwrap(), Counter(5));
+    assert_eq!(
+      fs::read_to_string(state_file.path()).unwrap(),
+      r#"{"version":2,"journal":1,"state":5}"#
+    );
+
+    state_file.append(&1).unwrap();
+    increment(&state_file);
+
+    assert_eq!(
+      StateFile::<Counter>::new(state_file.path().into())
+        .load()
+        .unwrap(),
+      Counter(7)
+    );
+  }
+
+  #[test]
+  fn compacting_empty_journal_does_not_write() {
+    let (state_file, _tempdir) = state_file();
+
+    assert_eq!(state_file.compact().unwrap(), Counter(0));
+    assert!(!state_file.path().exists());
+  }
+
+  #[test]
+  fn compacted_journal_is_not_replayed() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+    state_file.append(&2).unwrap();
+    increment(&state_file);
+
+    // The journal folded in by the last update is kept for the previous
+    // state, but isn't applied to the current one.
+    assert!(state_file.journal_path(1).exists());
+    assert_eq!(state_file.load().unwrap(), Counter(4));
+
+    // If the current state is lost, the previous one and its journal are
+    // read instead.
+    fs::remove_file(state_file.path()).unwrap();
+    assert_eq!(state_file.load().unwrap(), Counter(3));
+  }
+
+  #[test]
+  fn torn_journal_entry_is_ignored() {
+    let (state_file, _tempdir) = state_file();
+
+    state_file.append(&2).unwrap();
+
+    fs::OpenOptions::new()
+      .append(true)
+      .open(state_file.journal_path(0))
+      .and_then(|mut file| io::Write::write_all(&mut file, b"1"))
+      .unwrap();
+
+    assert_eq!(state_file.load().unwrap(), Counter(2));
+
+    state_file.append(&3).unwrap();
+    assert_eq!(state_file.load().unwrap(), Counter(5));
+  }
+
+  #[test]
+  fn invalid_journal_entry_is_an_error() {
+    let (state_file, _tempdir) = state_file();
+
+    fs::write(state_file.journal_path(0), "\"a\"\n").unwrap();
+
+    assert_eq!(
+      state_file.load().unwrap_err().to_string(),
+      format!(
+        "invalid entry in journal `{}`",
+        state_file.journal_path(0).display()
+      )
+    );
+  }
+
+  #[test]
+  fn written_files_are_private() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("secret");
+
+    write_atomically(&path, b"foo").unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "foo");
+
+    #[cfg(unix)]
+    assert_eq!(
+      std::os::unix::fs::PermissionsExt::mode(&fs::metadata(&path).unwrap().permissions()) & 0o777,
+      0o600
+    );
+  }
+
+  #[test]
+  fn other_schema_versions_are_rejected() {
+    let (state_file, _tempdir) = state_file();
+
+    increment(&state_file);
+
+    fs::write(state_file.path(), r#"{"version":3,"state":"new"}"#).unwrap();
+
+    assert_eq!(
+      state_file.load().unwrap_err().to_string(),
+      format!(
+        "state file `{}` has schema version 3, but this version of ord only supports schema version 2",
+        state_file.path().display()
+      )
+    );
+  }
+}
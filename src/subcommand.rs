@@ -1,37 +1,53 @@
 use super::*;
 
+mod block_sats;
 mod epochs;
+mod export_collection;
 mod find;
 mod index;
 mod info;
 mod list;
+mod names;
 mod parse;
 pub(crate) mod server;
+mod stats;
 mod subsidy;
 mod supply;
+mod test;
 mod traits;
-mod wallet;
+pub(crate) mod wallet;
 
 #[derive(Debug, Parser)]
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum Subcommand {
+  #[clap(about = "List rare satoshis mined in a block")]
+  BlockSats(block_sats::BlockSats),
   #[clap(about = "List the first satoshis of each reward epoch")]
   Epochs,
+  #[clap(about = "Export inscriptions as a static site")]
+  ExportCollection(export_collection::ExportCollection),
   #[clap(about = "Find a satoshi's current location")]
   Find(find::Find),
   #[clap(about = "Update the index")]
-  Index,
+  Index(index::Index),
   #[clap(about = "Display index statistics")]
   Info(info::Info),
   #[clap(about = "List the satoshis in an output")]
   List(list::List),
+  #[clap(about = "List satoshis whose names start with a prefix")]
+  Names(names::Names),
   #[clap(about = "Parse a satoshi from ordinal notation")]
   Parse(parse::Parse),
   #[clap(about = "Display information about a block's subsidy")]
   Subsidy(subsidy::Subsidy),
   #[clap(about = "Run the explorer server")]
   Server(server::Server),
+  #[clap(subcommand, about = "Display index statistics by category")]
+  Stats(stats::Stats),
   #[clap(about = "Display Bitcoin supply information")]
   Supply,
+  #[clap(subcommand, hide = true, about = "Regtest-only helpers for tests")]
+  Test(test::Test),
   #[clap(about = "Display satoshi traits")]
   Traits(traits::Traits),
   #[clap(subcommand, about = "Wallet commands")]
@@ -39,24 +55,28 @@ pub(crate) enum Subcommand {
 }
 
 impl Subcommand {
-  pub(crate) fn run(self, options: Options) -> Result {
+  pub(crate) fn run(self, settings: Settings) -> Result {
     match self {
+      Self::BlockSats(block_sats) => block_sats.run(settings),
       Self::Epochs => epochs::run(),
-      Self::Find(find) => find.run(options),
-      Self::Index => index::run(options),
-      Self::Info(info) => info.run(options),
-      Self::List(list) => list.run(options),
+      Self::ExportCollection(export_collection) => export_collection.run(settings),
+      Self::Find(find) => find.run(settings),
+      Self::Index(index) => index.run(settings),
+      Self::Info(info) => info.run(settings),
+      Self::List(list) => list.run(settings),
+      Self::Names(names) => names.run(settings),
       Self::Parse(parse) => parse.run(),
       Self::Subsidy(subsidy) => subsidy.run(),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&options)?);
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(options, index, handle)
+        server.run(settings, handle)
       }
+      Self::Stats(stats) => stats.run(settings),
       Self::Supply => supply::run(),
-      Self::Traits(traits) => traits.run(),
-      Self::Wallet(wallet) => wallet.run(options),
+      Self::Test(test) => test.run(settings),
+      Self::Traits(traits) => traits.run(settings),
+      Self::Wallet(wallet) => wallet.run(settings),
     }
   }
 }
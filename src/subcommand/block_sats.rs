@@ -0,0 +1,45 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct BlockSats {
+  #[clap(help = "List rare sats mined in block <HEIGHT>.")]
+  height: Height,
+  #[clap(
+    long,
+    default_value = "uncommon",
+    help = "Only list sats of at least <RARITY>."
+  )]
+  rarity: Rarity,
+}
+
+impl BlockSats {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let index = settings.index()?;
+
+    index.update()?;
+
+    index.require(IndexOption::RareSats, "block-sats")?;
+
+    if index.block_count()? <= self.height.n() {
+      bail!(
+        "block {} has not been mined as of index height",
+        self.height
+      );
+    }
+
+    for (sat, satpoint) in index.block_rare_sats(self.height)? {
+      let rarity = sat.rarity();
+
+      if rarity < self.rarity {
+        continue;
+      }
+
+      match satpoint {
+        Some(satpoint) => println!("{sat}\t{rarity}\t{satpoint}"),
+        None => println!("{sat}\t{rarity}"),
+      }
+    }
+
+    Ok(())
+  }
+}
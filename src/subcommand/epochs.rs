@@ -1,8 +1,8 @@
 use super::*;
 
 pub(crate) fn run() -> Result {
-  for sat in Epoch::STARTING_SATS {
-    println!("{}", sat);
+  for epoch in 0..=Epoch::first_post_subsidy().0 {
+    println!("{}", Epoch(epoch).starting_sat());
   }
   Ok(())
 }
@@ -0,0 +1,202 @@
+use {
+  super::*,
+  bitcoin::hashes::sha256,
+  server::{
+    explorer::Explorer,
+    templates::{CollectionHtml, ExportPageHtml, InscriptionHtml},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ExportCollection {
+  #[clap(
+    long,
+    help = "Export inscriptions listed in <INSCRIPTIONS>, one inscription ID per line."
+  )]
+  inscriptions: PathBuf,
+  #[clap(long, help = "Write export to directory <OUTPUT>.")]
+  output: PathBuf,
+}
+
+#[derive(Serialize)]
+struct IndexJson {
+  inscriptions: Vec<InscriptionJson>,
+}
+
+#[derive(Serialize)]
+struct InscriptionJson {
+  id: InscriptionId,
+  /// Position in the collection. The index doesn't assign global inscription
+  /// numbers.
+  number: usize,
+  satpoint: SatPoint,
+  content_type: Option<String>,
+  content_size: Option<usize>,
+  /// Path of the content, relative to the export directory. Content files are
+  /// named by their SHA-256 digest.
+  content: Option<String>,
+  sha256: Option<sha256::Hash>,
+  page: String,
+}
+
+impl ExportCollection {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let inscription_ids = self.inscription_ids()?;
+
+    if self.output.exists()
+      && fs::read_dir(&self.output)
+        .with_context(|| {
+          format!(
+            "failed to read output directory `{}`",
+            self.output.display()
+          )
+        })?
+        .next()
+        .is_some()
+    {
+      bail!("output directory `{}` is not empty", self.output.display());
+    }
+
+    let index = settings.index()?;
+    index.update()?;
+
+    let mut inscriptions = Vec::new();
+    for inscription_id in inscription_ids {
+      let (inscription, satpoint) = index
+        .get_inscription_by_inscription_id(inscription_id)?
+        .ok_or_else(|| anyhow!("inscription {inscription_id} not found"))?;
+      let inscription_number = index
+        .get_inscription_number(inscription_id)?
+        .ok_or_else(|| anyhow!("inscription {inscription_id} has no number"))?;
+      let burned = index.is_inscription_burned(inscription_id)?;
+      inscriptions.push((
+        inscription_id,
+        inscription_number,
+        inscription,
+        satpoint,
+        burned,
+      ));
+    }
+
+    for dir in ["content", "inscription"] {
+      fs::create_dir_all(self.output.join(dir))?;
+    }
+
+    let mut entries = Vec::new();
+    let mut collection = Vec::new();
+
+    for (number, (inscription_id, inscription_number, inscription, satpoint, burned)) in
+      inscriptions.into_iter().enumerate()
+    {
+      let (content, sha256) = match inscription.content_bytes() {
+        Some(bytes) => {
+          let sha256 = sha256::Hash::hash(bytes);
+          let path = format!("content/{sha256}.{}", extension(inscription.content_type()));
+          fs::write(self.output.join(&path), bytes)?;
+          (Some(path), Some(sha256))
+        }
+        None => (None, None),
+      };
+
+      let page = format!("inscription/{inscription_id}.html");
+
+      entries.push(InscriptionJson {
+        id: inscription_id,
+        number,
+        satpoint,
+        content_type: inscription.content_type().map(str::to_owned),
+        content_size: inscription.content_size(),
+        content: content.clone(),
+        sha256,
+        page: page.clone(),
+      });
+
+      collection.push((inscription_id, content));
+
+      fs::write(
+        self.output.join(&page),
+        ExportPageHtml::new(
+          InscriptionHtml::new(
+            &Explorer::default(),
+            inscription_id,
+            inscription_number,
+            inscription,
+            satpoint,
+            burned,
+          )
+          .inline(),
+          "../",
+        )
+        .to_string(),
+      )?;
+
+      println!("{inscription_id}\t{page}");
+    }
+
+    fs::write(
+      self.output.join("index.html"),
+      ExportPageHtml::new(
+        CollectionHtml {
+          inscriptions: collection,
+        },
+        "",
+      )
+      .to_string(),
+    )?;
+
+    fs::write(
+      self.output.join("index.json"),
+      serde_json::to_string_pretty(&IndexJson {
+        inscriptions: entries,
+      })?,
+    )?;
+
+    Ok(())
+  }
+
+  fn inscription_ids(&self) -> Result<Vec<InscriptionId>> {
+    let ids = fs::read_to_string(&self.inscriptions)
+      .with_context(|| format!("failed to read `{}`", self.inscriptions.display()))?;
+
+    let mut seen = BTreeSet::new();
+    let mut inscription_ids = Vec::new();
+
+    for (i, line) in ids.lines().enumerate() {
+      let line = line.trim();
+
+      if line.is_empty() {
+        continue;
+      }
+
+      let inscription_id = line
+        .parse::<InscriptionId>()
+        .with_context(|| format!("invalid inscription ID on line {}: `{line}`", i + 1))?;
+
+      if !seen.insert(inscription_id) {
+        bail!(
+          "duplicate inscription ID on line {}: {inscription_id}",
+          i + 1
+        );
+      }
+
+      inscription_ids.push(inscription_id);
+    }
+
+    if inscription_ids.is_empty() {
+      bail!(
+        "`{}` contains no inscription IDs",
+        self.inscriptions.display()
+      );
+    }
+
+    Ok(inscription_ids)
+  }
+}
+
+fn extension(content_type: Option<&str>) -> &'static str {
+  match content_type {
+    Some("text/plain;charset=utf-8") => "txt",
+    Some("image/png") => "png",
+    _ => "bin",
+  }
+}
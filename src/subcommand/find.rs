@@ -4,14 +4,55 @@ use super::*;
 pub(crate) struct Find {
   #[clap(help = "Find output and offset of <SAT>.")]
   sat: Sat,
+  #[clap(
+    long,
+    help = "Find first sat at or after <SAT> with rarity of at least <NEXT_RARITY>."
+  )]
+  next_rarity: Option<Rarity>,
+  #[clap(
+    long,
+    conflicts_with = "next-rarity",
+    help = "Find output and offset of <SAT> as of block <AT_HEIGHT> instead of the index tip. Requires index created with `--index-sat-history`."
+  )]
+  at_height: Option<Height>,
 }
 
 impl Find {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let index = Index::open(&options)?;
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let index = settings.index()?;
 
     index.update()?;
 
+    if let Some(rarity) = self.next_rarity {
+      return match index.find_next_rare(self.sat, rarity)? {
+        Some((sat, satpoint)) => {
+          println!("{sat}\t{satpoint}");
+          Ok(())
+        }
+        None => Err(anyhow!(
+          "no {rarity} or rarer sat at or after {} as of index height",
+          self.sat
+        )),
+      };
+    }
+
+    if let Some(height) = self.at_height {
+      if self.sat.is_common() {
+        bail!(
+          "sat {} is common, and history is only recorded for non-common sats",
+          self.sat
+        );
+      }
+
+      return match index.sat_history_at_height(self.sat, height)?.last() {
+        Some((_height, satpoint)) => {
+          println!("{satpoint}");
+          Ok(())
+        }
+        None => Err(anyhow!("sat has not been mined as of height {height}")),
+      };
+    }
+
     match index.find(self.sat.0)? {
       Some(satpoint) => {
         println!("{satpoint}");
@@ -1,9 +1,96 @@
 use super::*;
 
-pub(crate) fn run(options: Options) -> Result {
-  let index = Index::open(&options)?;
+#[derive(Debug, Parser)]
+pub(crate) struct Index {
+  #[clap(flatten)]
+  webhooks: WebhookOptions,
+  #[clap(subcommand)]
+  subcommand: Option<IndexSubcommand>,
+}
+
+#[derive(Debug, Parser)]
+enum IndexSubcommand {
+  #[clap(about = "Time indexing blocks into a throwaway copy of the index")]
+  Benchmark(Benchmark),
+  #[clap(about = "Rewrite the index into a fresh file, reclaiming space freed by deletes")]
+  Compact(Compact),
+}
+
+#[derive(Debug, Parser)]
+struct Benchmark {
+  #[clap(long, help = "Index <BLOCKS> blocks.")]
+  blocks: u64,
+  #[clap(
+    long,
+    help = "Start at <START_HEIGHT> in a fresh database instead of continuing from the index tip. Not supported when indexing sat ranges."
+  )]
+  start_height: Option<u64>,
+}
+
+#[derive(Debug, Parser)]
+struct Compact {
+  #[clap(
+    long,
+    help = "Write the compacted index to <OUTPUT> instead of replacing the index."
+  )]
+  output: Option<PathBuf>,
+}
+
+impl Index {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    match self.subcommand {
+      Some(IndexSubcommand::Benchmark(benchmark)) => return benchmark.run(settings),
+      Some(IndexSubcommand::Compact(compact)) => return compact.run(settings),
+      None => {}
+    }
+
+    let index = settings.index()?;
+
+    let webhooks = self.webhooks.spawn(&settings.options)?;
+
+    if let Some(webhooks) = &webhooks {
+      index.set_webhooks(webhooks.clone())?;
+    }
+
+    index.update()?;
+
+    if let Some(webhooks) = webhooks {
+      webhooks.flush()?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Benchmark {
+  fn run(self, settings: Settings) -> Result {
+    let report = crate::index::Index::benchmark(
+      &settings.options,
+      settings.rpc()?,
+      self.start_height,
+      self.blocks,
+    )?;
+
+    serde_json::to_writer(io::stdout(), &report)?;
+
+    Ok(())
+  }
+}
+
+impl Compact {
+  fn run(self, settings: Settings) -> Result {
+    let path = crate::index::Index::database_path(&settings.options)?;
+
+    let destination = match self.output {
+      Some(output) if output.exists() => bail!("`{}` already exists", output.display()),
+      Some(output) => output,
+      None => path.clone(),
+    };
+
+    let compaction = crate::index::Index::compact(&path, &destination)?;
 
-  index.update()?;
+    serde_json::to_writer(io::stdout(), &compaction)?;
 
-  Ok(())
+    Ok(())
+  }
 }
@@ -7,8 +7,8 @@ pub(crate) struct Info {
 }
 
 impl Info {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let index = Index::open(&options)?;
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let index = settings.index()?;
     index.update()?;
     let info = index.info()?;
 
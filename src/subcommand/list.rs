@@ -4,17 +4,26 @@ use super::*;
 pub(crate) struct List {
   #[clap(help = "List sats in <OUTPOINT>.")]
   outpoint: OutPoint,
+  #[clap(
+    long,
+    help = "Only list ranges starting with a sat of at least <RARITY>."
+  )]
+  rarity: Option<Rarity>,
 }
 
 impl List {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let index = Index::open(&options)?;
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let index = settings.index()?;
 
     index.update()?;
 
     match index.list(self.outpoint)? {
       Some(crate::index::List::Unspent(ranges)) => {
         for (output, start, size, rarity, name) in list(self.outpoint, ranges) {
+          if self.rarity.is_some_and(|min| rarity < min) {
+            continue;
+          }
+
           println!("{output}\t{start}\t{size}\t{rarity}\t{name}");
         }
 
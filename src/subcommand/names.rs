@@ -0,0 +1,48 @@
+use super::*;
+
+pub(crate) const DEFAULT_LIMIT: usize = 100;
+pub(crate) const MAX_LIMIT: usize = 1000;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Names {
+  #[clap(long, help = "List sats whose names start with <PREFIX>.")]
+  prefix: String,
+  #[clap(long, default_value_t = DEFAULT_LIMIT, help = "List at most <LIMIT> sats.")]
+  limit: usize,
+}
+
+impl Names {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let sats = sats(&self.prefix, self.limit)?;
+
+    let mut satpoints = if settings
+      .options
+      .index_options()
+      .contains(&IndexOption::SatRanges)
+    {
+      let index = settings.index()?;
+      index.update()?;
+      index.find_many(&sats)?
+    } else {
+      BTreeMap::new()
+    };
+
+    for sat in sats {
+      match satpoints.remove(&sat) {
+        Some(satpoint) => println!("{sat}\t{}\t{}\t{satpoint}", sat.name(), sat.rarity()),
+        None => println!("{sat}\t{}\t{}", sat.name(), sat.rarity()),
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// The first `limit` sats whose names start with `prefix`.
+pub(crate) fn sats(prefix: &str, limit: usize) -> Result<Vec<Sat>> {
+  if limit > MAX_LIMIT {
+    bail!("limit may not exceed {MAX_LIMIT}");
+  }
+
+  Ok(Sat::with_name_prefix(prefix)?.take(limit).collect())
+}
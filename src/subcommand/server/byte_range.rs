@@ -0,0 +1,124 @@
+use super::*;
+
+/// How to answer a request for content of a given length, according to its
+/// `Range` header.
+#[derive(Debug, PartialEq)]
+pub(crate) enum ByteRange {
+  /// Serve all of the content, because there is no `Range` header, or it is
+  /// malformed or uses a unit other than bytes, and so must be ignored.
+  Full,
+  /// Serve only these bytes of the content.
+  Partial(Range<usize>),
+  /// The range lies past the end of the content, or is one of several, which
+  /// aren't supported.
+  Unsatisfiable,
+}
+
+impl ByteRange {
+  pub(crate) fn new(headers: &HeaderMap, len: usize) -> Self {
+    let Some(ranges) = headers
+      .get(header::RANGE)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.trim().strip_prefix("bytes="))
+    else {
+      return Self::Full;
+    };
+
+    if ranges.contains(',') {
+      return Self::Unsatisfiable;
+    }
+
+    let Some((start, end)) = ranges.split_once('-') else {
+      return Self::Full;
+    };
+
+    let (start, end) = (start.trim(), end.trim());
+
+    // A range without a start is a suffix of `end` bytes.
+    if start.is_empty() {
+      return match end.parse::<usize>() {
+        Ok(0) => Self::Unsatisfiable,
+        Ok(_) if len == 0 => Self::Unsatisfiable,
+        Ok(suffix) => Self::Partial(len.saturating_sub(suffix)..len),
+        Err(_) => Self::Full,
+      };
+    }
+
+    let Ok(start) = start.parse::<usize>() else {
+      return Self::Full;
+    };
+
+    let end = if end.is_empty() {
+      len
+    } else {
+      match end.parse::<usize>() {
+        Ok(end) if end >= start => end.saturating_add(1).min(len),
+        _ => return Self::Full,
+      }
+    };
+
+    if start >= len {
+      return Self::Unsatisfiable;
+    }
+
+    Self::Partial(start..end)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn byte_range(range: &'static str, len: usize) -> ByteRange {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::RANGE, HeaderValue::from_static(range));
+    ByteRange::new(&headers, len)
+  }
+
+  #[test]
+  fn no_range() {
+    assert_eq!(ByteRange::new(&HeaderMap::new(), 10), ByteRange::Full);
+  }
+
+  #[test]
+  fn bounded() {
+    assert_eq!(byte_range("bytes=0-0", 10), ByteRange::Partial(0..1));
+    assert_eq!(byte_range("bytes=2-5", 10), ByteRange::Partial(2..6));
+    assert_eq!(byte_range("bytes=2-100", 10), ByteRange::Partial(2..10));
+  }
+
+  #[test]
+  fn unbounded() {
+    assert_eq!(byte_range("bytes=0-", 10), ByteRange::Partial(0..10));
+    assert_eq!(byte_range("bytes=9-", 10), ByteRange::Partial(9..10));
+  }
+
+  #[test]
+  fn suffix() {
+    assert_eq!(byte_range("bytes=-3", 10), ByteRange::Partial(7..10));
+    assert_eq!(byte_range("bytes=-100", 10), ByteRange::Partial(0..10));
+    assert_eq!(byte_range("bytes=-0", 10), ByteRange::Unsatisfiable);
+    assert_eq!(byte_range("bytes=-1", 0), ByteRange::Unsatisfiable);
+  }
+
+  #[test]
+  fn unsatisfiable() {
+    assert_eq!(byte_range("bytes=10-", 10), ByteRange::Unsatisfiable);
+    assert_eq!(byte_range("bytes=10-20", 10), ByteRange::Unsatisfiable);
+    assert_eq!(byte_range("bytes=0-", 0), ByteRange::Unsatisfiable);
+  }
+
+  #[test]
+  fn multiple_ranges_are_unsatisfiable() {
+    assert_eq!(byte_range("bytes=0-1,3-4", 10), ByteRange::Unsatisfiable);
+  }
+
+  #[test]
+  fn invalid_ranges_are_ignored() {
+    assert_eq!(byte_range("items=0-1", 10), ByteRange::Full);
+    assert_eq!(byte_range("bytes=5-2", 10), ByteRange::Full);
+    assert_eq!(byte_range("bytes=a-b", 10), ByteRange::Full);
+    assert_eq!(byte_range("bytes=3", 10), ByteRange::Full);
+    assert_eq!(byte_range("bytes=-", 10), ByteRange::Full);
+  }
+}
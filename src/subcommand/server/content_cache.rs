@@ -0,0 +1,134 @@
+use {super::*, axum::body::Bytes, lru::LruCache, std::num::NonZeroUsize};
+
+/// Recently served inscription content. Responses share the cached `Bytes`,
+/// so that popular inscriptions are read from the index and allocated once,
+/// rather than once per request. Least recently used entries are evicted
+/// first.
+pub(crate) struct ContentCache(Mutex<Cache>);
+
+/// An `LruCache` bounded by the total bytes of content, as well as by the
+/// number of entries.
+struct Cache {
+  entries: LruCache<InscriptionId, (String, Bytes)>,
+  capacity: usize,
+  size: usize,
+}
+
+impl Cache {
+  fn new(capacity: usize, max_entries: usize) -> Self {
+    Self {
+      entries: LruCache::new(NonZeroUsize::new(max_entries).unwrap()),
+      capacity,
+      size: 0,
+    }
+  }
+
+  fn get(&mut self, inscription_id: InscriptionId) -> Option<(String, Bytes)> {
+    self.entries.get(&inscription_id).cloned()
+  }
+
+  fn insert(&mut self, inscription_id: InscriptionId, content_type: String, content: Bytes) {
+    if content.len() > self.capacity || self.entries.contains(&inscription_id) {
+      return;
+    }
+
+    self.size += content.len();
+
+    if let Some((_, (_, evicted))) = self.entries.push(inscription_id, (content_type, content)) {
+      self.size -= evicted.len();
+    }
+
+    while self.size > self.capacity {
+      let Some((_, (_, evicted))) = self.entries.pop_lru() else {
+        break;
+      };
+
+      self.size -= evicted.len();
+    }
+  }
+}
+
+impl ContentCache {
+  /// Total bytes of content to keep.
+  const CAPACITY: usize = 64 * 1024 * 1024;
+  const MAX_ENTRIES: usize = 1024;
+
+  pub(crate) fn new() -> Self {
+    Self(Mutex::new(Cache::new(Self::CAPACITY, Self::MAX_ENTRIES)))
+  }
+
+  pub(crate) fn get(&self, inscription_id: InscriptionId) -> Option<(String, Bytes)> {
+    self.0.lock().unwrap().get(inscription_id)
+  }
+
+  pub(crate) fn insert(&self, inscription_id: InscriptionId, content_type: String, content: Bytes) {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .insert(inscription_id, content_type, content);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn insert(cache: &mut Cache, n: u64, size: usize) {
+    cache.insert(
+      outpoint(n).txid,
+      "text/plain".into(),
+      Bytes::from(vec![0; size]),
+    );
+  }
+
+  #[test]
+  fn entries_are_shared() {
+    let cache = ContentCache::new();
+    cache.insert(outpoint(1).txid, "text/plain".into(), Bytes::from("foo"));
+    let (content_type, content) = cache.get(outpoint(1).txid).unwrap();
+    assert_eq!(content_type, "text/plain");
+    assert_eq!(
+      content.as_ptr(),
+      cache.get(outpoint(1).txid).unwrap().1.as_ptr()
+    );
+  }
+
+  #[test]
+  fn least_recently_used_entries_are_evicted_by_size() {
+    let mut cache = Cache::new(10, 3);
+
+    insert(&mut cache, 1, 4);
+    insert(&mut cache, 2, 4);
+    assert!(cache.get(outpoint(1).txid).is_some());
+
+    insert(&mut cache, 3, 4);
+    assert!(cache.get(outpoint(1).txid).is_some());
+    assert!(cache.get(outpoint(2).txid).is_none());
+    assert!(cache.get(outpoint(3).txid).is_some());
+    assert_eq!(cache.size, 8);
+  }
+
+  #[test]
+  fn least_recently_used_entries_are_evicted_by_count() {
+    let mut cache = Cache::new(10, 3);
+
+    for n in 1..=4 {
+      insert(&mut cache, n, 1);
+    }
+
+    assert!(cache.get(outpoint(1).txid).is_none());
+    assert_eq!(cache.entries.len(), 3);
+    assert_eq!(cache.size, 3);
+  }
+
+  #[test]
+  fn content_larger_than_capacity_is_not_cached() {
+    let mut cache = Cache::new(10, 3);
+    insert(&mut cache, 1, 4);
+    insert(&mut cache, 2, 11);
+    assert!(cache.get(outpoint(1).txid).is_some());
+    assert!(cache.get(outpoint(2).txid).is_none());
+    assert_eq!(cache.size, 4);
+  }
+}
@@ -0,0 +1,95 @@
+use {super::*, lru::LruCache, std::num::NonZeroUsize};
+
+/// Recently computed content digests. Content never changes, so a digest
+/// can be served until it is evicted, least recently used first, and
+/// verification sweeps over large inscriptions don't hash them again.
+pub(crate) struct DigestCache(Mutex<LruCache<InscriptionId, InscriptionDigestJson>>);
+
+impl DigestCache {
+  const MAX_ENTRIES: usize = 4096;
+
+  pub(crate) fn new() -> Self {
+    Self(Mutex::new(LruCache::new(
+      NonZeroUsize::new(Self::MAX_ENTRIES).unwrap(),
+    )))
+  }
+
+  pub(crate) fn get(&self, inscription_id: InscriptionId) -> Option<InscriptionDigestJson> {
+    self.0.lock().unwrap().get(&inscription_id).cloned()
+  }
+
+  /// The digest of `inscription`'s content, hashed without holding the lock
+  /// if it isn't cached.
+  pub(crate) fn digest(
+    &self,
+    inscription_id: InscriptionId,
+    inscription: &Inscription,
+  ) -> InscriptionDigestJson {
+    if let Some(digest) = self.get(inscription_id) {
+      return digest;
+    }
+
+    let digest = InscriptionDigestJson::new(inscription_id, inscription);
+
+    self.0.lock().unwrap().put(inscription_id, digest.clone());
+
+    digest
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, bitcoin::hashes::sha256};
+
+  #[test]
+  fn digests_are_cached() {
+    let cache = DigestCache::new();
+
+    assert_eq!(cache.get(outpoint(1).txid), None);
+
+    let digest = cache.digest(
+      outpoint(1).txid,
+      &inscription("text/plain;charset=utf-8", "hello"),
+    );
+
+    assert_eq!(
+      digest.sha256.to_string(),
+      "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+    assert_eq!(digest.content_length, 5);
+    assert_eq!(
+      digest.content_type.as_deref(),
+      Some("text/plain;charset=utf-8")
+    );
+
+    assert_eq!(cache.get(outpoint(1).txid), Some(digest));
+  }
+
+  #[test]
+  fn empty_inscriptions_have_digest_of_no_bytes() {
+    let digest = DigestCache::new().digest(outpoint(1).txid, &Inscription::new(None, None));
+
+    assert_eq!(digest.sha256, sha256::Hash::hash(&[]));
+    assert_eq!(digest.content_length, 0);
+    assert_eq!(digest.content_type, None);
+  }
+
+  #[test]
+  fn least_recently_used_digests_are_evicted() {
+    let cache = DigestCache::new();
+    let inscription = inscription("text/plain;charset=utf-8", "hello");
+    let id = |n: usize| InscriptionId::hash(&n.to_le_bytes());
+
+    for n in 0..DigestCache::MAX_ENTRIES {
+      cache.digest(id(n), &inscription);
+    }
+
+    assert!(cache.get(id(0)).is_some());
+
+    cache.digest(id(DigestCache::MAX_ENTRIES), &inscription);
+
+    assert!(cache.get(id(0)).is_some());
+    assert!(cache.get(id(1)).is_none());
+    assert_eq!(cache.0.lock().unwrap().len(), DigestCache::MAX_ENTRIES);
+  }
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Placeholder {
+  Address,
+  Block,
+  Txid,
+}
+
+impl Placeholder {
+  const ALL: [Self; 3] = [Self::Address, Self::Block, Self::Txid];
+
+  fn name(self) -> &'static str {
+    match self {
+      Self::Address => "address",
+      Self::Block => "block",
+      Self::Txid => "txid",
+    }
+  }
+}
+
+impl Display for Placeholder {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{{{}}}", self.name())
+  }
+}
+
+/// URL template for an external block explorer, containing exactly one of
+/// `{address}`, `{block}`, or `{txid}`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ExplorerUrl {
+  placeholder: Placeholder,
+  template: String,
+}
+
+impl FromStr for ExplorerUrl {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    if !s.starts_with("https://") && !s.starts_with("http://") {
+      bail!("explorer URL must start with `https://` or `http://`: {s}");
+    }
+
+    if let Some(c) = s
+      .chars()
+      .find(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>'))
+    {
+      bail!("explorer URL may not contain {c:?}: {s}");
+    }
+
+    let mut placeholders = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('{') {
+      let end = rest[start..]
+        .find('}')
+        .ok_or_else(|| anyhow!("unclosed placeholder in explorer URL: {s}"))?;
+      let name = &rest[start + 1..start + end];
+      placeholders.push(
+        Placeholder::ALL
+          .into_iter()
+          .find(|placeholder| placeholder.name() == name)
+          .ok_or_else(|| anyhow!("unknown placeholder `{{{name}}}` in explorer URL: {s}"))?,
+      );
+      rest = &rest[start + end + 1..];
+    }
+
+    match placeholders.as_slice() {
+      [placeholder] => Ok(Self {
+        placeholder: *placeholder,
+        template: s.into(),
+      }),
+      _ => bail!(
+        "explorer URL must contain exactly one of `{{address}}`, `{{block}}`, or `{{txid}}`: {s}"
+      ),
+    }
+  }
+}
+
+impl Display for ExplorerUrl {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.template)
+  }
+}
+
+/// Outbound links to an external block explorer. Empty unless configured
+/// with `--explorer-url`, so by default no external links are rendered.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Explorer {
+  urls: BTreeMap<Placeholder, String>,
+}
+
+impl Explorer {
+  pub(crate) fn new(urls: Vec<ExplorerUrl>) -> Result<Self> {
+    let mut explorer = Self::default();
+
+    for url in urls {
+      if explorer
+        .urls
+        .insert(url.placeholder, url.template)
+        .is_some()
+      {
+        bail!(
+          "more than one explorer URL contains `{}` placeholder",
+          url.placeholder
+        );
+      }
+    }
+
+    Ok(explorer)
+  }
+
+  fn url(&self, placeholder: Placeholder, value: impl Display) -> Option<String> {
+    self
+      .urls
+      .get(&placeholder)
+      .map(|template| template.replace(&placeholder.to_string(), &value.to_string()))
+  }
+
+  pub(crate) fn address(&self, address: &Address) -> Option<String> {
+    self.url(Placeholder::Address, address)
+  }
+
+  pub(crate) fn block(&self, hash: BlockHash) -> Option<String> {
+    self.url(Placeholder::Block, hash)
+  }
+
+  pub(crate) fn transaction(&self, txid: Txid) -> Option<String> {
+    self.url(Placeholder::Txid, txid)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn explorer(urls: &[&str]) -> Explorer {
+    Explorer::new(urls.iter().map(|url| url.parse().unwrap()).collect()).unwrap()
+  }
+
+  #[test]
+  fn unconfigured() {
+    let explorer = Explorer::default();
+    assert_eq!(explorer.transaction(outpoint(1).txid), None);
+    assert_eq!(explorer.block(BlockHash::all_zeros()), None);
+    assert_eq!(explorer.address(&recipient()), None);
+  }
+
+  #[test]
+  fn substitution() {
+    let explorer = explorer(&[
+      "https://mempool.space/tx/{txid}",
+      "https://mempool.space/block/{block}",
+      "https://mempool.space/address/{address}",
+    ]);
+
+    assert_eq!(
+      explorer.transaction(outpoint(1).txid).unwrap(),
+      format!("https://mempool.space/tx/{}", outpoint(1).txid)
+    );
+    assert_eq!(
+      explorer.block(BlockHash::all_zeros()).unwrap(),
+      format!("https://mempool.space/block/{}", BlockHash::all_zeros())
+    );
+    assert_eq!(
+      explorer.address(&recipient()).unwrap(),
+      format!("https://mempool.space/address/{}", recipient())
+    );
+  }
+
+  #[test]
+  fn only_configured_placeholders_are_linked() {
+    let explorer = explorer(&["http://localhost/?tx={txid}&network=signet"]);
+    assert_eq!(
+      explorer.transaction(outpoint(1).txid).unwrap(),
+      format!("http://localhost/?tx={}&network=signet", outpoint(1).txid)
+    );
+    assert_eq!(explorer.address(&recipient()), None);
+  }
+
+  #[test]
+  fn invalid_urls() {
+    #[track_caller]
+    fn case(url: &str, expected: &str) {
+      assert_eq!(
+        url.parse::<ExplorerUrl>().unwrap_err().to_string(),
+        expected
+      );
+    }
+
+    case(
+      "mempool.space/tx/{txid}",
+      "explorer URL must start with `https://` or `http://`: mempool.space/tx/{txid}",
+    );
+    case(
+      "https://mempool.space/tx/ {txid}",
+      "explorer URL may not contain ' ': https://mempool.space/tx/ {txid}",
+    );
+    case(
+      "https://mempool.space/tx/{txid",
+      "unclosed placeholder in explorer URL: https://mempool.space/tx/{txid",
+    );
+    case(
+      "https://mempool.space/tx/{hash}",
+      "unknown placeholder `{hash}` in explorer URL: https://mempool.space/tx/{hash}",
+    );
+    case(
+      "https://mempool.space/",
+      "explorer URL must contain exactly one of `{address}`, `{block}`, or `{txid}`: https://mempool.space/",
+    );
+    case(
+      "https://mempool.space/{block}/{txid}",
+      "explorer URL must contain exactly one of `{address}`, `{block}`, or `{txid}`: https://mempool.space/{block}/{txid}",
+    );
+  }
+
+  #[test]
+  fn duplicate_placeholders() {
+    assert_eq!(
+      Explorer::new(vec![
+        "https://a.com/tx/{txid}".parse().unwrap(),
+        "https://b.com/tx/{txid}".parse().unwrap(),
+      ])
+      .unwrap_err()
+      .to_string(),
+      "more than one explorer URL contains `{txid}` placeholder"
+    );
+  }
+}
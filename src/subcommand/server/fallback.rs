@@ -0,0 +1,222 @@
+use {super::*, axum::http::Uri, lru::LruCache};
+
+/// Another ord instance, asked for inscriptions that this one's index misses
+/// while it is behind the chain, so that a new instance can serve them while
+/// its index catches up. Proxying only happens on a local miss, so cached
+/// responses stop being served once the index has caught up.
+pub(crate) struct Fallback {
+  cache: Mutex<Cache>,
+  client: reqwest::Client,
+  url: Url,
+}
+
+#[derive(Clone)]
+struct Proxied {
+  body: Vec<u8>,
+  headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// Least recently used entries are evicted first, until the bodies of those
+/// left fit in the cache's capacity in bytes.
+struct Cache {
+  entries: LruCache<String, Proxied>,
+  size: usize,
+}
+
+impl Cache {
+  fn new() -> Self {
+    Self {
+      entries: LruCache::unbounded(),
+      size: 0,
+    }
+  }
+
+  fn get(&mut self, key: &str) -> Option<Proxied> {
+    self.entries.get(key).cloned()
+  }
+
+  fn insert(&mut self, key: String, proxied: Proxied, capacity: usize) {
+    self.size += proxied.body.len();
+
+    if let Some((_, replaced)) = self.entries.push(key, proxied) {
+      self.size -= replaced.body.len();
+    }
+
+    while self.size > capacity {
+      let Some((_, evicted)) = self.entries.pop_lru() else {
+        break;
+      };
+
+      self.size -= evicted.body.len();
+    }
+  }
+}
+
+impl Fallback {
+  /// Total size of cached bodies, in bytes.
+  const CACHE_CAPACITY: usize = 16 * 1024 * 1024;
+  /// Larger bodies are proxied, but not cached.
+  const MAX_CACHED_BODY: usize = 1024 * 1024;
+  const TIMEOUT: Duration = Duration::from_secs(3);
+
+  /// Headers of the fallback's response which are passed on.
+  const HEADERS: [HeaderName; 3] = [
+    header::CONTENT_TYPE,
+    header::VARY,
+    header::X_CONTENT_TYPE_OPTIONS,
+  ];
+
+  pub(crate) fn new(url: Url) -> Result<Self> {
+    Ok(Self {
+      cache: Mutex::new(Cache::new()),
+      client: reqwest::Client::builder().timeout(Self::TIMEOUT).build()?,
+      url,
+    })
+  }
+
+  /// The fallback's response to `uri`, marked with `X-Ord-Proxied: true`, or
+  /// `None` if it failed or was anything but a success.
+  pub(crate) async fn proxy(&self, uri: &Uri, headers: &HeaderMap) -> Option<Response> {
+    let path = uri.path_and_query()?.as_str();
+
+    let accept = headers.get(header::ACCEPT);
+
+    // Inscription pages are negotiated on `Accept`, so it is part of the key.
+    let key = format!(
+      "{path} {}",
+      accept
+        .and_then(|accept| accept.to_str().ok())
+        .unwrap_or_default()
+    );
+
+    let cached = self.cache.lock().unwrap().get(&key);
+
+    let proxied = match cached {
+      Some(proxied) => proxied,
+      None => {
+        let proxied = self.fetch(path, accept).await?;
+
+        if proxied.body.len() <= Self::MAX_CACHED_BODY {
+          self
+            .cache
+            .lock()
+            .unwrap()
+            .insert(key, proxied.clone(), Self::CACHE_CAPACITY);
+        }
+
+        proxied
+      }
+    };
+
+    let mut response = proxied.body.into_response();
+
+    for (name, value) in proxied.headers {
+      response.headers_mut().insert(name, value);
+    }
+
+    response.headers_mut().insert(
+      HeaderName::from_static("x-ord-proxied"),
+      HeaderValue::from_static("true"),
+    );
+
+    Some(response)
+  }
+
+  async fn fetch(&self, path: &str, accept: Option<&HeaderValue>) -> Option<Proxied> {
+    let url = self.url.join(path.trim_start_matches('/')).ok()?;
+
+    let mut request = self.client.get(url.clone());
+
+    if let Some(accept) = accept {
+      request = request.header(header::ACCEPT, accept);
+    }
+
+    let response = match request.send().await {
+      Ok(response) => response,
+      Err(err) => {
+        log::warn!("Failed to fetch {url} from fallback: {err}");
+        return None;
+      }
+    };
+
+    if response.status() != StatusCode::OK {
+      return None;
+    }
+
+    let headers = Self::HEADERS
+      .into_iter()
+      .filter_map(|name| {
+        let value = response.headers().get(&name)?.clone();
+        Some((name, value))
+      })
+      .collect();
+
+    match response.bytes().await {
+      Ok(body) => Some(Proxied {
+        body: body.to_vec(),
+        headers,
+      }),
+      Err(err) => {
+        log::warn!("Failed to read {url} from fallback: {err}");
+        None
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn proxied(body: &str) -> Proxied {
+    Proxied {
+      body: body.into(),
+      headers: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn cache_evicts_least_recently_used() {
+    let mut cache = Cache::new();
+
+    cache.insert("a".into(), proxied("a"), 2);
+    cache.insert("b".into(), proxied("b"), 2);
+
+    assert!(cache.get("a").is_some());
+
+    cache.insert("c".into(), proxied("c"), 2);
+
+    assert_eq!(cache.get("a").unwrap().body, b"a");
+    assert!(cache.get("b").is_none());
+    assert_eq!(cache.get("c").unwrap().body, b"c");
+
+    cache.insert("a".into(), proxied("n"), 2);
+    cache.insert("d".into(), proxied("d"), 2);
+
+    assert_eq!(cache.get("a").unwrap().body, b"n");
+    assert!(cache.get("c").is_none());
+    assert_eq!(cache.entries.len(), 2);
+    assert_eq!(cache.size, 2);
+  }
+
+  #[test]
+  fn cache_is_bounded_by_body_size() {
+    let mut cache = Cache::new();
+
+    cache.insert("a".into(), proxied("aa"), 4);
+    cache.insert("b".into(), proxied("bb"), 4);
+    cache.insert("c".into(), proxied("ccc"), 4);
+
+    assert!(cache.get("a").is_none());
+    assert!(cache.get("b").is_none());
+    assert_eq!(cache.get("c").unwrap().body, b"ccc");
+    assert_eq!(cache.size, 3);
+
+    cache.insert("c".into(), proxied("c"), 4);
+    assert_eq!(cache.size, 1);
+
+    cache.insert("d".into(), proxied("ddddd"), 4);
+    assert!(cache.entries.is_empty());
+    assert_eq!(cache.size, 0);
+  }
+}
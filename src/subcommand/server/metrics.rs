@@ -0,0 +1,118 @@
+use {
+  super::*,
+  crate::index::Statistic,
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+  std::fmt::Write,
+};
+
+/// Counts of requests served, by route and status, for `/metrics`.
+#[derive(Default)]
+pub(crate) struct Metrics {
+  requests: Mutex<BTreeMap<(String, u16), u64>>,
+}
+
+impl Metrics {
+  /// Middleware which counts requests. Requests that matched no route are
+  /// counted together, since their paths are unbounded.
+  pub(crate) async fn record<B>(
+    Extension(metrics): Extension<Arc<Metrics>>,
+    request: Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let route = request
+      .extensions()
+      .get::<MatchedPath>()
+      .map(|path| path.as_str().to_owned())
+      .unwrap_or_else(|| "unmatched".into());
+
+    let response = next.run(request).await;
+
+    *metrics
+      .requests
+      .lock()
+      .unwrap()
+      .entry((route, response.status().as_u16()))
+      .or_default() += 1;
+
+    response
+  }
+
+  /// Metrics in the Prometheus text format. Only reads from the index, so
+  /// scraping doesn't hold up indexing.
+  pub(crate) fn render(&self, index: &Index) -> Result<String> {
+    let mut metrics = String::new();
+
+    let mut gauge = |name: &str, help: &str, value: u64| {
+      writeln!(metrics, "# HELP ord_{name} {help}").unwrap();
+      writeln!(metrics, "# TYPE ord_{name} gauge").unwrap();
+      writeln!(metrics, "ord_{name} {value}").unwrap();
+    };
+
+    if let Some(height) = index.height()? {
+      gauge(
+        "index_height",
+        "Height of the latest indexed block.",
+        height.n(),
+      );
+    }
+
+    match index
+      .rpc()
+      .get()
+      .and_then(|client| Ok(client.get_block_count()?))
+    {
+      Ok(height) => gauge("node_height", "Height of the node's latest block.", height),
+      Err(err) => log::warn!("Failed to get block count from node: {err}"),
+    }
+
+    gauge(
+      "sat_ranges",
+      "Sat ranges written to the index.",
+      index.statistic(Statistic::SatRanges)?,
+    );
+    gauge(
+      "outputs_traversed",
+      "Outputs traversed while indexing.",
+      index.statistic(Statistic::OutputsTraversed)?,
+    );
+    gauge(
+      "commits",
+      "Index write transactions committed.",
+      index.statistic(Statistic::Commits)?,
+    );
+
+    writeln!(
+      metrics,
+      "# HELP ord_http_requests_total HTTP requests served, by route and status."
+    )?;
+    writeln!(metrics, "# TYPE ord_http_requests_total counter")?;
+
+    for ((route, status), count) in self.requests.lock().unwrap().iter() {
+      writeln!(
+        metrics,
+        "ord_http_requests_total{{route=\"{}\",status=\"{status}\"}} {count}",
+        Self::escape(route),
+      )?;
+    }
+
+    Ok(metrics)
+  }
+
+  fn escape(label: &str) -> String {
+    label
+      .replace('\\', r"\\")
+      .replace('"', r#"\""#)
+      .replace('\n', r"\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn escape() {
+    assert_eq!(Metrics::escape("/tx/:txid"), "/tx/:txid");
+    assert_eq!(Metrics::escape("a\\b\"c\nd"), r#"a\\b\"c\nd"#);
+  }
+}
@@ -0,0 +1,371 @@
+use {
+  super::*,
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+  std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+  },
+};
+
+/// Per-client token buckets, each holding up to a minute's worth of requests
+/// and refilling continuously at the configured rate. IPv6 clients are
+/// limited by /64, since that is usually what a single host is assigned.
+/// Clients without an address, which connect over a Unix socket and aren't
+/// identified by a proxy, share a bucket.
+pub(crate) struct RateLimiter {
+  behind_proxy: bool,
+  per_minute: NonZeroU32,
+  state: Mutex<State>,
+}
+
+struct State {
+  /// Keyed by client network, or `None` for the bucket shared by clients
+  /// without an address, and by clients which arrive while the map is full.
+  buckets: HashMap<Option<IpAddr>, Bucket>,
+  swept: Instant,
+}
+
+struct Bucket {
+  tokens: f64,
+  updated: Instant,
+}
+
+impl RateLimiter {
+  /// Routes which are cheap to serve, and so are never limited.
+  const EXEMPT: &[&str] = &[
+    "/favicon.ico",
+    "/healthz",
+    "/readyz",
+    "/static/*path",
+    "/status",
+  ];
+  /// A bucket refills completely within this long, after which it is no
+  /// different from a new one and can be dropped.
+  const REFILL: Duration = Duration::from_secs(60);
+  /// Most buckets kept at once, bounding memory used by many clients.
+  const MAX_BUCKETS: usize = 100_000;
+
+  pub(crate) fn new(per_minute: NonZeroU32, behind_proxy: bool) -> Self {
+    Self {
+      behind_proxy,
+      per_minute,
+      state: Mutex::new(State {
+        buckets: HashMap::new(),
+        swept: Instant::now(),
+      }),
+    }
+  }
+
+  pub(crate) async fn limit<B>(
+    Extension(rate_limiter): Extension<Option<Arc<RateLimiter>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    request: Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let Some(rate_limiter) = rate_limiter else {
+      return next.run(request).await;
+    };
+
+    if let Some(path) = request.extensions().get::<MatchedPath>() {
+      if Self::EXEMPT.contains(&path.as_str()) {
+        return next.run(request).await;
+      }
+    }
+
+    let client = Self::client(
+      request.headers(),
+      peer.map(|ConnectInfo(peer)| peer),
+      rate_limiter.behind_proxy,
+    );
+
+    match rate_limiter.acquire(client, Instant::now()) {
+      Ok(()) => next.run(request).await,
+      Err(retry_after) => (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(
+          header::RETRY_AFTER,
+          retry_after.as_secs().max(1).to_string(),
+        )],
+        "rate limit exceeded",
+      )
+        .into_response(),
+    }
+  }
+
+  /// The address of the client making a request. Behind a proxy, this is the
+  /// last address in `X-Forwarded-For`, since that is the one the proxy added,
+  /// and earlier ones may be forged. Otherwise it is the address of `peer`,
+  /// which is `None` for Unix socket connections.
+  pub(crate) fn client(
+    headers: &HeaderMap,
+    peer: Option<SocketAddr>,
+    behind_proxy: bool,
+  ) -> Option<IpAddr> {
+    if behind_proxy {
+      if let Some(ip) = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .last()
+        .and_then(|ip| ip.trim().parse().ok())
+      {
+        return Some(ip);
+      }
+    }
+
+    peer.map(|peer| peer.ip())
+  }
+
+  /// The network which `client` is limited as part of.
+  fn network(client: IpAddr) -> IpAddr {
+    match client {
+      IpAddr::V4(_) => client,
+      IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+        Some(ip) => IpAddr::V4(ip),
+        None => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & !(u128::from(u64::MAX)))),
+      },
+    }
+  }
+
+  /// Take a token from `client`'s bucket, or return how long until one will
+  /// be available.
+  fn acquire(&self, client: Option<IpAddr>, now: Instant) -> Result<(), Duration> {
+    let mut state = self.state.lock().unwrap();
+
+    if now.saturating_duration_since(state.swept) >= Self::REFILL {
+      state
+        .buckets
+        .retain(|_, bucket| now.saturating_duration_since(bucket.updated) < Self::REFILL);
+      state.swept = now;
+    }
+
+    let capacity = f64::from(self.per_minute.get());
+    let per_second = capacity / Self::REFILL.as_secs_f64();
+
+    let network = client.map(Self::network);
+
+    let key = if state.buckets.len() < Self::MAX_BUCKETS || state.buckets.contains_key(&network) {
+      network
+    } else {
+      None
+    };
+
+    let bucket = state.buckets.entry(key).or_insert(Bucket {
+      tokens: capacity,
+      updated: now,
+    });
+
+    bucket.tokens = (bucket.tokens
+      + now.saturating_duration_since(bucket.updated).as_secs_f64() * per_second)
+      .min(capacity);
+    bucket.updated = now;
+
+    if bucket.tokens >= 1.0 {
+      bucket.tokens -= 1.0;
+      Ok(())
+    } else {
+      Err(Duration::from_secs_f64(
+        ((1.0 - bucket.tokens) / per_second).ceil(),
+      ))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rate_limiter(per_minute: u32) -> RateLimiter {
+    RateLimiter::new(NonZeroU32::new(per_minute).unwrap(), false)
+  }
+
+  #[test]
+  fn requests_beyond_limit_are_rejected() {
+    let rate_limiter = rate_limiter(2);
+    let client = IpAddr::from([1, 1, 1, 1]);
+    let now = Instant::now();
+
+    assert_eq!(rate_limiter.acquire(Some(client), now), Ok(()));
+    assert_eq!(rate_limiter.acquire(Some(client), now), Ok(()));
+    assert_eq!(
+      rate_limiter.acquire(Some(client), now),
+      Err(Duration::from_secs(30))
+    );
+  }
+
+  #[test]
+  fn clients_are_limited_separately() {
+    let rate_limiter = rate_limiter(1);
+    let now = Instant::now();
+
+    assert_eq!(
+      rate_limiter.acquire(Some(IpAddr::from([1, 1, 1, 1])), now),
+      Ok(())
+    );
+    assert_eq!(
+      rate_limiter.acquire(Some(IpAddr::from([2, 2, 2, 2])), now),
+      Ok(())
+    );
+    assert!(rate_limiter
+      .acquire(Some(IpAddr::from([1, 1, 1, 1])), now)
+      .is_err());
+  }
+
+  #[test]
+  fn buckets_refill() {
+    let rate_limiter = rate_limiter(60);
+    let client = IpAddr::from([1, 1, 1, 1]);
+    let now = Instant::now();
+
+    for _ in 0..60 {
+      assert_eq!(rate_limiter.acquire(Some(client), now), Ok(()));
+    }
+
+    assert_eq!(
+      rate_limiter.acquire(Some(client), now),
+      Err(Duration::from_secs(1))
+    );
+    assert_eq!(
+      rate_limiter.acquire(Some(client), now + Duration::from_secs(1)),
+      Ok(())
+    );
+  }
+
+  #[test]
+  fn idle_buckets_are_evicted() {
+    let rate_limiter = rate_limiter(1);
+    let now = Instant::now();
+
+    rate_limiter
+      .acquire(Some(IpAddr::from([1, 1, 1, 1])), now)
+      .unwrap();
+    rate_limiter
+      .acquire(
+        Some(IpAddr::from([2, 2, 2, 2])),
+        now + Duration::from_secs(30),
+      )
+      .unwrap();
+
+    assert_eq!(rate_limiter.state.lock().unwrap().buckets.len(), 2);
+
+    rate_limiter
+      .acquire(
+        Some(IpAddr::from([3, 3, 3, 3])),
+        now + Duration::from_secs(60),
+      )
+      .unwrap();
+
+    let state = rate_limiter.state.lock().unwrap();
+    assert_eq!(state.buckets.len(), 2);
+    assert!(!state
+      .buckets
+      .contains_key(&Some(IpAddr::from([1, 1, 1, 1]))));
+  }
+
+  #[test]
+  fn ipv6_clients_are_limited_by_network() {
+    let rate_limiter = rate_limiter(1);
+    let now = Instant::now();
+
+    let client = |ip: &str| ip.parse::<IpAddr>().unwrap();
+
+    assert_eq!(
+      rate_limiter.acquire(Some(client("2001:db8::1")), now),
+      Ok(())
+    );
+    assert!(rate_limiter
+      .acquire(Some(client("2001:db8::ffff:ffff:ffff:ffff")), now)
+      .is_err());
+    assert_eq!(
+      rate_limiter.acquire(Some(client("2001:db8:0:1::1")), now),
+      Ok(())
+    );
+
+    assert_eq!(
+      rate_limiter.acquire(Some(client("::ffff:1.1.1.1")), now),
+      Ok(())
+    );
+    assert!(rate_limiter.acquire(Some(client("1.1.1.1")), now).is_err());
+  }
+
+  #[test]
+  fn clients_beyond_bucket_limit_share_a_bucket() {
+    let rate_limiter = rate_limiter(1);
+    let now = Instant::now();
+
+    for i in 0..RateLimiter::MAX_BUCKETS {
+      rate_limiter
+        .acquire(
+          Some(IpAddr::from(u32::try_from(i).unwrap().to_be_bytes())),
+          now,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+      rate_limiter.acquire(Some(IpAddr::from([255, 0, 0, 1])), now),
+      Ok(())
+    );
+    assert!(rate_limiter
+      .acquire(Some(IpAddr::from([255, 0, 0, 2])), now)
+      .is_err());
+    assert!(rate_limiter
+      .acquire(Some(IpAddr::from([0, 0, 0, 1])), now)
+      .is_err());
+
+    assert_eq!(
+      rate_limiter.state.lock().unwrap().buckets.len(),
+      RateLimiter::MAX_BUCKETS + 1
+    );
+  }
+
+  #[test]
+  fn client_is_peer_unless_behind_proxy() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+      "x-forwarded-for",
+      HeaderValue::from_static("9.9.9.9, 2.2.2.2"),
+    );
+    let peer = Some(SocketAddr::from(([1, 1, 1, 1], 80)));
+
+    assert_eq!(
+      RateLimiter::client(&headers, peer, false),
+      Some(IpAddr::from([1, 1, 1, 1]))
+    );
+    assert_eq!(
+      RateLimiter::client(&headers, peer, true),
+      Some(IpAddr::from([2, 2, 2, 2]))
+    );
+    assert_eq!(
+      RateLimiter::client(&HeaderMap::new(), peer, true),
+      Some(IpAddr::from([1, 1, 1, 1]))
+    );
+  }
+
+  #[test]
+  fn unix_socket_clients_are_identified_only_by_proxy() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", HeaderValue::from_static("2.2.2.2"));
+
+    assert_eq!(RateLimiter::client(&headers, None, false), None);
+    assert_eq!(
+      RateLimiter::client(&headers, None, true),
+      Some(IpAddr::from([2, 2, 2, 2]))
+    );
+    assert_eq!(RateLimiter::client(&HeaderMap::new(), None, true), None);
+  }
+
+  #[test]
+  fn clients_without_address_share_a_bucket() {
+    let rate_limiter = rate_limiter(1);
+    let now = Instant::now();
+
+    assert_eq!(rate_limiter.acquire(None, now), Ok(()));
+    assert!(rate_limiter.acquire(None, now).is_err());
+    assert_eq!(
+      rate_limiter.acquire(Some(IpAddr::from([1, 1, 1, 1])), now),
+      Ok(())
+    );
+  }
+}
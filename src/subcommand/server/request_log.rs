@@ -0,0 +1,193 @@
+use {
+  super::*,
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+  clap::ValueEnum,
+  log::Level,
+  std::{io::Write, net::IpAddr},
+};
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum LogFormat {
+  Text,
+  Json,
+}
+
+/// Logs one line per request, with target `ord::request`, so request logging
+/// is enabled with `RUST_LOG=ord::request=info`.
+pub(crate) struct RequestLog {
+  behind_proxy: bool,
+  format: LogFormat,
+  quiet_health_checks: bool,
+}
+
+#[derive(Serialize)]
+struct Entry<'a> {
+  timestamp: String,
+  level: &'static str,
+  method: &'a str,
+  path: &'a str,
+  status: u16,
+  latency_ms: f64,
+  /// `None` for Unix socket clients not identified by a proxy.
+  client: Option<IpAddr>,
+}
+
+impl RequestLog {
+  /// Routes polled by load balancers and monitoring, which are logged at
+  /// debug level with `--quiet-health-checks`.
+  const HEALTH_CHECKS: &[&str] = &[
+    "/block-count",
+    "/blockheight",
+    "/healthz",
+    "/metrics",
+    "/readyz",
+    "/status",
+  ];
+  const TARGET: &str = "ord::request";
+
+  pub(crate) fn new(format: LogFormat, behind_proxy: bool, quiet_health_checks: bool) -> Self {
+    Self {
+      behind_proxy,
+      format,
+      quiet_health_checks,
+    }
+  }
+
+  pub(crate) async fn record<B>(
+    Extension(request_log): Extension<Arc<RequestLog>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    request: Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let level = request_log.level(
+      request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str()),
+    );
+
+    if !log::log_enabled!(target: Self::TARGET, level) {
+      return next.run(request).await;
+    }
+
+    let start = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+    let client = RateLimiter::client(
+      request.headers(),
+      peer.map(|ConnectInfo(peer)| peer),
+      request_log.behind_proxy,
+    );
+
+    let response = next.run(request).await;
+
+    let entry = Entry {
+      timestamp: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+      level: level.as_str(),
+      method: method.as_str(),
+      path: &path,
+      status: response.status().as_u16(),
+      latency_ms: start.elapsed().as_micros() as f64 / 1000.0,
+      client,
+    };
+
+    match request_log.format {
+      LogFormat::Text => log::log!(target: Self::TARGET, level, "{}", Self::text(&entry)),
+      // Written directly, since `env_logger` would prefix each line with a
+      // timestamp and level, and the result would no longer be JSON. Each
+      // line is written at once, so that lines aren't interleaved, and
+      // errors are ignored, since there is nowhere to report them.
+      LogFormat::Json => {
+        let line = format!("{}\n", Self::json(&entry));
+        io::stderr().lock().write_all(line.as_bytes()).ok();
+      }
+    }
+
+    response
+  }
+
+  fn level(&self, route: Option<&str>) -> Level {
+    match route {
+      Some(route) if self.quiet_health_checks && Self::HEALTH_CHECKS.contains(&route) => {
+        Level::Debug
+      }
+      _ => Level::Info,
+    }
+  }
+
+  fn text(entry: &Entry) -> String {
+    format!(
+      "{} {} {} {:.3}ms {}",
+      entry.method,
+      entry.path,
+      entry.status,
+      entry.latency_ms,
+      entry
+        .client
+        .map(|client| client.to_string())
+        .unwrap_or_else(|| "-".into())
+    )
+  }
+
+  fn json(entry: &Entry) -> String {
+    serde_json::to_string(entry).unwrap()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entry() -> Entry<'static> {
+    Entry {
+      timestamp: "2023-01-01T00:00:00.000Z".into(),
+      level: "INFO",
+      method: "GET",
+      path: "/block/1",
+      status: 200,
+      latency_ms: 1.5,
+      client: Some(IpAddr::from([1, 1, 1, 1])),
+    }
+  }
+
+  #[test]
+  fn text() {
+    assert_eq!(
+      RequestLog::text(&entry()),
+      "GET /block/1 200 1.500ms 1.1.1.1"
+    );
+  }
+
+  #[test]
+  fn json() {
+    assert_eq!(
+      RequestLog::json(&entry()),
+      r#"{"timestamp":"2023-01-01T00:00:00.000Z","level":"INFO","method":"GET","path":"/block/1","status":200,"latency_ms":1.5,"client":"1.1.1.1"}"#
+    );
+  }
+
+  #[test]
+  fn clients_without_address() {
+    let entry = Entry {
+      client: None,
+      ..entry()
+    };
+
+    assert_eq!(RequestLog::text(&entry), "GET /block/1 200 1.500ms -");
+    assert!(RequestLog::json(&entry).ends_with(r#""client":null}"#));
+  }
+
+  #[test]
+  fn health_checks_are_demoted_to_debug() {
+    let quiet = RequestLog::new(LogFormat::Text, false, true);
+
+    assert_eq!(quiet.level(Some("/status")), Level::Debug);
+    assert_eq!(quiet.level(Some("/metrics")), Level::Debug);
+    assert_eq!(quiet.level(Some("/block/:query")), Level::Info);
+    assert_eq!(quiet.level(None), Level::Info);
+
+    let loud = RequestLog::new(LogFormat::Text, false, false);
+
+    assert_eq!(loud.level(Some("/status")), Level::Info);
+  }
+}
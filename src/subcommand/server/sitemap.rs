@@ -0,0 +1,119 @@
+use super::*;
+
+/// Inscription pages, for search engines. Sitemaps may list at most
+/// `MAX_URLS` URLs, so `/sitemap.xml` is an index of numbered shards, each
+/// listing the inscriptions in a range of inscription numbers.
+pub(crate) struct Sitemap {
+  base_url: Url,
+}
+
+impl Sitemap {
+  pub(crate) const MAX_URLS: u64 = 50_000;
+
+  /// `base_url` must end in a slash.
+  pub(crate) fn new(base_url: Url) -> Self {
+    Self { base_url }
+  }
+
+  /// The number of shards needed to list `inscription_count` inscriptions.
+  pub(crate) fn shards(inscription_count: u64) -> u64 {
+    (inscription_count + Self::MAX_URLS - 1) / Self::MAX_URLS
+  }
+
+  /// The inscription numbers listed by `shard`.
+  pub(crate) fn shard_numbers(shard: u64) -> Range<u64> {
+    shard.saturating_mul(Self::MAX_URLS)..shard.saturating_add(1).saturating_mul(Self::MAX_URLS)
+  }
+
+  pub(crate) fn index(&self, shards: u64) -> String {
+    let mut xml = String::from(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+       <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+
+    for shard in 0..shards {
+      xml.push_str(&format!(
+        "  <sitemap><loc>{}</loc></sitemap>\n",
+        self.url(&format!("sitemap-{shard}.xml"))
+      ));
+    }
+
+    xml.push_str("</sitemapindex>\n");
+
+    xml
+  }
+
+  pub(crate) fn shard_header() -> &'static str {
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+     <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n"
+  }
+
+  pub(crate) fn shard_entry(&self, inscription_id: InscriptionId) -> String {
+    format!(
+      "  <url><loc>{}</loc></url>\n",
+      self.url(&format!("inscription/{inscription_id}"))
+    )
+  }
+
+  pub(crate) fn shard_footer() -> &'static str {
+    "</urlset>\n"
+  }
+
+  /// URLs can't contain `<`, `>`, or `"`, so only `&` needs escaping.
+  fn url(&self, path: &str) -> String {
+    self
+      .base_url
+      .join(path)
+      .unwrap()
+      .as_str()
+      .replace('&', "&amp;")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn shards() {
+    assert_eq!(Sitemap::shards(0), 0);
+    assert_eq!(Sitemap::shards(1), 1);
+    assert_eq!(Sitemap::shards(Sitemap::MAX_URLS - 1), 1);
+    assert_eq!(Sitemap::shards(Sitemap::MAX_URLS), 1);
+    assert_eq!(Sitemap::shards(Sitemap::MAX_URLS + 1), 2);
+    assert_eq!(Sitemap::shards(2 * Sitemap::MAX_URLS + 1), 3);
+  }
+
+  #[test]
+  fn shard_numbers() {
+    assert_eq!(Sitemap::shard_numbers(0), 0..50_000);
+    assert_eq!(Sitemap::shard_numbers(1), 50_000..100_000);
+    assert_eq!(Sitemap::shard_numbers(u64::MAX), u64::MAX..u64::MAX);
+  }
+
+  #[test]
+  fn index() {
+    pretty_assert_eq!(
+      Sitemap::new("https://example.com/ord/".parse().unwrap()).index(2),
+      "
+        <?xml version=\"1.0\" encoding=\"UTF-8\"?>
+        <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">
+          <sitemap><loc>https://example.com/ord/sitemap-0.xml</loc></sitemap>
+          <sitemap><loc>https://example.com/ord/sitemap-1.xml</loc></sitemap>
+        </sitemapindex>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn shard_entry() {
+    assert_eq!(
+      Sitemap::new("https://example.com/a&b/".parse().unwrap()).shard_entry(outpoint(1).txid),
+      format!(
+        "  <url><loc>https://example.com/a&amp;b/inscription/{}</loc></url>\n",
+        outpoint(1).txid
+      )
+    );
+  }
+}
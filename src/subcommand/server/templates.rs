@@ -1,55 +1,143 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
-  block::BlockHtml, clock::ClockSvg, content::ContentHtml, home::HomeHtml, input::InputHtml,
-  inscription::InscriptionHtml, output::OutputHtml, range::RangeHtml, rare::RareTxt, sat::SatHtml,
+  address::{AddressHtml, AddressJson, AddressOutputJson},
+  block::{BlockHtml, Reward},
+  block_inscriptions::BlockInscriptionsHtml,
+  bounties::BountiesHtml,
+  clock::ClockSvg,
+  collection::CollectionHtml,
+  content::ContentHtml,
+  cycle::CycleHtml,
+  display::{BytesHtml, Grouped, TimestampHtml},
+  epoch::EpochHtml,
+  error::ErrorHtml,
+  export_page::ExportPageHtml,
+  faq::FaqHtml,
+  feed::{FeedEntry, FeedXml},
+  gallery::{GalleryHtml, Thumbnail},
+  halving::HalvingHtml,
+  home::HomeHtml,
+  input::InputHtml,
+  inscription::{InscriptionDigestJson, InscriptionHtml, InscriptionJson},
+  inscription_prefix::InscriptionPrefixHtml,
+  inscriptions::InscriptionsHtml,
+  output::OutputHtml,
+  preview::PreviewHtml,
+  range::RangeHtml,
+  sat::SatHtml,
+  settings::{IndexSettings, RpcSettings, ServerSettings, SettingsHtml},
+  tracked::TrackedHtml,
   transaction::TransactionHtml,
 };
 
+mod address;
 mod block;
+mod block_inscriptions;
+mod bounties;
 mod clock;
+mod collection;
 mod content;
+mod cycle;
+mod display;
+mod epoch;
+mod error;
+mod export_page;
+mod faq;
+mod feed;
+mod gallery;
+mod halving;
 mod home;
 mod input;
 mod inscription;
+mod inscription_prefix;
+mod inscriptions;
 mod output;
+mod preview;
 mod range;
-mod rare;
 mod sat;
+mod settings;
+mod tracked;
 mod transaction;
 
 #[derive(Boilerplate)]
 pub(crate) struct PageHtml {
   chain: Chain,
   content: Box<dyn PageContent>,
-  has_satoshi_index: bool,
+  has_rare_sats: bool,
+  progress: Progress,
 }
 
 impl PageHtml {
   pub(crate) fn new<T: PageContent + 'static>(
     content: T,
     chain: Chain,
-    has_satoshi_index: bool,
+    has_rare_sats: bool,
+    progress: Progress,
   ) -> Self {
     Self {
       content: Box::new(content),
-      has_satoshi_index,
+      has_rare_sats,
       chain,
+      progress,
     }
   }
 }
 
+/// How far the index has gotten, shown in page footers, and in a banner while
+/// the index catches up with the node.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Progress {
+  pub(crate) height: Option<Height>,
+  pub(crate) node_block_count: Option<u64>,
+}
+
+impl Progress {
+  /// The indexed height and the node's height, if the index is behind.
+  pub(crate) fn syncing(self) -> Option<(u64, u64)> {
+    let node_height = self.node_block_count?.checked_sub(1)?;
+    let height = self.height.map(|height| height.n()).unwrap_or(0);
+
+    if height < node_height {
+      Some((height, node_height))
+    } else {
+      None
+    }
+  }
+}
+
+/// Open Graph metadata, which social media sites show in previews of links
+/// to a page, along with its title.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct OpenGraph {
+  pub(crate) description: Option<String>,
+  /// An absolute URL, since previews are rendered elsewhere.
+  pub(crate) image: Option<String>,
+}
+
 pub(crate) trait PageContent: Display + 'static {
   fn title(&self) -> String;
 
-  fn page(self, chain: Chain, has_satoshi_index: bool) -> PageHtml
+  fn open_graph(&self) -> OpenGraph {
+    OpenGraph::default()
+  }
+
+  fn page(self, chain: Chain, has_rare_sats: bool, progress: Progress) -> PageHtml
   where
     Self: Sized,
   {
-    PageHtml::new(self, chain, has_satoshi_index)
+    PageHtml::new(self, chain, has_rare_sats, progress)
   }
 }
 
+/// Page content rendered from a serializable data struct, which the server
+/// returns instead of HTML to clients that accept `application/json`.
+pub(crate) trait DataPage: PageContent {
+  type Data: Serialize;
+
+  fn data(&self) -> Self::Data;
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -71,7 +159,16 @@ mod tests {
     }
 
     assert_regex_match!(
-      Foo.page(Chain::Mainnet, true).to_string(),
+      Foo
+        .page(
+          Chain::Mainnet,
+          true,
+          Progress {
+            height: Some(Height(100)),
+            node_block_count: Some(101)
+          }
+        )
+        .to_string(),
       "<!doctype html>
 <html lang=en>
   <head>
@@ -79,8 +176,10 @@ mod tests {
     <meta name=format-detection content='telephone=no'>
     <meta name=viewport content='width=device-width,initial-scale=1.0'>
     <title>Foo</title>
-    <link href=/static/index.css rel=stylesheet>
-    <link href=/static/modern-normalize.css rel=stylesheet>
+    <meta property=og:title content='Foo'>
+    <meta name=twitter:card content=summary>
+    <link href=/static/index.css\\?[[:xdigit:]]{8} rel=stylesheet>
+    <link href=/static/modern-normalize.css\\?[[:xdigit:]]{8} rel=stylesheet>
   </head>
   <body>
   <header>
@@ -88,6 +187,7 @@ mod tests {
       <a href=/>Ordinals</a>
       .*
       <a href=/clock>Clock</a>
+      <a href=/inscriptions>Inscriptions</a>
       <a href=/rare.txt>rare.txt</a>
       <form action=/search method=get>
         <input type=text .*>
@@ -98,6 +198,9 @@ mod tests {
   <main>
 <h1>Foo</h1>
   </main>
+  <footer>
+    indexed through <a href=/block/100>block 100</a>
+  </footer>
   </body>
 </html>
 "
@@ -105,7 +208,44 @@ mod tests {
   }
 
   #[test]
-  fn page_no_satoshi_index() {
+  fn page_open_graph() {
+    struct Foo;
+
+    impl Display for Foo {
+      fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "<h1>Foo</h1>")
+      }
+    }
+
+    impl PageContent for Foo {
+      fn title(&self) -> String {
+        "Foo's".to_string()
+      }
+
+      fn open_graph(&self) -> OpenGraph {
+        OpenGraph {
+          description: Some("bar".into()),
+          image: Some("https://example.com/baz.png".into()),
+        }
+      }
+    }
+
+    assert_regex_match!(
+      Foo
+        .page(Chain::Mainnet, false, Progress::default())
+        .to_string(),
+      ".*
+    <title>Foo&apos;s</title>
+    <meta property=og:title content='Foo&apos;s'>
+    <meta property=og:description content='bar'>
+    <meta property=og:image content='https://example.com/baz.png'>
+    <meta name=twitter:card content=summary_large_image>
+    <link href.*"
+    );
+  }
+
+  #[test]
+  fn page_no_rare_sats() {
     struct Foo;
 
     impl Display for Foo {
@@ -121,7 +261,9 @@ mod tests {
     }
 
     assert_regex_match!(
-      Foo.page(Chain::Mainnet, false).to_string(),
+      Foo
+        .page(Chain::Mainnet, false, Progress::default())
+        .to_string(),
       "<!doctype html>
 <html lang=en>
   <head>
@@ -129,8 +271,10 @@ mod tests {
     <meta name=format-detection content='telephone=no'>
     <meta name=viewport content='width=device-width,initial-scale=1.0'>
     <title>Foo</title>
-    <link href=/static/index.css rel=stylesheet>
-    <link href=/static/modern-normalize.css rel=stylesheet>
+    <meta property=og:title content='Foo'>
+    <meta name=twitter:card content=summary>
+    <link href=/static/index.css\\?[[:xdigit:]]{8} rel=stylesheet>
+    <link href=/static/modern-normalize.css\\?[[:xdigit:]]{8} rel=stylesheet>
   </head>
   <body>
   <header>
@@ -138,6 +282,7 @@ mod tests {
       <a href=/>Ordinals</a>
       .*
       <a href=/clock>Clock</a>
+      <a href=/inscriptions>Inscriptions</a>
       <form action=/search method=get>
         <input type=text .*>
         <input type=submit value=Search>
@@ -147,12 +292,85 @@ mod tests {
   <main>
 <h1>Foo</h1>
   </main>
+  <footer>
+    no blocks indexed
+  </footer>
   </body>
 </html>
 "
     );
   }
 
+  #[test]
+  fn page_syncing() {
+    struct Foo;
+
+    impl Display for Foo {
+      fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "<h1>Foo</h1>")
+      }
+    }
+
+    impl PageContent for Foo {
+      fn title(&self) -> String {
+        "Foo".to_string()
+      }
+    }
+
+    assert_regex_match!(
+      Foo
+        .page(
+          Chain::Mainnet,
+          true,
+          Progress {
+            height: Some(Height(100)),
+            node_block_count: Some(1001),
+          }
+        )
+        .to_string(),
+      ".*</header>
+  <div class=syncing>syncing: height 100 of 1000</div>
+  <main>.*"
+    );
+  }
+
+  #[test]
+  fn progress_syncing() {
+    assert_eq!(Progress::default().syncing(), None);
+    assert_eq!(
+      Progress {
+        height: None,
+        node_block_count: Some(1),
+      }
+      .syncing(),
+      None
+    );
+    assert_eq!(
+      Progress {
+        height: None,
+        node_block_count: Some(10),
+      }
+      .syncing(),
+      Some((0, 9))
+    );
+    assert_eq!(
+      Progress {
+        height: Some(Height(5)),
+        node_block_count: Some(10),
+      }
+      .syncing(),
+      Some((5, 9))
+    );
+    assert_eq!(
+      Progress {
+        height: Some(Height(9)),
+        node_block_count: Some(10),
+      }
+      .syncing(),
+      None
+    );
+  }
+
   #[test]
   fn page_signet() {
     struct Foo;
@@ -170,7 +388,16 @@ mod tests {
     }
 
     assert_regex_match!(
-      Foo.page(Chain::Signet, true).to_string(),
+      Foo
+        .page(
+          Chain::Signet,
+          true,
+          Progress {
+            height: Some(Height(100)),
+            node_block_count: Some(101),
+          }
+        )
+        .to_string(),
       "<!doctype html>
 <html lang=en>
   <head>
@@ -178,8 +405,10 @@ mod tests {
     <meta name=format-detection content='telephone=no'>
     <meta name=viewport content='width=device-width,initial-scale=1.0'>
     <title>Foo</title>
-    <link href=/static/index.css rel=stylesheet>
-    <link href=/static/modern-normalize.css rel=stylesheet>
+    <meta property=og:title content='Foo'>
+    <meta name=twitter:card content=summary>
+    <link href=/static/index.css\\?[[:xdigit:]]{8} rel=stylesheet>
+    <link href=/static/modern-normalize.css\\?[[:xdigit:]]{8} rel=stylesheet>
   </head>
   <body>
   <header>
@@ -187,6 +416,7 @@ mod tests {
       <a href=/>Ordinals<sup>signet</sup></a>
       .*
       <a href=/clock>Clock</a>
+      <a href=/inscriptions>Inscriptions</a>
       <a href=/rare.txt>rare.txt</a>
       <form action=/search method=get>
         <input type=text .*>
@@ -197,6 +427,9 @@ mod tests {
   <main>
 <h1>Foo</h1>
   </main>
+  <footer>
+    indexed through <a href=/block/100>block 100</a>
+  </footer>
   </body>
 </html>
 "
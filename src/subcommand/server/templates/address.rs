@@ -0,0 +1,101 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHtml {
+  pub(crate) address: Address,
+  pub(crate) explorer: Explorer,
+  pub(crate) inscriptions: Vec<(SatPoint, InscriptionId)>,
+  pub(crate) outputs: Vec<OutPoint>,
+}
+
+/// Served by `/api/address/:address`, a page of outputs at a time.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct AddressJson {
+  pub(crate) address: String,
+  pub(crate) outputs: Vec<AddressOutputJson>,
+  pub(crate) page_index: usize,
+  pub(crate) more: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct AddressOutputJson {
+  pub(crate) outpoint: OutPoint,
+  pub(crate) value: u64,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+}
+
+impl PageContent for AddressHtml {
+  fn title(&self) -> String {
+    format!("Address {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_outputs() {
+    let address = recipient();
+
+    pretty_assert_eq!(
+      AddressHtml {
+        address: address.clone(),
+        explorer: Explorer::default(),
+        inscriptions: Vec::new(),
+        outputs: Vec::new(),
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Address <span class=monospace>{address}</span></h1>
+          <h2>0 Unspent Outputs</h2>
+          <ul class=monospace>
+          </ul>
+        "
+      )
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_outputs_and_inscriptions() {
+    let address = recipient();
+    let inscription_id = outpoint(3).txid;
+
+    pretty_assert_eq!(
+      AddressHtml {
+        address: address.clone(),
+        explorer: Explorer::new(vec!["https://mempool.space/address/{address}".parse().unwrap()])
+          .unwrap(),
+        inscriptions: vec![(satpoint(1, 0), inscription_id)],
+        outputs: vec![outpoint(1), outpoint(2)],
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Address <span class=monospace>{address}</span></h1>
+          <dl>
+            <dt>explorer</dt><dd><a href=https://mempool.space/address/{address} class=monospace>https://mempool.space/address/{address}</a></dd>
+          </dl>
+          <h2>1 Inscription</h2>
+          <ul class=monospace>
+            <li><a href=/inscription/{inscription_id}>{inscription_id}</a> at <a href=/output/{}>{}</a></li>
+          </ul>
+          <h2>2 Unspent Outputs</h2>
+          <ul class=monospace>
+            <li><a href=/output/{} class=monospace>{}</a></li>
+            <li><a href=/output/{} class=monospace>{}</a></li>
+          </ul>
+        ",
+        outpoint(1),
+        satpoint(1, 0),
+        outpoint(1),
+        outpoint(1),
+        outpoint(2),
+        outpoint(2),
+      )
+      .unindent()
+    );
+  }
+}
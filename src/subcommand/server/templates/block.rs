@@ -6,19 +6,116 @@ pub(crate) struct BlockHtml {
   target: BlockHash,
   best_height: Height,
   block: Block,
+  explorer: Explorer,
   height: Height,
+  inscriptions: Vec<(InscriptionId, Thumbnail)>,
+  more_inscriptions: bool,
+  now: i64,
+  rare_sats: Vec<(Sat, Option<SatPoint>)>,
+  reward: Vec<Reward>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub(crate) enum RewardSource {
+  Subsidy,
+  Fees { txid: Txid },
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Reward {
+  #[serde(flatten)]
+  pub(crate) source: RewardSource,
+  pub(crate) sats: u64,
+  /// Location of the first sat in the coinbase outputs, or `None` if the
+  /// miner didn't claim it.
+  pub(crate) satpoint: Option<SatPoint>,
+}
+
+impl Reward {
+  pub(crate) fn from_segments(block: &Block, segments: Vec<RewardSegment>) -> Vec<Self> {
+    let Some(coinbase) = block.coinbase() else {
+      return Vec::new();
+    };
+
+    let txid = coinbase.txid();
+
+    let locate = |mut offset: u64| {
+      for (vout, output) in coinbase.output.iter().enumerate() {
+        if offset < output.value {
+          return Some(SatPoint {
+            outpoint: OutPoint {
+              txid,
+              vout: vout.try_into().unwrap(),
+            },
+            offset,
+          });
+        }
+        offset -= output.value;
+      }
+      None
+    };
+
+    let mut offset = 0;
+
+    segments
+      .into_iter()
+      .map(|segment| {
+        let reward = Self {
+          source: match segment.fee_transaction {
+            Some(i) => RewardSource::Fees {
+              txid: block.txdata[i].txid(),
+            },
+            None => RewardSource::Subsidy,
+          },
+          sats: segment.sats,
+          satpoint: locate(offset),
+        };
+        offset += segment.sats;
+        reward
+      })
+      .collect()
+  }
 }
 
 impl BlockHtml {
-  pub(crate) fn new(block: Block, height: Height, best_height: Height) -> Self {
+  pub(crate) fn new(
+    block: Block,
+    height: Height,
+    best_height: Height,
+    rare_sats: Vec<(Sat, Option<SatPoint>)>,
+    reward: Vec<Reward>,
+    explorer: Explorer,
+    now: i64,
+  ) -> Self {
     let mut target = block.header.target().to_be_bytes();
     target.reverse();
     Self {
       hash: block.header.block_hash(),
       target: BlockHash::from_inner(target),
       block,
+      explorer,
       height,
       best_height,
+      inscriptions: Vec::new(),
+      more_inscriptions: false,
+      now,
+      rare_sats,
+      reward,
+    }
+  }
+
+  /// Show the first page of inscriptions revealed in the block, linking to
+  /// the next if there are `more`.
+  pub(crate) fn inscriptions(
+    self,
+    inscriptions: Vec<(InscriptionId, Thumbnail)>,
+    more: bool,
+  ) -> Self {
+    Self {
+      inscriptions,
+      more_inscriptions: more,
+      ..self
     }
   }
 }
@@ -36,19 +133,23 @@ mod tests {
   #[test]
   fn block_html() {
     pretty_assert_eq!(
-      BlockHtml::new( Chain::Mainnet.genesis_block() , Height(0), Height(0))
+      BlockHtml::new(Chain::Mainnet.genesis_block(), Height(0), Height(0), vec![(Sat(0), None)], Vec::new(), Explorer::default(), 1231006505 + 60)
       .to_string(),
       "
         <h1>Block 0</h1>
         <dl>
           <dt>hash</dt><dd class=monospace>000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f</dd>
           <dt>target</dt><dd class=monospace>00000000ffff0000000000000000000000000000000000000000000000000000</dd>
-          <dt>timestamp</dt><dd>1231006505</dd>
-          <dt>size</dt><dd>285</dd>
-          <dt>weight</dt><dd>1140</dd>
+          <dt>timestamp</dt><dd><time datetime=2009-01-03T18:15:05Z>2009-01-03T18:15:05Z</time> (1 minute ago)</dd>
+          <dt>size</dt><dd>285 bytes</dd>
+          <dt>weight</dt><dd>1 140</dd>
         </dl>
         prev
         next
+        <h2>Rare Sats</h2>
+        <ul>
+          <li><a href=/sat/0 class=mythic>0</a></li>
+        </ul>
         <h2>1 Transaction</h2>
         <ul class=monospace>
           <li><a href=/tx/4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b>4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b</a></li>
@@ -58,19 +159,52 @@ mod tests {
     );
   }
 
+  #[test]
+  fn block_html_with_inscriptions() {
+    let html = BlockHtml::new(
+      Chain::Mainnet.genesis_block(),
+      Height(0),
+      Height(0),
+      Vec::new(),
+      Vec::new(),
+      Explorer::default(),
+      1231006505 + 60,
+    )
+    .inscriptions(
+      vec![(outpoint(1).txid, Thumbnail::Image(outpoint(1).txid))],
+      true,
+    )
+    .to_string();
+
+    assert_regex_match!(
+      html,
+      format!(
+        ".*
+<h2>Inscriptions</h2>
+<div class=gallery>
+  <a href=/inscription/{0}><img src=/content/{0} alt={0} loading=lazy></a>
+</div>
+<a href=/block/0/inscriptions/1>more</a>
+<h2>1 Transaction</h2>
+.*",
+        outpoint(1).txid,
+      )
+    );
+  }
+
   #[test]
   fn next_active_when_not_last() {
     pretty_assert_eq!(
-      BlockHtml::new( Chain::Mainnet.genesis_block() , Height(0), Height(1))
+      BlockHtml::new(Chain::Mainnet.genesis_block(), Height(0), Height(1), Vec::new(), Vec::new(), Explorer::default(), 1231006505 + 60)
       .to_string(),
       "
         <h1>Block 0</h1>
         <dl>
           <dt>hash</dt><dd class=monospace>000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f</dd>
           <dt>target</dt><dd class=monospace>00000000ffff0000000000000000000000000000000000000000000000000000</dd>
-          <dt>timestamp</dt><dd>1231006505</dd>
-          <dt>size</dt><dd>285</dd>
-          <dt>weight</dt><dd>1140</dd>
+          <dt>timestamp</dt><dd><time datetime=2009-01-03T18:15:05Z>2009-01-03T18:15:05Z</time> (1 minute ago)</dd>
+          <dt>size</dt><dd>285 bytes</dd>
+          <dt>weight</dt><dd>1 140</dd>
         </dl>
         prev
         <a href=/block/1>next</a>
@@ -86,16 +220,16 @@ mod tests {
   #[test]
   fn prev_active_when_not_first() {
     pretty_assert_eq!(
-      BlockHtml::new( Chain::Mainnet.genesis_block() , Height(1), Height(1))
+      BlockHtml::new(Chain::Mainnet.genesis_block(), Height(1), Height(1), Vec::new(), Vec::new(), Explorer::default(), 1231006505 + 60)
       .to_string(),
       "
         <h1>Block 1</h1>
         <dl>
           <dt>hash</dt><dd class=monospace>000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f</dd>
           <dt>target</dt><dd class=monospace>00000000ffff0000000000000000000000000000000000000000000000000000</dd>
-          <dt>timestamp</dt><dd>1231006505</dd>
-          <dt>size</dt><dd>285</dd>
-          <dt>weight</dt><dd>1140</dd>
+          <dt>timestamp</dt><dd><time datetime=2009-01-03T18:15:05Z>2009-01-03T18:15:05Z</time> (1 minute ago)</dd>
+          <dt>size</dt><dd>285 bytes</dd>
+          <dt>weight</dt><dd>1 140</dd>
           <dt>previous blockhash</dt><dd><a href=/block/0000000000000000000000000000000000000000000000000000000000000000 class=monospace>0000000000000000000000000000000000000000000000000000000000000000</a></dd>
         </dl>
         <a href=/block/0>prev</a>
@@ -108,4 +242,110 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn rare_sat_location() {
+    assert_regex_match!(
+      BlockHtml::new(
+        Chain::Mainnet.genesis_block(),
+        Height(0),
+        Height(0),
+        vec![(
+          Sat(0),
+          Some(SatPoint {
+            outpoint: OutPoint::null(),
+            offset: 0,
+          })
+        )],
+        Vec::new(),
+        Explorer::default(),
+        1231006505 + 60,
+      )
+      .to_string(),
+      format!(
+        ".*<h2>Rare Sats</h2>
+<ul>
+  <li><a href=/sat/0 class=mythic>0</a> at <a href=/output/{0} class=monospace>{0}:0</a></li>
+</ul>
+.*",
+        OutPoint::null()
+      )
+    );
+  }
+
+  #[test]
+  fn reward() {
+    let block = Chain::Mainnet.genesis_block();
+    let coinbase = block.txdata[0].txid();
+
+    let reward = Reward::from_segments(
+      &block,
+      vec![
+        RewardSegment {
+          fee_transaction: None,
+          sats: 50 * COIN_VALUE,
+        },
+        RewardSegment {
+          fee_transaction: Some(0),
+          sats: 1000,
+        },
+      ],
+    );
+
+    assert_eq!(
+      reward,
+      vec![
+        Reward {
+          source: RewardSource::Subsidy,
+          sats: 50 * COIN_VALUE,
+          satpoint: Some(SatPoint {
+            outpoint: OutPoint {
+              txid: coinbase,
+              vout: 0,
+            },
+            offset: 0,
+          }),
+        },
+        Reward {
+          source: RewardSource::Fees { txid: coinbase },
+          sats: 1000,
+          satpoint: None,
+        },
+      ]
+    );
+
+    assert_regex_match!(
+      BlockHtml::new(block, Height(0), Height(0), Vec::new(), reward, Explorer::default(), 1231006505 + 60).to_string(),
+      format!(
+        ".*<h2>Reward</h2>
+<ul>
+  <li>5 000 000 000 sats of subsidy at <a href=/output/{coinbase}:0 class=monospace>{coinbase}:0:0</a></li>
+  <li>1 000 sats of fees from <a href=/tx/{coinbase} class=monospace>{coinbase}</a>, unclaimed</li>
+</ul>
+.*"
+      )
+    );
+  }
+
+  #[test]
+  fn explorer_link() {
+    let hash = Chain::Mainnet.genesis_block().block_hash();
+
+    assert_regex_match!(
+      BlockHtml::new(
+        Chain::Mainnet.genesis_block(),
+        Height(0),
+        Height(0),
+        Vec::new(),
+        Vec::new(),
+        Explorer::new(vec!["https://mempool.space/block/{block}".parse().unwrap()]).unwrap(),
+        0,
+      )
+      .to_string(),
+      format!(
+        ".*  <dt>explorer</dt><dd><a href=https://mempool.space/block/{hash} class=monospace>https://mempool.space/block/{hash}</a></dd>
+</dl>.*"
+      )
+    );
+  }
 }
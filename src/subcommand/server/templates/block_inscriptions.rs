@@ -0,0 +1,64 @@
+use super::*;
+
+/// One page of the inscriptions revealed in a block.
+#[derive(Boilerplate)]
+pub(crate) struct BlockInscriptionsHtml {
+  pub(crate) height: Height,
+  pub(crate) inscriptions: Vec<(InscriptionId, Thumbnail)>,
+  pub(crate) page_index: usize,
+  pub(crate) more: bool,
+}
+
+impl PageContent for BlockInscriptionsHtml {
+  fn title(&self) -> String {
+    format!("Inscriptions in Block {}", self.height)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn block_inscriptions_html() {
+    pretty_assert_eq!(
+      BlockInscriptionsHtml {
+        height: Height(100),
+        inscriptions: vec![
+          (outpoint(1).txid, Thumbnail::Image(outpoint(1).txid)),
+          (outpoint(2).txid, Thumbnail::Unknown(outpoint(2).txid)),
+        ],
+        page_index: 1,
+        more: true,
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Inscriptions in <a href=/block/100>Block 100</a></h1>
+          <div class=gallery>
+            <a href=/inscription/{0}><img src=/content/{0} alt={0} loading=lazy></a>
+            <a href=/inscription/{1}><span class=monospace>{1}</span></a>
+          </div>
+          <a href=/block/100/inscriptions/0>prev</a>
+          <a href=/block/100/inscriptions/2>next</a>
+        ",
+        outpoint(1).txid,
+        outpoint(2).txid,
+      )
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn first_and_last_page_links() {
+    let html = BlockInscriptionsHtml {
+      height: Height(0),
+      inscriptions: Vec::new(),
+      page_index: 0,
+      more: false,
+    }
+    .to_string();
+
+    assert!(html.ends_with("</div>\nprev\nnext\n"), "{html}");
+  }
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct BountiesHtml {
+  pub(crate) height: Height,
+}
+
+impl BountiesHtml {
+  /// Bounty 1 closed at the first block of difficulty adjustment period 374.
+  const BOUNTY_1_DEADLINE: Height = Height(374 * DIFFCHANGE_INTERVAL);
+  /// Bounty 3 closes at the fourth halving.
+  const BOUNTY_3_EPOCH: Epoch = Epoch(4);
+
+  fn bounty_3_deadline() -> Height {
+    Self::BOUNTY_3_EPOCH.starting_height()
+  }
+
+  /// The last sat eligible for bounty 3, which is the last mined before it
+  /// closes.
+  fn bounty_3_last_sat() -> Sat {
+    Sat(Self::BOUNTY_3_EPOCH.starting_sat().n() - 1)
+  }
+
+  /// Eligible sats for bounty 3 which have been mined so far.
+  fn bounty_3_mined(&self) -> u64 {
+    Height(self.height.n() + 1)
+      .starting_sat()
+      .n()
+      .min(Self::BOUNTY_3_EPOCH.starting_sat().n())
+  }
+
+  /// Blocks in which submissions may still be included, after the current
+  /// one.
+  fn blocks_remaining(&self, deadline: Height) -> u64 {
+    deadline.n().saturating_sub(self.height.n() + 1)
+  }
+}
+
+impl PageContent for BountiesHtml {
+  fn title(&self) -> String {
+    "Bounties".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bounties_html() {
+    pretty_assert_eq!(
+      BountiesHtml {
+        height: Height(780000),
+      }
+      .to_string(),
+      "
+        <h1>Bounties</h1>
+        <p>Bounties are paid for sending sats which meet their criteria to their submission address. Their full rules are in the <a href=https://docs.ordinals.com/bounty/>docs</a>.</p>
+        <h2>Bounty 0</h2>
+        <dl>
+          <dt>criteria</dt><dd>a sat whose number ends with a zero</dd>
+          <dt>reward</dt><dd>100 000 sats</dd>
+          <dt>status</dt><dd>claimed</dd>
+        </dl>
+        <h2>Bounty 1</h2>
+        <dl>
+          <dt>criteria</dt><dd>the lowest-numbered sat submitted before <a href=/block/753984>block 753 984</a></dd>
+          <dt>reward</dt><dd>200 000 sats</dd>
+          <dt>status</dt><dd>claimed</dd>
+        </dl>
+        <h2>Bounty 2</h2>
+        <dl>
+          <dt>criteria</dt><dd>an <span class=uncommon>uncommon</span> sat</dd>
+          <dt>reward</dt><dd>300 000 sats</dd>
+          <dt>status</dt><dd>claimed</dd>
+        </dl>
+        <h2>Bounty 3</h2>
+        <dl>
+          <dt>criteria</dt><dd>the sats whose names are the least and most frequently occurring words in <a href=https://docs.ordinals.com/bounty/frequency.tsv>frequency.tsv</a></dd>
+          <dt>eligible sats</dt><dd><a href=/sat/0>0</a>–<a href=/sat/1968749999999999>1 968 749 999 999 999</a></dd>
+          <dt>eligible names</dt><dd>nvtdijuwxlp–xdmgbccjnu</dd>
+          <dt>eligible sats mined</dt><dd>1 931 250 625 000 000</dd>
+          <dt>deadline</dt><dd>block 840 000</dd>
+          <dt>blocks remaining</dt><dd>59 999</dd>
+          <dt>reward</dt><dd>400 000 sats</dd>
+          <dt>status</dt><dd>open</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn bounty_3_closes_at_fourth_halving() {
+    let html = BountiesHtml {
+      height: Height(839999),
+    }
+    .to_string();
+
+    assert!(html.contains("<dt>eligible sats mined</dt><dd>1 968 750 000 000 000</dd>"));
+    assert!(html.contains("<dt>blocks remaining</dt><dd>0</dd>"));
+    assert!(html.contains("<dt>status</dt><dd>closed</dd>"));
+  }
+
+  #[test]
+  fn bounty_1_deadline() {
+    assert_eq!(BountiesHtml::BOUNTY_1_DEADLINE, Height(753984));
+  }
+}
@@ -10,14 +10,14 @@ pub(crate) struct ClockSvg {
 
 impl ClockSvg {
   pub(crate) fn new(height: Height) -> Self {
-    let min = height.min(Epoch::FIRST_POST_SUBSIDY.starting_height());
+    let last = Epoch::first_post_subsidy().starting_height();
+    let halving_interval = Schedule::current().halving_interval;
+    let min = height.min(last);
 
     Self {
       height,
-      hour: (min.n() % Epoch::FIRST_POST_SUBSIDY.starting_height().n()) as f64
-        / Epoch::FIRST_POST_SUBSIDY.starting_height().n() as f64
-        * 360.0,
-      minute: (min.n() % SUBSIDY_HALVING_INTERVAL) as f64 / SUBSIDY_HALVING_INTERVAL as f64 * 360.0,
+      hour: (min.n() % last.n()) as f64 / last.n() as f64 * 360.0,
+      minute: (min.n() % halving_interval) as f64 / halving_interval as f64 * 360.0,
       second: height.period_offset() as f64 / DIFFCHANGE_INTERVAL as f64 * 360.0,
     }
   }
@@ -0,0 +1,44 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct CollectionHtml {
+  /// Inscriptions and the relative paths of their content, if any.
+  pub(crate) inscriptions: Vec<(InscriptionId, Option<String>)>,
+}
+
+impl PageContent for CollectionHtml {
+  fn title(&self) -> String {
+    "Collection".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn collection_html() {
+    pretty_assert_eq!(
+      CollectionHtml {
+        inscriptions: vec![
+          (outpoint(1).txid, Some("content/abc.txt".into())),
+          (outpoint(2).txid, None),
+        ],
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Collection</h1>
+          <h2>2 Inscriptions</h2>
+          <ul class=monospace>
+            <li><a href=inscription/{0}.html>{0}</a> <a href=content/abc.txt>content</a></li>
+            <li><a href=inscription/{1}.html>{1}</a></li>
+          </ul>
+        ",
+        outpoint(1).txid,
+        outpoint(2).txid,
+      )
+      .unindent()
+    );
+  }
+}
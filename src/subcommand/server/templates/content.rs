@@ -11,6 +11,11 @@ impl<'a> Display for ContentHtml<'a> {
         "<img src='data:image/png;base64,{}'>",
         base64::encode(png)
       ),
+      Some(Content::Spoofed { declared, sniffed }) => {
+        write!(f, "<p>content declared as ")?;
+        declared.escape(f, false)?;
+        write!(f, " looks like {sniffed}, so it is not displayed</p>")
+      }
       None => write!(f, "UNKNOWN"),
     }
   }
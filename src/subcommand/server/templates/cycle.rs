@@ -0,0 +1,96 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct CycleHtml {
+  pub(crate) cycle: Cycle,
+  pub(crate) mined: bool,
+}
+
+impl CycleHtml {
+  fn epochs(&self) -> impl Iterator<Item = Epoch> {
+    let first = Epoch::from(self.cycle).0;
+    (first..first + CYCLE_EPOCHS)
+      .map(Epoch)
+      .take_while(|epoch| *epoch < Epoch::first_post_subsidy())
+  }
+}
+
+impl PageContent for CycleHtml {
+  fn title(&self) -> String {
+    format!("Cycle {}", self.cycle)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cycle_html() {
+    pretty_assert_eq!(
+      CycleHtml {
+        cycle: Cycle(1),
+        mined: false,
+      }
+      .to_string(),
+      "
+        <h1>Cycle 1</h1>
+        <dl>
+          <dt>starting height</dt><dd>1 260 000 (not yet mined)</dd>
+          <dt>starting sat</dt><dd><a href=/sat/2067187500000000>2 067 187 500 000 000</a></dd>
+        </dl>
+        <h2>Epochs</h2>
+        <ul>
+          <li><a href=/epoch/6>6</a></li>
+          <li><a href=/epoch/7>7</a></li>
+          <li><a href=/epoch/8>8</a></li>
+          <li><a href=/epoch/9>9</a></li>
+          <li><a href=/epoch/10>10</a></li>
+          <li><a href=/epoch/11>11</a></li>
+        </ul>
+        <a href=/cycle/0>prev</a>
+        <a href=/cycle/2>next</a>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn last_cycle_html() {
+    pretty_assert_eq!(
+      CycleHtml {
+        cycle: Cycle(5),
+        mined: false,
+      }
+      .to_string(),
+      "
+        <h1>Cycle 5</h1>
+        <dl>
+          <dt>starting height</dt><dd>6 300 000 (not yet mined)</dd>
+          <dt>starting sat</dt><dd><a href=/sat/2099999996220000>2 099 999 996 220 000</a></dd>
+        </dl>
+        <h2>Epochs</h2>
+        <ul>
+          <li><a href=/epoch/30>30</a></li>
+          <li><a href=/epoch/31>31</a></li>
+          <li><a href=/epoch/32>32</a></li>
+        </ul>
+        <a href=/cycle/4>prev</a>
+        next
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn first_cycle_is_mined() {
+    assert_regex_match!(
+      CycleHtml {
+        cycle: Cycle(0),
+        mined: true,
+      }
+      .to_string(),
+      ".*<dt>starting height</dt><dd><a href=/block/0>0</a></dd>.*prev\n<a href=/cycle/1>next</a>\n"
+    );
+  }
+}
@@ -0,0 +1,199 @@
+use super::*;
+
+/// A number with its digits grouped in threes by thin spaces, so that long
+/// sat numbers can be read at a glance.
+pub(crate) struct Grouped(pub(crate) u64);
+
+impl Display for Grouped {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let digits = self.0.to_string();
+
+    for (i, digit) in digits.chars().enumerate() {
+      if i > 0 && (digits.len() - i) % 3 == 0 {
+        write!(f, "\u{2009}")?;
+      }
+      write!(f, "{digit}")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A byte count in binary units, with the exact count in a tooltip.
+pub(crate) struct BytesHtml(pub(crate) u64);
+
+impl BytesHtml {
+  const UNITS: &'static [&'static str] = &["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+}
+
+impl Display for BytesHtml {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    if self.0 < 1024 {
+      return write!(f, "{}", "byte".tally(self.0.try_into().unwrap()));
+    }
+
+    let mut value = self.0 as f64 / 1024.0;
+    let mut unit = 0;
+
+    // Move up a unit rather than display a value that rounds to 1024.
+    while value >= 1023.95 && unit + 1 < Self::UNITS.len() {
+      value /= 1024.0;
+      unit += 1;
+    }
+
+    write!(
+      f,
+      "<span title='{} bytes'>{value:.1} {}</span>",
+      Grouped(self.0),
+      Self::UNITS[unit]
+    )
+  }
+}
+
+/// A UNIX timestamp as UTC ISO-8601, followed by how long before `now` it
+/// was.
+pub(crate) struct TimestampHtml {
+  pub(crate) timestamp: i64,
+  pub(crate) now: i64,
+}
+
+impl Display for TimestampHtml {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let datetime = NaiveDateTime::from_timestamp_opt(self.timestamp, 0)
+      .unwrap()
+      .format("%Y-%m-%dT%H:%M:%SZ");
+
+    write!(
+      f,
+      "<time datetime={datetime}>{datetime}</time> ({})",
+      relative(self.now - self.timestamp)
+    )
+  }
+}
+
+/// `seconds` elapsed, in the largest whole unit, e.g. `3 hours ago`, or
+/// `in 3 hours` if negative.
+pub(crate) fn relative(seconds: i64) -> String {
+  const UNITS: &[(&str, u64)] = &[
+    ("year", 365 * 24 * 60 * 60),
+    ("month", 30 * 24 * 60 * 60),
+    ("day", 24 * 60 * 60),
+    ("hour", 60 * 60),
+    ("minute", 60),
+    ("second", 1),
+  ];
+
+  if seconds == 0 {
+    return "just now".into();
+  }
+
+  let magnitude = seconds.unsigned_abs();
+
+  let (unit, length) = UNITS
+    .iter()
+    .find(|(_, length)| magnitude >= *length)
+    .unwrap();
+
+  let tallied = unit.tally((magnitude / length).try_into().unwrap());
+
+  if seconds > 0 {
+    format!("{tallied} ago")
+  } else {
+    format!("in {tallied}")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn grouped() {
+    assert_eq!(Grouped(0).to_string(), "0");
+    assert_eq!(Grouped(999).to_string(), "999");
+    assert_eq!(Grouped(1000).to_string(), "1\u{2009}000");
+    assert_eq!(Grouped(100000).to_string(), "100\u{2009}000");
+    assert_eq!(
+      Grouped(2099999997689999).to_string(),
+      "2\u{2009}099\u{2009}999\u{2009}997\u{2009}689\u{2009}999"
+    );
+    assert_eq!(
+      Grouped(u64::MAX).to_string(),
+      "18\u{2009}446\u{2009}744\u{2009}073\u{2009}709\u{2009}551\u{2009}615"
+    );
+  }
+
+  #[test]
+  fn bytes() {
+    assert_eq!(BytesHtml(0).to_string(), "0 bytes");
+    assert_eq!(BytesHtml(1).to_string(), "1 byte");
+    assert_eq!(BytesHtml(1023).to_string(), "1023 bytes");
+    assert_eq!(
+      BytesHtml(1024).to_string(),
+      "<span title='1\u{2009}024 bytes'>1.0 KiB</span>"
+    );
+    assert_eq!(
+      BytesHtml(1536).to_string(),
+      "<span title='1\u{2009}536 bytes'>1.5 KiB</span>"
+    );
+    assert_eq!(
+      BytesHtml(1024 * 1024 - 1).to_string(),
+      "<span title='1\u{2009}048\u{2009}575 bytes'>1.0 MiB</span>"
+    );
+    assert_eq!(
+      BytesHtml(4_000_000).to_string(),
+      "<span title='4\u{2009}000\u{2009}000 bytes'>3.8 MiB</span>"
+    );
+    assert_eq!(
+      BytesHtml(u64::MAX).to_string(),
+      "<span title='18\u{2009}446\u{2009}744\u{2009}073\u{2009}709\u{2009}551\u{2009}615 bytes'>16.0 EiB</span>"
+    );
+  }
+
+  #[test]
+  fn timestamp() {
+    assert_eq!(
+      TimestampHtml {
+        timestamp: 1231006505,
+        now: 1231006505 + 3 * 60 * 60 + 59,
+      }
+      .to_string(),
+      "<time datetime=2009-01-03T18:15:05Z>2009-01-03T18:15:05Z</time> (3 hours ago)"
+    );
+    assert_eq!(
+      TimestampHtml {
+        timestamp: 0,
+        now: 0,
+      }
+      .to_string(),
+      "<time datetime=1970-01-01T00:00:00Z>1970-01-01T00:00:00Z</time> (just now)"
+    );
+  }
+
+  #[test]
+  fn relative_sub_minute() {
+    assert_eq!(relative(0), "just now");
+    assert_eq!(relative(1), "1 second ago");
+    assert_eq!(relative(59), "59 seconds ago");
+    assert_eq!(relative(-1), "in 1 second");
+    assert_eq!(relative(-59), "in 59 seconds");
+  }
+
+  #[test]
+  fn relative_units() {
+    assert_eq!(relative(60), "1 minute ago");
+    assert_eq!(relative(119), "1 minute ago");
+    assert_eq!(relative(60 * 60), "1 hour ago");
+    assert_eq!(relative(2 * 60 * 60), "2 hours ago");
+    assert_eq!(relative(-2 * 60 * 60), "in 2 hours");
+    assert_eq!(relative(24 * 60 * 60), "1 day ago");
+    assert_eq!(relative(45 * 24 * 60 * 60), "1 month ago");
+    assert_eq!(relative(400 * 24 * 60 * 60), "1 year ago");
+  }
+
+  #[test]
+  fn relative_extremes() {
+    assert_eq!(relative(i64::MAX), "292471208677 years ago");
+    assert_eq!(relative(i64::MIN), "in 292471208677 years");
+  }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct EpochHtml {
+  pub(crate) epoch: Epoch,
+  pub(crate) mined: bool,
+}
+
+impl PageContent for EpochHtml {
+  fn title(&self) -> String {
+    format!("Epoch {}", self.epoch)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn epoch_html() {
+    pretty_assert_eq!(
+      EpochHtml {
+        epoch: Epoch(0),
+        mined: true,
+      }
+      .to_string(),
+      "
+        <h1>Epoch 0</h1>
+        <dl>
+          <dt>cycle</dt><dd><a href=/cycle/0>0</a></dd>
+          <dt>starting height</dt><dd><a href=/block/0>0</a></dd>
+          <dt>starting sat</dt><dd><a href=/sat/0>0</a></dd>
+          <dt>subsidy</dt><dd>5 000 000 000</dd>
+        </dl>
+        prev
+        <a href=/epoch/1>next</a>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn unmined_epoch_html() {
+    pretty_assert_eq!(
+      EpochHtml {
+        epoch: Epoch(32),
+        mined: false,
+      }
+      .to_string(),
+      "
+        <h1>Epoch 32</h1>
+        <dl>
+          <dt>cycle</dt><dd><a href=/cycle/5>5</a></dd>
+          <dt>starting height</dt><dd>6 720 000 (not yet mined)</dd>
+          <dt>starting sat</dt><dd><a href=/sat/2099999997480000>2 099 999 997 480 000</a></dd>
+          <dt>subsidy</dt><dd>1</dd>
+        </dl>
+        <a href=/epoch/31>prev</a>
+        next
+      "
+      .unindent()
+    );
+  }
+}
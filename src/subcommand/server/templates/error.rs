@@ -0,0 +1,63 @@
+use super::*;
+
+/// The page which server errors are rendered as for browsers.
+#[derive(Boilerplate)]
+pub(crate) struct ErrorHtml {
+  pub(crate) status: StatusCode,
+  pub(crate) message: String,
+}
+
+impl PageContent for ErrorHtml {
+  fn title(&self) -> String {
+    self.status.to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn error_html() {
+    pretty_assert_eq!(
+      ErrorHtml {
+        status: StatusCode::NOT_FOUND,
+        message: "output 0:0 unknown".into(),
+      }
+      .to_string(),
+      "
+        <h1>404 Not Found</h1>
+        <p>output 0:0 unknown</p>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn messages_are_escaped() {
+    pretty_assert_eq!(
+      ErrorHtml {
+        status: StatusCode::BAD_REQUEST,
+        message: "invalid sat `<script>`".into(),
+      }
+      .to_string(),
+      "
+        <h1>400 Bad Request</h1>
+        <p>invalid sat `&lt;script&gt;`</p>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn title_is_status() {
+    assert_eq!(
+      ErrorHtml {
+        status: StatusCode::INTERNAL_SERVER_ERROR,
+        message: "Internal Server Error".into(),
+      }
+      .title(),
+      "500 Internal Server Error"
+    );
+  }
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+/// Standalone page for collection exports. Unlike `PageHtml`, the stylesheet
+/// is inlined and links are relative, so exports can be viewed offline.
+#[derive(Boilerplate)]
+pub(crate) struct ExportPageHtml {
+  content: Box<dyn PageContent>,
+  root: &'static str,
+}
+
+impl ExportPageHtml {
+  const STYLESHEET: &'static str = concat!(
+    include_str!("../../../../static/index.css"),
+    include_str!("../../../../static/modern-normalize.css"),
+  );
+
+  /// `root` is the relative path from the page to the export directory.
+  pub(crate) fn new<T: PageContent + 'static>(content: T, root: &'static str) -> Self {
+    Self {
+      content: Box::new(content),
+      root,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn stylesheet_is_inlined_and_links_are_relative() {
+    let html = ExportPageHtml::new(
+      CollectionHtml {
+        inscriptions: Vec::new(),
+      },
+      "../",
+    )
+    .to_string();
+
+    assert!(html.contains("<title>Collection</title>"));
+    assert!(html.contains(include_str!("../../../../static/index.css")));
+    assert!(html.contains("<a href=../index.html>Collection</a>"));
+    assert!(!html.contains("href=/"));
+    assert!(!html.contains("src=/"));
+  }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct FaqHtml;
+
+impl PageContent for FaqHtml {
+  fn title(&self) -> String {
+    "FAQ".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn faq_html() {
+    assert_regex_match!(
+      FaqHtml.to_string(),
+      "<h1>FAQ</h1>
+<h2>What is ordinal theory\\?</h2>
+<p>.*</p>
+<h2>Does ordinal theory require a side chain, a separate token, or changes to Bitcoin\\?</h2>
+<p>.*</p>
+<h2>What is ordinal theory good for\\?</h2>
+<p>.*</p>
+<h2>How does ordinal theory work\\?</h2>
+<p>.*</p>
+<h2>What are inscriptions\\?</h2>
+<p>.*</p>
+<p>The full FAQ is at <a href=https://docs.ordinals.com/faq/>docs.ordinals.com</a>.</p>
+"
+    );
+  }
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+/// Atom feed of the latest inscriptions, newest first. Entries are dated by
+/// the block that revealed them.
+#[derive(Boilerplate)]
+pub(crate) struct FeedXml {
+  chain: Chain,
+  updated: DateTime<Utc>,
+  entries: Vec<FeedEntry>,
+}
+
+pub(crate) struct FeedEntry {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) number: u64,
+  pub(crate) content_type: Option<String>,
+  pub(crate) timestamp: DateTime<Utc>,
+}
+
+impl FeedXml {
+  pub(crate) fn new(chain: Chain, updated: DateTime<Utc>, entries: Vec<FeedEntry>) -> Self {
+    Self {
+      chain,
+      updated,
+      entries,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      FeedXml::new(Chain::Mainnet, Utc.timestamp_opt(0, 0).unwrap(), Vec::new()).to_string(),
+      r#"<\?xml version="1.0" encoding="UTF-8"\?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>urn:ord:mainnet:inscriptions</id>
+  <title>Inscriptions</title>
+  <link rel="self" href="/feed.xml"/>
+  <link rel="alternate" type="text/html" href="/inscriptions"/>
+  <updated>1970-01-01T00:00:00\+00:00</updated>
+</feed>
+"#
+    );
+  }
+
+  #[test]
+  fn entries() {
+    let inscription_id =
+      InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+        .unwrap();
+
+    assert_regex_match!(
+      FeedXml::new(
+        Chain::Signet,
+        Utc.timestamp_opt(1_000_000_000, 0).unwrap(),
+        vec![
+          FeedEntry {
+            inscription_id,
+            number: 1,
+            content_type: Some("text/html;charset=utf-8".into()),
+            timestamp: Utc.timestamp_opt(1_000_000_000, 0).unwrap(),
+          },
+          FeedEntry {
+            inscription_id,
+            number: 0,
+            content_type: None,
+            timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+          },
+        ],
+      )
+      .to_string(),
+      r#".*<id>urn:ord:signet:inscriptions</id>.*
+  <updated>2001-09-09T01:46:40\+00:00</updated>
+  <entry>
+    <id>urn:ord:inscription:ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</id>
+    <title>Inscription 1</title>
+    <link rel="alternate" type="text/html" href="/inscription/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc"/>
+    <category term="text/html;charset=utf-8" label="content type"/>
+    <published>2001-09-09T01:46:40\+00:00</published>
+    <updated>2001-09-09T01:46:40\+00:00</updated>
+  </entry>
+  <entry>
+    <id>urn:ord:inscription:ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</id>
+    <title>Inscription 0</title>
+    <link rel="alternate" type="text/html" href="/inscription/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc"/>
+    <published>1970-01-01T00:00:00\+00:00</published>
+    <updated>1970-01-01T00:00:00\+00:00</updated>
+  </entry>
+</feed>
+"#
+    );
+  }
+
+  #[test]
+  fn content_type_is_escaped() {
+    assert_regex_match!(
+      FeedXml::new(
+        Chain::Mainnet,
+        Utc.timestamp_opt(0, 0).unwrap(),
+        vec![FeedEntry {
+          inscription_id: Txid::all_zeros(),
+          number: 0,
+          content_type: Some("\"/><evil>".into()),
+          timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+        }],
+      )
+      .to_string(),
+      r#".*<category term="&quot;/&gt;&lt;evil&gt;" label="content type"/>.*"#
+    );
+  }
+}
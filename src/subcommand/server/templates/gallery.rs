@@ -0,0 +1,129 @@
+use super::*;
+
+/// One page of the inscriptions in a collection.
+#[derive(Boilerplate)]
+pub(crate) struct GalleryHtml {
+  pub(crate) collection: CollectionSlug,
+  pub(crate) inscriptions: Vec<(InscriptionId, Thumbnail)>,
+  pub(crate) page_index: usize,
+  pub(crate) more: bool,
+}
+
+impl PageContent for GalleryHtml {
+  fn title(&self) -> String {
+    format!("Collection {}", self.collection)
+  }
+}
+
+/// A small preview of an inscription, linking to its page.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Thumbnail {
+  Image(InscriptionId),
+  Text(String),
+  Unknown(InscriptionId),
+}
+
+impl Thumbnail {
+  const TEXT_LEN: usize = 64;
+
+  pub(crate) fn new(inscription_id: InscriptionId, inscription: &Inscription) -> Self {
+    match inscription.content() {
+      Some(Content::Png(_)) => Self::Image(inscription_id),
+      Some(Content::Text(text)) => Self::Text(text.chars().take(Self::TEXT_LEN).collect()),
+      Some(Content::Spoofed { .. }) | None => Self::Unknown(inscription_id),
+    }
+  }
+}
+
+impl Display for Thumbnail {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Image(inscription_id) => write!(
+        f,
+        "<img src=/content/{inscription_id} alt={inscription_id} loading=lazy>"
+      ),
+      Self::Text(text) => {
+        write!(f, "<pre>")?;
+        text.escape(f, false)?;
+        write!(f, "</pre>")
+      }
+      Self::Unknown(inscription_id) => write!(f, "<span class=monospace>{inscription_id}</span>"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn gallery_html() {
+    pretty_assert_eq!(
+      GalleryHtml {
+        collection: "pepes".parse().unwrap(),
+        inscriptions: vec![
+          (outpoint(1).txid, Thumbnail::Image(outpoint(1).txid)),
+          (outpoint(2).txid, Thumbnail::Text("<b>pepe</b>".into())),
+          (outpoint(3).txid, Thumbnail::Unknown(outpoint(3).txid)),
+        ],
+        page_index: 1,
+        more: true,
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Collection pepes</h1>
+          <div class=gallery>
+            <a href=/inscription/{0}><img src=/content/{0} alt={0} loading=lazy></a>
+            <a href=/inscription/{1}><pre>&lt;b&gt;pepe&lt;/b&gt;</pre></a>
+            <a href=/inscription/{2}><span class=monospace>{2}</span></a>
+          </div>
+          <a href=/collection/pepes/0>prev</a>
+          <a href=/collection/pepes/2>next</a>
+        ",
+        outpoint(1).txid,
+        outpoint(2).txid,
+        outpoint(3).txid,
+      )
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn first_and_last_page_links() {
+    let html = GalleryHtml {
+      collection: "pepes".parse().unwrap(),
+      inscriptions: Vec::new(),
+      page_index: 0,
+      more: false,
+    }
+    .to_string();
+
+    assert!(html.ends_with("</div>\nprev\nnext\n"), "{html}");
+  }
+
+  #[test]
+  fn thumbnail() {
+    let id = outpoint(1).txid;
+
+    assert_eq!(
+      Thumbnail::new(id, &inscription("image/png", [1; 100])),
+      Thumbnail::Image(id)
+    );
+    assert_eq!(
+      Thumbnail::new(
+        id,
+        &inscription("text/plain;charset=utf-8", "a".repeat(100))
+      ),
+      Thumbnail::Text("a".repeat(64))
+    );
+    assert_eq!(
+      Thumbnail::new(id, &inscription("image/png", "<html>")),
+      Thumbnail::Unknown(id)
+    );
+    assert_eq!(
+      Thumbnail::new(id, &inscription("video/mp4", [])),
+      Thumbnail::Unknown(id)
+    );
+  }
+}
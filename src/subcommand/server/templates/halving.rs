@@ -0,0 +1,140 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize)]
+pub(crate) struct HalvingHtml {
+  pub(crate) height: u64,
+  pub(crate) subsidy: u64,
+  pub(crate) next_subsidy: u64,
+  pub(crate) halving: Countdown,
+  pub(crate) difficulty_adjustment: Countdown,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Countdown {
+  pub(crate) height: u64,
+  pub(crate) blocks_remaining: u64,
+  pub(crate) estimated_timestamp: i64,
+}
+
+impl HalvingHtml {
+  /// Number of recent blocks used to estimate the average block interval.
+  pub(crate) const SAMPLE_BLOCKS: u64 = 144;
+
+  pub(crate) fn new(height: Height, start: (Height, i64), end: (Height, i64)) -> Self {
+    let countdown = |target: Height| Countdown {
+      height: target.n(),
+      blocks_remaining: target.n() - height.n(),
+      estimated_timestamp: Blocktime::extrapolate(start, end, target).timestamp(),
+    };
+
+    let next_halving = height.next_halving();
+
+    Self {
+      height: height.n(),
+      subsidy: height.subsidy(),
+      next_subsidy: next_halving.subsidy(),
+      halving: countdown(next_halving),
+      difficulty_adjustment: countdown(height.next_difficulty_adjustment()),
+    }
+  }
+}
+
+impl PageContent for HalvingHtml {
+  fn title(&self) -> String {
+    "Halving".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn countdown_from_genesis() {
+    pretty_assert_eq!(
+      HalvingHtml::new(Height(0), (Height(0), 0), (Height(0), 0)),
+      HalvingHtml {
+        height: 0,
+        subsidy: 50 * COIN_VALUE,
+        next_subsidy: 25 * COIN_VALUE,
+        halving: Countdown {
+          height: SUBSIDY_HALVING_INTERVAL,
+          blocks_remaining: SUBSIDY_HALVING_INTERVAL,
+          estimated_timestamp: 600 * 210000,
+        },
+        difficulty_adjustment: Countdown {
+          height: DIFFCHANGE_INTERVAL,
+          blocks_remaining: DIFFCHANGE_INTERVAL,
+          estimated_timestamp: 600 * 2016,
+        },
+      }
+    );
+  }
+
+  #[test]
+  fn countdown_uses_recent_interval() {
+    pretty_assert_eq!(
+      HalvingHtml::new(
+        Height(SUBSIDY_HALVING_INTERVAL - 1),
+        (Height(SUBSIDY_HALVING_INTERVAL - 145), 1000),
+        (Height(SUBSIDY_HALVING_INTERVAL - 1), 1000 + 144 * 300),
+      ),
+      HalvingHtml {
+        height: SUBSIDY_HALVING_INTERVAL - 1,
+        subsidy: 50 * COIN_VALUE,
+        next_subsidy: 25 * COIN_VALUE,
+        halving: Countdown {
+          height: SUBSIDY_HALVING_INTERVAL,
+          blocks_remaining: 1,
+          estimated_timestamp: 1000 + 145 * 300,
+        },
+        difficulty_adjustment: Countdown {
+          height: 211680,
+          blocks_remaining: 1681,
+          estimated_timestamp: 1000 + 144 * 300 + 1681 * 300,
+        },
+      }
+    );
+  }
+
+  #[test]
+  fn countdown_after_halving() {
+    let halving = HalvingHtml::new(
+      Height(SUBSIDY_HALVING_INTERVAL),
+      (Height(0), 0),
+      (Height(SUBSIDY_HALVING_INTERVAL), 0),
+    );
+    assert_eq!(halving.subsidy, 25 * COIN_VALUE);
+    assert_eq!(halving.next_subsidy, 1250000000);
+    assert_eq!(halving.halving.height, SUBSIDY_HALVING_INTERVAL * 2);
+    assert_eq!(halving.halving.blocks_remaining, SUBSIDY_HALVING_INTERVAL);
+  }
+
+  #[test]
+  fn halving_html() {
+    pretty_assert_eq!(
+      HalvingHtml::new(Height(0), (Height(0), 0), (Height(0), 0)).to_string(),
+      "
+        <h1>Halving</h1>
+        <dl>
+          <dt>block</dt><dd>0</dd>
+          <dt>subsidy</dt><dd>5 000 000 000</dd>
+        </dl>
+        <h2>Next Halving</h2>
+        <dl>
+          <dt>block</dt><dd>210 000</dd>
+          <dt>blocks remaining</dt><dd>210 000</dd>
+          <dt>subsidy</dt><dd>2 500 000 000</dd>
+          <dt>time</dt><dd>expected circa 1973-12-29</dd>
+        </dl>
+        <h2>Next Difficulty Adjustment</h2>
+        <dl>
+          <dt>block</dt><dd>2 016</dd>
+          <dt>blocks remaining</dt><dd>2 016</dd>
+          <dt>time</dt><dd>expected circa 1970-01-15</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
@@ -4,17 +4,22 @@ use super::*;
 pub(crate) struct HomeHtml {
   last: u64,
   blocks: Vec<BlockHash>,
+  inscriptions: Vec<(InscriptionId, Thumbnail)>,
   starting_sat: Option<Sat>,
 }
 
 impl HomeHtml {
-  pub(crate) fn new(blocks: Vec<(u64, BlockHash)>) -> Self {
+  pub(crate) fn new(
+    blocks: Vec<(u64, BlockHash)>,
+    inscriptions: Vec<(InscriptionId, Thumbnail)>,
+  ) -> Self {
     Self {
+      inscriptions,
       starting_sat: blocks
-        .get(0)
+        .first()
         .map(|(height, _)| Height(*height).starting_sat()),
       last: blocks
-        .get(0)
+        .first()
         .map(|(height, _)| height)
         .cloned()
         .unwrap_or(0),
@@ -36,27 +41,31 @@ mod tests {
   #[test]
   fn home_html() {
     assert_regex_match!(
-      &HomeHtml::new(vec![
-        (
-          1260001,
-          "1111111111111111111111111111111111111111111111111111111111111111"
-            .parse()
-            .unwrap()
-        ),
-        (
-          1260000,
-          "0000000000000000000000000000000000000000000000000000000000000000"
-            .parse()
-            .unwrap()
-        )
-      ],)
+      &HomeHtml::new(
+        vec![
+          (
+            1260001,
+            "1111111111111111111111111111111111111111111111111111111111111111"
+              .parse()
+              .unwrap()
+          ),
+          (
+            1260000,
+            "0000000000000000000000000000000000000000000000000000000000000000"
+              .parse()
+              .unwrap()
+          )
+        ],
+        Vec::new()
+      )
       .to_string(),
-      "<h1>Bitcoin-native NFTs</h1>.*<h2>Status</h2>
+      "<form action=/search method=get>.*</form>
+<h2>Status</h2>
 <dl>
   <dt>cycle</dt><dd>1</dd>
   <dt>epoch</dt><dd>6</dd>
   <dt>period</dt><dd>625</dd>
-  <dt>block</dt><dd>1260001</dd>
+  <dt>block</dt><dd>1 260 001</dd>
 </dl>
 <h2>Latest Blocks</h2>
 <ol start=1260001 reversed class=blocks>
@@ -66,4 +75,26 @@ mod tests {
 ",
     );
   }
+
+  #[test]
+  fn home_html_with_inscriptions() {
+    assert_regex_match!(
+      HomeHtml::new(
+        Vec::new(),
+        vec![(outpoint(1).txid, Thumbnail::Image(outpoint(1).txid))],
+      )
+      .to_string(),
+      format!(
+        ".*</form>
+<h2>Latest Inscriptions</h2>
+<div class=gallery>
+  <a href=/inscription/{0}><img src=/content/{0} alt={0} loading=lazy></a>
+</div>
+<a href=/inscriptions>more</a>
+<h2>Latest Blocks</h2>
+.*",
+        outpoint(1).txid
+      )
+    );
+  }
 }
@@ -4,6 +4,8 @@ use super::*;
 pub(crate) struct InputHtml {
   pub(crate) path: (u64, usize, usize),
   pub(crate) input: TxIn,
+  pub(crate) previous_output: Option<TxOut>,
+  pub(crate) sat_ranges: Option<Vec<(u64, u64)>>,
 }
 
 impl PageContent for InputHtml {
@@ -16,7 +18,10 @@ impl PageContent for InputHtml {
 mod tests {
   use {
     super::*,
-    bitcoin::{blockdata::script, Witness},
+    bitcoin::{
+      blockdata::{opcodes, script},
+      Witness,
+    },
   };
 
   #[test]
@@ -33,13 +38,15 @@ mod tests {
           script_sig: script::Builder::new().push_scriptint(4).into_script(),
           sequence: Sequence::MAX,
           witness,
-        }
+        },
+        previous_output: None,
+        sat_ranges: None,
       }
       .to_string(),
       "
       <h1>Input /1/2/3</h1>
       <dl>
-        <dt>previous output</dt><dd class=monospace>0000000000000000000000000000000000000000000000000000000000000000:0</dd>
+        <dt>previous output</dt><dd><a href=/output/0000000000000000000000000000000000000000000000000000000000000000:0 class=monospace>0000000000000000000000000000000000000000000000000000000000000000:0</a></dd>
         <dt>witness</dt><dd class=data>010101</dd>
         <dt>script sig</dt><dd class=data>OP_PUSHBYTES_1 04</dd>
         <dt>text</dt><dd>\x01\x04</dd>
@@ -59,7 +66,9 @@ mod tests {
           script_sig: script::Builder::new().into_script(),
           sequence: Sequence::MAX,
           witness: Witness::new(),
-        }
+        },
+        previous_output: None,
+        sat_ranges: None,
       }
       .to_string(),
       "
@@ -70,4 +79,41 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn previous_output_and_sat_ranges() {
+    pretty_assert_eq!(
+      InputHtml {
+        path: (1, 2, 3),
+        input: TxIn {
+          previous_output: outpoint(1),
+          script_sig: script::Builder::new().into_script(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        },
+        previous_output: Some(TxOut {
+          value: 3,
+          script_pubkey: script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .into_script(),
+        }),
+        sat_ranges: Some(vec![(0, 1), (1, 3)]),
+      }
+      .to_string(),
+      "
+      <h1>Input /1/2/3</h1>
+      <dl>
+        <dt>previous output</dt><dd><a href=/output/1111111111111111111111111111111111111111111111111111111111111111:1 class=monospace>1111111111111111111111111111111111111111111111111111111111111111:1</a></dd>
+        <dt>value</dt><dd>3</dd>
+        <dt>script pubkey</dt><dd class=data>OP_RETURN</dd>
+      </dl>
+      <h2>2 Sat Ranges</h2>
+      <ul class=monospace>
+        <li><a href=/sat/0 class=mythic>0</a></li>
+        <li><a href=/range/1/3 class=common>1–3</a></li>
+      </ul>
+      "
+      .unindent()
+    );
+  }
 }
@@ -1,15 +1,204 @@
-use super::*;
+use {super::*, bitcoin::hashes::sha256};
+
+/// Served by `/api/inscription/:inscription_id`, `/api/inscriptions`, and
+/// `/inscription/:inscription_id` to clients that accept JSON. `sat` is only
+/// known if the index tracks sat ranges.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct InscriptionJson {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) number: u64,
+  pub(crate) content_size: Option<usize>,
+  pub(crate) content_type: Option<String>,
+  /// Present if the content's leading bytes identify a different type than
+  /// the declared one.
+  pub(crate) sniffed_content_type: Option<String>,
+  pub(crate) sha256: Option<sha256::Hash>,
+  pub(crate) collection: Option<CollectionSlug>,
+  pub(crate) location: SatPoint,
+  pub(crate) sat: Option<Sat>,
+  pub(crate) explorer: Option<String>,
+  /// Whether the inscription was sent to an output that can never be spent.
+  pub(crate) burned: bool,
+}
+
+impl InscriptionJson {
+  pub(crate) fn new(
+    explorer: &Explorer,
+    inscription_id: InscriptionId,
+    number: u64,
+    inscription: &Inscription,
+    satpoint: SatPoint,
+    burned: bool,
+  ) -> Self {
+    Self {
+      inscription_id,
+      number,
+      content_size: inscription.content_size(),
+      content_type: inscription.content_type().map(str::to_owned),
+      sniffed_content_type: inscription.sniffed_content_type().map(str::to_owned),
+      sha256: None,
+      collection: inscription.collection(),
+      location: satpoint,
+      sat: None,
+      explorer: explorer.transaction(inscription_id),
+      burned,
+    }
+  }
+
+  /// Include the SHA-256 digest of the content.
+  pub(crate) fn sha256(self, sha256: sha256::Hash) -> Self {
+    Self {
+      sha256: Some(sha256),
+      ..self
+    }
+  }
+
+  /// Include the inscribed sat.
+  pub(crate) fn sat(self, sat: Option<Sat>) -> Self {
+    Self { sat, ..self }
+  }
+}
+
+/// Served by `/api/inscription/:inscription_id/digest`, so that copies of
+/// content can be checked without downloading it. Inscriptions without content
+/// have the digest of no bytes.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct InscriptionDigestJson {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) sha256: sha256::Hash,
+  pub(crate) content_length: usize,
+  pub(crate) content_type: Option<String>,
+}
+
+impl InscriptionDigestJson {
+  pub(crate) fn new(inscription_id: InscriptionId, inscription: &Inscription) -> Self {
+    let content = inscription.content_bytes().unwrap_or_default();
+
+    Self {
+      inscription_id,
+      sha256: sha256::Hash::hash(content),
+      content_length: content.len(),
+      content_type: inscription.content_type().map(str::to_owned),
+    }
+  }
+}
 
 #[derive(Boilerplate)]
 pub(crate) struct InscriptionHtml {
-  pub(crate) inscription_id: InscriptionId,
-  pub(crate) inscription: Inscription,
-  pub(crate) satpoint: SatPoint,
+  data: InscriptionJson,
+  inscription: Inscription,
+  inline: bool,
+  next: Option<InscriptionId>,
+  previous: Option<InscriptionId>,
+  base_url: Option<Url>,
+}
+
+impl InscriptionHtml {
+  pub(crate) fn new(
+    explorer: &Explorer,
+    inscription_id: InscriptionId,
+    number: u64,
+    inscription: Inscription,
+    satpoint: SatPoint,
+    burned: bool,
+  ) -> Self {
+    Self {
+      data: InscriptionJson::new(
+        explorer,
+        inscription_id,
+        number,
+        &inscription,
+        satpoint,
+        burned,
+      ),
+      inscription,
+      inline: false,
+      next: None,
+      previous: None,
+      base_url: None,
+    }
+  }
+
+  /// The URL the server is reachable at, ending in a slash, which image
+  /// inscriptions' Open Graph images are linked relative to.
+  pub(crate) fn base_url(self, base_url: Option<Url>) -> Self {
+    Self { base_url, ..self }
+  }
+
+  /// Show the SHA-256 digest of the content.
+  pub(crate) fn sha256(self, sha256: sha256::Hash) -> Self {
+    Self {
+      data: self.data.sha256(sha256),
+      ..self
+    }
+  }
+
+  /// Include the inscribed sat in the page's JSON.
+  pub(crate) fn sat(self, sat: Option<Sat>) -> Self {
+    Self {
+      data: self.data.sat(sat),
+      ..self
+    }
+  }
+
+  /// Link to the inscriptions numbered one before and one after this one.
+  pub(crate) fn navigation(
+    self,
+    previous: Option<InscriptionId>,
+    next: Option<InscriptionId>,
+  ) -> Self {
+    Self {
+      previous,
+      next,
+      ..self
+    }
+  }
+
+  /// Show the content in the page itself, instead of in an `<iframe>` loading
+  /// `/preview`, for pages viewed without a server.
+  pub(crate) fn inline(self) -> Self {
+    Self {
+      inline: true,
+      ..self
+    }
+  }
 }
 
 impl PageContent for InscriptionHtml {
   fn title(&self) -> String {
-    format!("Inscription {}", self.inscription_id)
+    format!("Inscription {}", self.data.inscription_id)
+  }
+
+  fn open_graph(&self) -> OpenGraph {
+    let description = match (&self.data.content_type, self.data.content_size) {
+      (Some(content_type), Some(size)) => Some(format!(
+        "Inscription {}, {content_type}, {}",
+        self.data.number,
+        "byte".tally(size)
+      )),
+      (Some(content_type), None) => {
+        Some(format!("Inscription {}, {content_type}", self.data.number))
+      }
+      (None, _) => Some(format!("Inscription {}", self.data.number)),
+    };
+
+    let image = match &self.base_url {
+      Some(base_url) if self.inscription.media() == Media::Image => base_url
+        .join(&format!("content/{}", self.data.inscription_id))
+        .ok()
+        .map(|url| url.to_string()),
+      _ => None,
+    };
+
+    OpenGraph { description, image }
+  }
+}
+
+impl DataPage for InscriptionHtml {
+  type Data = InscriptionJson;
+
+  fn data(&self) -> InscriptionJson {
+    self.data.clone()
   }
 }
 
@@ -20,18 +209,21 @@ mod tests {
   #[test]
   fn txt_inscription() {
     pretty_assert_eq!(
-      InscriptionHtml {
-        inscription_id: InscriptionId::from_str(
-          "ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc"
-        )
-        .unwrap(),
-        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
-        satpoint: satpoint(1, 0),
-      }
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        false,
+      )
       .to_string(),
       "
         <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
         <dl>
+          <dt>number</dt>
+          <dd>1</dd>
           <dt>content size</dt>
           <dd>10 bytes</dd>
           <dt>content type</dt>
@@ -39,7 +231,9 @@ mod tests {
           <dt>location</dt>
           <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
         </dl>
-        HELLOWORLD
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
       "
       .unindent()
     );
@@ -48,15 +242,20 @@ mod tests {
   #[test]
   fn png_inscription() {
     pretty_assert_eq!(
-      InscriptionHtml {
-        inscription_id: InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc").unwrap(),
-        inscription: inscription("image/png", [1; 100]),
-        satpoint: satpoint(1, 0),
-      }
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc").unwrap(),
+        1,
+        inscription("image/png", [1; 100]),
+        satpoint(1, 0),
+        false,
+      )
       .to_string(),
       "
         <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
         <dl>
+          <dt>number</dt>
+          <dd>1</dd>
           <dt>content size</dt>
           <dd>100 bytes</dd>
           <dt>content type</dt>
@@ -64,7 +263,136 @@ mod tests {
           <dt>location</dt>
           <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
         </dl>
-        <img src='data:image/png;base64,AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQ=='>
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn image_inscription_open_graph() {
+    let inscription_id =
+      InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+        .unwrap();
+
+    let html = |base_url: Option<&str>| {
+      InscriptionHtml::new(
+        &Explorer::default(),
+        inscription_id,
+        1,
+        inscription("image/png", [1; 100]),
+        satpoint(1, 0),
+        false,
+      )
+      .base_url(base_url.map(|base_url| base_url.parse().unwrap()))
+    };
+
+    assert_eq!(
+      html(Some("https://example.com/ord/")).open_graph(),
+      OpenGraph {
+        description: Some("Inscription 1, image/png, 100 bytes".into()),
+        image: Some(format!("https://example.com/ord/content/{inscription_id}")),
+      }
+    );
+
+    assert_eq!(html(None).open_graph().image, None);
+
+    assert_regex_match!(
+      html(Some("https://example.com/"))
+        .page(Chain::Mainnet, false, Progress::default())
+        .to_string(),
+      format!(
+        ".*<meta property=og:title content='Inscription {inscription_id}'>
+    <meta property=og:description content='Inscription 1, image/png, 100 bytes'>
+    <meta property=og:image content='https://example.com/content/{inscription_id}'>
+    <meta name=twitter:card content=summary_large_image>.*"
+      )
+    );
+  }
+
+  #[test]
+  fn text_inscription_open_graph() {
+    let html = InscriptionHtml::new(
+      &Explorer::default(),
+      InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+        .unwrap(),
+      1,
+      inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+      satpoint(1, 0),
+      false,
+    )
+    .base_url(Some("https://example.com/".parse().unwrap()));
+
+    assert_eq!(
+      html.open_graph(),
+      OpenGraph {
+        description: Some("Inscription 1, text/plain;charset=utf-8, 10 bytes".into()),
+        image: None,
+      }
+    );
+
+    let page = html
+      .page(Chain::Mainnet, false, Progress::default())
+      .to_string();
+
+    assert!(page.contains("<meta name=twitter:card content=summary>"));
+    assert!(!page.contains("og:image"));
+  }
+
+  #[test]
+  fn large_content_size_is_shown_in_binary_units() {
+    let html = InscriptionHtml::new(
+      &Explorer::default(),
+      InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+        .unwrap(),
+      1,
+      inscription("text/plain;charset=utf-8", vec![b'a'; 1536]),
+      satpoint(1, 0),
+      false,
+    );
+
+    assert_regex_match!(
+      html.to_string(),
+      ".*<dt>content size</dt>
+  <dd><span title='1\u{2009}536 bytes'>1.5 KiB</span></dd>
+.*"
+    );
+
+    assert_eq!(html.data().content_size, Some(1536));
+  }
+
+  #[test]
+  fn spoofed_inscription() {
+    pretty_assert_eq!(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        inscription("image/png", "<html><script>alert(1)</script></html>"),
+        satpoint(1, 0),
+        false,
+      )
+      .to_string(),
+      "
+        <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
+        <dl>
+          <dt>number</dt>
+          <dd>1</dd>
+          <dt>content size</dt>
+          <dd>38 bytes</dd>
+          <dt>content type</dt>
+          <dd>image/png</dd>
+          <dt>sniffed content type</dt>
+          <dd>text/html</dd>
+          <dt>location</dt>
+          <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
+        </dl>
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
       "
       .unindent()
     );
@@ -73,22 +401,309 @@ mod tests {
   #[test]
   fn empty_inscription() {
     pretty_assert_eq!(
-      InscriptionHtml {
-        inscription_id: InscriptionId::from_str(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        Inscription::new(None, None),
+        satpoint(1, 0),
+        false,
+      )
+      .to_string(),
+      "
+        <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
+        <dl>
+          <dt>number</dt>
+          <dd>1</dd>
+          <dt>location</dt>
+          <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
+        </dl>
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn explorer_link() {
+    pretty_assert_eq!(
+      InscriptionHtml::new(
+        &Explorer::new(vec!["https://mempool.space/tx/{txid}".parse().unwrap()]).unwrap(),
+        InscriptionId::from_str(
           "ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc"
         )
         .unwrap(),
-        inscription: Inscription::new(None, None),
-        satpoint: satpoint(1, 0),
-      }
+        1,
+        Inscription::new(None, None),
+        satpoint(1, 0),
+        false,
+      )
+      .to_string(),
+      "
+        <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
+        <dl>
+          <dt>number</dt>
+          <dd>1</dd>
+          <dt>location</dt>
+          <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
+          <dt>explorer</dt>
+          <dd><a href=https://mempool.space/tx/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc class=monospace>https://mempool.space/tx/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</a></dd>
+        </dl>
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn inline_content() {
+    pretty_assert_eq!(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        false,
+      )
+      .inline()
+      .to_string(),
+      "
+        <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
+        <dl>
+          <dt>number</dt>
+          <dd>1</dd>
+          <dt>content size</dt>
+          <dd>10 bytes</dd>
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
+          <dt>location</dt>
+          <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
+        </dl>
+        HELLOWORLD
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn navigation() {
+    let html = |previous, next| {
+      InscriptionHtml::new(
+        &Explorer::default(),
+        outpoint(2).txid,
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        false,
+      )
+      .navigation(previous, next)
+      .to_string()
+    };
+
+    let previous = outpoint(1).txid;
+    let next = outpoint(3).txid;
+
+    assert!(
+      html(None, Some(next)).ends_with(&format!(
+        "</iframe>\nprev\n<a href=/inscription/{next}>next</a>\n"
+      )),
+      "first inscription"
+    );
+    assert!(
+      html(Some(previous), Some(next)).ends_with(&format!(
+        "</iframe>\n<a href=/inscription/{previous}>prev</a>\n<a href=/inscription/{next}>next</a>\n"
+      )),
+      "middle inscription"
+    );
+    assert!(
+      html(Some(previous), None).ends_with(&format!(
+        "</iframe>\n<a href=/inscription/{previous}>prev</a>\nnext\n"
+      )),
+      "latest inscription"
+    );
+  }
+
+  #[test]
+  fn inline_content_has_no_navigation() {
+    let html = InscriptionHtml::new(
+      &Explorer::default(),
+      outpoint(2).txid,
+      1,
+      inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+      satpoint(1, 0),
+      false,
+    )
+    .navigation(Some(outpoint(1).txid), Some(outpoint(3).txid))
+    .inline()
+    .to_string();
+
+    assert!(!html.contains("prev"), "{html}");
+    assert!(!html.contains("next"), "{html}");
+  }
+
+  #[test]
+  fn json_fields_match_html_labels() {
+    #[track_caller]
+    fn case(html: InscriptionHtml) {
+      let labels = Regex::new("<dt>([^<]*)</dt>")
+        .unwrap()
+        .captures_iter(&html.to_string())
+        .map(|captures| captures[1].replace(' ', "_"))
+        .collect::<BTreeSet<String>>();
+
+      let fields = serde_json::to_value(html.data())
+        .unwrap()
+        .as_object()
+        .unwrap()
+        .iter()
+        .filter(|(key, value)| {
+          !["burned", "inscription_id"].contains(&key.as_str()) && !value.is_null()
+        })
+        .map(|(key, _)| key.clone())
+        .collect::<BTreeSet<String>>();
+
+      assert_eq!(labels, fields);
+    }
+
+    let inscription_id =
+      InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+        .unwrap();
+
+    case(InscriptionHtml::new(
+      &Explorer::new(vec!["https://mempool.space/tx/{txid}".parse().unwrap()]).unwrap(),
+      inscription_id,
+      1,
+      inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+      satpoint(1, 0),
+      false,
+    ));
+
+    case(InscriptionHtml::new(
+      &Explorer::default(),
+      inscription_id,
+      1,
+      Inscription::new(None, None),
+      satpoint(1, 0),
+      false,
+    ));
+
+    case(InscriptionHtml::new(
+      &Explorer::default(),
+      inscription_id,
+      1,
+      inscription("image/png", "<html>"),
+      satpoint(1, 0),
+      false,
+    ));
+
+    case(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        inscription_id,
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        false,
+      )
+      .sha256(sha256::Hash::hash(b"HELLOWORLD"))
+      .sat(Some(Sat(5))),
+    );
+  }
+
+  #[test]
+  fn sat_is_linked() {
+    assert_regex_match!(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        false,
+      )
+      .sat(Some(Sat(5000000000)))
+      .to_string(),
+      ".*<dt>location</dt>
+  <dd>1{64}:1:0</dd>
+  <dt>sat</dt>
+  <dd><a href=/sat/5000000000>5000000000</a></dd>
+</dl>.*"
+    );
+  }
+
+  #[test]
+  fn sha256() {
+    pretty_assert_eq!(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        false,
+      )
+      .sha256(sha256::Hash::hash(b"HELLOWORLD"))
       .to_string(),
       "
         <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
         <dl>
+          <dt>number</dt>
+          <dd>1</dd>
+          <dt>content size</dt>
+          <dd>10 bytes</dd>
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
+          <dt>sha256</dt>
+          <dd class=monospace>0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74</dd>
+          <dt>location</dt>
+          <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
+        </dl>
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn burned_inscription() {
+    pretty_assert_eq!(
+      InscriptionHtml::new(
+        &Explorer::default(),
+        InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+          .unwrap(),
+        1,
+        inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        satpoint(1, 0),
+        true,
+      )
+      .to_string(),
+      "
+        <h1>Inscription ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc</h1>
+        <p class=burned>burned: sent to an output that can never be spent</p>
+        <dl>
+          <dt>number</dt>
+          <dd>1</dd>
+          <dt>content size</dt>
+          <dd>10 bytes</dd>
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
           <dt>location</dt>
           <dd>1111111111111111111111111111111111111111111111111111111111111111:1:0</dd>
         </dl>
-        UNKNOWN
+        <iframe sandbox src=/preview/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+        prev
+        next
       "
       .unindent()
     );
@@ -0,0 +1,54 @@
+use super::*;
+
+/// Candidates for an inscription ID prefix that matches more than one
+/// inscription.
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionPrefixHtml {
+  pub(crate) prefix: InscriptionIdPrefix,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+}
+
+impl PageContent for InscriptionPrefixHtml {
+  fn title(&self) -> String {
+    format!("Inscriptions Starting With {}", self.prefix)
+  }
+}
+
+impl DataPage for InscriptionPrefixHtml {
+  type Data = Vec<InscriptionId>;
+
+  fn data(&self) -> Vec<InscriptionId> {
+    self.inscriptions.clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn inscription_prefix_html() {
+    let prefix = outpoint(1).txid.to_string()[..8].parse().unwrap();
+
+    pretty_assert_eq!(
+      InscriptionPrefixHtml {
+        prefix,
+        inscriptions: vec![outpoint(1).txid, outpoint(2).txid],
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Inscriptions Starting With {0}</h1>
+          <ul class=monospace>
+            <li><a href=/inscription/{1}>{1}</a></li>
+            <li><a href=/inscription/{2}>{2}</a></li>
+          </ul>
+        ",
+        &outpoint(1).txid.to_string()[..8],
+        outpoint(1).txid,
+        outpoint(2).txid,
+      )
+      .unindent()
+    );
+  }
+}
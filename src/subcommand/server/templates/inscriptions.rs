@@ -0,0 +1,63 @@
+use super::*;
+
+/// One page of all inscriptions, newest first. `prev` and `next` are the
+/// inscription numbers that the pages of older and newer inscriptions start
+/// at.
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionsHtml {
+  pub(crate) inscriptions: Vec<(InscriptionId, Thumbnail)>,
+  pub(crate) prev: Option<u64>,
+  pub(crate) next: Option<u64>,
+}
+
+impl PageContent for InscriptionsHtml {
+  fn title(&self) -> String {
+    "Inscriptions".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn inscriptions_html() {
+    pretty_assert_eq!(
+      InscriptionsHtml {
+        inscriptions: vec![
+          (outpoint(1).txid, Thumbnail::Image(outpoint(1).txid)),
+          (outpoint(2).txid, Thumbnail::Text("hello".into())),
+        ],
+        prev: Some(0),
+        next: Some(200),
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Inscriptions</h1>
+          <div class=gallery>
+            <a href=/inscription/{0}><img src=/content/{0} alt={0} loading=lazy></a>
+            <a href=/inscription/{1}><pre>hello</pre></a>
+          </div>
+          <a href=/inscriptions/0>prev</a>
+          <a href=/inscriptions/200>next</a>
+        ",
+        outpoint(1).txid,
+        outpoint(2).txid,
+      )
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn without_prev_and_next() {
+    let html = InscriptionsHtml {
+      inscriptions: Vec::new(),
+      prev: None,
+      next: None,
+    }
+    .to_string();
+
+    assert!(html.ends_with("</div>\nprev\nnext\n"), "{html}");
+  }
+}
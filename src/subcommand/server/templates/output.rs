@@ -3,9 +3,50 @@ use super::*;
 #[derive(Boilerplate)]
 pub(crate) struct OutputHtml {
   pub(crate) outpoint: OutPoint,
-  pub(crate) list: Option<List>,
+  pub(crate) list: Option<List<SatRanges>>,
   pub(crate) chain: Chain,
+  pub(crate) explorer: Explorer,
+  pub(crate) inscriptions: Vec<(SatPoint, InscriptionId)>,
   pub(crate) output: TxOut,
+  /// Which `page_size` sat ranges to show, for outputs with too many to show
+  /// at once.
+  pub(crate) page_index: usize,
+  pub(crate) page_size: usize,
+}
+
+impl OutputHtml {
+  fn sat_ranges_page<'a>(
+    &self,
+    sat_ranges: &'a SatRanges,
+  ) -> impl Iterator<Item = (u64, u64)> + 'a {
+    sat_ranges.page(
+      self.page_index.saturating_mul(self.page_size),
+      self.page_size,
+    )
+  }
+
+  /// Ranges after the shown page.
+  fn more_sat_ranges(&self, sat_ranges: &SatRanges) -> usize {
+    sat_ranges.len().saturating_sub(
+      self
+        .page_index
+        .saturating_add(1)
+        .saturating_mul(self.page_size),
+    )
+  }
+}
+
+/// `spent` and `sat_ranges` are only known if the index tracks sat ranges,
+/// and there are no sat ranges for a spent output.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct OutputJson {
+  pub(crate) outpoint: OutPoint,
+  pub(crate) value: u64,
+  pub(crate) script_pubkey: String,
+  pub(crate) address: Option<String>,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) spent: Option<bool>,
+  pub(crate) sat_ranges: Option<Vec<(u64, u64)>>,
 }
 
 impl PageContent for OutputHtml {
@@ -14,6 +55,33 @@ impl PageContent for OutputHtml {
   }
 }
 
+impl DataPage for OutputHtml {
+  type Data = OutputJson;
+
+  fn data(&self) -> OutputJson {
+    OutputJson {
+      outpoint: self.outpoint,
+      value: self.output.value,
+      script_pubkey: self.output.script_pubkey.asm(),
+      address: self
+        .chain
+        .address_from_script(&self.output.script_pubkey)
+        .ok()
+        .map(|address| address.to_string()),
+      inscriptions: self
+        .inscriptions
+        .iter()
+        .map(|(_satpoint, inscription_id)| *inscription_id)
+        .collect(),
+      spent: self.list.as_ref().map(|list| *list == List::Spent),
+      sat_ranges: match &self.list {
+        Some(List::Unspent(ranges)) => Some(ranges.iter().collect()),
+        _ => None,
+      },
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use {
@@ -28,12 +96,16 @@ mod tests {
         outpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
           .parse()
           .unwrap(),
-        list: Some(List::Unspent(vec![(0, 1), (1, 3)])),
+        list: Some(List::Unspent(vec![(0, 1), (1, 3)].into())),
         chain: Chain::Mainnet,
+        explorer: Explorer::default(),
+        inscriptions: Vec::new(),
         output: TxOut {
           value: 3,
           script_pubkey: Script::new_p2pkh(&PubkeyHash::all_zeros()),
         },
+        page_index: 0,
+        page_size: 100,
       }
       .to_string(),
       "
@@ -62,10 +134,14 @@ mod tests {
           .unwrap(),
         list: Some(List::Spent),
         chain: Chain::Mainnet,
+        explorer: Explorer::default(),
+        inscriptions: Vec::new(),
         output: TxOut {
           value: 1,
           script_pubkey: script::Builder::new().push_scriptint(0).into_script(),
         },
+        page_index: 0,
+        page_size: 100,
       }
       .to_string(),
       "
@@ -89,10 +165,14 @@ mod tests {
           .unwrap(),
         list: None,
         chain: Chain::Mainnet,
+        explorer: Explorer::default(),
+        inscriptions: Vec::new(),
         output: TxOut {
           value: 3,
           script_pubkey: Script::new_p2pkh(&PubkeyHash::all_zeros()),
         },
+        page_index: 0,
+        page_size: 100,
       }
       .to_string(),
       "
@@ -106,4 +186,164 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn with_inscriptions() {
+    let first = outpoint(2).txid;
+    let second = outpoint(3).txid;
+    let outpoint = outpoint(1);
+
+    pretty_assert_eq!(
+      OutputHtml {
+        outpoint,
+        list: None,
+        chain: Chain::Mainnet,
+        explorer: Explorer::default(),
+        inscriptions: vec![
+          (
+            SatPoint {
+              outpoint,
+              offset: 0
+            },
+            first
+          ),
+          (
+            SatPoint {
+              outpoint,
+              offset: u64::MAX,
+            },
+            second,
+          ),
+        ],
+        output: TxOut {
+          value: 3,
+          script_pubkey: Script::new(),
+        },
+        page_index: 0,
+        page_size: 100,
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Output <span class=monospace>{outpoint}</span></h1>
+          <dl>
+            <dt>value</dt><dd>3</dd>
+            <dt>script pubkey</dt><dd class=data></dd>
+          </dl>
+          <h2>2 Inscriptions</h2>
+          <ul class=monospace>
+            <li><a href=/inscription/{first}>{first}</a> at offset 0</li>
+            <li><a href=/inscription/{second}>{second}</a> at offset 18 446 744 073 709 551 615</li>
+          </ul>
+        "
+      )
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn explorer_links() {
+    pretty_assert_eq!(
+      OutputHtml {
+        outpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0"
+          .parse()
+          .unwrap(),
+        list: None,
+        chain: Chain::Mainnet,
+        explorer: Explorer::new(vec![
+          "https://mempool.space/address/{address}".parse().unwrap(),
+          "https://mempool.space/tx/{txid}".parse().unwrap(),
+        ])
+        .unwrap(),
+        inscriptions: Vec::new(),
+        output: TxOut {
+          value: 3,
+          script_pubkey: Script::new_p2pkh(&PubkeyHash::all_zeros()),
+        },
+        page_index: 0,
+        page_size: 100,
+      }
+      .to_string(),
+      "
+        <h1>Output <span class=monospace>4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0</span></h1>
+        <dl>
+          <dt>value</dt><dd>3</dd>
+          <dt>script pubkey</dt><dd class=data>OP_DUP OP_HASH160 OP_PUSHBYTES_20 0000000000000000000000000000000000000000 OP_EQUALVERIFY OP_CHECKSIG</dd>
+          <dt>address</dt><dd><a href=https://mempool.space/address/1111111111111111111114oLvT2 class=monospace>1111111111111111111114oLvT2</a></dd>
+          <dt>explorer</dt><dd><a href=https://mempool.space/tx/4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b class=monospace>https://mempool.space/tx/4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn paginated_sat_ranges() {
+    let output = |page_index| OutputHtml {
+      outpoint: outpoint(1),
+      list: Some(List::Unspent(
+        vec![(0, 1), (1, 3), (3, 4), (4, 10), (10, 11)].into(),
+      )),
+      chain: Chain::Mainnet,
+      explorer: Explorer::default(),
+      inscriptions: Vec::new(),
+      output: TxOut {
+        value: 11,
+        script_pubkey: Script::new(),
+      },
+      page_index,
+      page_size: 2,
+    };
+
+    pretty_assert_eq!(
+      output(0).to_string(),
+      format!(
+        "
+          <h1>Output <span class=monospace>{0}</span></h1>
+          <dl>
+            <dt>value</dt><dd>11</dd>
+            <dt>script pubkey</dt><dd class=data></dd>
+          </dl>
+          <h2>5 Sat Ranges</h2>
+          <ul class=monospace>
+            <li><a href=/sat/0 class=mythic>0</a></li>
+            <li><a href=/range/1/3 class=common>1–3</a></li>
+          </ul>
+          <p>3 more sat ranges</p>
+          prev
+          <a href=/output/{0}?page=1>next</a>
+        ",
+        outpoint(1),
+      )
+      .unindent()
+    );
+
+    assert_regex_match!(
+      output(1).to_string(),
+      format!(
+        ".*<ul class=monospace>
+  <li><a href=/sat/3 class=common>3</a></li>
+  <li><a href=/range/4/10 class=common>4–10</a></li>
+</ul>
+<p>1 more sat range</p>
+<a href=/output/{0}\\?page=0>prev</a>
+<a href=/output/{0}\\?page=2>next</a>
+",
+        outpoint(1),
+      )
+    );
+
+    assert_regex_match!(
+      output(2).to_string(),
+      format!(
+        ".*<ul class=monospace>
+  <li><a href=/sat/10 class=common>10</a></li>
+</ul>
+<a href=/output/{0}\\?page=1>prev</a>
+next
+",
+        outpoint(1),
+      )
+    );
+  }
 }
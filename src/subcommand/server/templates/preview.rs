@@ -0,0 +1,169 @@
+use super::*;
+
+/// A standalone document showing an inscription's content, for embedding in
+/// a sandboxed `<iframe>`. Media is loaded from `/content`, so large files
+/// aren't inlined into the document.
+#[derive(Boilerplate)]
+pub(crate) struct PreviewHtml {
+  inscription_id: InscriptionId,
+  inscription: Inscription,
+}
+
+impl PreviewHtml {
+  pub(crate) fn new(inscription_id: InscriptionId, inscription: Inscription) -> Self {
+    Self {
+      inscription_id,
+      inscription,
+    }
+  }
+
+  fn text(&self) -> Option<&str> {
+    str::from_utf8(self.inscription.content_bytes()?).ok()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn preview(inscription: Inscription) -> String {
+    PreviewHtml::new(
+      InscriptionId::from_str("ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc")
+        .unwrap(),
+      inscription,
+    )
+    .to_string()
+  }
+
+  #[test]
+  fn text() {
+    assert_regex_match!(
+      preview(inscription("text/plain;charset=utf-8", "<b>HELLO</b>")),
+      ".*<body>\n    <pre>&lt;b&gt;HELLO&lt;/b&gt;</pre>\n  </body>.*"
+    );
+  }
+
+  #[test]
+  fn large_text_is_framed() {
+    assert_regex_match!(
+      preview(inscription(
+        "text/plain;charset=utf-8",
+        "a".repeat(Inscription::MAX_INLINE_SIZE + 1)
+      )),
+      ".*<body>
+    <iframe sandbox src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn png() {
+    assert_regex_match!(
+      preview(inscription("image/png", [1; 100])),
+      ".*<body>
+    <img src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn spoofed() {
+    assert_regex_match!(
+      preview(inscription(
+        "image/png",
+        "<html><script>alert(1)</script></html>"
+      )),
+      ".*<body>
+    <p>content declared as image/png looks like text/html, so it is not displayed</p>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn image() {
+    assert_regex_match!(
+      preview(inscription("image/jpeg", [0xff, 0xd8, 0xff])),
+      ".*<body>
+    <img src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn audio() {
+    assert_regex_match!(
+      preview(inscription("audio/mpeg", [1; 100])),
+      ".*<body>
+    <audio controls src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></audio>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn video() {
+    assert_regex_match!(
+      preview(inscription("video/webm", [1; 100])),
+      ".*<body>
+    <video controls src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></video>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn svg_is_sandboxed() {
+    assert_regex_match!(
+      preview(inscription(
+        "image/svg+xml",
+        "<svg><script>alert(1)</script></svg>"
+      )),
+      ".*<body>
+    <iframe sandbox src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn html_is_sandboxed() {
+    assert_regex_match!(
+      preview(inscription(
+        "text/html;charset=utf-8",
+        "<html><script>alert(1)</script></html>"
+      )),
+      ".*<body>
+    <iframe sandbox src=/content/ec90757eb3b164aa43fc548faa2fa0c52025494f2c15d5ddf11260b4034ac6dc></iframe>
+  </body>.*"
+    );
+  }
+
+  #[test]
+  fn json_is_text() {
+    assert_regex_match!(
+      preview(inscription("application/json", r#"{"a":1}"#)),
+      ".*<body>\n    <pre>\\{&quot;a&quot;:1\\}</pre>\n  </body>.*"
+    );
+  }
+
+  #[test]
+  fn invalid_text() {
+    assert_regex_match!(
+      preview(inscription("text/plain;charset=utf-8", [0xff])),
+      ".*<body>\n    <p>UNKNOWN</p>\n  </body>.*"
+    );
+  }
+
+  #[test]
+  fn unknown() {
+    assert_regex_match!(
+      preview(inscription("application/pdf", "foo")),
+      ".*<body>\n    <p>UNKNOWN</p>\n  </body>.*"
+    );
+    assert_regex_match!(
+      preview(Inscription::new(None, None)),
+      ".*<body>\n    <p>UNKNOWN</p>\n  </body>.*"
+    );
+    assert_regex_match!(
+      preview(Inscription::new(Some("text/plain".into()), None)),
+      ".*<body>\n    <p>UNKNOWN</p>\n  </body>.*"
+    );
+  }
+}
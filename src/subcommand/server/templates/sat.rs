@@ -4,7 +4,39 @@ use super::*;
 pub(crate) struct SatHtml {
   pub(crate) sat: Sat,
   pub(crate) blocktime: Blocktime,
-  pub(crate) inscription: Option<Inscription>,
+  pub(crate) inscription: Option<InscriptionId>,
+  /// Whether the index records which sats are inscribed, without which
+  /// `inscription` is always `None`.
+  pub(crate) inscribed_sats: bool,
+  /// Present if the index records history for this sat.
+  pub(crate) history: Option<Vec<(Height, SatPoint)>>,
+  /// Transactions which paid this sat as a fee, if the index records them.
+  pub(crate) fees: Vec<(Height, Txid)>,
+  /// The current time, which confirmed block times are shown relative to.
+  pub(crate) now: i64,
+}
+
+/// `history` is only present if the index records history for this sat.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct SatJson {
+  pub(crate) number: u64,
+  pub(crate) decimal: String,
+  pub(crate) degree: String,
+  pub(crate) percentile: String,
+  pub(crate) name: String,
+  pub(crate) height: Height,
+  pub(crate) cycle: Cycle,
+  pub(crate) epoch: Epoch,
+  pub(crate) period: u64,
+  pub(crate) offset: u64,
+  pub(crate) rarity: Rarity,
+  pub(crate) traits: Vec<String>,
+  pub(crate) timestamp: i64,
+  /// Whether `timestamp` is that of a mined block, rather than an estimate.
+  pub(crate) confirmed: bool,
+  pub(crate) inscription: Option<InscriptionId>,
+  pub(crate) history: Option<Vec<(Height, SatPoint)>>,
+  pub(crate) fees: Vec<(Height, Txid)>,
 }
 
 impl PageContent for SatHtml {
@@ -13,6 +45,42 @@ impl PageContent for SatHtml {
   }
 }
 
+impl DataPage for SatHtml {
+  type Data = SatJson;
+
+  fn data(&self) -> SatJson {
+    let (timestamp, confirmed) = match self.blocktime {
+      Blocktime::Confirmed(timestamp) => (timestamp, true),
+      Blocktime::Expected(timestamp) => (timestamp, false),
+    };
+
+    SatJson {
+      number: self.sat.n(),
+      decimal: self.sat.decimal().to_string(),
+      degree: self.sat.degree().to_string(),
+      percentile: self.sat.percentile(),
+      name: self.sat.name(),
+      height: self.sat.height(),
+      cycle: self.sat.cycle(),
+      epoch: self.sat.epoch(),
+      period: self.sat.period(),
+      offset: self.sat.third(),
+      rarity: self.sat.rarity(),
+      traits: self
+        .sat
+        .traits()
+        .into_iter()
+        .map(|sat_trait| sat_trait.to_string())
+        .collect(),
+      timestamp,
+      confirmed,
+      inscription: self.inscription,
+      history: self.history.clone(),
+      fees: self.fees.clone(),
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -24,13 +92,17 @@ mod tests {
         sat: Sat(0),
         blocktime: Blocktime::Confirmed(0),
         inscription: None,
+        inscribed_sats: true,
+        history: None,
+        fees: Vec::new(),
+        now: 0,
       }
       .to_string(),
       "
         <h1>Sat 0</h1>
         <dl>
           <dt>decimal</dt><dd>0.0</dd>
-          <dt>degree</dt><dd>0°0′0″0‴</dd>
+          <dt>degree</dt><dd><a href=/cycle/0>0°</a><a href=/epoch/0>0′</a>0″<a href=/block/0>0‴</a></dd>
           <dt>percentile</dt><dd>0%</dd>
           <dt>name</dt><dd>nvtdijuwxlp</dd>
           <dt>cycle</dt><dd>0</dd>
@@ -39,7 +111,13 @@ mod tests {
           <dt>block</dt><dd>0</dd>
           <dt>offset</dt><dd>0</dd>
           <dt>rarity</dt><dd><span class=mythic>mythic</span></dd>
-          <dt>time</dt><dd>1970-01-01 00:00:00</dd>
+          <dt>traits</dt>
+          <dd>
+            <span>coin</span>
+            <span>alpha</span>
+            <span>decimal-palindrome</span>
+          </dd>
+          <dt>time</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01T00:00:00Z</time> (just now)</dd>
         </dl>
         prev
         <a href=/sat/1>next</a>
@@ -55,13 +133,17 @@ mod tests {
         sat: Sat(1),
         blocktime: Blocktime::Confirmed(0),
         inscription: None,
+        inscribed_sats: true,
+        history: None,
+        fees: Vec::new(),
+        now: 0,
       }
       .to_string(),
       "
         <h1>Sat 1</h1>
         <dl>
           <dt>decimal</dt><dd>0.1</dd>
-          <dt>degree</dt><dd>0°0′0″1‴</dd>
+          <dt>degree</dt><dd><a href=/cycle/0>0°</a><a href=/epoch/0>0′</a>0″<a href=/block/0>1‴</a></dd>
           <dt>percentile</dt><dd>0.000000000000047619047671428595%</dd>
           <dt>name</dt><dd>nvtdijuwxlo</dd>
           <dt>cycle</dt><dd>0</dd>
@@ -70,7 +152,11 @@ mod tests {
           <dt>block</dt><dd>0</dd>
           <dt>offset</dt><dd>1</dd>
           <dt>rarity</dt><dd><span class=common>common</span></dd>
-          <dt>time</dt><dd>1970-01-01 00:00:00</dd>
+          <dt>traits</dt>
+          <dd>
+            <span>decimal-palindrome</span>
+          </dd>
+          <dt>time</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01T00:00:00Z</time> (just now)</dd>
         </dl>
         <a href=/sat/0>prev</a>
         <a href=/sat/2>next</a>
@@ -85,66 +171,61 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         blocktime: Blocktime::Confirmed(0),
-        inscription: Some(inscription("text/plain;charset=utf-8", "HELLOWORLD")),
+        inscription: Some(outpoint(1).txid),
+        inscribed_sats: true,
+        history: None,
+        fees: Vec::new(),
+        now: 0,
       }
       .to_string(),
-      "
-        <h1>Sat 0</h1>
-        <dl>
-          <dt>decimal</dt><dd>0.0</dd>
-          <dt>degree</dt><dd>0°0′0″0‴</dd>
-          <dt>percentile</dt><dd>0%</dd>
-          <dt>name</dt><dd>nvtdijuwxlp</dd>
-          <dt>cycle</dt><dd>0</dd>
-          <dt>epoch</dt><dd>0</dd>
-          <dt>period</dt><dd>0</dd>
-          <dt>block</dt><dd>0</dd>
-          <dt>offset</dt><dd>0</dd>
-          <dt>rarity</dt><dd><span class=mythic>mythic</span></dd>
-          <dt>time</dt><dd>1970-01-01 00:00:00</dd>
-          <dt>inscription</dt>
-          <dd>HELLOWORLD</dd>
-        </dl>
-        prev
-        <a href=/sat/1>next</a>
-      "
+      format!(
+        "
+          <h1>Sat 0</h1>
+          <dl>
+            <dt>decimal</dt><dd>0.0</dd>
+            <dt>degree</dt><dd><a href=/cycle/0>0°</a><a href=/epoch/0>0′</a>0″<a href=/block/0>0‴</a></dd>
+            <dt>percentile</dt><dd>0%</dd>
+            <dt>name</dt><dd>nvtdijuwxlp</dd>
+            <dt>cycle</dt><dd>0</dd>
+            <dt>epoch</dt><dd>0</dd>
+            <dt>period</dt><dd>0</dd>
+            <dt>block</dt><dd>0</dd>
+            <dt>offset</dt><dd>0</dd>
+            <dt>rarity</dt><dd><span class=mythic>mythic</span></dd>
+            <dt>traits</dt>
+            <dd>
+              <span>coin</span>
+              <span>alpha</span>
+              <span>decimal-palindrome</span>
+            </dd>
+            <dt>time</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01T00:00:00Z</time> (just now)</dd>
+            <dt>inscription</dt>
+            <dd><a href=/inscription/{0} class=monospace>{0}</a></dd>
+          </dl>
+          <iframe sandbox src=/preview/{0}></iframe>
+          prev
+          <a href=/sat/1>next</a>
+        ",
+        outpoint(1).txid
+      )
       .unindent()
     );
   }
 
   #[test]
-  fn sat_inscriptions_are_escaped() {
-    pretty_assert_eq!(
+  fn inscribed_sat_index_disabled() {
+    assert_regex_match!(
       SatHtml {
         sat: Sat(0),
         blocktime: Blocktime::Confirmed(0),
-        inscription: Some(inscription(
-          "text/plain;charset=utf-8",
-          "<script>alert('HELLOWORLD');</script>",
-        )),
+        inscription: None,
+        inscribed_sats: false,
+        history: None,
+        fees: Vec::new(),
+        now: 0,
       }
       .to_string(),
-      "
-        <h1>Sat 0</h1>
-        <dl>
-          <dt>decimal</dt><dd>0.0</dd>
-          <dt>degree</dt><dd>0°0′0″0‴</dd>
-          <dt>percentile</dt><dd>0%</dd>
-          <dt>name</dt><dd>nvtdijuwxlp</dd>
-          <dt>cycle</dt><dd>0</dd>
-          <dt>epoch</dt><dd>0</dd>
-          <dt>period</dt><dd>0</dd>
-          <dt>block</dt><dd>0</dd>
-          <dt>offset</dt><dd>0</dd>
-          <dt>rarity</dt><dd><span class=mythic>mythic</span></dd>
-          <dt>time</dt><dd>1970-01-01 00:00:00</dd>
-          <dt>inscription</dt>
-          <dd>&lt;script&gt;alert(&apos;HELLOWORLD&apos;);&lt;/script&gt;</dd>
-        </dl>
-        prev
-        <a href=/sat/1>next</a>
-      "
-      .unindent()
+      ".*<dt>inscription</dt>\n  <dd>unknown, inscribed sat index disabled</dd>\n</dl>\nprev\n.*"
     );
   }
 
@@ -152,25 +233,33 @@ mod tests {
   fn last_sat_next_link_is_disabled() {
     pretty_assert_eq!(
       SatHtml {
-        sat: Sat::LAST,
+        sat: Sat::last(),
         blocktime: Blocktime::Confirmed(0),
         inscription: None,
+        inscribed_sats: true,
+        history: None,
+        fees: Vec::new(),
+        now: 0,
       }
       .to_string(),
       "
-        <h1>Sat 2099999997689999</h1>
+        <h1>Sat 2 099 999 997 689 999</h1>
         <dl>
           <dt>decimal</dt><dd>6929999.0</dd>
-          <dt>degree</dt><dd>5°209999′1007″0‴</dd>
+          <dt>degree</dt><dd><a href=/cycle/5>5°</a><a href=/epoch/32>209999′</a>1007″<a href=/block/6929999>0‴</a></dd>
           <dt>percentile</dt><dd>100%</dd>
           <dt>name</dt><dd>a</dd>
           <dt>cycle</dt><dd>5</dd>
           <dt>epoch</dt><dd>32</dd>
           <dt>period</dt><dd>3437</dd>
-          <dt>block</dt><dd>6929999</dd>
+          <dt>block</dt><dd>6 929 999</dd>
           <dt>offset</dt><dd>0</dd>
           <dt>rarity</dt><dd><span class=uncommon>uncommon</span></dd>
-          <dt>time</dt><dd>1970-01-01 00:00:00</dd>
+          <dt>traits</dt>
+          <dd>
+            <span>name-palindrome</span>
+          </dd>
+          <dt>time</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01T00:00:00Z</time> (just now)</dd>
         </dl>
         <a href=/sat/2099999997689998>prev</a>
         next
@@ -178,4 +267,79 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn sat_with_expected_blocktime() {
+    assert_regex_match!(
+      SatHtml {
+        sat: Sat(1),
+        blocktime: Blocktime::Expected(2035000000),
+        inscription: None,
+        inscribed_sats: true,
+        history: None,
+        fees: Vec::new(),
+        now: 0,
+      }
+      .to_string(),
+      ".*<dt>time</dt><dd>expected circa 2034-06-27</dd>.*"
+    );
+  }
+
+  #[test]
+  fn sat_with_history() {
+    assert_regex_match!(
+      SatHtml {
+        sat: Sat(5000000000),
+        blocktime: Blocktime::Confirmed(0),
+        inscription: None,
+        inscribed_sats: true,
+        history: Some(vec![
+          (Height(1), satpoint(1, 0)),
+          (Height(2), satpoint(2, 0))
+        ]),
+        fees: Vec::new(),
+        now: 0,
+      }
+      .to_string(),
+      format!(
+        ".*<h2>History</h2>
+<ol>
+  <li><a href=/block/1>1</a> <a href=/output/{} class=monospace>{}</a></li>
+  <li><a href=/block/2>2</a> <a href=/output/{} class=monospace>{}</a></li>
+</ol>
+",
+        outpoint(1),
+        satpoint(1, 0),
+        outpoint(2),
+        satpoint(2, 0),
+      )
+    );
+  }
+
+  #[test]
+  fn sat_paid_as_fee() {
+    assert_regex_match!(
+      SatHtml {
+        sat: Sat(0),
+        blocktime: Blocktime::Confirmed(0),
+        inscription: None,
+        inscribed_sats: true,
+        history: None,
+        fees: vec![(Height(1), outpoint(1).txid), (Height(2), outpoint(2).txid)],
+        now: 0,
+      }
+      .to_string(),
+      format!(
+        ".*<dt>time</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01T00:00:00Z</time> \\(just now\\)</dd>
+  <dt>paid as fee</dt><dd><a href=/tx/{} class=monospace>{}</a> in block <a href=/block/1>1</a></dd>
+  <dt>paid as fee</dt><dd><a href=/tx/{} class=monospace>{}</a> in block <a href=/block/2>2</a></dd>
+</dl>
+.*",
+        outpoint(1).txid,
+        outpoint(1).txid,
+        outpoint(2).txid,
+        outpoint(2).txid,
+      )
+    );
+  }
 }
@@ -0,0 +1,188 @@
+use super::*;
+
+#[derive(Boilerplate, Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct SettingsHtml {
+  pub(crate) version: Version,
+  pub(crate) chain: Chain,
+  pub(crate) data_dir: PathBuf,
+  pub(crate) config: Option<PathBuf>,
+  pub(crate) index: IndexSettings,
+  pub(crate) rpc: RpcSettings,
+  pub(crate) server: ServerSettings,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct IndexSettings {
+  pub(crate) path: PathBuf,
+  pub(crate) height_limit: Option<u64>,
+  pub(crate) sat_ranges: bool,
+  pub(crate) rare_sats: bool,
+  pub(crate) inscribed_sats: bool,
+  pub(crate) sat_history: bool,
+  pub(crate) fee_provenance: bool,
+  pub(crate) inscription_history: bool,
+  pub(crate) update_interval_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct RpcSettings {
+  pub(crate) url: String,
+  /// `None` if the default cookie file path can't be determined, which
+  /// doesn't matter when the RPC URL carries credentials.
+  pub(crate) cookie_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct ServerSettings {
+  pub(crate) address: String,
+  pub(crate) http_port: Option<u16>,
+  pub(crate) https_port: Option<u16>,
+  pub(crate) acme_domains: Vec<String>,
+  pub(crate) acme_contacts: Vec<String>,
+  pub(crate) acme_cache: Option<PathBuf>,
+  pub(crate) explorer_urls: Vec<String>,
+}
+
+impl PageContent for SettingsHtml {
+  fn title(&self) -> String {
+    "Settings".into()
+  }
+}
+
+impl DataPage for SettingsHtml {
+  type Data = Self;
+
+  fn data(&self) -> Self {
+    self.clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn settings() -> SettingsHtml {
+    SettingsHtml {
+      version: Version {
+        version: "1.2.3",
+        commit: Some("0123456789abcdef0123456789abcdef01234567"),
+        describe: None,
+        build_date: None,
+        features: Vec::new(),
+        index_schema_version: 1,
+      },
+      chain: Chain::Signet,
+      data_dir: "/ord/signet".into(),
+      config: None,
+      index: IndexSettings {
+        path: "/ord/signet/index.redb".into(),
+        height_limit: None,
+        sat_ranges: true,
+        rare_sats: false,
+        inscribed_sats: false,
+        sat_history: false,
+        fee_provenance: false,
+        inscription_history: false,
+        update_interval_ms: 100,
+      },
+      rpc: RpcSettings {
+        url: "http://[redacted]@127.0.0.1:38332".into(),
+        cookie_file: Some("/bitcoin/signet/.cookie".into()),
+      },
+      server: ServerSettings {
+        address: "0.0.0.0".into(),
+        http_port: Some(80),
+        https_port: None,
+        acme_domains: Vec::new(),
+        acme_contacts: Vec::new(),
+        acme_cache: None,
+        explorer_urls: Vec::new(),
+      },
+    }
+  }
+
+  #[test]
+  fn minimal() {
+    pretty_assert_eq!(
+      settings().to_string(),
+      "
+        <h1>Settings</h1>
+        <dl>
+          <dt>version</dt><dd>1.2.3 (0123456789abcdef0123456789abcdef01234567)</dd>
+          <dt>chain</dt><dd>signet</dd>
+          <dt>data dir</dt><dd class=monospace>/ord/signet</dd>
+        </dl>
+        <h2>Index</h2>
+        <dl>
+          <dt>path</dt><dd class=monospace>/ord/signet/index.redb</dd>
+          <dt>sat ranges</dt><dd>true</dd>
+          <dt>rare sats</dt><dd>false</dd>
+          <dt>inscribed sats</dt><dd>false</dd>
+          <dt>sat history</dt><dd>false</dd>
+          <dt>fee provenance</dt><dd>false</dd>
+          <dt>inscription history</dt><dd>false</dd>
+          <dt>update interval</dt><dd>100 ms</dd>
+        </dl>
+        <h2>Bitcoin Core RPC</h2>
+        <dl>
+          <dt>url</dt><dd class=monospace>http://[redacted]@127.0.0.1:38332</dd>
+          <dt>cookie file</dt><dd class=monospace>/bitcoin/signet/.cookie</dd>
+        </dl>
+        <h2>Server</h2>
+        <dl>
+          <dt>address</dt><dd class=monospace>0.0.0.0</dd>
+          <dt>http port</dt><dd>80</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn unresolved_cookie_file_is_omitted() {
+    let mut settings = settings();
+    settings.rpc.cookie_file = None;
+    assert!(!settings.to_string().contains("cookie file"));
+  }
+
+  #[test]
+  fn optional_settings() {
+    let mut settings = settings();
+    settings.config = Some("/ord/config.json".into());
+    settings.index.height_limit = Some(1000);
+    settings.server.https_port = Some(443);
+    settings.server.acme_domains = vec!["a.com".into(), "b.com".into()];
+    settings.server.acme_contacts = vec!["mailto:admin@a.com".into()];
+    settings.server.acme_cache = Some("/ord/signet/acme-cache".into());
+    settings.server.explorer_urls = vec!["https://mempool.space/tx/{txid}".into()];
+
+    assert_regex_match!(
+      settings.to_string(),
+      "
+        <h1>Settings</h1>
+        <dl>
+          .*
+          <dt>config</dt><dd class=monospace>/ord/config.json</dd>
+        </dl>
+        <h2>Index</h2>
+        <dl>
+          <dt>path</dt><dd class=monospace>/ord/signet/index.redb</dd>
+          <dt>height limit</dt><dd>1000</dd>
+          .*
+        </dl>
+        .*
+        <h2>Server</h2>
+        <dl>
+          <dt>address</dt><dd class=monospace>0.0.0.0</dd>
+          <dt>http port</dt><dd>80</dd>
+          <dt>https port</dt><dd>443</dd>
+          <dt>acme domains</dt><dd class=monospace>a.com b.com</dd>
+          <dt>acme contacts</dt><dd class=monospace>mailto:admin@a.com</dd>
+          <dt>acme cache</dt><dd class=monospace>/ord/signet/acme-cache</dd>
+          <dt>explorer urls</dt><dd class=monospace>https://mempool.space/tx/\\{txid\\}</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
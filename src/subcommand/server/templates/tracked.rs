@@ -0,0 +1,54 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct TrackedHtml {
+  pub(crate) descriptors: Vec<String>,
+  pub(crate) inscriptions: Vec<TrackedInscription>,
+}
+
+impl PageContent for TrackedHtml {
+  fn title(&self) -> String {
+    "Tracked Inscriptions".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tracked_html() {
+    let address = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+      .parse::<Address>()
+      .unwrap();
+
+    pretty_assert_eq!(
+      TrackedHtml {
+        descriptors: vec!["tr(xpub/0/*)#checksum".into()],
+        inscriptions: vec![TrackedInscription {
+          inscription: outpoint(1).txid,
+          satpoint: satpoint(1, 0),
+          address: address.clone(),
+        }],
+      }
+      .to_string(),
+      format!(
+        "
+          <h1>Tracked Inscriptions</h1>
+          <h2>1 Descriptor</h2>
+          <ul class=monospace>
+            <li>tr(xpub/0/*)#checksum</li>
+          </ul>
+          <h2>1 Inscription</h2>
+          <ul class=monospace>
+            <li><a href=/inscription/{0}>{0}</a> at <a href=/output/{1}>{2}</a> on {address}</li>
+          </ul>
+        ",
+        outpoint(1).txid,
+        outpoint(1),
+        satpoint(1, 0),
+      )
+      .unindent()
+    );
+  }
+}
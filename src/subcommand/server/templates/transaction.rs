@@ -3,7 +3,10 @@ use super::*;
 #[derive(Boilerplate)]
 pub(crate) struct TransactionHtml {
   chain: Chain,
+  explorer: Explorer,
+  fee_sat: Option<Sat>,
   inscription: Option<Inscription>,
+  inscriptions: Vec<(SatPoint, InscriptionId)>,
   transaction: Transaction,
   txid: Txid,
 }
@@ -12,12 +15,18 @@ impl TransactionHtml {
   pub(crate) fn new(
     transaction: Transaction,
     inscription: Option<Inscription>,
+    inscriptions: Vec<(SatPoint, InscriptionId)>,
+    fee_sat: Option<Sat>,
     chain: Chain,
+    explorer: Explorer,
   ) -> Self {
     Self {
       txid: transaction.txid(),
       chain,
+      explorer,
+      fee_sat,
       inscription,
+      inscriptions,
       transaction,
     }
   }
@@ -55,9 +64,20 @@ mod tests {
     };
 
     pretty_assert_eq!(
-      TransactionHtml::new(transaction, None, Chain::Mainnet).to_string(),
+      TransactionHtml::new(
+        transaction,
+        None,
+        Vec::new(),
+        None,
+        Chain::Mainnet,
+        Explorer::default()
+      )
+      .to_string(),
       "
         <h1>Transaction <span class=monospace>9108ec7cbe9f1231dbf6374251b7267fb31cb23f36ed5a1d7344f5635b17dfe9</span></h1>
+        <h2>0 Inputs</h2>
+        <ul class=monospace>
+        </ul>
         <h2>2 Outputs</h2>
         <ul class=monospace>
           <li>
@@ -65,7 +85,7 @@ mod tests {
               9108ec7cbe9f1231dbf6374251b7267fb31cb23f36ed5a1d7344f5635b17dfe9:0
             </a>
             <dl>
-              <dt>value</dt><dd>5000000000</dd>
+              <dt>value</dt><dd>5 000 000 000</dd>
               <dt>script pubkey</dt><dd class=data>OP_0</dd>
             </dl>
           </li>
@@ -74,7 +94,7 @@ mod tests {
               9108ec7cbe9f1231dbf6374251b7267fb31cb23f36ed5a1d7344f5635b17dfe9:1
             </a>
             <dl>
-              <dt>value</dt><dd>5000000000</dd>
+              <dt>value</dt><dd>5 000 000 000</dd>
               <dt>script pubkey</dt><dd class=data>OP_PUSHBYTES_1 01</dd>
             </dl>
           </li>
@@ -83,4 +103,196 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn explorer_links() {
+    let transaction = Transaction {
+      version: 0,
+      lock_time: PackedLockTime(0),
+      input: Vec::new(),
+      output: vec![TxOut {
+        value: 50 * COIN_VALUE,
+        script_pubkey: recipient().script_pubkey(),
+      }],
+    };
+
+    let txid = transaction.txid();
+
+    assert_regex_match!(
+      TransactionHtml::new(
+        transaction,
+        None,
+        Vec::new(),
+        None,
+        Chain::Mainnet,
+        Explorer::new(vec![
+          "https://mempool.space/address/{address}".parse().unwrap(),
+          "https://mempool.space/tx/{txid}".parse().unwrap(),
+        ])
+        .unwrap(),
+      )
+      .to_string(),
+      format!(
+        "<h1>Transaction <span class=monospace>{txid}</span></h1>
+<dl>
+  <dt>explorer</dt><dd><a href=https://mempool.space/tx/{txid} class=monospace>https://mempool.space/tx/{txid}</a></dd>
+</dl>
+.*
+      <dt>address</dt><dd><a href=https://mempool.space/address/{} class=monospace>{}</a></dd>
+.*",
+        Chain::Mainnet.address_from_script(&recipient().script_pubkey()).unwrap(),
+        Chain::Mainnet.address_from_script(&recipient().script_pubkey()).unwrap(),
+      )
+    );
+  }
+
+  #[test]
+  fn fee_sat() {
+    let transaction = Transaction {
+      version: 0,
+      lock_time: PackedLockTime(0),
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    let txid = transaction.txid();
+
+    assert_regex_match!(
+      TransactionHtml::new(
+        transaction,
+        None,
+        Vec::new(),
+        Some(Sat(100)),
+        Chain::Mainnet,
+        Explorer::default(),
+      )
+      .to_string(),
+      format!(
+        "<h1>Transaction <span class=monospace>{txid}</span></h1>
+<dl>
+  <dt>fees</dt><dd><a href=/sat/100 class=monospace>100</a> \\(<a href=/tx/{txid}/fees>location</a>\\)</dd>
+</dl>
+.*"
+      )
+    );
+  }
+
+  #[test]
+  fn inputs() {
+    let transaction = Transaction {
+      version: 0,
+      lock_time: PackedLockTime(0),
+      input: vec![
+        TxIn {
+          previous_output: OutPoint::null(),
+          script_sig: Script::new(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        },
+        TxIn {
+          previous_output: outpoint(1),
+          script_sig: Script::new(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        },
+      ],
+      output: Vec::new(),
+    };
+
+    assert_regex_match!(
+      TransactionHtml::new(
+        transaction,
+        None,
+        Vec::new(),
+        None,
+        Chain::Mainnet,
+        Explorer::default(),
+      )
+      .to_string(),
+      format!(
+        ".*<h2>2 Inputs</h2>
+<ul class=monospace>
+  <li>coinbase</li>
+  <li><a href=/output/{} class=monospace>{}</a></li>
+</ul>
+<h2>0 Outputs</h2>.*",
+        outpoint(1),
+        outpoint(1),
+      )
+    );
+  }
+
+  #[test]
+  fn inscriptions() {
+    let transaction = Transaction {
+      version: 0,
+      lock_time: PackedLockTime(0),
+      input: Vec::new(),
+      output: vec![TxOut {
+        value: 50 * COIN_VALUE,
+        script_pubkey: Script::new(),
+      }],
+    };
+
+    let txid = transaction.txid();
+
+    let satpoint = SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 1,
+    };
+
+    let inscription_id = outpoint(1).txid;
+
+    assert_regex_match!(
+      TransactionHtml::new(
+        transaction,
+        Some(inscription("text/plain;charset=utf-8", "HELLOWORLD")),
+        vec![(satpoint, inscription_id)],
+        None,
+        Chain::Mainnet,
+        Explorer::default(),
+      )
+      .to_string(),
+      format!(
+        "<h1>Transaction <span class=monospace>{txid}</span></h1>
+<h2>Inscription</h2>
+<a href=/inscription/{txid}>
+HELLOWORLD
+</a>
+<h2>1 Inscription in Outputs</h2>
+<ul class=monospace>
+  <li><a href=/inscription/{inscription_id}>{inscription_id}</a> at <a href=/output/{txid}:0>{satpoint}</a></li>
+</ul>
+<h2>0 Inputs</h2>.*"
+      )
+    );
+  }
+
+  #[test]
+  fn large_inscription_is_framed() {
+    let transaction = Transaction {
+      version: 0,
+      lock_time: PackedLockTime(0),
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    let txid = transaction.txid();
+
+    assert_regex_match!(
+      TransactionHtml::new(
+        transaction,
+        Some(inscription(
+          "text/plain;charset=utf-8",
+          "a".repeat(Inscription::MAX_INLINE_SIZE + 1)
+        )),
+        Vec::new(),
+        None,
+        Chain::Mainnet,
+        Explorer::default(),
+      )
+      .to_string(),
+      format!(".*<h2>Inscription</h2>\n<iframe sandbox src=/preview/{txid}></iframe>\n<h2>0 Inputs</h2>.*")
+    );
+  }
 }
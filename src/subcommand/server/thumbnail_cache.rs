@@ -0,0 +1,233 @@
+use {
+  super::*,
+  image::{
+    codecs::webp::WebPEncoder,
+    imageops::FilterType,
+    io::{Limits, Reader},
+    ColorType,
+  },
+  std::io::Cursor,
+  tokio::sync::Semaphore,
+};
+
+/// Downscaled WebP copies of image inscriptions, served by `/thumbnail`. Kept
+/// on disk, keyed by inscription ID, so that each inscription is only
+/// decoded and resized once. Images which can't be decoded are recorded too,
+/// so that they aren't decoded again.
+pub(crate) struct ThumbnailCache {
+  dir: PathBuf,
+  permits: Semaphore,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Entry {
+  Thumbnail(Vec<u8>),
+  Undecodable,
+}
+
+impl ThumbnailCache {
+  /// Longest side of a thumbnail, in pixels.
+  pub(crate) const MAX_DIMENSION: u32 = 256;
+  /// Longest side of an image which will be decoded, in pixels.
+  const MAX_DECODED_DIMENSION: u32 = 8192;
+  /// Most memory a decoder may allocate, in bytes.
+  const MAX_DECODER_ALLOCATION: u64 = 128 * 1024 * 1024;
+  /// Most thumbnails rendered at once.
+  const MAX_RENDERS: usize = 4;
+
+  pub(crate) fn new(dir: PathBuf, clear: bool) -> Result<Self> {
+    if clear && dir.exists() {
+      fs::remove_dir_all(&dir)
+        .with_context(|| format!("failed to clear thumbnail cache `{}`", dir.display()))?;
+    }
+
+    fs::create_dir_all(&dir)
+      .with_context(|| format!("failed to create thumbnail cache `{}`", dir.display()))?;
+
+    Ok(Self {
+      dir,
+      permits: Semaphore::new(Self::MAX_RENDERS),
+    })
+  }
+
+  pub(crate) fn get(&self, inscription_id: InscriptionId) -> Result<Option<Entry>> {
+    match fs::read(self.path(inscription_id)) {
+      Ok(thumbnail) => return Ok(Some(Entry::Thumbnail(thumbnail))),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+      Err(err) => return Err(err.into()),
+    }
+
+    if self.undecodable_path(inscription_id).exists() {
+      return Ok(Some(Entry::Undecodable));
+    }
+
+    Ok(None)
+  }
+
+  /// Written to a temporary file first, so that concurrent requests never
+  /// read a partial thumbnail.
+  pub(crate) fn insert(&self, inscription_id: InscriptionId, entry: &Entry) -> Result {
+    let (path, contents) = match entry {
+      Entry::Thumbnail(thumbnail) => (self.path(inscription_id), thumbnail.as_slice()),
+      Entry::Undecodable => (self.undecodable_path(inscription_id), [].as_slice()),
+    };
+    let temporary = self
+      .dir
+      .join(format!("{inscription_id}.{:?}.tmp", thread::current().id()));
+    fs::write(&temporary, contents)?;
+    fs::rename(temporary, path)?;
+    Ok(())
+  }
+
+  /// Render and cache the thumbnail of `content`, off the executor, waiting
+  /// while `MAX_RENDERS` others are being rendered.
+  pub(crate) async fn render(
+    self: Arc<Self>,
+    inscription_id: InscriptionId,
+    content: Bytes,
+  ) -> Result<Entry> {
+    let _permit = self.permits.acquire().await?;
+
+    let cache = self.clone();
+
+    task::spawn_blocking(move || {
+      let entry = match Self::thumbnail(&content) {
+        Some(thumbnail) => Entry::Thumbnail(thumbnail),
+        None => Entry::Undecodable,
+      };
+      cache.insert(inscription_id, &entry)?;
+      Ok(entry)
+    })
+    .await?
+  }
+
+  /// Decode `content` as a PNG, JPEG, GIF, or WebP image, shrink it so that
+  /// its longest side is at most `MAX_DIMENSION`, and encode it as lossless
+  /// WebP. Returns `None` if `content` can't be decoded, or is larger than
+  /// the decoding limits.
+  pub(crate) fn thumbnail(content: &[u8]) -> Option<Vec<u8>> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(Self::MAX_DECODED_DIMENSION);
+    limits.max_image_height = Some(Self::MAX_DECODED_DIMENSION);
+    limits.max_alloc = Some(Self::MAX_DECODER_ALLOCATION);
+
+    let mut reader = Reader::new(Cursor::new(content))
+      .with_guessed_format()
+      .ok()?;
+    reader.limits(limits);
+
+    let mut image = reader.decode().ok()?;
+
+    if image.width().max(image.height()) > Self::MAX_DIMENSION {
+      image = image.resize(
+        Self::MAX_DIMENSION,
+        Self::MAX_DIMENSION,
+        FilterType::Triangle,
+      );
+    }
+
+    let image = image.to_rgba8();
+
+    let mut thumbnail = Vec::new();
+
+    WebPEncoder::new_lossless(&mut thumbnail)
+      .encode(&image, image.width(), image.height(), ColorType::Rgba8)
+      .ok()?;
+
+    Some(thumbnail)
+  }
+
+  fn path(&self, inscription_id: InscriptionId) -> PathBuf {
+    self.dir.join(format!("{inscription_id}.webp"))
+  }
+
+  fn undecodable_path(&self, inscription_id: InscriptionId) -> PathBuf {
+    self.dir.join(format!("{inscription_id}.undecodable"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn dimensions(thumbnail: &[u8]) -> (u32, u32) {
+    let image = image::load_from_memory_with_format(thumbnail, image::ImageFormat::WebP).unwrap();
+    (image.width(), image.height())
+  }
+
+  #[test]
+  fn longest_side_is_shrunk() {
+    assert_eq!(
+      dimensions(&ThumbnailCache::thumbnail(&png(1024, 512)).unwrap()),
+      (256, 128)
+    );
+    assert_eq!(
+      dimensions(&ThumbnailCache::thumbnail(&png(100, 400)).unwrap()),
+      (64, 256)
+    );
+  }
+
+  #[test]
+  fn small_images_are_not_enlarged() {
+    assert_eq!(
+      dimensions(&ThumbnailCache::thumbnail(&png(16, 32)).unwrap()),
+      (16, 32)
+    );
+  }
+
+  #[test]
+  fn undecodable_images_have_no_thumbnail() {
+    assert_eq!(ThumbnailCache::thumbnail(b"foo"), None);
+  }
+
+  #[test]
+  fn images_beyond_decoding_limits_have_no_thumbnail() {
+    assert!(ThumbnailCache::thumbnail(&png(8192, 1)).is_some());
+    assert_eq!(ThumbnailCache::thumbnail(&png(8193, 1)), None);
+    assert_eq!(ThumbnailCache::thumbnail(&png(1, 8193)), None);
+  }
+
+  #[test]
+  fn thumbnails_are_cached_on_disk() {
+    let tempdir = TempDir::new().unwrap();
+    let dir = tempdir.path().join("thumbnails");
+
+    let cache = ThumbnailCache::new(dir.clone(), false).unwrap();
+    assert_eq!(cache.get(outpoint(1).txid).unwrap(), None);
+
+    cache
+      .insert(outpoint(1).txid, &Entry::Thumbnail(b"foo".to_vec()))
+      .unwrap();
+    assert_eq!(
+      cache.get(outpoint(1).txid).unwrap(),
+      Some(Entry::Thumbnail(b"foo".to_vec()))
+    );
+
+    let cache = ThumbnailCache::new(dir.clone(), false).unwrap();
+    assert_eq!(
+      cache.get(outpoint(1).txid).unwrap(),
+      Some(Entry::Thumbnail(b"foo".to_vec()))
+    );
+
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    let cache = ThumbnailCache::new(dir, true).unwrap();
+    assert_eq!(cache.get(outpoint(1).txid).unwrap(), None);
+  }
+
+  #[test]
+  fn undecodable_images_are_cached_on_disk() {
+    let tempdir = TempDir::new().unwrap();
+    let dir = tempdir.path().join("thumbnails");
+
+    let cache = ThumbnailCache::new(dir.clone(), false).unwrap();
+    cache.insert(outpoint(1).txid, &Entry::Undecodable).unwrap();
+
+    let cache = ThumbnailCache::new(dir, false).unwrap();
+    assert_eq!(
+      cache.get(outpoint(1).txid).unwrap(),
+      Some(Entry::Undecodable)
+    );
+    assert_eq!(cache.get(outpoint(2).txid).unwrap(), None);
+  }
+}
@@ -0,0 +1,212 @@
+use {
+  super::*,
+  crate::descriptor::Descriptor,
+  bitcoin::{
+    secp256k1::{Secp256k1, VerifyOnly},
+    Network,
+  },
+  std::collections::{HashMap, HashSet},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct TrackedInscription {
+  pub(crate) inscription: InscriptionId,
+  pub(crate) satpoint: SatPoint,
+  pub(crate) address: Address,
+}
+
+/// Scripts derived from watch-only descriptors, for finding the inscriptions
+/// they hold through the address index. Ranged descriptors start out derived through the gap limit,
+/// and whenever one of their scripts is seen holding an inscription, more
+/// are derived so that the gap limit still lies beyond it.
+pub(crate) struct Tracker {
+  descriptors: Vec<Descriptor>,
+  gap_limit: u32,
+  network: Network,
+  secp: Secp256k1<VerifyOnly>,
+  state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+  /// How many scripts have been derived from each descriptor.
+  derived: Vec<u32>,
+  /// Derived scripts, and the descriptor and index they were derived from.
+  scripts: HashMap<Script, (usize, u32)>,
+}
+
+impl Tracker {
+  pub(crate) fn new(descriptors: Vec<Descriptor>, gap_limit: u32, chain: Chain) -> Result<Self> {
+    for descriptor in &descriptors {
+      if let Some(network) = descriptor.network() {
+        if (network == Network::Bitcoin) != (chain == Chain::Mainnet) {
+          bail!("descriptor `{descriptor}` has {network} keys, but ord is running on {chain}");
+        }
+      }
+    }
+
+    let tracker = Self {
+      gap_limit,
+      network: chain.network(),
+      secp: Secp256k1::verification_only(),
+      state: Mutex::new(State {
+        derived: vec![0; descriptors.len()],
+        ..Default::default()
+      }),
+      descriptors,
+    };
+
+    {
+      let mut state = tracker.state.lock().unwrap();
+      for i in 0..tracker.descriptors.len() {
+        tracker.derive(&mut state, i, gap_limit)?;
+      }
+    }
+
+    Ok(tracker)
+  }
+
+  pub(crate) fn descriptors(&self) -> &[Descriptor] {
+    &self.descriptors
+  }
+
+  /// Derive scripts from descriptor `i` until `count` have been derived.
+  /// Returns whether any new ones were.
+  fn derive(&self, state: &mut State, i: usize, count: u32) -> Result<bool> {
+    let descriptor = &self.descriptors[i];
+
+    let count = if descriptor.is_ranged() { count } else { 1 };
+
+    let start = state.derived[i];
+
+    for index in start..count {
+      state
+        .scripts
+        .insert(descriptor.script_pubkey(&self.secp, index)?, (i, index));
+    }
+
+    state.derived[i] = state.derived[i].max(count);
+
+    Ok(count > start)
+  }
+
+  /// The inscriptions on tracked scripts, ordered by satpoint. Requires an
+  /// index created with `--index-addresses`.
+  pub(crate) fn inscriptions(&self, index: &Index) -> Result<Vec<TrackedInscription>> {
+    let mut checked = HashSet::new();
+    let mut tracked = Vec::new();
+
+    // Scripts holding inscriptions cause more to be derived, so keep going
+    // until every derived script has been checked.
+    loop {
+      let unchecked = self
+        .state
+        .lock()
+        .unwrap()
+        .scripts
+        .iter()
+        .filter(|(script_pubkey, _)| !checked.contains(*script_pubkey))
+        .map(|(script_pubkey, &derivation)| (script_pubkey.clone(), derivation))
+        .collect::<Vec<(Script, (usize, u32))>>();
+
+      if unchecked.is_empty() {
+        break;
+      }
+
+      for (script_pubkey, (i, used)) in unchecked {
+        let mut used_script = false;
+
+        for outpoint in index.get_address_outpoints(&script_pubkey)? {
+          for (satpoint, inscription) in index.get_inscriptions_on_output(outpoint)? {
+            used_script = true;
+            tracked.push(TrackedInscription {
+              inscription,
+              satpoint,
+              address: Address::from_script(&script_pubkey, self.network)?,
+            });
+          }
+        }
+
+        if used_script {
+          self.derive(
+            &mut self.state.lock().unwrap(),
+            i,
+            used.saturating_add(1).saturating_add(self.gap_limit),
+          )?;
+        }
+
+        checked.insert(script_pubkey);
+      }
+    }
+
+    tracked.sort_by_key(|tracked| tracked.satpoint);
+
+    Ok(tracked)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+
+  fn tracker(descriptors: &[&str], gap_limit: u32) -> Tracker {
+    Tracker::new(
+      descriptors
+        .iter()
+        .map(|descriptor| descriptor.parse().unwrap())
+        .collect(),
+      gap_limit,
+      Chain::Mainnet,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn ranged_descriptors_are_derived_through_gap_limit() {
+    let tracker = tracker(
+      &[
+        &format!("tr({XPUB}/0/*)"),
+        "pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)",
+      ],
+      5,
+    );
+    let state = tracker.state.lock().unwrap();
+    assert_eq!(state.derived, [5, 1]);
+    assert_eq!(state.scripts.len(), 6);
+  }
+
+  #[test]
+  fn use_derives_through_gap_limit_beyond_it() {
+    let tracker = tracker(&[&format!("tr({XPUB}/0/*)")], 5);
+    let mut state = tracker.state.lock().unwrap();
+    assert!(tracker.derive(&mut state, 0, 4 + 1 + 5).unwrap());
+    assert!(!tracker.derive(&mut state, 0, 2 + 1 + 5).unwrap());
+    assert_eq!(state.derived, [10]);
+    assert_eq!(
+      state.scripts[&tracker.descriptors[0]
+        .script_pubkey(&tracker.secp, 9)
+        .unwrap()],
+      (0, 9)
+    );
+  }
+
+  #[test]
+  fn descriptor_network_must_match_chain() {
+    assert_eq!(
+      Tracker::new(
+        vec![format!("tr({XPUB}/0/*)").parse().unwrap()],
+        20,
+        Chain::Regtest
+      )
+      .err()
+      .unwrap()
+      .to_string(),
+      format!(
+        "descriptor `{}` has bitcoin keys, but ord is running on regtest",
+        format!("tr({XPUB}/0/*)").parse::<Descriptor>().unwrap()
+      )
+    );
+  }
+}
@@ -0,0 +1,252 @@
+use {
+  super::*,
+  crate::subcommand::wallet::{
+    balance::{self, Balance},
+    inscribe::{self, Inscribe},
+    inscriptions::{self, Inscriptions},
+    receive::{self, Receive},
+    send::{self, Reference, Send},
+    utxos::{self, Utxos},
+  },
+  bitcoin::{
+    hashes::{cmp::fixed_time_eq, sha256},
+    secp256k1::rand::{self, Rng},
+  },
+};
+
+#[derive(Deserialize)]
+pub(super) struct InscribeRequest {
+  file: PathBuf,
+  metadata: Option<PathBuf>,
+  satpoint: Option<DeserializeFromStr<SatPoint>>,
+  #[serde(default)]
+  skip_duplicates: bool,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SendRequest {
+  outgoing: DeserializeFromStr<Reference>,
+  address: DeserializeFromStr<Address>,
+}
+
+/// JSON endpoints mirroring the wallet subcommands, for GUI frontends. They
+/// call the same functions as the CLI, so both see the same wallet. Requests
+/// must come from a loopback address, or over the Unix socket if `unix` is
+/// set, and carry the token stored in the data dir.
+pub(crate) struct WalletApi {
+  index: Arc<Index>,
+  options: Options,
+  token: String,
+  unix: bool,
+}
+
+impl WalletApi {
+  const TOKEN_FILE: &'static str = "wallet-api-token";
+
+  pub(crate) fn new(
+    options: &Options,
+    index: Arc<Index>,
+    address: &str,
+    unix: bool,
+  ) -> Result<Self> {
+    let addrs = (address, 0).to_socket_addrs()?.collect::<Vec<SocketAddr>>();
+
+    if addrs.is_empty() || addrs.iter().any(|addr| !addr.ip().is_loopback()) {
+      bail!(
+        "`--enable-wallet-api` requires listening on a loopback address, such as `--address 127.0.0.1`, not `{address}`"
+      );
+    }
+
+    let api = Self {
+      token: Self::load_or_create_token(&options.data_dir()?.join(Self::TOKEN_FILE))?,
+      options: options.clone(),
+      index,
+      unix,
+    };
+
+    api
+      .settings()
+      .bitcoin_rpc_client_for_wallet_command("ord server --enable-wallet-api")?;
+
+    Ok(api)
+  }
+
+  /// Read the token at `path`, or generate one and write it there so that it
+  /// stays the same across restarts.
+  fn load_or_create_token(path: &std::path::Path) -> Result<String> {
+    match fs::read_to_string(path) {
+      Ok(token) if !token.trim().is_empty() => return Ok(token.trim().into()),
+      Ok(_) => bail!("wallet API token file `{}` is empty", path.display()),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+      Err(err) => {
+        return Err(err)
+          .with_context(|| format!("failed to read wallet API token from `{}`", path.display()))
+      }
+    }
+
+    let token = hex::encode(rand::thread_rng().gen::<[u8; 32]>());
+
+    state_file::write_atomically(path, token.as_bytes())
+      .with_context(|| format!("failed to write wallet API token to `{}`", path.display()))?;
+
+    log::info!("Wrote wallet API token to `{}`", path.display());
+
+    Ok(token)
+  }
+
+  fn settings(&self) -> Settings {
+    Settings::with_index(self.options.clone(), self.index.clone())
+  }
+
+  /// Compares digests, which are always the same length, in constant time,
+  /// so that response times don't reveal how much of a guess was right.
+  fn token_matches(&self, token: &str) -> bool {
+    fixed_time_eq(
+      &sha256::Hash::hash(token.as_bytes()),
+      &sha256::Hash::hash(self.token.as_bytes()),
+    )
+  }
+
+  /// Wallet commands block on the node, so they are run off the executor.
+  async fn run<T: std::marker::Send + 'static>(
+    &self,
+    command: impl FnOnce(&Settings) -> ServerResult<T> + std::marker::Send + 'static,
+  ) -> ServerResult<Json<T>> {
+    let settings = self.settings();
+
+    task::spawn_blocking(move || command(&settings))
+      .await
+      .map_err(|err| ServerError::Internal(err.into()))?
+      .map(Json)
+  }
+
+  fn authorize(
+    api: Option<Arc<Self>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: &HeaderMap,
+  ) -> ServerResult<Arc<Self>> {
+    let api = api.ok_or_else(|| {
+      ServerError::NotFound(
+        "wallet API requires server started with `--enable-wallet-api` flag".into(),
+      )
+    })?;
+
+    // Connections over the Unix socket have no address, and may come from
+    // any user allowed to connect to it.
+    match peer {
+      Some(ConnectInfo(peer)) if !peer.ip().is_loopback() => {
+        return Err(ServerError::Forbidden(
+          "wallet API may only be used from loopback addresses".into(),
+        ))
+      }
+      None if !api.unix => {
+        return Err(ServerError::Forbidden(
+          "wallet API may only be used over the Unix socket with `--listen-unix-wallet-api`".into(),
+        ))
+      }
+      _ => {}
+    }
+
+    // Browsers attach `Origin` to cross-origin requests, which would otherwise
+    // let any web page read the wallet through the permissive CORS policy.
+    if headers.contains_key(header::ORIGIN) {
+      return Err(ServerError::Forbidden(
+        "wallet API may not be used from web pages".into(),
+      ));
+    }
+
+    let token = headers
+      .get(header::AUTHORIZATION)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.strip_prefix("Bearer "));
+
+    if !token.is_some_and(|token| api.token_matches(token)) {
+      return Err(ServerError::Unauthorized(format!(
+        "wallet API requests must carry `Authorization: Bearer <TOKEN>` with the token in `{}`",
+        Self::TOKEN_FILE
+      )));
+    }
+
+    Ok(api)
+  }
+
+  pub(super) async fn balance(
+    Extension(api): Extension<Option<Arc<Self>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<balance::Output>> {
+    let api = Self::authorize(api, peer, &headers)?;
+    api
+      .run(|settings| Balance::balance(settings).map_err(ServerError::Internal))
+      .await
+  }
+
+  pub(super) async fn inscriptions(
+    Extension(api): Extension<Option<Arc<Self>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<Vec<inscriptions::Output>>> {
+    let api = Self::authorize(api, peer, &headers)?;
+    api
+      .run(|settings| Inscriptions::list(settings).map_err(ServerError::Internal))
+      .await
+  }
+
+  pub(super) async fn outputs(
+    Extension(api): Extension<Option<Arc<Self>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<Vec<utxos::Output>>> {
+    let api = Self::authorize(api, peer, &headers)?;
+    api
+      .run(|settings| Utxos::list(settings).map_err(ServerError::Internal))
+      .await
+  }
+
+  pub(super) async fn receive(
+    Extension(api): Extension<Option<Arc<Self>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<receive::Output>> {
+    let api = Self::authorize(api, peer, &headers)?;
+    api
+      .run(|settings| Receive::receive(settings).map_err(ServerError::Internal))
+      .await
+  }
+
+  pub(super) async fn inscribe(
+    Extension(api): Extension<Option<Arc<Self>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(request): Json<InscribeRequest>,
+  ) -> ServerResult<Json<inscribe::Output>> {
+    let api = Self::authorize(api, peer, &headers)?;
+    api
+      .run(move |settings| {
+        Inscribe::inscribe(
+          settings,
+          request.satpoint.map(|satpoint| satpoint.0),
+          &request.file,
+          request.metadata.as_deref(),
+          request.skip_duplicates,
+        )
+        .map_err(|err| ServerError::BadRequest(err.to_string()))
+      })
+      .await
+  }
+
+  pub(super) async fn send(
+    Extension(api): Extension<Option<Arc<Self>>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(request): Json<SendRequest>,
+  ) -> ServerResult<Json<send::Output>> {
+    let api = Self::authorize(api, peer, &headers)?;
+    api
+      .run(move |settings| {
+        Send::send(settings, request.outgoing.0, request.address.0)
+          .map_err(|err| ServerError::BadRequest(err.to_string()))
+      })
+      .await
+  }
+}
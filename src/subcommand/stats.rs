@@ -0,0 +1,23 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Stats {
+  #[clap(about = "Display blocks, subsidy, fees, and inscriptions indexed in each epoch")]
+  Epochs,
+  #[clap(about = "Display supply and inscription counts by rarity")]
+  Rarities,
+}
+
+impl Stats {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let index = settings.index()?;
+    index.update()?;
+
+    match self {
+      Self::Epochs => serde_json::to_writer(io::stdout(), &index.epoch_statistics()?)?,
+      Self::Rarities => serde_json::to_writer(io::stdout(), &index.rarity_statistics()?)?,
+    }
+
+    Ok(())
+  }
+}
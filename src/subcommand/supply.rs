@@ -1,19 +1,45 @@
 use super::*;
 
 pub(crate) fn run() -> Result {
-  let mut last = 0;
+  print!("{}", report());
+  Ok(())
+}
+
+fn report() -> String {
+  format!(
+    "supply: {}\nfirst: {}\nlast: {}\nlast mined in block: {}\n",
+    Sat::supply(),
+    0,
+    Sat::last(),
+    Epoch::first_post_subsidy().starting_height() - 1,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-  loop {
-    if Height(last + 1).subsidy() == 0 {
-      break;
+  #[test]
+  fn report_matches_constants() {
+    let mut last = 0;
+    while Height(last + 1).subsidy() > 0 {
+      last += 1;
     }
-    last += 1;
-  }
 
-  println!("supply: {}", Sat::SUPPLY);
-  println!("first: {}", 0);
-  println!("last: {}", Sat::SUPPLY - 1);
-  println!("last mined in block: {}", last);
+    let mut supply = 0;
+    for epoch in 0..Epoch::first_post_subsidy().0 {
+      supply += Epoch(epoch).subsidy() * SUBSIDY_HALVING_INTERVAL;
+    }
 
-  Ok(())
+    assert_eq!(supply, SUPPLY);
+    assert_eq!(Sat::last().height(), last);
+
+    assert_eq!(
+      report(),
+      format!(
+        "supply: {SUPPLY}\nfirst: 0\nlast: {}\nlast mined in block: {last}\n",
+        SUPPLY - 1
+      )
+    );
+  }
 }
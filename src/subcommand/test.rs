@@ -0,0 +1,18 @@
+use super::*;
+
+mod reorg;
+
+/// Helpers for manufacturing chain states in tests. Only usable on regtest.
+#[derive(Debug, Parser)]
+pub(crate) enum Test {
+  #[clap(about = "Replace recent blocks with a new branch")]
+  Reorg(reorg::Reorg),
+}
+
+impl Test {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    match self {
+      Self::Reorg(reorg) => reorg.run(settings),
+    }
+  }
+}
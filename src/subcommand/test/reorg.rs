@@ -0,0 +1,49 @@
+use {super::*, bitcoin::blockdata::opcodes};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Reorg {
+  #[clap(long, help = "Replace the last <DEPTH> blocks.")]
+  depth: u64,
+}
+
+impl Reorg {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let client = settings.bitcoin_rpc_client_regtest_only("ord test reorg")?;
+
+    let height = client.get_block_count()?;
+
+    if self.depth == 0 || self.depth > height {
+      bail!(
+        "reorg depth must be between 1 and the chain height {height}, not {}",
+        self.depth
+      );
+    }
+
+    let invalidated = (height - self.depth + 1..=height)
+      .map(|height| client.get_block_hash(height))
+      .collect::<Result<Vec<BlockHash>, bitcoincore_rpc::Error>>()?;
+
+    client.invalidate_block(&invalidated[0])?;
+
+    // Mine one block more than was invalidated, so that the new branch has
+    // more work than the old one, as it would in a natural reorg. Coinbase
+    // outputs go to an anyone-can-spend address, so no wallet is needed.
+    let mined = client.generate_to_address(
+      self.depth + 1,
+      &Address::p2wsh(
+        &Script::from(vec![opcodes::OP_TRUE.to_u8()]),
+        settings.options.chain().network(),
+      ),
+    )?;
+
+    for hash in invalidated {
+      println!("invalidated\t{hash}");
+    }
+
+    for hash in mined {
+      println!("mined\t{hash}");
+    }
+
+    Ok(())
+  }
+}
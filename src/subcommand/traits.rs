@@ -4,13 +4,73 @@ use super::*;
 pub(crate) struct Traits {
   #[clap(help = "Show traits for <SAT>.")]
   sat: Sat,
+  #[clap(long, help = "Print traits as JSON.")]
+  json: bool,
+  #[clap(
+    long,
+    help = "Show time <SAT> was mined, or estimate it from the index if it hasn't been."
+  )]
+  time: bool,
+}
+
+#[derive(Serialize)]
+struct Json {
+  number: u64,
+  decimal: String,
+  degree: String,
+  name: String,
+  height: Height,
+  cycle: Cycle,
+  epoch: Epoch,
+  period: u64,
+  offset: u64,
+  rarity: Rarity,
+  traits: BTreeMap<String, bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  time: Option<String>,
 }
 
 impl Traits {
-  pub(crate) fn run(self) -> Result {
-    print!("{}", self);
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let time = if self.time {
+      let index = settings.index()?;
+      index.update()?;
+      Some(self.sat.expected_timestamp(&index)?)
+    } else {
+      None
+    };
+
+    if self.json {
+      serde_json::to_writer(io::stdout(), &self.json(time))?;
+    } else {
+      print!("{}", self);
+      if let Some(time) = time {
+        println!("time: {time}");
+      }
+    }
+
     Ok(())
   }
+
+  fn json(&self, time: Option<Blocktime>) -> Json {
+    Json {
+      number: self.sat.n(),
+      decimal: self.sat.decimal().to_string(),
+      degree: self.sat.degree().to_string(),
+      name: self.sat.name(),
+      height: self.sat.height(),
+      cycle: self.sat.cycle(),
+      epoch: self.sat.epoch(),
+      period: self.sat.period(),
+      offset: self.sat.third(),
+      rarity: self.sat.rarity(),
+      traits: SatTrait::ALL
+        .into_iter()
+        .map(|sat_trait| (sat_trait.to_string(), sat_trait.is_set(self.sat)))
+        .collect(),
+      time: time.map(|time| time.to_string()),
+    }
+  }
 }
 
 impl Display for Traits {
@@ -25,6 +85,9 @@ impl Display for Traits {
     writeln!(f, "period: {}", self.sat.period())?;
     writeln!(f, "offset: {}", self.sat.third())?;
     writeln!(f, "rarity: {}", self.sat.rarity())?;
+    for sat_trait in SatTrait::ALL {
+      writeln!(f, "{sat_trait}: {}", sat_trait.is_set(self.sat))?;
+    }
     Ok(())
   }
 }
@@ -36,7 +99,12 @@ mod tests {
   #[test]
   fn first() {
     assert_eq!(
-      Traits { sat: Sat(0) }.to_string(),
+      Traits {
+        sat: Sat(0),
+        json: false,
+        time: false,
+      }
+      .to_string(),
       "\
 number: 0
 decimal: 0.0
@@ -48,6 +116,11 @@ epoch: 0
 period: 0
 offset: 0
 rarity: mythic
+coin: true
+alpha: true
+omega: false
+decimal-palindrome: true
+name-palindrome: false
 ",
     );
   }
@@ -56,7 +129,9 @@ rarity: mythic
   fn last() {
     assert_eq!(
       Traits {
-        sat: Sat(2099999997689999)
+        sat: Sat(2099999997689999),
+        json: false,
+        time: false,
       }
       .to_string(),
       "\
@@ -70,6 +145,11 @@ epoch: 32
 period: 3437
 offset: 0
 rarity: uncommon
+coin: false
+alpha: false
+omega: false
+decimal-palindrome: false
+name-palindrome: true
 ",
     );
   }
@@ -1,17 +1,20 @@
 use {super::*, transaction_builder::TransactionBuilder};
 
+pub(crate) mod balance;
 mod create;
-mod inscribe;
-mod inscriptions;
-mod receive;
+pub(crate) mod inscribe;
+pub(crate) mod inscriptions;
+pub(crate) mod receive;
 mod satoshis;
-mod send;
+pub(crate) mod send;
 mod transaction_builder;
 mod transactions;
-mod utxos;
+pub(crate) mod utxos;
 
 #[derive(Debug, Parser)]
 pub(crate) enum Wallet {
+  #[clap(about = "Get cardinal balance")]
+  Balance(balance::Balance),
   #[clap(about = "Create a new wallet")]
   Create(create::Create),
   #[clap(about = "Create an inscription")]
@@ -20,7 +23,7 @@ pub(crate) enum Wallet {
   Inscriptions(inscriptions::Inscriptions),
   #[clap(about = "Generate a receive address")]
   Receive(receive::Receive),
-  #[clap(about = "List wallet satoshis")]
+  #[clap(about = "List wallet satoshis", alias = "sats")]
   Satoshis(satoshis::Satoshis),
   #[clap(about = "Send a satoshi or inscription")]
   Send(send::Send),
@@ -31,22 +34,23 @@ pub(crate) enum Wallet {
 }
 
 impl Wallet {
-  pub(crate) fn run(self, options: Options) -> Result {
+  pub(crate) fn run(self, settings: Settings) -> Result {
     match self {
-      Self::Create(create) => create.run(options),
-      Self::Inscribe(inscribe) => inscribe.run(options),
-      Self::Inscriptions(inscriptions) => inscriptions.run(options),
-      Self::Receive(receive) => receive.run(options),
-      Self::Satoshis(satoshis) => satoshis.run(options),
-      Self::Send(send) => send.run(options),
-      Self::Transactions(transactions) => transactions.run(options),
-      Self::Utxos(utxos) => utxos.run(options),
+      Self::Balance(balance) => balance.run(settings),
+      Self::Create(create) => create.run(settings),
+      Self::Inscribe(inscribe) => inscribe.run(settings),
+      Self::Inscriptions(inscriptions) => inscriptions.run(settings),
+      Self::Receive(receive) => receive.run(settings),
+      Self::Satoshis(satoshis) => satoshis.run(settings),
+      Self::Send(send) => send.run(settings),
+      Self::Transactions(transactions) => transactions.run(settings),
+      Self::Utxos(utxos) => utxos.run(settings),
     }
   }
 }
 
-fn list_unspent(options: &Options, index: &Index) -> Result<Vec<(OutPoint, Vec<(u64, u64)>)>> {
-  let client = options.bitcoin_rpc_client()?;
+fn list_unspent(settings: &Settings, index: &Index) -> Result<Vec<(OutPoint, Vec<(u64, u64)>)>> {
+  let client = settings.bitcoin_rpc_client()?;
 
   client
     .list_unspent(None, None, None, None, None)?
@@ -62,8 +66,8 @@ fn list_unspent(options: &Options, index: &Index) -> Result<Vec<(OutPoint, Vec<(
     .collect()
 }
 
-fn list_utxos(options: &Options) -> Result<BTreeMap<OutPoint, Amount>> {
-  let client = options.bitcoin_rpc_client()?;
+fn list_utxos(settings: &Settings) -> Result<BTreeMap<OutPoint, Amount>> {
+  let client = settings.bitcoin_rpc_client()?;
 
   Ok(
     client
@@ -79,8 +83,8 @@ fn list_utxos(options: &Options) -> Result<BTreeMap<OutPoint, Amount>> {
   )
 }
 
-fn get_change_addresses(options: &Options, n: usize) -> Result<Vec<Address>> {
-  let client = options.bitcoin_rpc_client()?;
+fn get_change_addresses(settings: &Settings, n: usize) -> Result<Vec<Address>> {
+  let client = settings.bitcoin_rpc_client()?;
 
   let mut addresses = Vec::new();
   for _ in 0..n {
@@ -0,0 +1,40 @@
+use {super::*, std::collections::BTreeSet};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Balance {}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  pub(crate) cardinal: u64,
+}
+
+impl Balance {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    println!("{}", Self::balance(&settings)?.cardinal);
+    Ok(())
+  }
+
+  /// Sats in wallet outputs that carry no inscriptions, and so are safe to
+  /// spend as fees or send.
+  pub(crate) fn balance(settings: &Settings) -> Result<Output> {
+    let client = settings.bitcoin_rpc_client_for_wallet_command("ord wallet balance")?;
+
+    let index = settings.index()?;
+    index.update()?;
+
+    let inscribed = index
+      .get_inscriptions()?
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    Ok(Output {
+      cardinal: client
+        .list_unspent(None, None, None, None, None)?
+        .iter()
+        .filter(|utxo| !inscribed.contains(&OutPoint::new(utxo.txid, utxo.vout)))
+        .map(|utxo| utxo.amount.to_sat())
+        .sum(),
+    })
+  }
+}
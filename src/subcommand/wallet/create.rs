@@ -4,8 +4,8 @@ use super::*;
 pub(crate) struct Create {}
 
 impl Create {
-  pub(crate) fn run(self, options: Options) -> Result {
-    options
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    settings
       .bitcoin_rpc_client_mainnet_forbidden("ord wallet create")?
       .create_wallet("ord", None, None, None, None)?;
     Ok(())
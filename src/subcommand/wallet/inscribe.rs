@@ -2,6 +2,7 @@ use {
   super::*,
   bitcoin::{
     blockdata::{opcodes, script},
+    hashes::{sha256, Hash},
     schnorr::{TapTweak, TweakedKeyPair, TweakedPublicKey, UntweakedKeyPair},
     secp256k1::{
       self, constants::SCHNORR_SIGNATURE_SIZE, rand, schnorr::Signature, Secp256k1, XOnlyPublicKey,
@@ -16,59 +17,204 @@ use {
   std::collections::BTreeSet,
 };
 
+/// Number of recent wallet transactions searched for unconfirmed duplicates.
+const MEMPOOL_TRANSACTIONS: usize = 1000;
+
 #[derive(Debug, Parser)]
 pub(crate) struct Inscribe {
   #[clap(long, help = "Inscribe <SATPOINT>")]
   satpoint: Option<SatPoint>,
   #[clap(long, help = "Inscribe sat with contents of <FILE>")]
   file: PathBuf,
+  #[clap(
+    long,
+    help = "Include JSON object in <METADATA> as inscription metadata. A `collection` field groups the inscription with others in the same collection."
+  )]
+  metadata: Option<PathBuf>,
+  #[clap(long, help = "Print transaction IDs as JSON.")]
+  json: bool,
+  #[clap(
+    long,
+    help = "Don't inscribe <FILE> if the wallet already holds an inscription with the same content, or has one with the same content in the mempool, and report that inscription instead. Inscriptions the wallet has sent away are not checked."
+  )]
+  skip_duplicates: bool,
+  #[clap(
+    long,
+    conflicts_with = "skip-duplicates",
+    help = "Inscribe <FILE> even if the wallet already holds an inscription with the same content. This is the default."
+  )]
+  #[allow(unused)]
+  allow_duplicates: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  pub(crate) commit: Txid,
+  pub(crate) reveal: Txid,
+  pub(crate) inscription: InscriptionId,
+  /// True if nothing was broadcast, because the wallet already held an
+  /// inscription with the same content, which the other fields describe.
+  pub(crate) duplicate: bool,
 }
 
 impl Inscribe {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let client = options.bitcoin_rpc_client_mainnet_forbidden("ord wallet inscribe")?;
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let output = Self::inscribe(
+      &settings,
+      self.satpoint,
+      &self.file,
+      self.metadata.as_deref(),
+      self.skip_duplicates,
+    )?;
+
+    if self.json {
+      println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if output.duplicate {
+      println!("duplicate\t{}", output.inscription);
+    } else {
+      println!("commit\t{}", output.commit);
+      println!("reveal\t{}", output.reveal);
+    }
+
+    Ok(())
+  }
 
-    let inscription = Inscription::from_file(options.chain(), &self.file)?;
+  /// Inscribe the contents of `file`, and broadcast the commit and reveal
+  /// transactions. Shared by `ord wallet inscribe` and the wallet API.
+  pub(crate) fn inscribe(
+    settings: &Settings,
+    satpoint: Option<SatPoint>,
+    file: &Path,
+    metadata: Option<&Path>,
+    skip_duplicates: bool,
+  ) -> Result<Output> {
+    let client = settings.bitcoin_rpc_client_mainnet_forbidden("ord wallet inscribe")?;
+
+    let mut inscription = Inscription::from_file(settings.options.chain(), file)?;
+
+    if let Some(metadata) = metadata {
+      inscription = inscription
+        .with_metadata(
+          &fs::read(metadata)
+            .with_context(|| format!("io error reading {}", metadata.display()))?,
+        )
+        .with_context(|| format!("invalid metadata in {}", metadata.display()))?;
+    }
 
-    let index = Index::open(&options)?;
+    let index = settings.index()?;
     index.update()?;
 
-    let utxos = list_utxos(&options)?;
+    let utxos = list_utxos(settings)?;
 
     let inscriptions = index.get_inscriptions()?;
 
-    let commit_tx_change = get_change_addresses(&options, 2)?;
+    if skip_duplicates {
+      if let Some(output) =
+        Self::find_duplicate(&client, &index, &inscription, &inscriptions, &utxos)?
+      {
+        return Ok(output);
+      }
+    }
+
+    let commit_tx_change = get_change_addresses(settings, 2)?;
 
-    let reveal_tx_destination = get_change_addresses(&options, 1)?[0].clone();
+    let reveal_tx_destination = get_change_addresses(settings, 1)?[0].clone();
 
     let (unsigned_commit_tx, reveal_tx, recovery_key_pair) =
       Inscribe::create_inscription_transactions(
-        self.satpoint,
+        satpoint,
         inscription,
         inscriptions,
-        options.chain().network(),
+        settings.options.chain().network(),
         utxos,
         commit_tx_change,
         reveal_tx_destination,
       )?;
 
-    Inscribe::backup_recovery_key(&client, recovery_key_pair, options.chain().network())?;
+    Inscribe::backup_recovery_key(
+      &client,
+      recovery_key_pair,
+      settings.options.chain().network(),
+    )?;
 
     let signed_raw_commit_tx = client
       .sign_raw_transaction_with_wallet(&unsigned_commit_tx, None, None)?
       .hex;
 
-    let commit_txid = client
+    let commit = client
       .send_raw_transaction(&signed_raw_commit_tx)
       .context("Failed to send commit transaction")?;
 
-    let reveal_txid = client
+    let reveal = client
       .send_raw_transaction(&reveal_tx)
       .context("Failed to send reveal transaction")?;
 
-    println!("commit\t{commit_txid}");
-    println!("reveal\t{reveal_txid}");
-    Ok(())
+    Ok(Output {
+      commit,
+      reveal,
+      inscription: reveal,
+      duplicate: false,
+    })
+  }
+
+  /// An inscription whose content has the same SHA-256 digest as that of
+  /// `inscription`, if there is one. Inscriptions on unspent outputs in the
+  /// wallet are checked, as are inscriptions revealed by wallet transactions
+  /// still in the mempool. Inscriptions the wallet has since sent away are not.
+  fn find_duplicate(
+    client: &Client,
+    index: &Index,
+    inscription: &Inscription,
+    inscriptions: &BTreeMap<SatPoint, InscriptionId>,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<Option<Output>> {
+    let digest = |inscription: &Inscription| inscription.content_bytes().map(sha256::Hash::hash);
+
+    let Some(expected) = digest(inscription) else {
+      return Ok(None);
+    };
+
+    let duplicate = |reveal: &Transaction| {
+      (Inscription::from_transaction(reveal)
+        .and_then(|existing| digest(&existing))
+        .as_ref()
+        == Some(&expected))
+      .then(|| Output {
+        commit: reveal.input[0].previous_output.txid,
+        reveal: reveal.txid(),
+        inscription: reveal.txid(),
+        duplicate: true,
+      })
+    };
+
+    for (satpoint, inscription_id) in inscriptions {
+      if !utxos.contains_key(&satpoint.outpoint) {
+        continue;
+      }
+
+      let Some(reveal) = index.get_transaction(*inscription_id)? else {
+        continue;
+      };
+
+      if let Some(output) = duplicate(&reveal) {
+        return Ok(Some(output));
+      }
+    }
+
+    let unconfirmed = client
+      .list_transactions(None, Some(MEMPOOL_TRANSACTIONS), None, None)?
+      .into_iter()
+      .filter(|tx| tx.info.confirmations == 0)
+      .map(|tx| tx.info.txid)
+      .collect::<BTreeSet<Txid>>();
+
+    for txid in unconfirmed {
+      if let Some(output) = duplicate(&client.get_transaction(&txid, None)?.transaction()?) {
+        return Ok(Some(output));
+      }
+    }
+
+    Ok(None)
   }
 
   fn create_inscription_transactions(
@@ -175,7 +321,7 @@ impl Inscribe {
           .as_ref(),
       );
       reveal_tx.input[0].witness.push(&reveal_script);
-      reveal_tx.input[0].witness.push(&control_block.serialize());
+      reveal_tx.input[0].witness.push(control_block.serialize());
 
       TransactionBuilder::TARGET_FEE_RATE * reveal_tx.vsize().try_into().unwrap()
     };
@@ -185,7 +331,7 @@ impl Inscribe {
       .checked_sub(fee.to_sat())
       .context("commit transaction output value insufficient to pay transaction fee")?;
 
-    if reveal_tx.output[0].value < reveal_tx.output[0].script_pubkey.dust_value().to_sat() {
+    if reveal_tx.output[0].value < policy::dust_limit(&reveal_tx.output[0].script_pubkey).to_sat() {
       bail!("commit transaction output would be dust");
     }
 
@@ -211,7 +357,7 @@ impl Inscribe {
       .expect("getting mutable witness reference should work");
     witness.push(signature.as_ref());
     witness.push(reveal_script);
-    witness.push(&control_block.serialize());
+    witness.push(control_block.serialize());
 
     let recovery_key_pair = key_pair.tap_tweak(&secp256k1, taproot_spend_info.merkle_root());
 
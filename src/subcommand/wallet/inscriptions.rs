@@ -3,20 +3,40 @@ use super::*;
 #[derive(Debug, Parser)]
 pub(crate) struct Inscriptions {}
 
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  pub(crate) inscription: InscriptionId,
+  pub(crate) location: SatPoint,
+}
+
 impl Inscriptions {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let index = Index::open(&options)?;
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    for output in Self::list(&settings)? {
+      println!("{}\t{}", output.inscription, output.location);
+    }
+
+    Ok(())
+  }
+
+  /// Inscriptions on outputs in the wallet, ordered by location. Burned
+  /// inscriptions are left out, since they can never be sent.
+  pub(crate) fn list(settings: &Settings) -> Result<Vec<Output>> {
+    let index = settings.index()?;
     index.update()?;
 
     let inscriptions = index.get_inscriptions()?;
-    let utxos = list_utxos(&options)?;
+    let utxos = list_utxos(settings)?;
 
-    for (satpoint, inscription_id) in inscriptions {
-      if utxos.contains_key(&satpoint.outpoint) {
-        println!("{}\t{}", inscription_id, satpoint);
+    let mut outputs = Vec::new();
+    for (location, inscription) in inscriptions {
+      if utxos.contains_key(&location.outpoint) && !index.is_inscription_burned(inscription)? {
+        outputs.push(Output {
+          inscription,
+          location,
+        });
       }
     }
 
-    Ok(())
+    Ok(outputs)
   }
 }
@@ -1,17 +1,184 @@
-use super::*;
+use {
+  super::*,
+  bitcoin::Denomination,
+  qrcode::{render::unicode::Dense1x2, QrCode},
+};
 
 #[derive(Debug, Parser)]
-pub(crate) struct Receive {}
+pub(crate) struct Receive {
+  #[clap(
+    long,
+    help = "Print a BIP-21 `bitcoin:` payment URI instead of an address."
+  )]
+  uri: bool,
+  #[clap(
+    long,
+    requires = "uri",
+    value_parser = parse_amount,
+    help = "Request <AMOUNT> bitcoin in the payment URI."
+  )]
+  amount: Option<Amount>,
+  #[clap(long, help = "Print a QR code of the address or payment URI.")]
+  qr: bool,
+}
+
+fn parse_amount(s: &str) -> Result<Amount> {
+  Ok(Amount::from_str_in(s, Denomination::Bitcoin)?)
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  pub(crate) address: Address,
+}
 
 impl Receive {
-  pub(crate) fn run(self, options: Options) -> Result {
-    println!(
-      "{}",
-      options
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let Output { address } = Self::receive(&settings)?;
+
+    let payload = if self.uri {
+      payment_uri(&address, self.amount)
+    } else {
+      address.to_string()
+    };
+
+    println!("{payload}");
+
+    if self.qr {
+      println!("{}", qr_code(&payload)?);
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn receive(settings: &Settings) -> Result<Output> {
+    Ok(Output {
+      address: settings
         .bitcoin_rpc_client_for_wallet_command("ord wallet receive")?
-        .get_new_address(None, None)?
+        .get_new_address(None, None)?,
+    })
+  }
+}
+
+fn payment_uri(address: &Address, amount: Option<Amount>) -> String {
+  match amount {
+    Some(amount) => {
+      let sat = amount.to_sat();
+      let fraction = format!("{:08}", sat % COIN_VALUE);
+      let fraction = fraction.trim_end_matches('0');
+      if fraction.is_empty() {
+        format!("bitcoin:{address}?amount={}", sat / COIN_VALUE)
+      } else {
+        format!("bitcoin:{address}?amount={}.{fraction}", sat / COIN_VALUE)
+      }
+    }
+    None => format!("bitcoin:{address}"),
+  }
+}
+
+/// Render `data` as a QR code of half-block characters. Light modules are
+/// drawn filled, so the code scans on terminals with dark backgrounds.
+fn qr_code(data: &str) -> Result<String> {
+  Ok(
+    QrCode::new(data)?
+      .render::<Dense1x2>()
+      .dark_color(Dense1x2::Light)
+      .light_color(Dense1x2::Dark)
+      .build(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Turn a rendered QR code back into pixels, two rows per line of text,
+  /// scaled up so the decoder can find its finder patterns.
+  fn decode(qr_code: &str) -> String {
+    const SCALE: usize = 4;
+
+    let lines = qr_code.lines().collect::<Vec<&str>>();
+    let width = lines[0].chars().count();
+
+    let mut light = Vec::new();
+    for line in &lines {
+      let (top, bottom): (Vec<bool>, Vec<bool>) = line
+        .chars()
+        .map(|c| match c {
+          ' ' => (false, false),
+          '\u{2580}' => (true, false),
+          '\u{2584}' => (false, true),
+          '\u{2588}' => (true, true),
+          _ => panic!("unexpected character {c:?}"),
+        })
+        .unzip();
+      light.push(top);
+      light.push(bottom);
+    }
+
+    let height = light.len();
+
+    let mut image =
+      rqrr::PreparedImage::prepare_from_greyscale(width * SCALE, height * SCALE, |x, y| {
+        if light[y / SCALE][x / SCALE] {
+          255
+        } else {
+          0
+        }
+      });
+
+    let grids = image.detect_grids();
+    assert_eq!(grids.len(), 1);
+    grids[0].decode().unwrap().1
+  }
+
+  #[test]
+  fn qr_code_round_trips_address() {
+    let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    assert_eq!(decode(&qr_code(address).unwrap()), address);
+  }
+
+  #[test]
+  fn qr_code_round_trips_payment_uri() {
+    let uri = payment_uri(
+      &"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        .parse()
+        .unwrap(),
+      Some(Amount::from_sat(150_000)),
     );
+    assert_eq!(decode(&qr_code(&uri).unwrap()), uri);
+  }
 
-    Ok(())
+  #[test]
+  fn payment_uri_amount() {
+    let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse()
+      .unwrap();
+
+    assert_eq!(
+      payment_uri(&address, None),
+      "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    );
+    assert_eq!(
+      payment_uri(&address, Some(Amount::from_sat(150_000))),
+      "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4?amount=0.0015"
+    );
+    assert_eq!(
+      payment_uri(&address, Some(Amount::from_sat(100_000_000))),
+      "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4?amount=1"
+    );
+    assert_eq!(
+      payment_uri(&address, Some(Amount::from_sat(1_000_000_000))),
+      "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4?amount=10"
+    );
+    assert_eq!(
+      payment_uri(&address, Some(Amount::from_sat(1))),
+      "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4?amount=0.00000001"
+    );
+  }
+
+  #[test]
+  fn amount_is_parsed_as_bitcoin() {
+    assert_eq!(parse_amount("0.0015").unwrap(), Amount::from_sat(150_000));
+    assert!(parse_amount("0.000000001").is_err());
   }
 }
@@ -7,14 +7,20 @@ pub(crate) struct Satoshis {
     help = "Find satoshis listed in first column of tab-separated value file <TSV>."
   )]
   tsv: Option<PathBuf>,
+  #[clap(
+    long = "trait",
+    conflicts_with = "tsv",
+    help = "List satoshis with trait <TRAIT>."
+  )]
+  sat_trait: Option<SatTrait>,
 }
 
 impl Satoshis {
-  pub(crate) fn run(&self, options: Options) -> Result {
-    let index = Index::open(&options)?;
+  pub(crate) fn run(&self, settings: Settings) -> Result {
+    let index = settings.index()?;
     index.update()?;
 
-    let utxos = list_unspent(&options, &index)?;
+    let utxos = list_unspent(&settings, &index)?;
 
     if let Some(path) = &self.tsv {
       for (output, sat) in satoshis_from_tsv(
@@ -24,6 +30,10 @@ impl Satoshis {
       )? {
         println!("{output}\t{sat}");
       }
+    } else if let Some(sat_trait) = self.sat_trait {
+      for (output, sat, offset) in satoshis_with_trait(utxos, sat_trait) {
+        println!("{output}\t{sat}\t{offset}");
+      }
     } else {
       for (output, sat, offset, rarity) in rare_satoshis(utxos) {
         println!("{output}\t{sat}\t{offset}\t{rarity}");
@@ -54,6 +64,27 @@ fn rare_satoshis(utxos: Vec<(OutPoint, Vec<(u64, u64)>)>) -> Vec<(OutPoint, Sat,
     .collect()
 }
 
+fn satoshis_with_trait(
+  utxos: Vec<(OutPoint, Vec<(u64, u64)>)>,
+  sat_trait: SatTrait,
+) -> Vec<(OutPoint, Sat, u64)> {
+  let mut results = Vec::new();
+
+  for (outpoint, sat_ranges) in utxos {
+    let mut offset = 0;
+    for (start, end) in sat_ranges {
+      let mut sat = Sat(start);
+      while let Some(next) = sat_trait.next(sat).filter(|next| next.n() < end) {
+        results.push((outpoint, next, offset + next.n() - start));
+        sat = next + 1;
+      }
+      offset += end - start;
+    }
+  }
+
+  results
+}
+
 fn satoshis_from_tsv(
   utxos: Vec<(OutPoint, Vec<(u64, u64)>)>,
   tsv: &str,
@@ -162,6 +193,50 @@ mod tests {
     )
   }
 
+  #[test]
+  fn identify_sats_with_trait() {
+    assert_eq!(
+      satoshis_with_trait(
+        vec![
+          (
+            outpoint(1),
+            vec![(10, 12), (3 * COIN_VALUE - 1, 4 * COIN_VALUE + 1)]
+          ),
+          (outpoint(2), vec![(5 * COIN_VALUE + 1, 6 * COIN_VALUE)]),
+        ],
+        SatTrait::Coin,
+      ),
+      vec![
+        (outpoint(1), Sat(3 * COIN_VALUE), 3),
+        (outpoint(1), Sat(4 * COIN_VALUE), COIN_VALUE + 3),
+      ]
+    );
+
+    assert_eq!(
+      satoshis_with_trait(
+        vec![(outpoint(1), vec![(0, 12), (100, 103)])],
+        SatTrait::DecimalPalindrome
+      )
+      .into_iter()
+      .map(|(_, sat, offset)| (sat.n(), offset))
+      .collect::<Vec<(u64, u64)>>(),
+      vec![
+        (0, 0),
+        (1, 1),
+        (2, 2),
+        (3, 3),
+        (4, 4),
+        (5, 5),
+        (6, 6),
+        (7, 7),
+        (8, 8),
+        (9, 9),
+        (11, 11),
+        (101, 13),
+      ]
+    );
+  }
+
   #[test]
   fn identify_from_tsv_none() {
     assert_eq!(
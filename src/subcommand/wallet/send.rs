@@ -1,9 +1,10 @@
 use super::*;
 
 #[derive(Debug)]
-enum Reference {
+pub(crate) enum Reference {
   SatPoint(SatPoint),
   InscriptionId(Txid),
+  InscriptionIdPrefix(InscriptionIdPrefix),
 }
 
 impl FromStr for Reference {
@@ -12,6 +13,8 @@ impl FromStr for Reference {
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     Ok(if s.len() == 64 {
       Self::InscriptionId(s.parse()?)
+    } else if s.chars().all(|c| c.is_ascii_hexdigit()) {
+      Self::InscriptionIdPrefix(s.parse()?)
     } else {
       Self::SatPoint(s.parse()?)
     })
@@ -24,28 +27,43 @@ pub(crate) struct Send {
   address: Address,
 }
 
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  pub(crate) transaction: Txid,
+}
+
 impl Send {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let client = options.bitcoin_rpc_client_for_wallet_command("ord wallet send")?;
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    println!(
+      "{}",
+      Self::send(&settings, self.outgoing, self.address)?.transaction
+    );
+    Ok(())
+  }
 
-    if !self.address.is_valid_for_network(options.chain().network()) {
+  /// Send `outgoing` to `address`. Shared by `ord wallet send` and the wallet
+  /// API.
+  pub(crate) fn send(settings: &Settings, outgoing: Reference, address: Address) -> Result<Output> {
+    let client = settings.bitcoin_rpc_client_for_wallet_command("ord wallet send")?;
+
+    if !address.is_valid_for_network(settings.options.chain().network()) {
       bail!(
         "Address `{}` is not valid for {}",
-        self.address,
-        options.chain()
+        address,
+        settings.options.chain()
       );
     }
 
-    let index = Index::open(&options)?;
+    let index = settings.index()?;
     index.update()?;
 
-    let utxos = list_utxos(&options)?;
+    let utxos = list_utxos(settings)?;
 
     let inscriptions = index.get_inscriptions()?;
 
-    let change = get_change_addresses(&options, 2)?;
+    let change = get_change_addresses(settings, 2)?;
 
-    let satpoint = match self.outgoing {
+    let satpoint = match outgoing {
       Reference::SatPoint(satpoint) => {
         for inscription_satpoint in inscriptions.keys() {
           if satpoint == *inscription_satpoint {
@@ -54,22 +72,44 @@ impl Send {
         }
         satpoint
       }
-      Reference::InscriptionId(txid) => match index.get_inscription_by_inscription_id(txid)? {
-        Some((_inscription, satpoint)) => satpoint,
-        None => bail!("No inscription found for {txid}"),
-      },
+      Reference::InscriptionId(txid) => Self::inscription_satpoint(&index, txid)?,
+      Reference::InscriptionIdPrefix(prefix) => {
+        Self::inscription_satpoint(&index, Self::resolve_prefix(&index, &prefix)?)?
+      }
     };
 
     let unsigned_transaction =
-      TransactionBuilder::build_transaction(satpoint, inscriptions, utxos, self.address, change)?;
+      TransactionBuilder::build_transaction(satpoint, inscriptions, utxos, address, change)?;
 
     let signed_tx = client
       .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
       .hex;
 
-    let txid = client.send_raw_transaction(&signed_tx)?;
+    Ok(Output {
+      transaction: client.send_raw_transaction(&signed_tx)?,
+    })
+  }
 
-    println!("{txid}");
-    Ok(())
+  fn inscription_satpoint(index: &Index, txid: Txid) -> Result<SatPoint> {
+    match index.get_inscription_by_inscription_id(txid)? {
+      Some((_inscription, satpoint)) => Ok(satpoint),
+      None => bail!("No inscription found for {txid}"),
+    }
+  }
+
+  /// The one inscription ID that starts with `prefix`.
+  fn resolve_prefix(index: &Index, prefix: &InscriptionIdPrefix) -> Result<Txid> {
+    match index.find_inscriptions_by_prefix(prefix)?.as_slice() {
+      [] => bail!("No inscription found for prefix {prefix}"),
+      [inscription_id] => Ok(*inscription_id),
+      inscription_ids => bail!(
+        "Inscription ID prefix {prefix} is ambiguous, matching: {}",
+        inscription_ids
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+    }
   }
 }
@@ -81,9 +81,7 @@ pub(crate) struct TransactionBuilder {
 type Result<T> = std::result::Result<T, Error>;
 
 impl TransactionBuilder {
-  pub(crate) const TARGET_FEE_RATE: Amount = Amount::from_sat(1);
-  const MAX_POSTAGE: Amount = Amount::from_sat(2 * 10_000);
-  const TARGET_POSTAGE: Amount = Amount::from_sat(10_000);
+  pub(crate) const TARGET_FEE_RATE: Amount = Amount::from_sat(policy::MIN_RELAY_FEE_RATE);
 
   pub(crate) fn build_transaction(
     outgoing: SatPoint,
@@ -176,7 +174,7 @@ impl TransactionBuilder {
 
   fn pad_alignment_output(mut self) -> Result<Self> {
     if self.outputs[0].0 != self.recipient {
-      let dust_limit = self.recipient.script_pubkey().dust_value();
+      let dust_limit = policy::dust_limit(&self.recipient.script_pubkey());
       if self.outputs[0].1 < dust_limit {
         let (utxo, size) = self.select_cardinal_utxo(dust_limit - self.outputs[0].1)?;
         self.inputs.insert(0, utxo);
@@ -189,7 +187,7 @@ impl TransactionBuilder {
 
   fn add_postage(mut self) -> Result<Self> {
     let estimated_fee = self.estimate_fee();
-    let dust_limit = self.outputs.last().unwrap().0.script_pubkey().dust_value();
+    let dust_limit = policy::dust_limit(&self.outputs.last().unwrap().0.script_pubkey());
 
     if self.outputs.last().unwrap().1 < dust_limit + estimated_fee {
       let (utxo, size) =
@@ -215,20 +213,31 @@ impl TransactionBuilder {
       .expect("couldn't find output that contains the index");
 
     let postage = total_output_amount - Amount::from_sat(sat_offset);
-    if postage > Self::MAX_POSTAGE {
-      self.outputs.last_mut().expect("no outputs found").1 = Self::TARGET_POSTAGE;
+    if postage > self.max_postage() {
+      self.outputs.last_mut().expect("no outputs found").1 = self.target_postage();
       self.outputs.push((
         self
           .unused_change_addresses
           .pop()
           .expect("not enough change addresses"),
-        postage - Self::TARGET_POSTAGE,
+        postage - self.target_postage(),
       ));
     }
 
     self
   }
 
+  fn target_postage(&self) -> Amount {
+    policy::postage(
+      &self.recipient.script_pubkey(),
+      Self::TARGET_FEE_RATE.to_sat(),
+    )
+  }
+
+  fn max_postage(&self) -> Amount {
+    self.target_postage() * 2
+  }
+
   fn deduct_fee(mut self) -> Self {
     let sat_offset = self.calculate_sat_offset();
 
@@ -395,7 +404,7 @@ impl TransactionBuilder {
     for output in &transaction.output {
       if output.script_pubkey == self.recipient.script_pubkey() {
         assert!(
-          Amount::from_sat(output.value) < Self::MAX_POSTAGE,
+          Amount::from_sat(output.value) < self.max_postage(),
           "invariant: excess postage is stripped"
         );
         assert_eq!(
@@ -434,7 +443,7 @@ impl TransactionBuilder {
 
     for tx_out in &transaction.output {
       assert!(
-        Amount::from_sat(tx_out.value) >= tx_out.script_pubkey.dust_value(),
+        Amount::from_sat(tx_out.value) >= policy::dust_limit(&tx_out.script_pubkey),
         "invariant: all outputs are above dust limit",
       );
     }
@@ -695,7 +704,7 @@ mod tests {
         input: vec![tx_in(outpoint(1)), tx_in(outpoint(2))],
         output: vec![
           tx_out(4_950, change(1)),
-          tx_out(TransactionBuilder::TARGET_POSTAGE.to_sat(), recipient()),
+          tx_out(policy::TARGET_POSTAGE.to_sat(), recipient()),
           tx_out(9_589, change(0)),
         ],
       })
@@ -809,7 +818,7 @@ mod tests {
         lock_time: PackedLockTime::ZERO,
         input: vec![tx_in(outpoint(1))],
         output: vec![
-          tx_out(TransactionBuilder::TARGET_POSTAGE.to_sat(), recipient()),
+          tx_out(policy::TARGET_POSTAGE.to_sat(), recipient()),
           tx_out(989_749, change(1))
         ],
       })
@@ -4,8 +4,8 @@ use super::*;
 pub(crate) struct Transactions {}
 
 impl Transactions {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let txs = options
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    let txs = settings
       .bitcoin_rpc_client()?
       .list_transactions(None, None, None, None)?
       .iter()
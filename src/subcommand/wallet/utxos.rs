@@ -3,19 +3,32 @@ use super::*;
 #[derive(Debug, Parser)]
 pub(crate) struct Utxos {}
 
-impl Utxos {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let utxos = options
-      .bitcoin_rpc_client_for_wallet_command("ord wallet utxos")?
-      .list_unspent(None, None, None, None, None)?
-      .iter()
-      .map(|utxo| (OutPoint::new(utxo.txid, utxo.vout), utxo.amount))
-      .collect::<Vec<(OutPoint, Amount)>>();
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  pub(crate) output: OutPoint,
+  pub(crate) amount: u64,
+}
 
-    for (outpoint, amount) in utxos {
-      println!("{outpoint}\t{}", amount.to_sat());
+impl Utxos {
+  pub(crate) fn run(self, settings: Settings) -> Result {
+    for output in Self::list(&settings)? {
+      println!("{}\t{}", output.output, output.amount);
     }
 
     Ok(())
   }
+
+  pub(crate) fn list(settings: &Settings) -> Result<Vec<Output>> {
+    Ok(
+      settings
+        .bitcoin_rpc_client_for_wallet_command("ord wallet utxos")?
+        .list_unspent(None, None, None, None, None)?
+        .iter()
+        .map(|utxo| Output {
+          output: OutPoint::new(utxo.txid, utxo.vout),
+          amount: utxo.amount.to_sat(),
+        })
+        .collect(),
+    )
+  }
 }
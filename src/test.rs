@@ -67,6 +67,91 @@ pub(crate) fn tx_out(value: u64, address: Address) -> TxOut {
   }
 }
 
+/// A blank `width` by `height` PNG.
+pub(crate) fn png(width: u32, height: u32) -> Vec<u8> {
+  let mut png = io::Cursor::new(Vec::new());
+  image::RgbaImage::new(width, height)
+    .write_to(&mut png, image::ImageFormat::Png)
+    .unwrap();
+  png.into_inner()
+}
+
 pub(crate) fn inscription(content_type: &str, content: impl AsRef<[u8]>) -> Inscription {
   Inscription::new(Some(content_type.into()), Some(content.as_ref().into()))
 }
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct WebhookRequest {
+  pub(crate) body: String,
+  pub(crate) signature: Option<String>,
+}
+
+/// An HTTP server that records the requests it receives, and responds to
+/// them with the given statuses, followed by 200s.
+pub(crate) struct WebhookSink {
+  port: u16,
+  requests: Arc<Mutex<Vec<WebhookRequest>>>,
+}
+
+impl WebhookSink {
+  pub(crate) fn spawn(statuses: &[u16]) -> Self {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let clone = requests.clone();
+    let mut statuses = statuses.iter().copied().collect::<VecDeque<u16>>();
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut reader = BufReader::new(stream.unwrap());
+
+        let mut content_length = 0;
+        let mut signature = None;
+        loop {
+          let mut line = String::new();
+          reader.read_line(&mut line).unwrap();
+          let line = line.trim_end();
+          if line.is_empty() {
+            break;
+          }
+          if let Some((name, value)) = line.split_once(": ") {
+            match name.to_lowercase().as_str() {
+              "content-length" => content_length = value.parse().unwrap(),
+              "x-ord-signature" => signature = Some(value.to_string()),
+              _ => {}
+            }
+          }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        clone.lock().unwrap().push(WebhookRequest {
+          body: String::from_utf8(body).unwrap(),
+          signature,
+        });
+
+        write!(
+          reader.get_mut(),
+          "HTTP/1.1 {} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+          statuses.pop_front().unwrap_or(200),
+        )
+        .unwrap();
+      }
+    });
+
+    Self { port, requests }
+  }
+
+  pub(crate) fn url(&self) -> reqwest::Url {
+    format!("http://127.0.0.1:{}/webhook", self.port)
+      .parse()
+      .unwrap()
+  }
+
+  pub(crate) fn requests(&self) -> Vec<WebhookRequest> {
+    self.requests.lock().unwrap().drain(..).collect()
+  }
+}
@@ -0,0 +1,143 @@
+use super::*;
+
+/// Version and build metadata, embedded at compile time by `build.rs`.
+/// Fields that can't be determined, such as the commit of a build from
+/// crates.io, are `None`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct Version {
+  pub(crate) version: &'static str,
+  pub(crate) commit: Option<&'static str>,
+  pub(crate) describe: Option<&'static str>,
+  pub(crate) build_date: Option<String>,
+  pub(crate) features: Vec<&'static str>,
+  pub(crate) index_schema_version: u64,
+}
+
+impl Version {
+  pub(crate) fn current() -> Self {
+    Self {
+      version: env!("CARGO_PKG_VERSION"),
+      commit: option_env!("ORD_GIT_COMMIT"),
+      describe: option_env!("ORD_GIT_DESCRIBE"),
+      build_date: option_env!("ORD_BUILD_TIMESTAMP")
+        .and_then(|timestamp| timestamp.parse().ok())
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+      features: option_env!("ORD_FEATURES")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect(),
+      index_schema_version: index::SCHEMA_VERSION,
+    }
+  }
+
+  /// Output of `ord --version --verbose`, one field per line.
+  pub(crate) fn long(&self) -> String {
+    let unknown = |value: Option<&str>| value.unwrap_or("unknown").to_string();
+
+    format!(
+      "ord {}\ncommit: {}\ndescribe: {}\nbuild date: {}\nfeatures: {}\nindex schema version: {}\n",
+      self.version,
+      unknown(self.commit),
+      unknown(self.describe),
+      unknown(self.build_date.as_deref()),
+      if self.features.is_empty() {
+        "none".into()
+      } else {
+        self.features.join(",")
+      },
+      self.index_schema_version,
+    )
+  }
+}
+
+/// The crate version, followed by the commit if known. Used wherever a
+/// single-line version is reported, like the `X-Ord-Version` header.
+impl Display for Version {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.version)?;
+
+    if let Some(commit) = self.commit {
+      write!(f, " ({commit})")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn version() -> Version {
+    Version {
+      version: "1.2.3",
+      commit: Some("0123456789abcdef0123456789abcdef01234567"),
+      describe: Some("1.2.3-4-g0123456-dirty"),
+      build_date: Some("2023-01-02".into()),
+      features: vec!["foo", "bar"],
+      index_schema_version: 7,
+    }
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(
+      version().to_string(),
+      "1.2.3 (0123456789abcdef0123456789abcdef01234567)"
+    );
+
+    assert_eq!(
+      Version {
+        commit: None,
+        ..version()
+      }
+      .to_string(),
+      "1.2.3"
+    );
+  }
+
+  #[test]
+  fn long() {
+    assert_eq!(
+      version().long(),
+      "ord 1.2.3
+commit: 0123456789abcdef0123456789abcdef01234567
+describe: 1.2.3-4-g0123456-dirty
+build date: 2023-01-02
+features: foo,bar
+index schema version: 7
+"
+    );
+  }
+
+  #[test]
+  fn long_with_unknown_fields() {
+    assert_eq!(
+      Version {
+        commit: None,
+        describe: None,
+        build_date: None,
+        features: Vec::new(),
+        ..version()
+      }
+      .long(),
+      "ord 1.2.3
+commit: unknown
+describe: unknown
+build date: unknown
+features: none
+index schema version: 7
+"
+    );
+  }
+
+  #[test]
+  fn current() {
+    let version = Version::current();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(version.index_schema_version, index::SCHEMA_VERSION);
+    assert!(version.build_date.is_some());
+  }
+}
@@ -0,0 +1,525 @@
+use {
+  super::*,
+  bitcoin::hashes::{hmac, sha256, HashEngine},
+  reqwest::{blocking::Client, Url},
+  std::sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct WebhookOptions {
+  #[clap(
+    long,
+    help = "POST the new inscriptions and transfers in each indexed block to <WEBHOOK_URL>. May be given more than once."
+  )]
+  webhook_url: Vec<Url>,
+  #[clap(
+    long,
+    requires = "webhook-url",
+    help = "Sign webhook payloads with HMAC-SHA256 keyed by <WEBHOOK_SECRET>, sent as `X-Ord-Signature: sha256=<HEX>`."
+  )]
+  webhook_secret: Option<String>,
+}
+
+impl WebhookOptions {
+  /// Start delivering to the configured URLs, if there are any.
+  pub(crate) fn spawn(&self, options: &Options) -> Result<Option<Webhooks>> {
+    if self.webhook_url.is_empty() {
+      return Ok(None);
+    }
+
+    Ok(Some(Webhooks::spawn(
+      self.webhook_url.clone(),
+      self.webhook_secret.clone(),
+      options.data_dir()?.join(Webhooks::DEAD_LETTER_FILE),
+    )?))
+  }
+}
+
+/// The new inscriptions and transfers in a block, as POSTed to webhooks.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Notification {
+  pub(crate) height: u64,
+  pub(crate) hash: BlockHash,
+  pub(crate) inscriptions: Vec<NewInscription>,
+  pub(crate) transfers: Vec<Transfer>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct NewInscription {
+  pub(crate) inscription: InscriptionId,
+  pub(crate) satpoint: SatPoint,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Transfer {
+  pub(crate) inscription: InscriptionId,
+  pub(crate) from: SatPoint,
+  pub(crate) to: SatPoint,
+}
+
+/// A notification that could not be delivered.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DeadLetter {
+  pub(crate) url: Option<Url>,
+  pub(crate) error: String,
+  pub(crate) notification: Notification,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DeadLetters(pub(crate) Vec<DeadLetter>);
+
+impl Versioned for DeadLetters {
+  const VERSION: u32 = 1;
+
+  fn apply(&mut self, entry: serde_json::Value) -> Result {
+    self.0.push(serde_json::from_value(entry)?);
+    Ok(())
+  }
+}
+
+enum Message {
+  Notify(Notification),
+  Flush(SyncSender<()>),
+}
+
+/// Handle to a thread that POSTs notifications to webhooks. Notifications
+/// are queued without waiting, so slow or unreachable endpoints never hold
+/// up the updater. Those that cannot be delivered are appended to the
+/// journal of a dead letter state file in the data dir.
+#[derive(Clone)]
+pub(crate) struct Webhooks {
+  dead_letters: Arc<StateFile<DeadLetters>>,
+  sender: SyncSender<Message>,
+}
+
+impl Webhooks {
+  const ATTEMPTS: u32 = 4;
+  pub(crate) const DEAD_LETTER_FILE: &'static str = "webhook-dead-letters.json";
+  const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+  /// Dead letters were appended to this file, beside the state file, before
+  /// there was a state file.
+  const LEGACY_DEAD_LETTER_FILE: &'static str = "webhook-dead-letters.jsonl";
+  const QUEUE_CAPACITY: usize = 1024;
+  const TIMEOUT: Duration = Duration::from_secs(10);
+
+  pub(crate) fn spawn(
+    urls: Vec<Url>,
+    secret: Option<String>,
+    dead_letters: PathBuf,
+  ) -> Result<Self> {
+    let (sender, receiver) = mpsc::sync_channel(Self::QUEUE_CAPACITY);
+
+    let legacy = dead_letters.with_file_name(Self::LEGACY_DEAD_LETTER_FILE);
+
+    let dead_letters = Arc::new(StateFile::<DeadLetters>::new(dead_letters));
+
+    Self::migrate_legacy_dead_letters(&legacy, &dead_letters)?;
+
+    let undelivered = dead_letters.compact()?.0.len();
+
+    if undelivered > 0 {
+      log::warn!(
+        "{undelivered} undelivered webhook notifications in `{}`",
+        dead_letters.path().display()
+      );
+    }
+
+    let delivery = Delivery {
+      client: Client::builder().timeout(Self::TIMEOUT).build()?,
+      dead_letters: dead_letters.clone(),
+      secret,
+      urls,
+    };
+
+    thread::Builder::new()
+      .name("webhooks".into())
+      .spawn(move || delivery.run(receiver))?;
+
+    Ok(Self {
+      dead_letters,
+      sender,
+    })
+  }
+
+  /// Move dead letters from the legacy append-only file at `legacy`, if
+  /// there is one, into `dead_letters`.
+  fn migrate_legacy_dead_letters(legacy: &Path, dead_letters: &StateFile<DeadLetters>) -> Result {
+    let contents = match fs::read_to_string(legacy) {
+      Ok(contents) => contents,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let migrated = contents
+      .lines()
+      .filter(|line| !line.trim().is_empty())
+      .map(serde_json::from_str)
+      .collect::<serde_json::Result<Vec<DeadLetter>>>()
+      .with_context(|| format!("invalid dead letter in `{}`", legacy.display()))?;
+
+    log::info!(
+      "Migrating {} webhook dead letters from `{}` to `{}`",
+      migrated.len(),
+      legacy.display(),
+      dead_letters.path().display()
+    );
+
+    dead_letters.update(|dead_letters| dead_letters.0.extend(migrated))?;
+
+    fs::remove_file(legacy)?;
+
+    Ok(())
+  }
+
+  /// Queue `notification` for delivery. If the queue is full, it is dead
+  /// lettered immediately instead of waiting for room.
+  pub(crate) fn notify(&self, notification: Notification) {
+    match self.sender.try_send(Message::Notify(notification)) {
+      Ok(()) => {}
+      Err(TrySendError::Full(Message::Notify(notification))) => dead_letter(
+        &self.dead_letters,
+        None,
+        &notification,
+        "delivery queue full",
+      ),
+      Err(_) => log::error!("Webhook delivery thread has stopped"),
+    }
+  }
+
+  /// Wait until everything queued so far has been delivered or dead
+  /// lettered.
+  pub(crate) fn flush(&self) -> Result {
+    let (sender, receiver) = mpsc::sync_channel(1);
+
+    self
+      .sender
+      .send(Message::Flush(sender))
+      .ok()
+      .and_then(|()| receiver.recv().ok())
+      .ok_or_else(|| anyhow!("webhook delivery thread has stopped"))
+  }
+}
+
+struct Delivery {
+  client: Client,
+  dead_letters: Arc<StateFile<DeadLetters>>,
+  secret: Option<String>,
+  urls: Vec<Url>,
+}
+
+impl Delivery {
+  fn run(self, receiver: Receiver<Message>) {
+    for message in receiver {
+      match message {
+        Message::Notify(notification) => self.deliver(&notification),
+        Message::Flush(sender) => {
+          sender.send(()).ok();
+        }
+      }
+    }
+  }
+
+  fn deliver(&self, notification: &Notification) {
+    let body = serde_json::to_vec(notification).unwrap();
+
+    let signature = self
+      .secret
+      .as_ref()
+      .map(|secret| signature(secret.as_bytes(), &body));
+
+    for url in &self.urls {
+      let mut backoff = Webhooks::INITIAL_BACKOFF;
+
+      for attempt in 1..=Webhooks::ATTEMPTS {
+        let mut request = self
+          .client
+          .post(url.clone())
+          .header(reqwest::header::CONTENT_TYPE, "application/json")
+          .body(body.clone());
+
+        if let Some(signature) = &signature {
+          request = request.header("X-Ord-Signature", signature);
+        }
+
+        let error = match request.send() {
+          Ok(response) if response.status().is_success() => break,
+          Ok(response) => format!("{url} responded with {}", response.status()),
+          Err(err) => err.to_string(),
+        };
+
+        if attempt == Webhooks::ATTEMPTS {
+          dead_letter(&self.dead_letters, Some(url), notification, &error);
+        } else {
+          log::warn!(
+            "Webhook delivery of block {} attempt {attempt} failed: {error}",
+            notification.height
+          );
+          thread::sleep(backoff);
+          backoff *= 2;
+        }
+      }
+    }
+  }
+}
+
+/// The value of the `X-Ord-Signature` header for `body`.
+pub(crate) fn signature(secret: &[u8], body: &[u8]) -> String {
+  let mut engine = hmac::HmacEngine::<sha256::Hash>::new(secret);
+  engine.input(body);
+  format!(
+    "sha256={}",
+    hex::encode(hmac::Hmac::from_engine(engine).into_inner())
+  )
+}
+
+fn dead_letter(
+  dead_letters: &StateFile<DeadLetters>,
+  url: Option<&Url>,
+  notification: &Notification,
+  error: &str,
+) {
+  log::error!(
+    "Failed to deliver webhook for block {}: {error}",
+    notification.height
+  );
+
+  let dead_letter = DeadLetter {
+    url: url.cloned(),
+    error: error.into(),
+    notification: notification.clone(),
+  };
+
+  if let Err(err) = dead_letters.append(&dead_letter) {
+    log::error!(
+      "Failed to write webhook dead letter to `{}`: {err}",
+      dead_letters.path().display()
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn notification(height: u64) -> Notification {
+    Notification {
+      height,
+      hash: BlockHash::all_zeros(),
+      inscriptions: vec![NewInscription {
+        inscription: outpoint(1).txid,
+        satpoint: satpoint(1, 0),
+      }],
+      transfers: vec![Transfer {
+        inscription: outpoint(2).txid,
+        from: satpoint(2, 0),
+        to: satpoint(3, 0),
+      }],
+    }
+  }
+
+  #[test]
+  fn signature_is_hmac_sha256() {
+    assert_eq!(
+      signature(b"Jefe", b"what do ya want for nothing?"),
+      "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+  }
+
+  #[test]
+  fn notifications_are_signed_and_delivered_to_every_url() {
+    let a = WebhookSink::spawn(&[]);
+    let b = WebhookSink::spawn(&[]);
+    let tempdir = TempDir::new().unwrap();
+
+    let webhooks = Webhooks::spawn(
+      vec![a.url(), b.url()],
+      Some("secret".into()),
+      tempdir.path().join(Webhooks::DEAD_LETTER_FILE),
+    )
+    .unwrap();
+
+    webhooks.notify(notification(7));
+    webhooks.flush().unwrap();
+
+    let body = serde_json::to_string(&notification(7)).unwrap();
+
+    for sink in [a, b] {
+      let requests = sink.requests();
+      assert_eq!(requests.len(), 1);
+      assert_eq!(requests[0].body, body);
+      assert_eq!(
+        requests[0].signature.as_deref(),
+        Some(signature(b"secret", body.as_bytes()).as_str())
+      );
+    }
+
+    assert_eq!(
+      serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+      serde_json::json!({
+        "height": 7,
+        "hash": BlockHash::all_zeros(),
+        "inscriptions": [{ "inscription": outpoint(1).txid, "satpoint": satpoint(1, 0) }],
+        "transfers": [{ "inscription": outpoint(2).txid, "from": satpoint(2, 0), "to": satpoint(3, 0) }],
+      })
+    );
+  }
+
+  #[test]
+  fn unsigned_without_secret() {
+    let sink = WebhookSink::spawn(&[]);
+    let tempdir = TempDir::new().unwrap();
+
+    let webhooks = Webhooks::spawn(
+      vec![sink.url()],
+      None,
+      tempdir.path().join(Webhooks::DEAD_LETTER_FILE),
+    )
+    .unwrap();
+
+    webhooks.notify(notification(0));
+    webhooks.flush().unwrap();
+
+    assert_eq!(sink.requests()[0].signature, None);
+  }
+
+  #[test]
+  fn failed_deliveries_are_retried() {
+    let sink = WebhookSink::spawn(&[500, 503]);
+    let tempdir = TempDir::new().unwrap();
+    let dead_letters = tempdir.path().join(Webhooks::DEAD_LETTER_FILE);
+
+    let webhooks = Webhooks::spawn(vec![sink.url()], None, dead_letters.clone()).unwrap();
+
+    webhooks.notify(notification(0));
+    webhooks.flush().unwrap();
+
+    assert_eq!(sink.requests().len(), 3);
+    assert!(!dead_letters.exists());
+  }
+
+  #[test]
+  fn permanently_failed_deliveries_are_dead_lettered() {
+    let sink = WebhookSink::spawn(&[500; 8]);
+    let tempdir = TempDir::new().unwrap();
+    let dead_letters = tempdir.path().join(Webhooks::DEAD_LETTER_FILE);
+
+    let webhooks = Webhooks::spawn(vec![sink.url()], None, dead_letters.clone()).unwrap();
+
+    webhooks.notify(notification(5));
+    webhooks.flush().unwrap();
+
+    assert_eq!(sink.requests().len(), 4);
+
+    webhooks.notify(notification(6));
+    webhooks.flush().unwrap();
+
+    // Dead letters are appended to the journal without rewriting the state
+    // file.
+    assert!(!dead_letters.exists());
+
+    assert_eq!(
+      StateFile::<DeadLetters>::new(dead_letters.clone())
+        .load()
+        .unwrap()
+        .0
+        .into_iter()
+        .map(|dead_letter| dead_letter.notification)
+        .collect::<Vec<Notification>>(),
+      [notification(5), notification(6)],
+    );
+
+    // They are folded into the state file at startup.
+    Webhooks::spawn(vec![], None, dead_letters.clone()).unwrap();
+
+    let state =
+      serde_json::from_str::<serde_json::Value>(&fs::read_to_string(&dead_letters).unwrap())
+        .unwrap();
+
+    assert_eq!(state["version"], DeadLetters::VERSION);
+    assert_eq!(
+      state["state"][0],
+      serde_json::json!({
+        "url": sink.url(),
+        "error": format!("{} responded with 500 Internal Server Error", sink.url()),
+        "notification": notification(5),
+      })
+    );
+    assert_eq!(state["state"].as_array().unwrap().len(), 2);
+  }
+
+  #[test]
+  fn legacy_dead_letters_are_migrated() {
+    let tempdir = TempDir::new().unwrap();
+    let dead_letters = tempdir.path().join(Webhooks::DEAD_LETTER_FILE);
+    let legacy = tempdir.path().join(Webhooks::LEGACY_DEAD_LETTER_FILE);
+
+    let dead_letter = DeadLetter {
+      url: None,
+      error: "delivery queue full".into(),
+      notification: notification(3),
+    };
+
+    fs::write(
+      &legacy,
+      format!("{}\n", serde_json::to_string(&dead_letter).unwrap()),
+    )
+    .unwrap();
+
+    Webhooks::spawn(vec![], None, dead_letters.clone()).unwrap();
+
+    assert!(!legacy.exists());
+    assert_eq!(
+      StateFile::<DeadLetters>::new(dead_letters).load().unwrap(),
+      DeadLetters(vec![dead_letter]),
+    );
+  }
+
+  #[test]
+  fn dead_letters_with_unsupported_schema_are_rejected_at_startup() {
+    let tempdir = TempDir::new().unwrap();
+    let dead_letters = tempdir.path().join(Webhooks::DEAD_LETTER_FILE);
+
+    fs::write(&dead_letters, r#"{"version":0,"state":[]}"#).unwrap();
+
+    assert!(Webhooks::spawn(vec![], None, dead_letters)
+      .err()
+      .unwrap()
+      .to_string()
+      .contains("has schema version 0"));
+  }
+
+  #[test]
+  fn notify_does_not_wait_for_delivery() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap())
+      .parse()
+      .unwrap();
+    let tempdir = TempDir::new().unwrap();
+    let dead_letters = tempdir.path().join(Webhooks::DEAD_LETTER_FILE);
+
+    // Nothing ever accepts connections, so the first delivery stalls until
+    // it times out, and the queue fills up behind it.
+    let webhooks = Webhooks::spawn(vec![url], None, dead_letters.clone()).unwrap();
+
+    let start = Instant::now();
+
+    for height in 0..=Webhooks::QUEUE_CAPACITY + 1 {
+      webhooks.notify(notification(height.try_into().unwrap()));
+    }
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // Depending on whether the delivery thread has taken the first
+    // notification off the queue yet, one or two are dead lettered.
+    let dead_letters = StateFile::<DeadLetters>::new(dead_letters).load().unwrap();
+
+    assert_eq!(
+      dead_letters.0.last().unwrap(),
+      &DeadLetter {
+        url: None,
+        error: "delivery queue full".into(),
+        notification: notification((Webhooks::QUEUE_CAPACITY + 1).try_into().unwrap()),
+      }
+    );
+  }
+}
@@ -116,4 +116,14 @@ pub trait Api {
     skip: Option<usize>,
     include_watchonly: Option<bool>,
   ) -> Result<Vec<ListTransactionResult>, jsonrpc_core::Error>;
+
+  #[rpc(name = "invalidateblock")]
+  fn invalidate_block(&self, blockhash: BlockHash) -> Result<(), jsonrpc_core::Error>;
+
+  #[rpc(name = "generatetoaddress")]
+  fn generate_to_address(
+    &self,
+    nblocks: u64,
+    address: String,
+  ) -> Result<Vec<BlockHash>, jsonrpc_core::Error>;
 }
@@ -6,9 +6,13 @@ use {
     consensus::encode::{deserialize, serialize},
     hash_types::BlockHash,
     hashes::Hash,
-    util::amount::SignedAmount,
-    Amount, Block, BlockHeader, Network, OutPoint, PackedLockTime, Script, Sequence, Transaction,
-    TxIn, TxMerkleNode, TxOut, Txid, Witness, Wtxid,
+    secp256k1::Secp256k1,
+    util::{
+      amount::SignedAmount,
+      bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey},
+    },
+    Address, Amount, Block, BlockHeader, Network, OutPoint, PackedLockTime, Script, Sequence,
+    Transaction, TxIn, TxMerkleNode, TxOut, Txid, Witness, Wtxid,
   },
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, GetBalancesResult, GetBalancesResultEntry,
@@ -17,8 +21,10 @@ use {
     GetTransactionResultDetailCategory, GetWalletInfoResult, ListTransactionResult,
     ListUnspentResultEntry, LoadWalletResult, SignRawTransactionResult, WalletTxInfo,
   },
-  jsonrpc_core::{IoHandler, Value},
+  jsonrpc_core::{MetaIoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
+  proxy::Proxy,
+  recorder::Recorder,
   server::Server,
   state::State,
   std::{
@@ -30,13 +36,15 @@ use {
 };
 
 mod api;
+mod proxy;
+mod recorder;
 mod server;
 mod state;
 
 pub fn spawn_with(network: Network, wallet_name: &str) -> Handle {
   let state = Arc::new(Mutex::new(State::new(network, wallet_name)));
   let server = Server::new(state.clone());
-  let mut io = IoHandler::default();
+  let mut io = MetaIoHandler::with_middleware(Recorder::new(state.clone()));
   io.extend_with(server.to_delegate());
 
   let rpc_server = ServerBuilder::new(io)
@@ -45,7 +53,8 @@ pub fn spawn_with(network: Network, wallet_name: &str) -> Handle {
     .unwrap();
 
   let close_handle = rpc_server.close_handle();
-  let port = rpc_server.address().port();
+  let proxy = Proxy::spawn(*rpc_server.address(), state.clone());
+  let port = proxy.port();
 
   thread::spawn(|| rpc_server.wait());
 
@@ -65,6 +74,7 @@ pub fn spawn_with(network: Network, wallet_name: &str) -> Handle {
   Handle {
     close_handle: Some(close_handle),
     port,
+    proxy,
     state,
   }
 }
@@ -82,6 +92,8 @@ pub struct TransactionTemplate<'a> {
 pub struct Handle {
   close_handle: Option<CloseHandle>,
   port: u16,
+  #[allow(unused)]
+  proxy: Proxy,
   state: Arc<Mutex<State>>,
 }
 
@@ -90,7 +102,7 @@ impl Handle {
     format!("http://127.0.0.1:{}", self.port)
   }
 
-  fn state(&self) -> MutexGuard<State> {
+  fn state(&self) -> MutexGuard<'_, State> {
     self.state.lock().unwrap()
   }
 
@@ -116,6 +128,10 @@ impl Handle {
     self.state().broadcast_tx(options)
   }
 
+  pub fn broadcast(&self, tx: Transaction) -> Txid {
+    self.state().broadcast(tx)
+  }
+
   pub fn invalidate_tip(&self) -> BlockHash {
     self.state().pop_block()
   }
@@ -125,9 +141,40 @@ impl Handle {
     state.blocks[&state.hashes[bi]].txdata[ti].clone()
   }
 
+  /// Number of HTTP requests received, counting a batch as one request.
+  pub fn request_count(&self) -> usize {
+    self.state().requests
+  }
+
+  /// Number of TCP connections accepted.
+  pub fn connections(&self) -> usize {
+    self.state().connections
+  }
+
+  pub fn batch_count(&self) -> usize {
+    self.state().batches
+  }
+
+  /// Respond to batch requests with an error, like a node or proxy which
+  /// doesn't support them.
+  pub fn reject_batches(&self) {
+    self.state().reject_batches = true;
+  }
+
+  /// Wait `latency` before handling each request, like a busy node.
+  pub fn set_latency(&self, latency: Duration) {
+    self.state().latency = latency;
+  }
+
   pub fn mempool(&self) -> Vec<Transaction> {
     self.state.lock().unwrap().mempool().to_vec()
   }
+
+  /// A public descriptor for the wallet's change addresses, which is where
+  /// inscriptions are sent when they are revealed.
+  pub fn change_descriptor(&self) -> String {
+    self.state.lock().unwrap().descriptor(true)
+  }
 }
 
 impl Drop for Handle {
@@ -0,0 +1,65 @@
+use {
+  super::*,
+  std::{
+    io,
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::atomic::{AtomicBool, Ordering},
+  },
+};
+
+/// Forwards connections to the RPC server, counting them, so that tests can
+/// check that clients reuse connections.
+pub(crate) struct Proxy {
+  closed: Arc<AtomicBool>,
+  port: u16,
+}
+
+impl Proxy {
+  pub(crate) fn spawn(server: SocketAddr, state: Arc<Mutex<State>>) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let closed = Arc::new(AtomicBool::new(false));
+
+    {
+      let closed = closed.clone();
+      thread::spawn(move || {
+        for client in listener.incoming() {
+          if closed.load(Ordering::Relaxed) {
+            break;
+          }
+
+          let (Ok(client), Ok(server)) = (client, TcpStream::connect(server)) else {
+            continue;
+          };
+
+          state.lock().unwrap().connections += 1;
+
+          Self::forward(client.try_clone().unwrap(), server.try_clone().unwrap());
+          Self::forward(server, client);
+        }
+      });
+    }
+
+    Self { closed, port }
+  }
+
+  pub(crate) fn port(&self) -> u16 {
+    self.port
+  }
+
+  fn forward(mut from: TcpStream, mut to: TcpStream) {
+    thread::spawn(move || {
+      io::copy(&mut from, &mut to).ok();
+      from.shutdown(Shutdown::Both).ok();
+      to.shutdown(Shutdown::Both).ok();
+    });
+  }
+}
+
+impl Drop for Proxy {
+  fn drop(&mut self) {
+    self.closed.store(true, Ordering::Relaxed);
+    // Wake the accept loop so that it sees that the proxy is closed.
+    TcpStream::connect(("127.0.0.1", self.port)).ok();
+  }
+}
@@ -0,0 +1,54 @@
+use {
+  super::*,
+  jsonrpc_core::{
+    futures::future::{self, Either},
+    middleware::{Middleware, NoopCallFuture, NoopFuture},
+    Error, Request, Response, Version,
+  },
+};
+
+/// Counts requests, rejects batches if asked to, and delays responses by
+/// `State::latency`.
+pub(crate) struct Recorder {
+  state: Arc<Mutex<State>>,
+}
+
+impl Recorder {
+  pub(crate) fn new(state: Arc<Mutex<State>>) -> Self {
+    Self { state }
+  }
+}
+
+impl Middleware<()> for Recorder {
+  type Future = NoopFuture;
+  type CallFuture = NoopCallFuture;
+
+  fn on_request<F, X>(&self, request: Request, meta: (), next: F) -> Either<Self::Future, X>
+  where
+    F: Fn(Request, ()) -> X + Send + Sync,
+    X: std::future::Future<Output = Option<Response>> + Send + 'static,
+  {
+    let mut state = self.state.lock().unwrap();
+
+    state.requests += 1;
+
+    if let Request::Batch(_) = request {
+      if state.reject_batches {
+        return Either::Left(Box::pin(future::ready(Some(Response::from(
+          Error::invalid_request(),
+          Some(Version::V2),
+        )))));
+      }
+
+      state.batches += 1;
+    }
+
+    let latency = state.latency;
+
+    drop(state);
+
+    thread::sleep(latency);
+
+    Either::Right(next(request, meta))
+  }
+}
@@ -1,11 +1,4 @@
-use {
-  super::*,
-  bitcoin::{
-    secp256k1::{rand, KeyPair, Secp256k1, XOnlyPublicKey},
-    Address, Witness,
-  },
-  serde_json::json,
-};
+use {super::*, bitcoin::Witness, serde_json::json};
 
 pub(crate) struct Server {
   pub(crate) state: Arc<Mutex<State>>,
@@ -18,7 +11,7 @@ impl Server {
     Self { network, state }
   }
 
-  fn state(&self) -> MutexGuard<State> {
+  fn state(&self) -> MutexGuard<'_, State> {
     self.state.lock().unwrap()
   }
 
@@ -254,9 +247,7 @@ impl Api for Server {
 
   fn send_raw_transaction(&self, tx: String) -> Result<String, jsonrpc_core::Error> {
     let tx: Transaction = deserialize(&hex::decode(tx).unwrap()).unwrap();
-    self.state.lock().unwrap().mempool.push(tx.clone());
-
-    Ok(tx.txid().to_string())
+    Ok(self.state().broadcast(tx).to_string())
   }
 
   fn get_transaction(
@@ -264,7 +255,12 @@ impl Api for Server {
     txid: Txid,
     _include_watchonly: Option<bool>,
   ) -> Result<Value, jsonrpc_core::Error> {
-    match self.state.lock().unwrap().transactions.get(&txid) {
+    let state = self.state();
+    match state
+      .transactions
+      .get(&txid)
+      .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+    {
       Some(tx) => Ok(
         serde_json::to_value(GetTransactionResult {
           info: WalletTxInfo {
@@ -369,12 +365,7 @@ impl Api for Server {
   }
 
   fn get_raw_change_address(&self) -> Result<bitcoin::Address, jsonrpc_core::Error> {
-    let secp256k1 = Secp256k1::new();
-    let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
-    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
-    let address = Address::p2tr(&secp256k1, public_key, None, self.network);
-
-    Ok(address)
+    Ok(self.state().next_address(true))
   }
 
   fn get_descriptor_info(
@@ -402,12 +393,7 @@ impl Api for Server {
     _label: Option<String>,
     _address_type: Option<()>,
   ) -> Result<bitcoin::Address, jsonrpc_core::Error> {
-    let secp256k1 = Secp256k1::new();
-    let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
-    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
-    let address = Address::p2tr(&secp256k1, public_key, None, self.network);
-
-    Ok(address)
+    Ok(self.state().next_address(false))
   }
 
   fn list_transactions(
@@ -452,4 +438,25 @@ impl Api for Server {
         .collect(),
     )
   }
+
+  fn invalidate_block(&self, blockhash: BlockHash) -> Result<(), jsonrpc_core::Error> {
+    if self.state().invalidate_block(blockhash) {
+      Ok(())
+    } else {
+      Err(Self::not_found())
+    }
+  }
+
+  fn generate_to_address(
+    &self,
+    nblocks: u64,
+    _address: String,
+  ) -> Result<Vec<BlockHash>, jsonrpc_core::Error> {
+    let mut state = self.state();
+    Ok(
+      (0..nblocks)
+        .map(|_| state.push_block(50 * COIN_VALUE).block_hash())
+        .collect(),
+    )
+  }
 }
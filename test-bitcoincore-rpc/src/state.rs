@@ -1,11 +1,18 @@
 use super::*;
 
 pub(crate) struct State {
+  pub(crate) account: ExtendedPrivKey,
+  pub(crate) addresses: [u32; 2],
+  pub(crate) batches: usize,
   pub(crate) blocks: BTreeMap<BlockHash, Block>,
+  pub(crate) connections: usize,
   pub(crate) hashes: Vec<BlockHash>,
+  pub(crate) latency: Duration,
   pub(crate) mempool: Vec<Transaction>,
   pub(crate) network: Network,
   pub(crate) nonce: u32,
+  pub(crate) reject_batches: bool,
+  pub(crate) requests: usize,
   pub(crate) transactions: BTreeMap<Txid, Transaction>,
   pub(crate) utxos: BTreeMap<OutPoint, Amount>,
   pub(crate) wallet_name: String,
@@ -22,12 +29,25 @@ impl State {
     hashes.push(genesis_block_hash);
     blocks.insert(genesis_block_hash, genesis_block);
 
+    let secp256k1 = Secp256k1::new();
+    let master = ExtendedPrivKey::new_master(network, &[0; 32]).unwrap();
+    let account = master
+      .derive_priv(&secp256k1, &Self::account_path(network))
+      .unwrap();
+
     Self {
+      account,
+      addresses: [0, 0],
+      batches: 0,
       blocks,
+      connections: 0,
       hashes,
+      latency: Duration::ZERO,
       mempool: Vec::new(),
       network,
       nonce: 0,
+      reject_batches: false,
+      requests: 0,
       transactions: BTreeMap::new(),
       utxos: BTreeMap::new(),
       wallet_name: wallet_name.to_string(),
@@ -35,6 +55,54 @@ impl State {
     }
   }
 
+  fn account_path(network: Network) -> DerivationPath {
+    format!(
+      "m/86'/{}'/0'",
+      if network == Network::Bitcoin { 0 } else { 1 }
+    )
+    .parse()
+    .unwrap()
+  }
+
+  /// The next unused BIP 86 wallet address, from the change chain if
+  /// `change`.
+  pub(crate) fn next_address(&mut self, change: bool) -> Address {
+    let secp256k1 = Secp256k1::new();
+    let index = &mut self.addresses[usize::from(change)];
+    let key = self
+      .account
+      .derive_priv(
+        &secp256k1,
+        &[
+          ChildNumber::from_normal_idx(change.into()).unwrap(),
+          ChildNumber::from_normal_idx(*index).unwrap(),
+        ],
+      )
+      .unwrap();
+    *index += 1;
+    Address::p2tr(
+      &secp256k1,
+      key.to_keypair(&secp256k1).x_only_public_key().0,
+      None,
+      self.network,
+    )
+  }
+
+  /// A watch-only descriptor for the wallet's receive or change addresses.
+  pub(crate) fn descriptor(&self, change: bool) -> String {
+    let secp256k1 = Secp256k1::new();
+    let master = ExtendedPrivKey::new_master(self.network, &[0; 32]).unwrap();
+    format!(
+      "tr([{}/{}]{}/{}/*)",
+      master.fingerprint(&secp256k1),
+      Self::account_path(self.network)
+        .to_string()
+        .trim_start_matches("m/"),
+      ExtendedPubKey::from_priv(&secp256k1, &self.account),
+      u8::from(change),
+    )
+  }
+
   pub(crate) fn push_block(&mut self, subsidy: u64) -> Block {
     let coinbase = Transaction {
       version: 0,
@@ -118,6 +186,56 @@ impl State {
     blockhash
   }
 
+  /// Disconnect `blockhash` and all of its descendants, like Bitcoin Core's
+  /// `invalidateblock`. Non-coinbase transactions in disconnected blocks are
+  /// returned to the mempool. Returns false if `blockhash` is not in the
+  /// active chain, or is the genesis block.
+  pub(crate) fn invalidate_block(&mut self, blockhash: BlockHash) -> bool {
+    let height = match self.hashes.iter().position(|hash| *hash == blockhash) {
+      Some(0) | None => return false,
+      Some(height) => height,
+    };
+
+    let mut disconnected = Vec::new();
+
+    while self.hashes.len() > height {
+      let block = self.blocks.remove(&self.hashes.pop().unwrap()).unwrap();
+
+      for tx in block.txdata.iter().rev() {
+        for vout in 0..tx.output.len() {
+          self
+            .utxos
+            .remove(&OutPoint::new(tx.txid(), vout.try_into().unwrap()));
+        }
+
+        for input in &tx.input {
+          if !input.previous_output.is_null() {
+            let value = self.transactions[&input.previous_output.txid].output
+              [usize::try_from(input.previous_output.vout).unwrap()]
+            .value;
+            self
+              .utxos
+              .insert(input.previous_output, Amount::from_sat(value));
+          }
+        }
+      }
+
+      disconnected.push(block);
+    }
+
+    let mut mempool = disconnected
+      .into_iter()
+      .rev()
+      .flat_map(|block| block.txdata.into_iter().skip(1))
+      .collect::<Vec<Transaction>>();
+
+    mempool.append(&mut self.mempool);
+
+    self.mempool = mempool;
+
+    true
+  }
+
   pub(crate) fn broadcast_tx(&mut self, options: TransactionTemplate) -> Txid {
     let mut total_value = 0;
     let mut input = Vec::new();
@@ -138,7 +256,7 @@ impl State {
       total_value
     );
 
-    let tx = Transaction {
+    self.broadcast(Transaction {
       version: 0,
       lock_time: PackedLockTime(0),
       input,
@@ -148,10 +266,13 @@ impl State {
           script_pubkey: script::Builder::new().into_script(),
         })
         .collect(),
-    };
-    self.mempool.push(tx.clone());
+    })
+  }
 
-    tx.txid()
+  pub(crate) fn broadcast(&mut self, tx: Transaction) -> Txid {
+    let txid = tx.txid();
+    self.mempool.push(tx);
+    txid
   }
 
   pub(crate) fn mempool(&self) -> &[Transaction] {
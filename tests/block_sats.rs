@@ -0,0 +1,60 @@
+use super::*;
+
+#[test]
+fn genesis() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new("--index-satoshis block-sats 0")
+    .rpc_server(&rpc_server)
+    .expected_stdout(
+      "0\tmythic\t4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0\n",
+    )
+    .run();
+}
+
+#[test]
+fn tracks_spent_sat() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(1, 0, 0)],
+    output_count: 1,
+    fee: 0,
+  });
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-satoshis block-sats 1")
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!("5000000000\tuncommon\t{txid}:0:0\n"))
+    .run();
+}
+
+#[test]
+fn rarity_filter() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-satoshis block-sats 1 --rarity rare")
+    .rpc_server(&rpc_server)
+    .expected_stdout("")
+    .run();
+}
+
+#[test]
+fn unmined_block() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new("--index-satoshis block-sats 1")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: block 1 has not been mined as of index height\n")
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn no_satoshi_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new("block-sats 0")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: block-sats requires index created with `--index-rare-sats` flag\n")
+    .expected_exit_code(1)
+    .run();
+}
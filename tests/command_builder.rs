@@ -16,6 +16,12 @@ impl ToArgs for &str {
   }
 }
 
+impl ToArgs for Vec<String> {
+  fn to_args(&self) -> Vec<String> {
+    self.clone()
+  }
+}
+
 impl<const N: usize> ToArgs for [&str; N] {
   fn to_args(&self) -> Vec<String> {
     self.iter().cloned().map(str::to_string).collect()
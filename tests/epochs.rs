@@ -44,3 +44,21 @@ fn empty() {
     )
     .run();
 }
+
+#[test]
+fn custom_schedule() {
+  let mut expected = String::new();
+  let mut start = 0;
+  for epoch in 0..=33 {
+    expected.push_str(&format!("{start}\n"));
+    start += ((50 * COIN_VALUE) >> epoch) * 2;
+  }
+
+  CommandBuilder::new("--chain custom --config config.json epochs")
+    .write(
+      "config.json",
+      r#"{"schedule": {"initial_subsidy": 5000000000, "halving_interval": 2}}"#,
+    )
+    .expected_stdout(expected)
+    .run();
+}
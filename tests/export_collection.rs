@@ -0,0 +1,116 @@
+use super::*;
+
+fn inscribe(rpc_server: &test_bitcoincore_rpc::Handle, file: &str, contents: &[u8]) -> Txid {
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let stdout = CommandBuilder::new(format!(
+    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file {file}"
+  ))
+  .write(file, contents)
+  .rpc_server(rpc_server)
+  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+  .run();
+
+  rpc_server.mine_blocks(1);
+
+  reveal_txid_from_inscribe_stdout(&stdout)
+}
+
+fn links(html: &str) -> Vec<String> {
+  Regex::new("(?:href|src)=([^ >]+)")
+    .unwrap()
+    .captures_iter(html)
+    .map(|captures| captures[1].trim_matches('\'').to_string())
+    .filter(|link| !link.starts_with("data:"))
+    .collect()
+}
+
+#[test]
+fn export_collection() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+
+  let text = inscribe(&rpc_server, "hello.txt", b"HELLOWORLD");
+  let png = inscribe(&rpc_server, "degenerate.png", &[1; 520]);
+
+  let tempdir = TempDir::new().unwrap();
+  let output = tempdir.path().join("export");
+
+  CommandBuilder::new(format!(
+    "--chain regtest export-collection --inscriptions ids.txt --output {}",
+    output.display()
+  ))
+  .write("ids.txt", format!("{text}\n{png}\n"))
+  .rpc_server(&rpc_server)
+  .expected_stdout(format!(
+    "{text}\tinscription/{text}.html\n{png}\tinscription/{png}.html\n"
+  ))
+  .run();
+
+  let index: serde_json::Value =
+    serde_json::from_str(&fs::read_to_string(output.join("index.json")).unwrap()).unwrap();
+
+  let inscriptions = index["inscriptions"].as_array().unwrap();
+  assert_eq!(inscriptions.len(), 2);
+  assert_eq!(inscriptions[0]["id"], text.to_string());
+  assert_eq!(inscriptions[0]["number"], 0);
+  assert_eq!(inscriptions[0]["content_type"], "text/plain;charset=utf-8");
+  assert_eq!(inscriptions[1]["id"], png.to_string());
+  assert_eq!(inscriptions[1]["number"], 1);
+  assert_eq!(inscriptions[1]["content_size"], 520);
+
+  assert_eq!(
+    fs::read(output.join(inscriptions[0]["content"].as_str().unwrap())).unwrap(),
+    b"HELLOWORLD"
+  );
+
+  let mut pages = vec![output.join("index.html")];
+  for inscription in inscriptions {
+    pages.push(output.join(inscription["page"].as_str().unwrap()));
+  }
+
+  for page in pages {
+    let html = fs::read_to_string(&page).unwrap();
+    assert!(!html.contains("/static/"));
+
+    let links = links(&html);
+    assert!(!links.is_empty());
+
+    for link in links {
+      assert!(
+        page.parent().unwrap().join(&link).is_file(),
+        "{} links to missing file {link}",
+        page.display()
+      );
+    }
+  }
+
+  assert!(
+    fs::read_to_string(output.join(format!("inscription/{text}.html")))
+      .unwrap()
+      .contains("HELLOWORLD")
+  );
+}
+
+#[test]
+fn unknown_inscription() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  CommandBuilder::new("--chain regtest export-collection --inscriptions ids.txt --output export")
+    .write("ids.txt", format!("{txid}\n"))
+    .rpc_server(&rpc_server)
+    .expected_stderr(format!("error: inscription {txid} not found\n"))
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn invalid_inscription_id() {
+  CommandBuilder::new("export-collection --inscriptions ids.txt --output export")
+    .write("ids.txt", "\nfoo\n")
+    .expected_stderr(
+      "error: invalid inscription ID on line 2: `foo`\nbecause: odd hex string length 3\n",
+    )
+    .expected_exit_code(1)
+    .run();
+}
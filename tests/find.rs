@@ -24,7 +24,80 @@ fn no_satoshi_index() {
   let rpc_server = test_bitcoincore_rpc::spawn();
   CommandBuilder::new("find 0")
     .rpc_server(&rpc_server)
-    .expected_stderr("error: find requires index created with `--index-satoshis` flag\n")
+    .expected_stderr("error: find requires index created with `--index-sat-ranges` flag\n")
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn next_rarity() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+  CommandBuilder::new("--index-satoshis find --next-rarity uncommon 1")
+    .rpc_server(&rpc_server)
+    .expected_stdout(
+      "5000000000\t0c4eb1fa83a7d6ce0e21e5e616a96e83a7b1658170fb544acf6f5c6a2d4b3f90:0:0\n",
+    )
+    .run();
+}
+
+#[test]
+fn next_rarity_not_found() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new("--index-satoshis find --next-rarity uncommon 1")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: no uncommon or rarer sat at or after 1 as of index height\n")
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn at_height() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+  let first = rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(1, 0, 0)],
+    output_count: 1,
+    fee: 0,
+  });
+  rpc_server.mine_blocks(1);
+  let second = rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(2, 1, 0)],
+    output_count: 1,
+    fee: 0,
+  });
+  rpc_server.mine_blocks(1);
+
+  let coinbase = rpc_server.tx(1, 0).txid();
+
+  for (height, txid) in [(1, coinbase), (2, first), (3, second)] {
+    CommandBuilder::new(format!(
+      "--index-sat-history find --at-height {height} 5000000000"
+    ))
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!("{txid}:0:0\n"))
+    .run();
+  }
+
+  CommandBuilder::new("--index-sat-history find --at-height 0 5000000000")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: sat has not been mined as of height 0\n")
+    .expected_exit_code(1)
+    .run();
+
+  CommandBuilder::new("--index-sat-history find --at-height 4 5000000000")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: height 4 is above index height 3\n")
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn at_height_requires_sat_history() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new("--index-satoshis find --at-height 0 0")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: sat history requires index created with `--index-sat-history` flag\n")
     .expected_exit_code(1)
     .run();
 }
@@ -0,0 +1,110 @@
+use {
+  super::*,
+  crate::command_builder::ToArgs,
+  bitcoin::{Block, Transaction},
+};
+
+/// A mock bitcoind with an `ord` wallet, and helpers for driving it and
+/// running `ord` against it.
+pub(crate) struct Fixture {
+  network: Network,
+  rpc_server: test_bitcoincore_rpc::Handle,
+}
+
+impl Fixture {
+  pub(crate) fn new(network: Network) -> Self {
+    Self::with_wallet_name(network, "ord")
+  }
+
+  pub(crate) fn regtest() -> Self {
+    Self::new(Network::Regtest)
+  }
+
+  pub(crate) fn signet() -> Self {
+    Self::new(Network::Signet)
+  }
+
+  pub(crate) fn with_wallet_name(network: Network, wallet_name: &str) -> Self {
+    Self {
+      network,
+      rpc_server: test_bitcoincore_rpc::spawn_with(network, wallet_name),
+    }
+  }
+
+  pub(crate) fn rpc_server(&self) -> &test_bitcoincore_rpc::Handle {
+    &self.rpc_server
+  }
+
+  pub(crate) fn mine_blocks(&self, n: u64) -> Vec<Block> {
+    self.rpc_server.mine_blocks(n)
+  }
+
+  /// Mine a block paying the default subsidy to the wallet, and return the
+  /// coinbase output.
+  pub(crate) fn fund_wallet(&self) -> OutPoint {
+    Self::coinbase_outpoint(self.rpc_server.mine_blocks(1))
+  }
+
+  /// Mine a block paying `amount` to the wallet, and return the coinbase
+  /// output.
+  pub(crate) fn fund_wallet_with(&self, amount: u64) -> OutPoint {
+    Self::coinbase_outpoint(self.rpc_server.mine_blocks_with_subsidy(1, amount))
+  }
+
+  fn coinbase_outpoint(blocks: Vec<Block>) -> OutPoint {
+    OutPoint::new(blocks[0].txdata[0].txid(), 0)
+  }
+
+  pub(crate) fn broadcast(&self, tx: Transaction) -> Txid {
+    self.rpc_server.broadcast(tx)
+  }
+
+  /// An `ord` invocation on this fixture's chain, talking to its bitcoind.
+  pub(crate) fn ord(&self, args: impl ToArgs) -> CommandBuilder {
+    let mut chain = match self.network {
+      Network::Bitcoin => Vec::new(),
+      network => vec!["--chain".to_string(), network.to_string()],
+    };
+
+    chain.extend(args.to_args());
+
+    CommandBuilder::new(chain).rpc_server(&self.rpc_server)
+  }
+
+  /// Inscribe `contents` on the first sat of `outpoint`, and return the
+  /// inscription ID.
+  pub(crate) fn inscribe(
+    &self,
+    outpoint: OutPoint,
+    file: &str,
+    contents: impl AsRef<[u8]>,
+  ) -> Txid {
+    reveal_txid_from_inscribe_stdout(
+      &self
+        .ord(format!(
+          "wallet inscribe --satpoint {outpoint}:0 --file {file}"
+        ))
+        .write(file, contents)
+        .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+        .run(),
+    )
+  }
+
+  pub(crate) fn receive(&self) -> Address {
+    self
+      .ord("wallet receive")
+      .stdout_regex(".*")
+      .run()
+      .trim()
+      .parse()
+      .unwrap()
+  }
+
+  pub(crate) fn server(&self, args: &[&str]) -> TestServer {
+    TestServer::spawn_with_args(&self.rpc_server, args)
+  }
+
+  pub(crate) fn wallet_api_server(&self) -> TestServer {
+    TestServer::spawn_with_server_args(&self.rpc_server, &[], &["--enable-wallet-api"])
+  }
+}
@@ -15,3 +15,283 @@ fn custom_index_path() {
 
   assert!(index_path.is_file())
 }
+
+#[test]
+fn sat_ranges_without_rare_sats() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sat-ranges list 4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stdout("4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0\t0\t5000000000\tmythic\tnvtdijuwxlp\n")
+  .run();
+
+  CommandBuilder::new("--index-sat-ranges find --next-rarity uncommon 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: find requires index created with `--index-rare-sats` flag\n")
+    .run();
+
+  CommandBuilder::new("--index-sat-ranges block-sats 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: block-sats requires index created with `--index-rare-sats` flag\n")
+    .run();
+}
+
+#[test]
+fn rare_sats_imply_sat_ranges() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-rare-sats find --next-rarity uncommon 1")
+    .rpc_server(&rpc_server)
+    .stdout_regex(format!("{}\t[[:xdigit:]]{{64}}:0:0\n", 50 * COIN_VALUE))
+    .run();
+
+  CommandBuilder::new(
+    "--index-rare-sats list 4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stdout("4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0\t0\t5000000000\tmythic\tnvtdijuwxlp\n")
+  .run();
+}
+
+#[test]
+fn inscribed_sats_without_rare_sats() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-inscribed-sats find 0")
+    .rpc_server(&rpc_server)
+    .expected_stdout("4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0\n")
+    .run();
+
+  CommandBuilder::new("--index-inscribed-sats find --next-rarity uncommon 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: find requires index created with `--index-rare-sats` flag\n")
+    .run();
+}
+
+#[test]
+fn header_only_sync_batches_requests() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(100);
+
+  CommandBuilder::new("info")
+    .rpc_server(&rpc_server)
+    .stdout_regex(r#"\{"blocks_indexed":101,.*"#)
+    .run();
+
+  assert_eq!(rpc_server.batch_count(), 2);
+  assert!(rpc_server.request_count() < 10);
+}
+
+#[test]
+fn header_only_sync_falls_back_to_serial_requests_if_batches_are_rejected() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(100);
+  rpc_server.reject_batches();
+
+  CommandBuilder::new("info")
+    .rpc_server(&rpc_server)
+    .stdout_regex(r#"\{"blocks_indexed":101,.*"#)
+    .run();
+
+  assert_eq!(rpc_server.batch_count(), 0);
+  assert!(rpc_server.request_count() > 200);
+}
+
+#[test]
+fn compact_output_writes_to_new_path() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+  let index_path = tempdir.path().join("index.redb");
+  let output_path = tempdir.path().join("output.redb");
+
+  CommandBuilder::new(format!("--index {} index", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run();
+
+  let size = fs::metadata(&index_path).unwrap().len();
+
+  let output = CommandBuilder::new(format!(
+    "--index {} index compact --output {}",
+    index_path.display(),
+    output_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(".*")
+  .run();
+
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&output).unwrap(),
+    serde_json::json!({
+      "path": output_path,
+      "size_before": size,
+      "size_after": fs::metadata(&output_path).unwrap().len(),
+    })
+  );
+
+  assert_eq!(fs::metadata(&index_path).unwrap().len(), size);
+
+  CommandBuilder::new(format!("--index {} info", output_path.display()))
+    .rpc_server(&rpc_server)
+    .stdout_regex(r#"\{"blocks_indexed":2,.*"#)
+    .run();
+}
+
+#[test]
+fn compact_refuses_to_run_while_index_is_open() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let index_path = ord_server.index_path();
+  let size = fs::metadata(&index_path).unwrap().len();
+
+  CommandBuilder::new(format!("--index {} index compact", index_path.display()))
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: index at `{}` is in use by another process, which must be stopped before compacting\n",
+      index_path.display()
+    ))
+    .run();
+
+  assert_eq!(fs::metadata(&index_path).unwrap().len(), size);
+  assert!(!index_path.with_extension("redb.partial").exists());
+}
+
+#[test]
+fn compact_output_must_not_exist() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+  let index_path = tempdir.path().join("index.redb");
+  let output_path = tempdir.path().join("output.redb");
+
+  CommandBuilder::new(format!("--index {} index", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run();
+
+  fs::write(&output_path, "foo").unwrap();
+
+  CommandBuilder::new(format!(
+    "--index {} index compact --output {}",
+    index_path.display(),
+    output_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: `{}` already exists\n",
+    output_path.display()
+  ))
+  .run();
+
+  assert_eq!(fs::read_to_string(output_path).unwrap(), "foo");
+}
+
+#[test]
+fn benchmark_leaves_index_untouched() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+  let index_path = tempdir.path().join("index.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} --index-sat-ranges index",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run();
+
+  rpc_server.mine_blocks(5);
+
+  let index = fs::read(&index_path).unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "--index {} --index-sat-ranges index benchmark --blocks 3",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(".*")
+  .run();
+
+  let report = serde_json::from_str::<serde_json::Value>(&output).unwrap();
+
+  assert_eq!(report["start_height"], 4);
+  assert_eq!(report["blocks"], 3);
+  assert_eq!(report["sat_ranges"], 3);
+  assert!(report["peak_cache_size"].as_u64().unwrap() > 0);
+
+  for field in [
+    "wall_time_secs",
+    "blocks_per_sec",
+    "sat_ranges_per_sec",
+    "rpc_time_secs",
+    "index_time_secs",
+  ] {
+    assert!(report[field].as_f64().unwrap() >= 0.0, "{field}");
+  }
+
+  assert!(fs::read(&index_path).unwrap() == index);
+  assert!(!index_path.with_extension("redb.benchmark").exists());
+}
+
+#[test]
+fn benchmark_from_start_height() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(10);
+
+  let tempdir = TempDir::new().unwrap();
+  let index_path = tempdir.path().join("index.redb");
+
+  let output = CommandBuilder::new(format!(
+    "--index {} index benchmark --blocks 2 --start-height 6",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(".*")
+  .run();
+
+  let report = serde_json::from_str::<serde_json::Value>(&output).unwrap();
+
+  assert_eq!(report["start_height"], 6);
+  assert_eq!(report["blocks"], 2);
+  assert_eq!(report["sat_ranges"], 0);
+
+  assert!(!index_path.exists());
+  assert!(!index_path.with_extension("redb.benchmark").exists());
+
+  CommandBuilder::new(format!(
+    "--index {} index benchmark --blocks 2 --start-height 11",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: no blocks to benchmark at height 11\n")
+  .run();
+}
+
+#[test]
+fn benchmark_from_start_height_requires_index_without_sat_ranges() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("--index-sat-ranges index benchmark --blocks 2 --start-height 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: cannot benchmark sat ranges from height 1, since a fresh index doesn't know the sat ranges of earlier outputs\n",
+    )
+    .run();
+}
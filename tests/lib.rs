@@ -1,7 +1,9 @@
 #![allow(clippy::type_complexity)]
 
 use {
-  self::{command_builder::CommandBuilder, expected::Expected, test_server::TestServer},
+  self::{
+    command_builder::CommandBuilder, expected::Expected, fixture::Fixture, test_server::TestServer,
+  },
   bitcoin::{blockdata::constants::COIN_VALUE, Address, Network, OutPoint, Txid},
   executable_path::executable_path,
   pretty_assertions::assert_eq as pretty_assert_eq,
@@ -10,12 +12,13 @@ use {
   std::{
     fs,
     net::TcpListener,
-    path::Path,
-    process::{Child, Command, Stdio},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
     str, thread,
     time::Duration,
   },
   tempfile::TempDir,
+  test_bitcoincore_rpc::TransactionTemplate,
   unindent::Unindent,
 };
 
@@ -45,18 +48,28 @@ fn reveal_txid_from_inscribe_stdout(stdout: &str) -> Txid {
     .unwrap()
 }
 
+mod block_sats;
 mod command_builder;
 mod epochs;
 mod expected;
+mod export_collection;
 mod find;
+mod fixture;
 mod index;
 mod info;
+#[cfg(feature = "library")]
+mod library;
 mod list;
+mod names;
 mod parse;
+mod reorg;
 mod server;
+mod stats;
 mod subsidy;
 mod supply;
 mod test_server;
 mod traits;
 mod version;
 mod wallet;
+mod wallet_api;
+mod webhooks;
@@ -0,0 +1,39 @@
+use ord::sat::{Cycle, Epoch, Height, Rarity, Sat};
+
+#[test]
+fn sat_types_are_public() {
+  assert_eq!(Height::new(210000).subsidy(), 2500000000);
+  assert_eq!(
+    Height::new(210000).starting_sat(),
+    Sat::new(1050000000000000).unwrap()
+  );
+  assert_eq!(Epoch::from(Height::new(209999)), Epoch::new(0));
+  assert_eq!(Epoch::new(1).starting_height(), Height::new(210000));
+  assert_eq!(
+    Cycle::from(Sat::new(2067187500000000).unwrap()),
+    Cycle::new(1)
+  );
+  assert_eq!(Sat::last().checked_add(1), None);
+  assert_eq!(Sat::new(Sat::supply()), None);
+  assert_eq!(Sat::new(0).unwrap().name(), "nvtdijuwxlp");
+  assert_eq!(Sat::new(0).unwrap().rarity(), Rarity::Mythic);
+  assert_eq!(Sat::new(0).unwrap().degree().to_string(), "0°0′0″0‴");
+  assert_eq!(Sat::new(0).unwrap().decimal().to_string(), "0.0");
+}
+
+#[test]
+fn constants_are_public() {
+  use ord::constants::{
+    COIN_VALUE, CYCLE_EPOCHS, DIFFCHANGE_INTERVAL, SUBSIDY_HALVING_INTERVAL, SUPPLY,
+  };
+
+  assert_eq!(Sat::supply(), SUPPLY);
+  assert_eq!(
+    Height::new(SUBSIDY_HALVING_INTERVAL).subsidy(),
+    25 * COIN_VALUE
+  );
+  assert_eq!(
+    (SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS) % DIFFCHANGE_INTERVAL,
+    0
+  );
+}
@@ -28,7 +28,71 @@ fn no_satoshi_index() {
   let rpc_server = test_bitcoincore_rpc::spawn();
   CommandBuilder::new("list 4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0")
     .rpc_server(&rpc_server)
-    .expected_stderr("error: list requires index created with `--index-satoshis` flag\n")
+    .expected_stderr("error: list requires index created with `--index-sat-ranges` flag\n")
     .expected_exit_code(1)
     .run();
 }
+
+#[test]
+fn rarity_filter() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+  let split = rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(1, 0, 0)],
+    output_count: 2,
+    fee: 0,
+  });
+  rpc_server.mine_blocks(1);
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(2, 1, 1), (2, 0, 0)],
+    output_count: 1,
+    fee: 0,
+  });
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index-satoshis list {split}:0"))
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!(
+      "{split}:0\t5000000000\t2500000000\tuncommon\tnvtcsezkbth\n"
+    ))
+    .run();
+
+  CommandBuilder::new(format!("--index-satoshis list --rarity uncommon {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!(
+      "{txid}:0\t10000000000\t5000000000\tuncommon\tnvtccadxgaz\n"
+    ))
+    .run();
+
+  CommandBuilder::new(format!("--index-satoshis list --rarity rare {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_stdout("")
+    .run();
+}
+
+#[test]
+fn custom_schedule_matches_brute_force() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord");
+
+  let mut start = 50 * COIN_VALUE;
+  for height in 1..8 {
+    let subsidy = (50 * COIN_VALUE) >> (height / 2);
+    let coinbase = rpc_server.mine_blocks_with_subsidy(1, subsidy)[0].txdata[0].txid();
+
+    CommandBuilder::new(format!(
+      "--chain custom --config config.json --index-satoshis list {coinbase}:0"
+    ))
+    .write(
+      "config.json",
+      r#"{"schedule": {"initial_subsidy": 5000000000, "halving_interval": 2}}"#,
+    )
+    .rpc_server(&rpc_server)
+    .stdout_regex(format!(
+      "{coinbase}:0\t{start}\t{subsidy}\t{}\t[a-z]+\n",
+      if height % 2 == 0 { "epic" } else { "uncommon" }
+    ))
+    .run();
+
+    start += subsidy;
+  }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn prefix() {
+  CommandBuilder::new("names --prefix nvtdijuwxl --limit 3")
+    .expected_stdout(
+      "
+        0\tnvtdijuwxlp\tmythic
+        1\tnvtdijuwxlo\tcommon
+        2\tnvtdijuwxln\tcommon
+      "
+      .unindent(),
+    )
+    .run();
+}
+
+#[test]
+fn prefix_with_satpoints() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("--index-sat-ranges names --prefix nvtdijuwxlp")
+    .rpc_server(&rpc_server)
+    .expected_stdout(
+      "0\tnvtdijuwxlp\tmythic\t4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0\n",
+    )
+    .run();
+}
+
+#[test]
+fn invalid_prefix() {
+  CommandBuilder::new("names --prefix 0rd")
+    .expected_exit_code(1)
+    .expected_stderr("error: invalid character `0` (U+0030) at position 1 in sat name\n")
+    .run();
+}
+
+#[test]
+fn limit_is_capped() {
+  CommandBuilder::new("names --prefix ord --limit 1001")
+    .expected_exit_code(1)
+    .expected_stderr("error: limit may not exceed 1000\n")
+    .run();
+}
@@ -9,8 +9,25 @@ fn ok() {
 
 #[test]
 fn err() {
+  CommandBuilder::new("parse 1x")
+    .stderr_regex("error: .*: invalid character `1` \\(U\\+0031\\) at position 1 in sat name.*")
+    .expected_exit_code(2)
+    .run();
+}
+
+#[test]
+fn names_are_normalized() {
   CommandBuilder::new("parse A")
-    .stderr_regex("error: .*: invalid digit found in string.*")
+    .expected_stdout("2099999997689999\n")
+    .run();
+}
+
+#[test]
+fn non_ascii_names_are_rejected() {
+  CommandBuilder::new("parse \u{0430}")
+    .stderr_regex(
+      "error: .*: invalid character `\u{0430}` \\(U\\+0430\\) at position 1 in sat name.*",
+    )
     .expected_exit_code(2)
     .run();
 }
@@ -0,0 +1,159 @@
+use {
+  super::*,
+  bitcoincore_rpc::{Auth, Client, RpcApi},
+};
+
+fn client(rpc_server: &test_bitcoincore_rpc::Handle) -> Client {
+  Client::new(&rpc_server.url(), Auth::None).unwrap()
+}
+
+#[test]
+fn reorg_replaces_blocks_with_longer_branch() {
+  let fixture = Fixture::regtest();
+
+  let blocks = fixture.mine_blocks(3);
+
+  fixture
+    .ord("test reorg --depth 2")
+    .stdout_regex(format!(
+      "invalidated\t{}\ninvalidated\t{}\n(mined\t[[:xdigit:]]{{64}}\n){{3}}",
+      blocks[1].block_hash(),
+      blocks[2].block_hash(),
+    ))
+    .run();
+
+  let client = client(fixture.rpc_server());
+
+  assert_eq!(client.get_block_count().unwrap(), 4);
+  assert_eq!(client.get_block_hash(1).unwrap(), blocks[0].block_hash());
+  assert_ne!(client.get_block_hash(2).unwrap(), blocks[1].block_hash());
+  assert_ne!(client.get_block_hash(3).unwrap(), blocks[2].block_hash());
+}
+
+#[test]
+fn reorg_depth_must_be_within_chain() {
+  let fixture = Fixture::regtest();
+
+  fixture.mine_blocks(2);
+
+  fixture
+    .ord("test reorg --depth 0")
+    .expected_stderr("error: reorg depth must be between 1 and the chain height 2, not 0\n")
+    .expected_exit_code(1)
+    .run();
+
+  fixture
+    .ord("test reorg --depth 3")
+    .expected_stderr("error: reorg depth must be between 1 and the chain height 2, not 3\n")
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn reorg_refuses_to_run_off_regtest() {
+  for (flags, chain) in [
+    ("", "mainnet"),
+    ("--chain mainnet", "mainnet"),
+    ("--chain signet", "signet"),
+    ("--signet", "signet"),
+    ("--testnet", "testnet"),
+    ("--chain custom", "custom"),
+  ] {
+    CommandBuilder::new(format!("{flags} test reorg --depth 1"))
+      .rpc_server(&test_bitcoincore_rpc::spawn())
+      .expected_stderr(format!(
+        "error: `ord test reorg` may only be used on regtest, not {chain}\n"
+      ))
+      .expected_exit_code(1)
+      .run();
+  }
+}
+
+#[test]
+fn reorg_refuses_to_run_against_mainnet_node() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--regtest test reorg --depth 1")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: Bitcoin RPC server is on mainnet but ord is on regtest\n")
+    .expected_exit_code(1)
+    .run();
+
+  assert_eq!(client(&rpc_server).get_block_count().unwrap(), 1);
+}
+
+#[test]
+fn reorg_is_hidden() {
+  assert!(!CommandBuilder::new("--help")
+    .stdout_regex(".*")
+    .run()
+    .contains("reorg"));
+}
+
+#[test]
+fn reorg_with_inscription_on_orphaned_branch_is_detected() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  let orphaned = fixture.mine_blocks(1)[0].block_hash();
+
+  let server = fixture.server(&[]);
+
+  server.assert_response_regex(
+    &format!("/inscription/{reveal_txid}"),
+    &format!(".*<dt>location</dt>\n  <dd>{reveal_txid}:0:0</dd>.*"),
+  );
+
+  fixture
+    .ord("test reorg --depth 1")
+    .stdout_regex(format!(
+      "invalidated\t{orphaned}\nmined\t[[:xdigit:]]{{64}}\nmined\t[[:xdigit:]]{{64}}\n"
+    ))
+    .run();
+
+  // The index can't unwind blocks, so it stops updating and reports the
+  // reorg, leaving the orphaned inscription in place.
+  for i in 0.. {
+    let status = reqwest::blocking::get(server.url().join("/status").unwrap())
+      .unwrap()
+      .text()
+      .unwrap();
+
+    if status == "reorg detected, please rebuild the database." {
+      break;
+    }
+
+    if i == 100 {
+      panic!("reorg was not detected");
+    }
+
+    thread::sleep(Duration::from_millis(25));
+  }
+
+  assert_eq!(
+    reqwest::blocking::get(server.url().join("/block-count").unwrap())
+      .unwrap()
+      .text()
+      .unwrap(),
+    "3"
+  );
+
+  let response = reqwest::blocking::get(
+    server
+      .url()
+      .join(&format!("/inscription/{reveal_txid}"))
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_regex_match!(
+    response.text().unwrap(),
+    format!(".*<dt>location</dt>\n  <dd>{reveal_txid}:0:0</dd>.*")
+  );
+}
@@ -32,6 +32,7 @@ fn run() {
   }
 
   child.kill().unwrap();
+  child.wait().unwrap();
 }
 
 #[test]
@@ -56,14 +57,18 @@ fn inscription_page() {
     &format!(
       ".*<h1>Inscription {reveal_tx}</h1>
 <dl>
+  <dt>number</dt>
+  <dd>0</dd>
   <dt>content size</dt>
   <dd>10 bytes</dd>
   <dt>content type</dt>
   <dd>text/plain;charset=utf-8</dd>
+  <dt>sha256</dt>
+  <dd class=monospace>[[:xdigit:]]{{64}}</dd>
   <dt>location</dt>
   <dd>{reveal_tx}:0:0</dd>
 </dl>
-HELLOWORLD.*",
+<iframe sandbox src=/preview/{reveal_tx}></iframe>.*",
     ),
   );
 }
@@ -120,14 +125,18 @@ fn inscription_page_after_send() {
     &format!(
       ".*<h1>Inscription {reveal_txid}</h1>
 <dl>
+  <dt>number</dt>
+  <dd>0</dd>
   <dt>content size</dt>
   <dd>10 bytes</dd>
   <dt>content type</dt>
   <dd>text/plain;charset=utf-8</dd>
+  <dt>sha256</dt>
+  <dd class=monospace>[[:xdigit:]]{{64}}</dd>
   <dt>location</dt>
   <dd>{reveal_txid}:0:0</dd>
 </dl>
-HELLOWORLD.*",
+<iframe sandbox src=/preview/{reveal_txid}></iframe>.*",
     ),
   );
 
@@ -147,19 +156,205 @@ HELLOWORLD.*",
     &format!(
       ".*<h1>Inscription {reveal_txid}</h1>
 <dl>
+  <dt>number</dt>
+  <dd>0</dd>
   <dt>content size</dt>
   <dd>10 bytes</dd>
   <dt>content type</dt>
   <dd>text/plain;charset=utf-8</dd>
+  <dt>sha256</dt>
+  <dd class=monospace>[[:xdigit:]]{{64}}</dd>
   <dt>location</dt>
   <dd>{}:0:0</dd>
 </dl>
-HELLOWORLD.*",
+<iframe sandbox src=/preview/{reveal_txid}></iframe>.*",
       txid.trim(),
     ),
   )
 }
 
+#[test]
+fn inscription_json_api() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  fixture.mine_blocks(1);
+
+  let json = |server: TestServer| {
+    let response = server.request(&format!("/api/inscription/{reveal_txid}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()
+  };
+
+  assert_eq!(
+    json(fixture.server(&[])),
+    serde_json::json!({
+      "inscription_id": reveal_txid,
+      "number": 0,
+      "content_size": 10,
+      "content_type": "text/plain;charset=utf-8",
+      "sniffed_content_type": null,
+      "sha256": "0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74",
+      "collection": null,
+      "location": format!("{reveal_txid}:0:0"),
+      "sat": null,
+      "explorer": null,
+      "burned": false,
+    })
+  );
+
+  assert_eq!(
+    json(fixture.server(&["--index-sat-ranges"])),
+    serde_json::json!({
+      "inscription_id": reveal_txid,
+      "number": 0,
+      "content_size": 10,
+      "content_type": "text/plain;charset=utf-8",
+      "sniffed_content_type": null,
+      "sha256": "0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74",
+      "collection": null,
+      "location": format!("{reveal_txid}:0:0"),
+      "sat": 50 * COIN_VALUE,
+      "explorer": null,
+      "burned": false,
+    })
+  );
+}
+
+#[test]
+fn sat_page_links_to_inscription_on_sat() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  fixture.mine_blocks(1);
+
+  let server = fixture.server(&["--index-inscribed-sats"]);
+
+  let response = server.request(&format!("/api/inscription/{reveal_txid}"));
+  assert_eq!(response.status(), StatusCode::OK);
+  let sat = serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["sat"]
+    .as_u64()
+    .unwrap();
+
+  server.assert_response_regex(
+    &format!("/sat/{sat}"),
+    &format!(
+      ".*<dt>inscription</dt>
+  <dd><a href=/inscription/{reveal_txid} class=monospace>{reveal_txid}</a></dd>
+.*<iframe sandbox src=/preview/{reveal_txid}></iframe>.*"
+    ),
+  );
+
+  fixture.server(&[]).assert_response_regex(
+    &format!("/sat/{sat}"),
+    ".*<dt>inscription</dt>\n  <dd>unknown, inscribed sat index disabled</dd>.*",
+  );
+}
+
+#[test]
+fn inscription_json_api_unknown_inscription() {
+  let fixture = Fixture::regtest();
+
+  let txid = fixture.mine_blocks(1)[0].txdata[0].txid();
+
+  let response = fixture
+    .server(&[])
+    .request(&format!("/api/inscription/{txid}"));
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap(),
+    serde_json::json!({
+      "error": format!("transaction {txid} has no inscription"),
+    })
+  );
+}
+
+#[test]
+fn inscriptions_missing_from_lagging_index_are_proxied_to_fallback() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  fixture.mine_blocks(1);
+
+  let fallback = fixture.server(&[]);
+
+  let expected = fallback
+    .request(&format!("/api/inscription/{reveal_txid}"))
+    .text()
+    .unwrap();
+
+  let lagging = TestServer::spawn_with_server_args(
+    fixture.rpc_server(),
+    &["--height-limit", "2"],
+    &["--fallback-url", fallback.url().as_str()],
+  );
+
+  let get = |path: &str| reqwest::blocking::get(lagging.url().join(path).unwrap()).unwrap();
+
+  let response = get(&format!("/content/{reveal_txid}"));
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.headers().get("x-ord-proxied").unwrap(), "true");
+  assert_eq!(
+    response.headers().get("content-type").unwrap(),
+    "text/plain;charset=utf-8"
+  );
+  assert_eq!(response.text().unwrap(), "HELLOWORLD");
+
+  let response = get(&format!("/api/inscription/{reveal_txid}"));
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.headers().get("x-ord-proxied").unwrap(), "true");
+  assert_eq!(response.text().unwrap(), expected);
+
+  let response = get("/status");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert!(response.headers().get("x-ord-proxied").is_none());
+
+  let txid = fixture.mine_blocks(1)[0].txdata[0].txid();
+
+  let response = get(&format!("/content/{txid}"));
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert!(response.headers().get("x-ord-proxied").is_none());
+}
+
+#[test]
+fn inscriptions_missing_from_synced_index_are_not_proxied() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  fixture.mine_blocks(1);
+
+  let fallback = fixture.server(&[]);
+
+  let txid = fixture.mine_blocks(1)[0].txdata[0].txid();
+
+  let server = TestServer::spawn_with_server_args(
+    fixture.rpc_server(),
+    &[],
+    &["--fallback-url", fallback.url().as_str()],
+  );
+
+  let response = server.request(&format!("/content/{reveal_txid}"));
+  assert_eq!(response.status(), StatusCode::OK);
+  assert!(response.headers().get("x-ord-proxied").is_none());
+
+  let response = server.request(&format!("/content/{txid}"));
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert!(response.headers().get("x-ord-proxied").is_none());
+}
+
 #[test]
 fn inscription_content() {
   let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
@@ -187,3 +382,563 @@ fn inscription_content() {
   );
   assert_eq!(response.bytes().unwrap(), "HELLOWORLD");
 }
+
+#[test]
+fn fee_provenance_links_transaction_to_coinbase() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  let coinbase = fixture.mine_blocks(1)[0].txdata[0].txid();
+
+  let server = fixture.server(&["--index-fee-provenance"]);
+
+  server.assert_response_regex(
+    &format!("/tx/{reveal_txid}"),
+    &format!(
+      ".*<dt>fees</dt><dd><a href=/sat/[[:digit:]]+ class=monospace>[[:digit:]]+</a> \\(<a href=/tx/{reveal_txid}/fees>location</a>\\)</dd>.*"
+    ),
+  );
+
+  server.assert_response_regex(
+    &format!("/tx/{reveal_txid}/fees"),
+    &format!(".*<h1>Output <span class=monospace>{coinbase}:0</span></h1>.*"),
+  );
+}
+
+#[test]
+fn html_inscribed_as_png_is_served_as_octet_stream() {
+  let fixture = Fixture::regtest();
+
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "evil.png", "<html><script>alert(1)</script>");
+
+  fixture.mine_blocks(1);
+
+  let response = fixture
+    .server(&[])
+    .request(&format!("/content/{reveal_txid}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.headers().get("content-type").unwrap(),
+    "application/octet-stream"
+  );
+  assert_eq!(
+    response.headers().get("x-content-type-options").unwrap(),
+    "nosniff"
+  );
+}
+
+#[test]
+fn inscriptions_page_lists_newest_first() {
+  let fixture = Fixture::regtest();
+
+  let inscribe = |contents: &str| {
+    let outpoint = fixture.fund_wallet();
+    let reveal_txid = fixture.inscribe(outpoint, "item.txt", contents);
+    fixture.mine_blocks(1);
+    reveal_txid
+  };
+
+  let a = inscribe("a");
+  let b = inscribe("b");
+  let c = inscribe("c");
+
+  let server = fixture.server(&[]);
+
+  server.assert_response_regex(
+    "/inscriptions",
+    &format!(
+      ".*<a href=/inscriptions>Inscriptions</a>.*<h1>Inscriptions</h1>
+<div class=gallery>
+  <a href=/inscription/{c}><pre>c</pre></a>
+  <a href=/inscription/{b}><pre>b</pre></a>
+  <a href=/inscription/{a}><pre>a</pre></a>
+</div>
+prev
+next
+.*"
+    ),
+  );
+
+  server.assert_response_regex(
+    "/inscriptions/1",
+    &format!(
+      ".*<div class=gallery>
+  <a href=/inscription/{b}><pre>b</pre></a>
+  <a href=/inscription/{a}><pre>a</pre></a>
+</div>
+prev
+<a href=/inscriptions/2>next</a>
+.*"
+    ),
+  );
+}
+
+#[test]
+fn collection_groups_inscriptions_with_shared_metadata() {
+  let fixture = Fixture::regtest();
+
+  let inscribe = |contents: &str, metadata: &str| {
+    let outpoint = fixture.fund_wallet();
+
+    let stdout = fixture
+      .ord(format!(
+        "wallet inscribe --satpoint {outpoint}:0 --file item.txt --metadata metadata.json"
+      ))
+      .write("item.txt", contents)
+      .write("metadata.json", metadata)
+      .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+      .run();
+
+    fixture.mine_blocks(1);
+
+    reveal_txid_from_inscribe_stdout(&stdout)
+  };
+
+  let a = inscribe("a", r#"{"collection":"pepes","name":"Pepe #1"}"#);
+  let other = inscribe("other", r#"{"collection":"frogs"}"#);
+  let b = inscribe("b", r#"{"collection":"Pepes","name":"Pepe #2"}"#);
+  let c = inscribe("c", r#"{"collection":"pepes","name":"Pepe #3"}"#);
+
+  let server = fixture.server(&[]);
+
+  let response = server.request("/api/collection/pepes");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    serde_json::from_str::<Vec<Txid>>(&response.text().unwrap()).unwrap(),
+    [a, b, c]
+  );
+
+  server.assert_response_regex(
+    "/collection/pepes",
+    &format!(
+      ".*<div class=gallery>
+  <a href=/inscription/{a}><pre>a</pre></a>
+  <a href=/inscription/{b}><pre>b</pre></a>
+  <a href=/inscription/{c}><pre>c</pre></a>
+</div>
+prev
+next
+.*"
+    ),
+  );
+
+  assert_eq!(
+    server.request("/collection/pepes/1").status(),
+    StatusCode::NOT_FOUND
+  );
+
+  let response = server.request("/api/collection/frogs");
+  assert_eq!(
+    serde_json::from_str::<Vec<Txid>>(&response.text().unwrap()).unwrap(),
+    [other]
+  );
+}
+
+#[test]
+fn tracked_descriptor_lists_wallet_inscriptions() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let stdout = CommandBuilder::new(format!(
+    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
+  ))
+  .write("hello.txt", "HELLOWORLD")
+  .rpc_server(&rpc_server)
+  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+  .run();
+
+  let reveal_tx = reveal_txid_from_inscribe_stdout(&stdout);
+
+  rpc_server.mine_blocks(1);
+
+  let descriptor = rpc_server.change_descriptor();
+
+  let ord_server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-addresses"],
+    &["--track-descriptor", &descriptor],
+  );
+
+  let response = ord_server.request("/api/tracked");
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let tracked = serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap();
+
+  assert_eq!(tracked.as_array().unwrap().len(), 1);
+  assert_eq!(tracked[0]["inscription"], reveal_tx.to_string());
+  assert_eq!(tracked[0]["satpoint"], format!("{reveal_tx}:0:0"));
+  assert!(tracked[0]["address"]
+    .as_str()
+    .unwrap()
+    .starts_with("bcrt1p"));
+
+  ord_server.assert_response_regex(
+    "/tracked",
+    &format!(
+      ".*<h1>Tracked Inscriptions</h1>
+<h2>1 Descriptor</h2>
+<ul class=monospace>
+  <li>{}#[a-z0-9]{{8}}</li>
+</ul>
+<h2>1 Inscription</h2>
+<ul class=monospace>
+  <li><a href=/inscription/{reveal_tx}>{reveal_tx}</a> at <a href=/output/{reveal_tx}:0>{reveal_tx}:0:0</a> on bcrt1p.*</li>
+</ul>.*",
+      regex::escape(&descriptor.replace('\'', "&apos;")),
+    ),
+  );
+}
+
+#[test]
+fn tracked_descriptor_ignores_inscriptions_beyond_gap_limit() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  CommandBuilder::new(format!(
+    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
+  ))
+  .write("hello.txt", "HELLOWORLD")
+  .rpc_server(&rpc_server)
+  .stdout_regex(".*")
+  .run();
+
+  rpc_server.mine_blocks(1);
+
+  // The commit transaction's change takes the first two change addresses,
+  // so the inscription lands on the third.
+  let ord_server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-addresses"],
+    &[
+      "--track-descriptor",
+      &rpc_server.change_descriptor(),
+      "--track-gap-limit",
+      "2",
+    ],
+  );
+
+  assert_eq!(ord_server.request("/api/tracked").text().unwrap(), "[]");
+}
+
+#[test]
+fn tracked_requires_descriptor() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let response = TestServer::spawn_with_args(&rpc_server, &[]).request("/api/tracked");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    response.text().unwrap(),
+    "tracked inscriptions require server started with `--track-descriptor`"
+  );
+}
+
+#[test]
+fn track_descriptor_requires_address_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+
+  CommandBuilder::new(format!(
+    "--chain regtest server --track-descriptor {}",
+    rpc_server.change_descriptor()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(
+    "error: `--track-descriptor` requires index created with `--index-addresses` flag\n",
+  )
+  .expected_exit_code(1)
+  .run();
+}
+
+#[test]
+fn track_descriptor_rejects_private_keys() {
+  CommandBuilder::new(
+    "server --track-descriptor wpkh(cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy)",
+  )
+  .stderr_regex(".*descriptors with private keys are not supported.*")
+  .expected_exit_code(2)
+  .run();
+}
+
+#[test]
+fn rare_txt_lists_uncommon_sats_of_mined_blocks() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+
+  let coinbases = rpc_server
+    .mine_blocks(3)
+    .into_iter()
+    .map(|block| block.txdata[0].txid())
+    .collect::<Vec<Txid>>();
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &["--index-rare-sats"]);
+
+  let response = ord_server.request("/rare.txt");
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response
+      .headers()
+      .get(reqwest::header::CONTENT_TYPE)
+      .unwrap(),
+    "text/plain; charset=utf-8"
+  );
+
+  let mut lines = response
+    .text()
+    .unwrap()
+    .lines()
+    .map(str::to_owned)
+    .collect::<Vec<String>>();
+
+  assert_eq!(lines.remove(0), "sat\tsatpoint");
+
+  for (i, txid) in coinbases.iter().enumerate() {
+    assert!(
+      lines.contains(&format!("{}\t{txid}:0:0", (i as u64 + 1) * 50 * COIN_VALUE)),
+      "uncommon sat of block {} missing from {lines:?}",
+      i + 1,
+    );
+  }
+}
+
+#[test]
+fn rare_txt_requires_rare_sat_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+
+  let response = TestServer::spawn_with_args(&rpc_server, &[]).request("/rare.txt");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+#[cfg(unix)]
+fn sigterm_lets_in_flight_requests_complete() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+
+  let mut ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  assert_eq!(ord_server.request("/block-count").status(), StatusCode::OK);
+
+  // Serving a block requires fetching it from the node, so with a slow node,
+  // the request is still in flight when the server is terminated.
+  rpc_server.set_latency(Duration::from_millis(500));
+
+  let url = ord_server.url().join("/block/0").unwrap();
+  let request = thread::spawn(move || reqwest::blocking::get(url).unwrap());
+
+  thread::sleep(Duration::from_millis(200));
+
+  assert!(ord_server.terminate().success());
+
+  let response = request.join().unwrap();
+  assert_eq!(response.status(), StatusCode::OK);
+  assert!(response.text().unwrap().contains("<h1>Block 0</h1>"));
+}
+
+#[test]
+#[cfg(unix)]
+fn listen_unix_serves_requests_on_socket() {
+  use std::{
+    io::{Read, Write},
+    os::unix::{
+      fs::PermissionsExt,
+      net::{UnixListener, UnixStream},
+    },
+  };
+
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+
+  let tempdir = TempDir::new().unwrap();
+  let socket = tempdir.path().join("ord.sock");
+
+  // A socket left behind by a server that didn't shut down cleanly.
+  drop(UnixListener::bind(&socket).unwrap());
+
+  let mut ord_server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &["--listen-unix", socket.to_str().unwrap()],
+  );
+
+  assert_eq!(
+    fs::metadata(&socket).unwrap().permissions().mode() & 0o777,
+    0o660
+  );
+
+  let mut stream = UnixStream::connect(&socket).unwrap();
+  stream
+    .write_all(b"GET /status HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+    .unwrap();
+
+  let mut response = String::new();
+  stream.read_to_string(&mut response).unwrap();
+
+  assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+  assert!(response.ends_with("\r\n\r\nOK"), "{response}");
+
+  assert!(ord_server.terminate().success());
+
+  assert!(!socket.exists());
+}
+
+#[test]
+#[cfg(unix)]
+fn listen_unix_socket_is_removed_when_tcp_listener_fails() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+  let socket = tempdir.path().join("ord.sock");
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let port = listener.local_addr().unwrap().port();
+
+  CommandBuilder::new(format!(
+    "server --address 127.0.0.1 --http-port {port} --listen-unix {}",
+    socket.display()
+  ))
+  .rpc_server(&rpc_server)
+  .stderr_regex("error: .*")
+  .expected_exit_code(1)
+  .run();
+
+  assert!(!socket.exists());
+}
+
+#[test]
+fn acme_flags_without_https_are_rejected() {
+  CommandBuilder::new("server --acme-domain example.com")
+    .expected_stderr("error: `--acme-domain` requires `--https` or `--https-port`\n")
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+fn requests_are_served_with_json_request_log() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let mut ord_server = TestServer::spawn_with_request_log(&rpc_server, &["--log-format", "json"]);
+
+  assert_eq!(
+    reqwest::blocking::get(ord_server.url().join("/clock").unwrap())
+      .unwrap()
+      .status(),
+    StatusCode::OK
+  );
+
+  let entry =
+    serde_json::from_str::<serde_json::Value>(&ord_server.logged_request("/clock")).unwrap();
+
+  assert_eq!(entry["level"], "INFO");
+  assert_eq!(entry["method"], "GET");
+  assert_eq!(entry["path"], "/clock");
+  assert_eq!(entry["status"], 200);
+  assert_eq!(entry["client"], "127.0.0.1");
+  assert!(entry["latency_ms"].is_f64());
+  assert!(entry["timestamp"].is_string());
+  assert_eq!(entry.as_object().unwrap().len(), 7);
+}
+
+#[test]
+fn http_redirects_to_https_while_both_listen() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let port = || {
+    TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port()
+  };
+
+  let (http_port, https_port) = (port(), port());
+
+  // A certificate for the ACME cache, so that the HTTPS listener serves it
+  // instead of ordering one from Let's Encrypt.
+  let certificate = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+
+  let cached_certificate = format!(
+    "cached_cert_{}",
+    base64::encode_engine(
+      <bitcoin::hashes::sha256::Hash as bitcoin::hashes::Hash>::hash(
+        format!(
+          "localhost\0{}",
+          rustls_acme::acme::LETS_ENCRYPT_PRODUCTION_DIRECTORY
+        )
+        .as_bytes()
+      ),
+      &base64::engine::fast_portable::FastPortable::from(
+        &base64::alphabet::URL_SAFE,
+        base64::engine::fast_portable::NO_PAD,
+      ),
+    )
+  );
+
+  let builder = CommandBuilder::new(format!(
+    "server --address 127.0.0.1 --http-port {http_port} --https-port {https_port} --redirect-http-to-https --acme-domain localhost --acme-cache ."
+  ))
+  .write(
+    cached_certificate,
+    certificate.serialize_private_key_pem() + &certificate.serialize_pem().unwrap(),
+  )
+  .rpc_server(&rpc_server);
+
+  let mut child = builder.command().spawn().unwrap();
+
+  let http = reqwest::blocking::Client::builder()
+    .redirect(reqwest::redirect::Policy::none())
+    .build()
+    .unwrap();
+
+  for attempt in 0.. {
+    if let Ok(response) = http
+      .get(format!("http://127.0.0.1:{http_port}/status"))
+      .send()
+    {
+      assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+      assert_eq!(
+        response.headers()[reqwest::header::LOCATION],
+        format!("https://localhost:{https_port}/status")
+      );
+      break;
+    }
+
+    if attempt == 100 {
+      panic!("Server did not respond to status check");
+    }
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  let https = reqwest::blocking::Client::builder()
+    .danger_accept_invalid_certs(true)
+    .build()
+    .unwrap();
+
+  for attempt in 0.. {
+    match https
+      .get(format!("https://localhost:{https_port}/status"))
+      .send()
+    {
+      Ok(response) => {
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().unwrap(), "OK");
+        break;
+      }
+      Err(err) => {
+        if attempt == 100 {
+          panic!("HTTPS listener did not respond: {err}");
+        }
+      }
+    }
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  child.kill().unwrap();
+  child.wait().unwrap();
+}
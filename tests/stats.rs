@@ -0,0 +1,103 @@
+use super::*;
+
+#[test]
+fn rarities() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-satoshis wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
+  ))
+  .write("hello.txt", "HELLOWORLD")
+  .rpc_server(&rpc_server)
+  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+  .run();
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--chain regtest --index-satoshis stats rarities")
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!(
+      r#"{{"blocks":3,"rarities":[{{"rarity":"common","supply":{},"inscribed":0}},{{"rarity":"uncommon","supply":2,"inscribed":1}},{{"rarity":"rare","supply":0,"inscribed":0}},{{"rarity":"epic","supply":0,"inscribed":0}},{{"rarity":"legendary","supply":0,"inscribed":0}},{{"rarity":"mythic","supply":1,"inscribed":0}}]}}"#,
+      150 * COIN_VALUE - 3
+    ))
+    .run();
+}
+
+#[test]
+fn rarities_without_satoshi_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("stats rarities")
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!(
+      r#"{{"blocks":1,"rarities":[{{"rarity":"common","supply":{},"inscribed":null}},{{"rarity":"uncommon","supply":0,"inscribed":null}},{{"rarity":"rare","supply":0,"inscribed":null}},{{"rarity":"epic","supply":0,"inscribed":null}},{{"rarity":"legendary","supply":0,"inscribed":null}},{{"rarity":"mythic","supply":1,"inscribed":null}}]}}"#,
+      50 * COIN_VALUE - 1
+    ))
+    .run();
+}
+
+#[test]
+fn rarities_with_only_inscribed_or_rare_sats() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-satoshis wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
+  ))
+  .write("hello.txt", "HELLOWORLD")
+  .rpc_server(&rpc_server)
+  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+  .run();
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--chain regtest --index-inscribed-sats stats rarities")
+    .rpc_server(&rpc_server)
+    .stdout_regex(r#".*\{"rarity":"uncommon","supply":2,"inscribed":1\}.*"#)
+    .run();
+
+  CommandBuilder::new("--chain regtest --index-rare-sats stats rarities")
+    .rpc_server(&rpc_server)
+    .stdout_regex(r#".*\{"rarity":"uncommon","supply":2,"inscribed":null\}.*"#)
+    .run();
+}
+
+#[test]
+fn epochs() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  rpc_server.mine_blocks(2);
+  rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(1, 0, 0)],
+    output_count: 1,
+    fee: 1000,
+  });
+  rpc_server.broadcast_tx(TransactionTemplate {
+    input_slots: &[(2, 0, 0)],
+    output_count: 1,
+    fee: 234,
+  });
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--chain regtest --index-sat-ranges stats epochs")
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!(
+      r#"[{{"epoch":0,"blocks":4,"subsidy":{},"fees":1234,"inscriptions":0}}]"#,
+      200 * COIN_VALUE
+    ))
+    .run();
+}
+
+#[test]
+fn epochs_without_sat_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--chain regtest stats epochs")
+    .rpc_server(&rpc_server)
+    .expected_stdout(format!(
+      r#"[{{"epoch":0,"blocks":2,"subsidy":{},"fees":null,"inscriptions":0}}]"#,
+      100 * COIN_VALUE
+    ))
+    .run();
+}
@@ -14,3 +14,37 @@ fn genesis() {
     )
     .run();
 }
+
+const SCHEDULE: &str = r#"{"schedule": {"initial_subsidy": 5000000000, "halving_interval": 2}}"#;
+
+#[test]
+fn custom_schedule() {
+  let mut supply = 0;
+  let mut height = 0;
+  while (50 * COIN_VALUE) >> (height / 2) > 0 {
+    supply += (50 * COIN_VALUE) >> (height / 2);
+    height += 1;
+  }
+
+  CommandBuilder::new("--chain custom --config config.json supply")
+    .write("config.json", SCHEDULE)
+    .expected_stdout(format!(
+      "supply: {supply}\nfirst: 0\nlast: {}\nlast mined in block: {}\n",
+      supply - 1,
+      height - 1
+    ))
+    .run();
+}
+
+#[test]
+fn schedule_override_refused_on_standard_chains() {
+  for chain in ["mainnet", "testnet", "signet"] {
+    CommandBuilder::new(format!("--chain {chain} --config config.json supply"))
+      .write("config.json", SCHEDULE)
+      .expected_exit_code(1)
+      .expected_stderr(format!(
+        "error: subsidy schedule may only be overridden with `--chain custom`, not on {chain}\n"
+      ))
+      .run();
+  }
+}
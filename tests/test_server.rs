@@ -3,18 +3,47 @@ use {
   crate::command_builder::ToArgs,
   bitcoincore_rpc::{Auth, Client, RpcApi},
   reqwest::blocking::Response,
+  std::{
+    io::{BufRead, BufReader},
+    process::ChildStderr,
+  },
 };
 
 pub(crate) struct TestServer {
   child: Child,
   port: u16,
-  #[allow(unused)]
+  request_log: Option<BufReader<ChildStderr>>,
   tempdir: TempDir,
   rpc_url: String,
 }
 
 impl TestServer {
   pub(crate) fn spawn_with_args(rpc_server: &test_bitcoincore_rpc::Handle, args: &[&str]) -> Self {
+    Self::spawn_with_server_args(rpc_server, args, &[])
+  }
+
+  pub(crate) fn spawn_with_server_args(
+    rpc_server: &test_bitcoincore_rpc::Handle,
+    args: &[&str],
+    server_args: &[&str],
+  ) -> Self {
+    Self::spawn(rpc_server, args, server_args, false)
+  }
+
+  /// Log requests to a pipe, which is read by `logged_request`.
+  pub(crate) fn spawn_with_request_log(
+    rpc_server: &test_bitcoincore_rpc::Handle,
+    server_args: &[&str],
+  ) -> Self {
+    Self::spawn(rpc_server, &[], server_args, true)
+  }
+
+  fn spawn(
+    rpc_server: &test_bitcoincore_rpc::Handle,
+    args: &[&str],
+    server_args: &[&str],
+    request_log: bool,
+  ) -> Self {
     let tempdir = TempDir::new().unwrap();
     fs::create_dir(tempdir.path().join("regtest")).unwrap();
     fs::write(tempdir.path().join("regtest/.cookie"), "foo:bar").unwrap();
@@ -24,16 +53,26 @@ impl TestServer {
       .unwrap()
       .port();
 
-    let child = Command::new(executable_path("ord")).args(format!(
-      "--chain regtest --rpc-url {} --bitcoin-data-dir {} --data-dir {} {} server --http-port {port} --address 127.0.0.1",
+    let mut command = Command::new(executable_path("ord"));
+
+    command.args(format!(
+      "--chain regtest --rpc-url {} --bitcoin-data-dir {} --data-dir {} {} server --http-port {port} --address 127.0.0.1 --index-update-interval 0.1 {}",
       rpc_server.url(),
       tempdir.path().display(),
       tempdir.path().display(),
       args.join(" "),
+      server_args.join(" "),
     ).to_args())
       .env("ORD_DISABLE_PROGRESS_BAR", "1")
-      .current_dir(&tempdir)
-      .spawn().unwrap();
+      .current_dir(&tempdir);
+
+    if request_log {
+      command
+        .env("RUST_LOG", "ord::request=info")
+        .stderr(Stdio::piped());
+    }
+
+    let mut child = command.spawn().unwrap();
 
     for i in 0.. {
       match reqwest::blocking::get(format!("http://127.0.0.1:{port}/status")) {
@@ -49,6 +88,7 @@ impl TestServer {
     }
 
     Self {
+      request_log: child.stderr.take().map(BufReader::new),
       child,
       tempdir,
       port,
@@ -56,6 +96,28 @@ impl TestServer {
     }
   }
 
+  /// The next line logged for a request for `path`.
+  pub(crate) fn logged_request(&mut self, path: &str) -> String {
+    let request_log = self.request_log.as_mut().unwrap();
+
+    let needle = format!("\"path\":\"{path}\"");
+
+    loop {
+      let mut line = String::new();
+      assert!(
+        request_log.read_line(&mut line).unwrap() > 0,
+        "request log ended"
+      );
+      if line.contains(&needle) {
+        return line;
+      }
+    }
+  }
+
+  pub(crate) fn index_path(&self) -> PathBuf {
+    self.tempdir.path().join("regtest/index.redb")
+  }
+
   pub(crate) fn url(&self) -> Url {
     format!("http://127.0.0.1:{}", self.port).parse().unwrap()
   }
@@ -99,6 +161,77 @@ impl TestServer {
   }
 }
 
+impl TestServer {
+  fn wallet_api_token(&self) -> String {
+    fs::read_to_string(self.tempdir.path().join("regtest/wallet-api-token")).unwrap()
+  }
+
+  pub(crate) fn wallet_api_get(&self, path: &str) -> serde_json::Value {
+    let response = reqwest::blocking::Client::new()
+      .get(self.url().join(path).unwrap())
+      .bearer_auth(self.wallet_api_token())
+      .send()
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_str(&response.text().unwrap()).unwrap()
+  }
+
+  /// Send a wallet API request over the Unix socket at `socket`, returning
+  /// the raw response.
+  #[cfg(unix)]
+  pub(crate) fn wallet_api_unix_get(&self, socket: &Path, path: &str) -> String {
+    use std::{
+      io::{Read, Write},
+      os::unix::net::UnixStream,
+    };
+
+    let mut stream = UnixStream::connect(socket).unwrap();
+    write!(
+      stream,
+      "GET {path} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\nConnection: close\r\n\r\n",
+      self.wallet_api_token(),
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+  }
+
+  pub(crate) fn wallet_api_post(&self, path: &str, body: serde_json::Value) -> Response {
+    reqwest::blocking::Client::new()
+      .post(self.url().join(path).unwrap())
+      .bearer_auth(self.wallet_api_token())
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(body.to_string())
+      .send()
+      .unwrap()
+  }
+}
+
+impl TestServer {
+  /// Send SIGTERM, as a service manager stopping the server would, and wait
+  /// for it to exit.
+  #[cfg(unix)]
+  pub(crate) fn terminate(&mut self) -> ExitStatus {
+    assert!(Command::new("kill")
+      .args(["-TERM", &self.child.id().to_string()])
+      .status()
+      .unwrap()
+      .success());
+
+    for _ in 0..400 {
+      if let Some(status) = self.child.try_wait().unwrap() {
+        return status;
+      }
+
+      thread::sleep(Duration::from_millis(50));
+    }
+
+    panic!("server did not exit after SIGTERM");
+  }
+}
+
 impl Drop for TestServer {
   fn drop(&mut self) {
     self.child.kill().unwrap()
@@ -15,7 +15,39 @@ epoch: 0
 period: 0
 offset: 0
 rarity: mythic
+coin: true
+alpha: true
+omega: false
+decimal-palindrome: true
+name-palindrome: false
 ",
     )
     .run();
 }
+
+#[test]
+fn json() {
+  CommandBuilder::new("traits --json 99999999")
+    .expected_stdout(
+      r#"{"number":99999999,"decimal":"0.99999999","degree":"0°0′0″99999999‴","name":"nvtdibkbilu","height":0,"cycle":0,"epoch":0,"period":0,"offset":99999999,"rarity":"common","traits":{"alpha":false,"coin":false,"decimal-palindrome":true,"name-palindrome":false,"omega":true}}"#,
+    )
+    .run();
+}
+
+#[test]
+fn time() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new("traits --time 0")
+    .rpc_server(&rpc_server)
+    .stdout_regex(".*rarity: mythic\n.*time: 2009-01-03 18:15:05\n")
+    .run();
+}
+
+#[test]
+fn expected_time() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  CommandBuilder::new(format!("traits --time --json {}", 100 * COIN_VALUE))
+    .rpc_server(&rpc_server)
+    .stdout_regex(r#".*"time":"expected circa 2009-01-03"}"#)
+    .run();
+}
@@ -6,3 +6,20 @@ fn version_flag_prints_version() {
     .stdout_regex("ord .*\n")
     .run();
 }
+
+#[test]
+fn verbose_version_flag_prints_build_metadata() {
+  CommandBuilder::new("--version --verbose")
+    .stdout_regex(
+      "ord .*\ncommit: .*\ndescribe: .*\nbuild date: .*\nfeatures: .*\nindex schema version: [0-9]+\n",
+    )
+    .run();
+}
+
+#[test]
+fn verbose_requires_version() {
+  CommandBuilder::new("--verbose index")
+    .expected_exit_code(2)
+    .stderr_regex(".*--version.*")
+    .run();
+}
@@ -1,41 +1,58 @@
-use {super::*, std::str::FromStr};
+use {
+  super::*,
+  bitcoin::{PackedLockTime, Script, Sequence, Transaction, TxIn, TxOut, Witness},
+  std::str::FromStr,
+};
 
 #[test]
 fn satoshis() {
-  let rpc_server = test_bitcoincore_rpc::spawn();
-  let second_coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
-
-  CommandBuilder::new("--index-satoshis wallet satoshis")
-    .rpc_server(&rpc_server)
-    .expected_stdout(format!(
-      "{}\t{}\t0\tuncommon\n",
-      OutPoint::new(second_coinbase, 0),
-      50 * COIN_VALUE,
-    ))
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet();
+
+  fixture
+    .ord("--index-satoshis wallet satoshis")
+    .expected_stdout(format!("{outpoint}\t{}\t0\tuncommon\n", 50 * COIN_VALUE))
+    .run();
+}
+
+#[test]
+fn satoshis_with_trait() {
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet();
+
+  fixture
+    .ord("--index-satoshis wallet sats --trait omega")
+    .expected_stdout(
+      (0..50)
+        .map(|i| {
+          format!(
+            "{outpoint}\t{}\t{}\n",
+            (51 + i) * COIN_VALUE - 1,
+            (i + 1) * COIN_VALUE - 1,
+          )
+        })
+        .collect::<String>(),
+    )
     .run();
 }
 
 #[test]
 fn satoshis_from_tsv_success() {
-  let rpc_server = test_bitcoincore_rpc::spawn();
-  let second_coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet();
 
-  CommandBuilder::new("--index-satoshis wallet satoshis --tsv foo.tsv")
+  fixture
+    .ord("--index-satoshis wallet satoshis --tsv foo.tsv")
     .write("foo.tsv", "nvtcsezkbtg")
-    .rpc_server(&rpc_server)
-    .expected_stdout(format!(
-      "{}\tnvtcsezkbtg\n",
-      OutPoint::new(second_coinbase, 0),
-    ))
+    .expected_stdout(format!("{outpoint}\tnvtcsezkbtg\n"))
     .run();
 }
 
 #[test]
 fn satoshis_from_tsv_parse_error() {
-  let rpc_server = test_bitcoincore_rpc::spawn();
-  CommandBuilder::new("wallet satoshis --tsv foo.tsv")
+  Fixture::new(Network::Bitcoin)
+    .ord("wallet satoshis --tsv foo.tsv")
     .write("foo.tsv", "===")
-    .rpc_server(&rpc_server)
     .expected_exit_code(1)
     .expected_stderr(
       "error: failed to parse sat from string \"===\" on line 1: invalid digit found in string\n",
@@ -45,9 +62,8 @@ fn satoshis_from_tsv_parse_error() {
 
 #[test]
 fn satoshis_from_tsv_file_not_found() {
-  let rpc_server = test_bitcoincore_rpc::spawn();
-  CommandBuilder::new("wallet satoshis --tsv foo.tsv")
-    .rpc_server(&rpc_server)
+  Fixture::new(Network::Bitcoin)
+    .ord("wallet satoshis --tsv foo.tsv")
     .expected_exit_code(1)
     .stderr_regex("error: I/O error reading `.*`\nbecause: .*\n")
     .run();
@@ -55,46 +71,48 @@ fn satoshis_from_tsv_file_not_found() {
 
 #[test]
 fn send_works_on_signet() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord");
-
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
-
-  let stdout = CommandBuilder::new(format!(
-    "--chain signet --index-satoshis wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 520])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
-
-  let reveal_txid = reveal_txid_from_inscribe_stdout(&stdout);
+  let fixture = Fixture::signet();
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = reveal_txid_from_inscribe_stdout(
+    &fixture
+      .ord(format!(
+        "--index-satoshis wallet inscribe --satpoint {outpoint}:0 --file degenerate.png"
+      ))
+      .write("degenerate.png", [1; 520])
+      .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+      .run(),
+  );
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
-  let stdout = CommandBuilder::new(format!(
-    "--chain signet wallet send {reveal_txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
-  ))
-  .rpc_server(&rpc_server)
-  .stdout_regex(r".*")
-  .run();
+  let stdout = fixture
+    .ord(format!(
+      "wallet send {reveal_txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
+    ))
+    .stdout_regex(r".*")
+    .run();
 
-  let txid = rpc_server.mempool()[0].txid();
+  let txid = fixture.rpc_server().mempool()[0].txid();
   assert_eq!(format!("{}\n", txid), stdout);
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
   let send_txid = stdout.trim();
 
-  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
-  ord_server.assert_response_regex(
+  fixture.server(&[]).assert_response_regex(
     &format!("/inscription/{reveal_txid}"),
     &format!(
       ".*<h1>Inscription {reveal_txid}</h1>
 <dl>
+  <dt>number</dt>
+  <dd>0</dd>
   <dt>content size</dt>
   <dd>520 bytes</dd>
   <dt>content type</dt>
   <dd>image/png</dd>
+  <dt>sha256</dt>
+  <dd class=monospace>[[:xdigit:]]{{64}}</dd>
   <dt>location</dt>
   <dd>{send_txid}:0:0</dd>
 </dl>
@@ -105,57 +123,116 @@ fn send_works_on_signet() {
 
 #[test]
 fn send_unknown_inscription() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord");
+  let fixture = Fixture::signet();
+  let txid = fixture.fund_wallet().txid;
 
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  fixture
+    .ord(format!(
+      "wallet send {txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
+    ))
+    .expected_stderr(format!("error: No inscription found for {txid}\n"))
+    .expected_exit_code(1)
+    .run();
+}
 
-  CommandBuilder::new(format!(
-    "--chain signet wallet send {txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
-  ))
-  .rpc_server(&rpc_server)
-  .expected_stderr(format!("error: No inscription found for {txid}\n"))
-  .expected_exit_code(1)
-  .run();
+#[test]
+fn send_by_inscription_id_prefix() {
+  let fixture = Fixture::signet();
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "degenerate.png", [1; 520]);
+
+  fixture.mine_blocks(1);
+
+  let stdout = fixture
+    .ord(format!(
+      "wallet send {} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw",
+      &reveal_txid.to_string()[..8]
+    ))
+    .stdout_regex("[[:xdigit:]]{64}\n")
+    .run();
+
+  fixture.mine_blocks(1);
+
+  fixture.server(&[]).assert_response_regex(
+    &format!("/inscription/{reveal_txid}"),
+    &format!(".*<dt>location</dt>\n  <dd>{}:0:0</dd>.*", stdout.trim()),
+  );
 }
 
 #[test]
-fn send_inscribed_sat() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+fn send_unknown_inscription_id_prefix() {
+  let fixture = Fixture::signet();
+  fixture.fund_wallet();
 
-  let stdout = CommandBuilder::new(format!(
-    "--chain signet --index-satoshis wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 520])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  fixture
+    .ord("wallet send 0123abcd tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw")
+    .expected_stderr("error: No inscription found for prefix 0123abcd\n")
+    .expected_exit_code(1)
+    .run();
+}
 
-  rpc_server.mine_blocks(1);
+#[test]
+fn send_reuses_rpc_connections() {
+  let fixture = Fixture::signet();
+  let outpoint = fixture.fund_wallet();
 
-  let reveal_txid = reveal_txid_from_inscribe_stdout(&stdout);
+  let connections = fixture.rpc_server().connections();
 
-  let stdout = CommandBuilder::new(format!(
-    "--chain signet wallet send {reveal_txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
-  ))
-  .rpc_server(&rpc_server)
-  .stdout_regex("[[:xdigit:]]{64}\n")
-  .run();
+  fixture
+    .ord(format!(
+      "wallet send {outpoint}:0 tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
+    ))
+    .stdout_regex("[[:xdigit:]]{64}\n")
+    .run();
+
+  // One client is held for the whole send, and the index borrows others.
+  // The block fetching thread may still be returning its client when the
+  // next is borrowed, so that may take a third.
+  assert!(fixture.rpc_server().connections() - connections <= 3);
+}
+
+#[test]
+fn send_inscribed_sat() {
+  let fixture = Fixture::signet();
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = reveal_txid_from_inscribe_stdout(
+    &fixture
+      .ord(format!(
+        "--index-satoshis wallet inscribe --satpoint {outpoint}:0 --file degenerate.png"
+      ))
+      .write("degenerate.png", [1; 520])
+      .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+      .run(),
+  );
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
+
+  let stdout = fixture
+    .ord(format!(
+      "wallet send {reveal_txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
+    ))
+    .stdout_regex("[[:xdigit:]]{64}\n")
+    .run();
+
+  fixture.mine_blocks(1);
 
   let send_txid = stdout.trim();
 
-  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
-  ord_server.assert_response_regex(
+  fixture.server(&[]).assert_response_regex(
     &format!("/inscription/{reveal_txid}"),
     &format!(
       ".*<h1>Inscription {reveal_txid}</h1>
 <dl>
+  <dt>number</dt>
+  <dd>0</dd>
   <dt>content size</dt>
   <dd>520 bytes</dd>
   <dt>content type</dt>
   <dd>image/png</dd>
+  <dt>sha256</dt>
+  <dd class=monospace>[[:xdigit:]]{{64}}</dd>
   <dt>location</dt>
   <dd>{send_txid}:0:0</dd>
 </dl>
@@ -166,73 +243,73 @@ fn send_inscribed_sat() {
 
 #[test]
 fn send_on_mainnnet_refuses_to_work_with_wallet_name_foo() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Bitcoin, "foo");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::with_wallet_name(Network::Bitcoin, "foo");
+  let outpoint = fixture.fund_wallet();
 
-  CommandBuilder::new(
-    format!("wallet send {txid}:0:0 bc1qzjeg3h996kw24zrg69nge97fw8jc4v7v7yznftzk06j3429t52vse9tkp9"),
-  )
-  .rpc_server(&rpc_server)
-  .expected_stderr("error: `ord wallet send` may only be used on mainnet with a wallet named `ord` or whose name starts with `ord-`\n")
-  .expected_exit_code(1)
-  .run();
+  fixture
+    .ord(format!(
+      "wallet send {outpoint}:0 bc1qzjeg3h996kw24zrg69nge97fw8jc4v7v7yznftzk06j3429t52vse9tkp9"
+    ))
+    .expected_stderr("error: `ord wallet send` may only be used on mainnet with a wallet named `ord` or whose name starts with `ord-`\n")
+    .expected_exit_code(1)
+    .run();
 }
 
 #[test]
 fn send_addresses_must_be_valid_for_network() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Bitcoin, "ord");
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 1_000_000)[0].txdata[0].txid();
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet_with(1_000_000);
 
-  CommandBuilder::new(format!(
-    "wallet send {txid}:0:0 tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
-  ))
-  .rpc_server(&rpc_server)
-  .expected_stderr(
-    "error: Address `tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw` is not valid for mainnet\n",
-  )
-  .expected_exit_code(1)
-  .run();
+  fixture
+    .ord(format!(
+      "wallet send {outpoint}:0 tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
+    ))
+    .expected_stderr(
+      "error: Address `tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw` is not valid for mainnet\n",
+    )
+    .expected_exit_code(1)
+    .run();
 }
 
 #[test]
 fn send_on_mainnnet_works_with_wallet_named_ord() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Bitcoin, "ord");
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 1_000_000)[0].txdata[0].txid();
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet_with(1_000_000);
 
-  let stdout = CommandBuilder::new(format!(
-    "wallet send {txid}:0:0 bc1qzjeg3h996kw24zrg69nge97fw8jc4v7v7yznftzk06j3429t52vse9tkp9"
-  ))
-  .rpc_server(&rpc_server)
-  .stdout_regex(r".*")
-  .run();
+  let stdout = fixture
+    .ord(format!(
+      "wallet send {outpoint}:0 bc1qzjeg3h996kw24zrg69nge97fw8jc4v7v7yznftzk06j3429t52vse9tkp9"
+    ))
+    .stdout_regex(r".*")
+    .run();
 
-  let txid = rpc_server.mempool()[0].txid();
+  let txid = fixture.rpc_server().mempool()[0].txid();
   assert_eq!(format!("{}\n", txid), stdout);
 }
 
 #[test]
 fn send_on_mainnnet_works_with_wallet_whose_name_starts_with_ord() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Bitcoin, "ord-foo");
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 1_000_000)[0].txdata[0].txid();
+  let fixture = Fixture::with_wallet_name(Network::Bitcoin, "ord-foo");
+  let outpoint = fixture.fund_wallet_with(1_000_000);
 
-  let stdout = CommandBuilder::new(format!(
-    "wallet send {txid}:0:0 bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"
-  ))
-  .rpc_server(&rpc_server)
-  .stdout_regex(r".*")
-  .run();
+  let stdout = fixture
+    .ord(format!(
+      "wallet send {outpoint}:0 bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"
+    ))
+    .stdout_regex(r".*")
+    .run();
 
-  let txid = rpc_server.mempool()[0].txid();
+  let txid = fixture.rpc_server().mempool()[0].txid();
   assert_eq!(format!("{}\n", txid), stdout);
 }
 
 #[test]
 fn send_on_mainnnet_refuses_to_work_with_wallet_with_high_balance() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Bitcoin, "ord");
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 1_000_001)[0].txdata[0].txid();
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet_with(1_000_001);
 
-  CommandBuilder::new(format!("wallet send {txid}:0:0 bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"))
-    .rpc_server(&rpc_server)
+  fixture
+    .ord(format!("wallet send {outpoint}:0 bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"))
     .expected_stderr(
       "error: `ord wallet send` may not be used on mainnet with wallets containing more than 1,000,000 sats\n",
     )
@@ -242,430 +319,615 @@ fn send_on_mainnnet_refuses_to_work_with_wallet_with_high_balance() {
 
 #[test]
 fn inscribe() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
 
-  let stdout = CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
-  ))
-  .write("hello.txt", "HELLOWORLD")
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
-  TestServer::spawn_with_args(&rpc_server, &["--index-satoshis"]).assert_response_regex(
+  fixture.server(&["--index-satoshis"]).assert_response_regex(
     "/sat/5000000000",
-    ".*<dt>inscription</dt>\n  <dd>HELLOWORLD</dd>.*",
+    &format!(".*<dt>inscription</dt>\n  <dd><a href=/inscription/{reveal_txid} .*"),
   );
 
-  TestServer::spawn_with_args(&rpc_server, &[]).assert_response_regex(
-    &format!("/inscription/{}", reveal_txid_from_inscribe_stdout(&stdout)),
-    ".*HELLOWORLD.*",
-  );
+  fixture
+    .server(&[])
+    .assert_response_regex(&format!("/preview/{reveal_txid}"), ".*HELLOWORLD.*");
 }
 
 #[test]
 fn inscribe_forbidden_on_mainnet() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Bitcoin, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet();
 
-  CommandBuilder::new(format!(
-    "wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
-  ))
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr("error: `ord wallet inscribe` is unstable and not yet supported on mainnet.\n")
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {outpoint}:0 --file hello.txt"
+    ))
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord wallet inscribe` is unstable and not yet supported on mainnet.\n")
+    .run();
 }
 
 #[test]
 fn inscribe_unknown_file_extension() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file pepe.jpg"
-  ))
-  .write("pepe.jpg", [1; 520])
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr("error: unrecognized file extension `.jpg`, only .txt and .png accepted\n")
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {outpoint}:0 --file pepe.jpg"
+    ))
+    .write("pepe.jpg", [1; 520])
+    .expected_exit_code(1)
+    .expected_stderr("error: unrecognized file extension `.jpg`, only .txt and .png accepted\n")
+    .run();
 }
 
 #[test]
-fn inscribe_png() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+fn inscribe_with_invalid_metadata() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
 
-  CommandBuilder::new(format!(
-    "--chain regtest --index-satoshis wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 520])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {outpoint}:0 --file hello.txt --metadata metadata.json"
+    ))
+    .write("hello.txt", "HELLOWORLD")
+    .write("metadata.json", r#"["pepes"]"#)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: invalid metadata in metadata.json\nbecause: metadata must be a JSON object\n",
+    )
+    .run();
+}
 
-  rpc_server.mine_blocks(1);
+#[test]
+fn inscribe_png() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = reveal_txid_from_inscribe_stdout(
+    &fixture
+      .ord(format!(
+        "--index-satoshis wallet inscribe --satpoint {outpoint}:0 --file degenerate.png"
+      ))
+      .write("degenerate.png", [1; 520])
+      .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+      .run(),
+  );
 
-  let ord_server = TestServer::spawn_with_args(&rpc_server, &["--index-satoshis"]);
+  fixture.mine_blocks(1);
 
-  ord_server.assert_response_regex(
+  fixture.server(&["--index-satoshis"]).assert_response_regex(
     "/sat/5000000000",
-    ".*<dt>inscription</dt>\n  <dd><img src=.*",
-  )
+    &format!(".*<dt>inscription</dt>\n  <dd><a href=/inscription/{reveal_txid} .*"),
+  );
+
+  fixture
+    .server(&[])
+    .assert_response_regex(&format!("/preview/{reveal_txid}"), ".*<img src=.*");
 }
 
 #[test]
 fn inscribe_exceeds_push_byte_limit() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::signet();
+  let outpoint = fixture.fund_wallet();
 
-  CommandBuilder::new(format!(
-    "--chain signet wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 1025])
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr(
-    "error: content size of 1025 bytes exceeds 1024 byte limit for signet inscriptions\n",
-  )
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {outpoint}:0 --file degenerate.png"
+    ))
+    .write("degenerate.png", [1; 1025])
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: content size of 1025 bytes exceeds 1024 byte limit for signet inscriptions\n",
+    )
+    .run();
 }
 
 #[test]
 fn regtest_has_no_content_size_limit() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
-
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 1025])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+  fixture.inscribe(outpoint, "degenerate.png", [1; 1025]);
 }
 
 #[test]
 fn inscribe_does_not_use_inscribed_sats_as_cardinal_utxos() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 800)[0].txdata[0].txid();
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 100])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet_with(800);
+  fixture.inscribe(outpoint, "degenerate.png", [1; 100]);
 
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 100)[0].txdata[0].txid();
+  let outpoint = fixture.fund_wallet_with(100);
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .rpc_server(&rpc_server)
-  .write("degenerate.png", [1; 100])
-  .expected_exit_code(1)
-  .expected_stderr("error: wallet does not contain enough cardinal UTXOs, please add additional funds to wallet.\n")
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {outpoint}:0 --file degenerate.png"
+    ))
+    .write("degenerate.png", [1; 100])
+    .expected_exit_code(1)
+    .expected_stderr("error: wallet does not contain enough cardinal UTXOs, please add additional funds to wallet.\n")
+    .run();
 }
 
 #[test]
 fn send_does_not_use_inscribed_sats_as_cardinal_utxos() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 800)[0].txdata[0].txid();
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 100])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet_with(800);
+  fixture.inscribe(outpoint, "degenerate.png", [1; 100]);
 
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 100)[0].txdata[0].txid();
+  let outpoint = fixture.fund_wallet_with(100);
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet send {txid}:0:0 bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x"
-  ))
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr("error: wallet does not contain enough cardinal UTXOs, please add additional funds to wallet.\n")
-  .run();
+  fixture
+    .ord(format!(
+      "wallet send {outpoint}:0 bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x"
+    ))
+    .expected_exit_code(1)
+    .expected_stderr("error: wallet does not contain enough cardinal UTXOs, please add additional funds to wallet.\n")
+    .run();
 }
 
 #[test]
 fn refuse_to_reinscribe_sats() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-
-  let txid = rpc_server.mine_blocks_with_subsidy(1, 800)[0].txdata[0].txid();
-  let stdout = CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 100])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet_with(800);
 
-  let first_inscription_id = reveal_txid_from_inscribe_stdout(&stdout);
+  let first_inscription_id = fixture.inscribe(outpoint, "degenerate.png", [1; 100]);
 
-  rpc_server.mine_blocks_with_subsidy(1, 100)[0].txdata[0].txid();
+  fixture.fund_wallet_with(100);
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {first_inscription_id}:0:0 --file hello.txt"
-  ))
-  .write("hello.txt", "HELLOWORLD")
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr(format!(
-    "error: sat at {first_inscription_id}:0:0 already inscribed\n"
-  ))
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {first_inscription_id}:0:0 --file hello.txt"
+    ))
+    .write("hello.txt", "HELLOWORLD")
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: sat at {first_inscription_id}:0:0 already inscribed\n"
+    ))
+    .run();
 }
 
 #[test]
 fn do_not_accidentally_send_an_inscription() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
-  let stdout = CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 100])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
 
-  let inscription_id = reveal_txid_from_inscribe_stdout(&stdout);
+  let inscription_id = fixture.inscribe(outpoint, "degenerate.png", [1; 100]);
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
   let inscription_utxo = OutPoint {
-    txid: reveal_txid_from_inscribe_stdout(&stdout),
+    txid: inscription_id,
     vout: 0,
   };
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet send {inscription_utxo}:55 bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x"
-  ))
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr(format!(
-    "error: cannot send {inscription_utxo}:55 without also sending inscription {inscription_id} at {inscription_utxo}:0\n"
-  ))
-  .run();
+  fixture
+    .ord(format!(
+      "wallet send {inscription_utxo}:55 bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x"
+    ))
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: cannot send {inscription_utxo}:55 without also sending inscription {inscription_id} at {inscription_utxo}:0\n"
+    ))
+    .run();
 }
 
 #[test]
 fn refuse_to_inscribe_already_inscribed_utxo() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
 
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
-  let stdout = CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file degenerate.png"
-  ))
-  .write("degenerate.png", [1; 100])
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
-
-  rpc_server.mine_blocks(1);
+  let inscription_id = fixture.inscribe(outpoint, "degenerate.png", [1; 100]);
 
-  let inscription_id = reveal_txid_from_inscribe_stdout(&stdout);
+  fixture.mine_blocks(1);
 
   let inscription_utxo = OutPoint {
-    txid: reveal_txid_from_inscribe_stdout(&stdout),
+    txid: inscription_id,
     vout: 0,
   };
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {inscription_utxo}:55555 --file hello.txt"
-  ))
-  .write("hello.txt", "HELLOWORLD")
-  .rpc_server(&rpc_server)
-  .expected_exit_code(1)
-  .expected_stderr(format!(
-    "error: utxo {inscription_utxo} already inscribed with inscription {inscription_id} on sat {inscription_utxo}:0\n",
-  ))
-  .run();
+  fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {inscription_utxo}:55555 --file hello.txt"
+    ))
+    .write("hello.txt", "HELLOWORLD")
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: utxo {inscription_utxo} already inscribed with inscription {inscription_id} on sat {inscription_utxo}:0\n",
+    ))
+    .run();
 }
 
 #[test]
 fn inscriptions_cannot_be_sent_by_satpoint() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
-
-  let stdout = CommandBuilder::new(format!(
-    "--chain regtest wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
-  ))
-  .write("hello.txt", "HELLOWORLD")
-  .rpc_server(&rpc_server)
-  .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-  .run();
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
 
-  let reveal_txid = reveal_txid_from_inscribe_stdout(&stdout);
+  let reveal_txid = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
-  CommandBuilder::new(format!(
-    "--chain regtest wallet send {reveal_txid}:0:0 bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x"
-  ))
-  .write("hello.txt", "HELLOWORLD")
-  .rpc_server(&rpc_server)
-  .expected_stderr("error: inscriptions must be sent by inscription ID\n")
-  .expected_exit_code(1)
-  .run();
+  fixture
+    .ord(format!(
+      "wallet send {reveal_txid}:0:0 bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x"
+    ))
+    .expected_stderr("error: inscriptions must be sent by inscription ID\n")
+    .expected_exit_code(1)
+    .run();
 }
 
 #[test]
 fn receive() {
-  let rpc_server = test_bitcoincore_rpc::spawn();
-
-  let stdout = CommandBuilder::new("wallet receive")
-    .rpc_server(&rpc_server)
+  let stdout = Fixture::new(Network::Bitcoin)
+    .ord("wallet receive")
     .stdout_regex(".*")
     .run();
 
   assert!(Address::from_str(stdout.trim()).is_ok());
 }
 
+#[test]
+fn receive_uri() {
+  let stdout = Fixture::new(Network::Bitcoin)
+    .ord("wallet receive --uri --amount 0.5")
+    .stdout_regex("bitcoin:.*\\?amount=0\\.5\n")
+    .run();
+
+  let address = stdout
+    .trim()
+    .strip_prefix("bitcoin:")
+    .unwrap()
+    .strip_suffix("?amount=0.5")
+    .unwrap();
+
+  assert!(Address::from_str(address).is_ok());
+}
+
+#[test]
+fn receive_amount_requires_uri() {
+  Fixture::new(Network::Bitcoin)
+    .ord("wallet receive --amount 0.5")
+    .stderr_regex(".*--uri.*")
+    .expected_exit_code(2)
+    .run();
+}
+
+#[test]
+fn receive_qr() {
+  let stdout = Fixture::new(Network::Bitcoin)
+    .ord("wallet receive --qr")
+    .stdout_regex(".*\n[ \u{2580}\u{2584}\u{2588}\n]+")
+    .run();
+
+  assert!(Address::from_str(stdout.lines().next().unwrap()).is_ok());
+}
+
 #[test]
 fn utxos() {
-  let rpc_server = test_bitcoincore_rpc::spawn();
+  let fixture = Fixture::new(Network::Bitcoin);
+  let outpoint = fixture.fund_wallet_with(1_000_000);
+
+  fixture
+    .ord("wallet utxos")
+    .expected_stdout(format!("{outpoint}\t1000000\n"))
+    .run();
+}
 
-  let coinbase_tx = &rpc_server.mine_blocks_with_subsidy(1, 1_000_000)[0].txdata[0];
-  let outpoint = OutPoint::new(coinbase_tx.txid(), 0);
-  let amount = coinbase_tx.output[0].value;
+#[test]
+fn connection_options_may_follow_wallet_subcommand() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet_with(1_000_000);
 
-  CommandBuilder::new("wallet utxos")
-    .rpc_server(&rpc_server)
-    .expected_stdout(format!("{outpoint}\t{amount}\n"))
+  CommandBuilder::new(format!(
+    "--regtest wallet utxos --rpc-url {} --cookie-file cookie",
+    fixture.rpc_server().url()
+  ))
+  .write("cookie", "username:password")
+  .expected_stdout(format!("{outpoint}\t1000000\n"))
+  .run();
+}
+
+#[test]
+fn utxos_include_outputs_of_broadcast_transactions() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet_with(1_000_000);
+
+  let txid = fixture.broadcast(Transaction {
+    version: 1,
+    lock_time: PackedLockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: outpoint,
+      script_sig: Script::new(),
+      sequence: Sequence::MAX,
+      witness: Witness::new(),
+    }],
+    output: vec![
+      TxOut {
+        value: 600_000,
+        script_pubkey: fixture.receive().script_pubkey(),
+      },
+      TxOut {
+        value: 400_000,
+        script_pubkey: fixture.receive().script_pubkey(),
+      },
+    ],
+  });
+
+  fixture.mine_blocks(1);
+
+  fixture
+    .ord("wallet utxos")
+    .stdout_regex(format!(
+      ".*{}\t600000\n{}\t400000\n.*",
+      OutPoint::new(txid, 0),
+      OutPoint::new(txid, 1),
+    ))
     .run();
 }
 
 #[test]
 fn inscriptions() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord-wallet");
-  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+  let fixture = Fixture::with_wallet_name(Network::Signet, "ord-wallet");
+  let outpoint = fixture.fund_wallet();
 
-  let inscription_id = reveal_txid_from_inscribe_stdout(
-    &CommandBuilder::new(format!(
-      "--chain signet wallet inscribe --satpoint {txid}:0:0 --file hello.txt"
-    ))
-    .write("hello.txt", "HELLOWORLD")
-    .rpc_server(&rpc_server)
-    .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
-    .run(),
-  );
+  let inscription_id = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
-  CommandBuilder::new("--chain signet wallet inscriptions")
-    .rpc_server(&rpc_server)
+  fixture
+    .ord("wallet inscriptions")
     .expected_stdout(format!("{inscription_id}\t{inscription_id}:0:0\n"))
     .run();
 
-  let stdout = CommandBuilder::new("--chain signet wallet receive")
-    .rpc_server(&rpc_server)
-    .expected_exit_code(0)
+  let address = fixture.receive();
+
+  let stdout = fixture
+    .ord(format!("wallet send {inscription_id} {address}"))
     .stdout_regex(".*")
     .run();
 
-  let address = stdout.trim();
-
-  let stdout = CommandBuilder::new(format!(
-    "--chain signet wallet send {inscription_id} {address}"
-  ))
-  .rpc_server(&rpc_server)
-  .expected_exit_code(0)
-  .stdout_regex(".*")
-  .run();
-
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
   let txid = Txid::from_str(stdout.trim()).unwrap();
 
   let outpoint = OutPoint::new(txid, 0);
 
-  CommandBuilder::new("--chain signet wallet inscriptions")
-    .rpc_server(&rpc_server)
+  fixture
+    .ord("wallet inscriptions")
     .expected_stdout(format!("{inscription_id}\t{outpoint}:0\n"))
     .run();
 }
 
+#[test]
+fn burned_inscriptions_are_flagged_and_not_listed() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+
+  let inscription_id = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+  let postage = fixture.rpc_server().mempool()[1].output[0].value;
+
+  fixture.mine_blocks(1);
+
+  let burn = fixture.broadcast(Transaction {
+    version: 1,
+    lock_time: PackedLockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: OutPoint::new(inscription_id, 0),
+      script_sig: Script::new(),
+      sequence: Sequence::MAX,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      value: postage,
+      script_pubkey: Script::new_op_return(&[]),
+    }],
+  });
+
+  fixture.mine_blocks(1);
+
+  fixture.ord("wallet inscriptions").run();
+
+  let server = fixture.server(&[]);
+
+  server.assert_response_regex(
+    &format!("/inscription/{inscription_id}"),
+    ".*<p class=burned>burned: sent to an output that can never be spent</p>.*",
+  );
+
+  let json = serde_json::from_str::<serde_json::Value>(
+    &reqwest::blocking::Client::new()
+      .get(
+        server
+          .url()
+          .join(&format!("/inscription/{inscription_id}"))
+          .unwrap(),
+      )
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()
+      .unwrap()
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(json["location"], format!("{burn}:0:0"));
+  assert_eq!(json["burned"], true);
+}
+
 #[test]
 fn inscribe_with_optional_satpoint_arg() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
-  rpc_server.mine_blocks(1);
+  let fixture = Fixture::regtest();
+  fixture.fund_wallet();
 
-  let stdout = CommandBuilder::new("--chain regtest wallet inscribe --file hello.txt")
+  let stdout = fixture
+    .ord("wallet inscribe --file hello.txt")
     .write("hello.txt", "HELLOWORLD")
-    .rpc_server(&rpc_server)
     .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
     .run();
 
-  rpc_server.mine_blocks(1);
+  let reveal_txid = reveal_txid_from_inscribe_stdout(&stdout);
+
+  fixture.mine_blocks(1);
 
-  TestServer::spawn_with_args(&rpc_server, &["--index-satoshis"]).assert_response_regex(
+  fixture.server(&["--index-satoshis"]).assert_response_regex(
     "/sat/5000000000",
-    ".*<dt>inscription</dt>\n  <dd>HELLOWORLD</dd>.*",
+    &format!(".*<dt>inscription</dt>\n  <dd><a href=/inscription/{reveal_txid} .*"),
   );
 
-  TestServer::spawn_with_args(&rpc_server, &[]).assert_response_regex(
-    &format!("/inscription/{}", reveal_txid_from_inscribe_stdout(&stdout)),
-    ".*HELLOWORLD.*",
-  );
+  fixture
+    .server(&[])
+    .assert_response_regex(&format!("/preview/{reveal_txid}"), ".*HELLOWORLD.*");
 }
 
 #[test]
-fn create() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Regtest, "ord");
+fn inscribe_skip_duplicates_does_not_reinscribe_identical_content() {
+  let fixture = Fixture::regtest();
+  fixture.fund_wallet();
+
+  let stdout = fixture
+    .ord("wallet inscribe --skip-duplicates --file hello.txt")
+    .write("hello.txt", "HELLOWORLD")
+    .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+    .run();
+
+  let commit = fixture.rpc_server().mempool()[0].txid();
+  let reveal = reveal_txid_from_inscribe_stdout(&stdout);
+
+  fixture.mine_blocks(1);
+
+  fixture
+    .ord("wallet inscribe --skip-duplicates --file hello.txt")
+    .write("hello.txt", "HELLOWORLD")
+    .expected_stdout(format!("duplicate\t{reveal}\n"))
+    .run();
 
-  assert!(!rpc_server.wallets().contains("ord"));
+  let stdout = fixture
+    .ord("wallet inscribe --skip-duplicates --file hello.txt --json")
+    .write("hello.txt", "HELLOWORLD")
+    .stdout_regex(".*")
+    .run();
 
-  CommandBuilder::new("--chain regtest wallet create")
-    .rpc_server(&rpc_server)
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&stdout).unwrap(),
+    serde_json::json!({
+      "commit": commit.to_string(),
+      "reveal": reveal.to_string(),
+      "inscription": reveal.to_string(),
+      "duplicate": true,
+    })
+  );
+
+  assert!(fixture.rpc_server().mempool().is_empty());
+
+  fixture.mine_blocks(1);
+
+  fixture
+    .ord("wallet inscriptions")
+    .expected_stdout(format!("{reveal}\t{reveal}:0:0\n"))
     .run();
 
-  assert!(rpc_server.wallets().contains("ord"));
+  fixture
+    .ord("wallet inscribe --skip-duplicates --file hello.txt")
+    .write("hello.txt", "GOODBYE")
+    .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+    .run();
 }
 
 #[test]
-fn transactions() {
-  let rpc_server = test_bitcoincore_rpc::spawn_with(Network::Signet, "ord");
-  rpc_server.mine_blocks(1);
+fn inscribe_skip_duplicates_detects_unconfirmed_inscription() {
+  let fixture = Fixture::regtest();
+  fixture.fund_wallet();
 
-  let stdout = CommandBuilder::new("--chain signet wallet inscribe --file degenerate.png")
-    .write("degenerate.png", [1; 520])
-    .rpc_server(&rpc_server)
+  let stdout = fixture
+    .ord("wallet inscribe --skip-duplicates --file hello.txt")
+    .write("hello.txt", "HELLOWORLD")
     .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
     .run();
 
-  let reveal_txid = reveal_txid_from_inscribe_stdout(&stdout);
+  let reveal = reveal_txid_from_inscribe_stdout(&stdout);
+
+  fixture
+    .ord("wallet inscribe --skip-duplicates --file hello.txt")
+    .write("hello.txt", "HELLOWORLD")
+    .expected_stdout(format!("duplicate\t{reveal}\n"))
+    .run();
+
+  assert_eq!(fixture.rpc_server().mempool().len(), 2);
+}
+
+#[test]
+fn inscribe_allows_duplicates_by_default() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+
+  fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  fixture.mine_blocks(1);
+
+  for flag in ["", "--allow-duplicates"] {
+    fixture
+      .ord(format!("wallet inscribe {flag} --file hello.txt"))
+      .write("hello.txt", "HELLOWORLD")
+      .stdout_regex("commit\t[[:xdigit:]]{64}\nreveal\t[[:xdigit:]]{64}\n")
+      .run();
+
+    assert_eq!(fixture.rpc_server().mempool().len(), 2);
+
+    fixture.mine_blocks(1);
+  }
+}
+
+#[test]
+fn skip_duplicates_conflicts_with_allow_duplicates() {
+  Fixture::regtest()
+    .ord("wallet inscribe --skip-duplicates --allow-duplicates --file hello.txt")
+    .stderr_regex(".*cannot be used with.*")
+    .expected_exit_code(2)
+    .run();
+}
+
+#[test]
+fn create() {
+  let fixture = Fixture::regtest();
+
+  assert!(!fixture.rpc_server().wallets().contains("ord"));
+
+  fixture.ord("wallet create").run();
+
+  assert!(fixture.rpc_server().wallets().contains("ord"));
+}
+
+#[test]
+fn transactions() {
+  let fixture = Fixture::signet();
+  let outpoint = fixture.fund_wallet();
+
+  let reveal_txid = fixture.inscribe(outpoint, "degenerate.png", [1; 520]);
 
-  CommandBuilder::new("--chain signet wallet transactions")
-    .rpc_server(&rpc_server)
+  fixture
+    .ord("wallet transactions")
     .stdout_regex(format!(".*{}\t0.*", reveal_txid))
     .run();
 
-  rpc_server.mine_blocks(1);
+  fixture.mine_blocks(1);
 
-  CommandBuilder::new("--chain signet wallet transactions")
-    .rpc_server(&rpc_server)
+  fixture
+    .ord("wallet transactions")
     .stdout_regex(format!(".*{}\t1\n.*", reveal_txid))
     .run();
 
-  let txid = CommandBuilder::new(format!(
-    "--chain signet wallet send {reveal_txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
-  ))
-  .rpc_server(&rpc_server)
-  .stdout_regex(r".*")
-  .run();
+  let txid = fixture
+    .ord(format!(
+      "wallet send {reveal_txid} tb1qx4gf3ya0cxfcwydpq8vr2lhrysneuj5d7lqatw"
+    ))
+    .stdout_regex(r".*")
+    .run();
 
-  CommandBuilder::new("--chain signet wallet transactions")
-    .rpc_server(&rpc_server)
+  fixture
+    .ord("wallet transactions")
     .stdout_regex(format!(".*{}\t0\n.*", txid.trim()))
     .run();
 }
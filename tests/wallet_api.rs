@@ -0,0 +1,229 @@
+use super::*;
+
+#[test]
+fn outputs_and_balance_match_cli() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet_with(1_000_000);
+
+  let server = fixture.wallet_api_server();
+
+  assert_eq!(
+    server.wallet_api_get("/api/wallet/outputs"),
+    serde_json::json!([{ "output": outpoint.to_string(), "amount": 1_000_000 }])
+  );
+
+  fixture
+    .ord("wallet utxos")
+    .expected_stdout(format!("{outpoint}\t1000000\n"))
+    .run();
+
+  assert_eq!(
+    server.wallet_api_get("/api/wallet/balance"),
+    serde_json::json!({ "cardinal": 1_000_000 })
+  );
+
+  fixture
+    .ord("wallet balance")
+    .expected_stdout("1000000\n")
+    .run();
+}
+
+#[test]
+fn inscribe_through_api_is_visible_to_cli() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+
+  let server = fixture.wallet_api_server();
+
+  let tempdir = TempDir::new().unwrap();
+  let file = tempdir.path().join("hello.txt");
+  fs::write(&file, "HELLOWORLD").unwrap();
+
+  let response = server.wallet_api_post(
+    "/api/wallet/inscribe",
+    serde_json::json!({ "file": file, "satpoint": format!("{outpoint}:0") }),
+  );
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let json: serde_json::Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  let mempool = fixture.rpc_server().mempool();
+  let commit = mempool[0].txid();
+  let reveal = mempool[1].txid();
+
+  assert_eq!(
+    json,
+    serde_json::json!({
+      "commit": commit.to_string(),
+      "reveal": reveal.to_string(),
+      "inscription": reveal.to_string(),
+      "duplicate": false,
+    })
+  );
+
+  fixture.mine_blocks(1);
+
+  fixture
+    .ord("wallet inscriptions")
+    .expected_stdout(format!("{reveal}\t{reveal}:0:0\n"))
+    .run();
+
+  assert_eq!(
+    server.wallet_api_get("/api/wallet/inscriptions"),
+    serde_json::json!([{
+      "inscription": reveal.to_string(),
+      "location": format!("{reveal}:0:0"),
+    }])
+  );
+
+  let balance = server.wallet_api_get("/api/wallet/balance")["cardinal"]
+    .as_u64()
+    .unwrap();
+
+  fixture
+    .ord("wallet balance")
+    .expected_stdout(format!("{balance}\n"))
+    .run();
+}
+
+#[test]
+fn inscribe_json_matches_api() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+
+  let stdout = fixture
+    .ord(format!(
+      "wallet inscribe --satpoint {outpoint}:0 --file hello.txt --json"
+    ))
+    .write("hello.txt", "HELLOWORLD")
+    .stdout_regex(".*")
+    .run();
+
+  let mempool = fixture.rpc_server().mempool();
+
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&stdout).unwrap(),
+    serde_json::json!({
+      "commit": mempool[0].txid().to_string(),
+      "reveal": mempool[1].txid().to_string(),
+      "inscription": mempool[1].txid().to_string(),
+      "duplicate": false,
+    })
+  );
+}
+
+#[test]
+fn send_through_api_is_visible_to_cli() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+
+  let inscription = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+
+  fixture.mine_blocks(1);
+
+  let server = fixture.wallet_api_server();
+
+  let response = server.wallet_api_post(
+    "/api/wallet/send",
+    serde_json::json!({
+      "outgoing": inscription.to_string(),
+      "address": fixture.receive().to_string(),
+    }),
+  );
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let json: serde_json::Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  let send = fixture.rpc_server().mempool()[0].txid();
+
+  assert_eq!(json, serde_json::json!({ "transaction": send.to_string() }));
+
+  fixture.mine_blocks(1);
+
+  fixture
+    .ord("wallet inscriptions")
+    .expected_stdout(format!("{inscription}\t{send}:0:0\n"))
+    .run();
+}
+
+#[test]
+fn send_errors_are_reported() {
+  let fixture = Fixture::regtest();
+  let txid = fixture.fund_wallet().txid;
+
+  let server = fixture.wallet_api_server();
+
+  let response = server.wallet_api_post(
+    "/api/wallet/send",
+    serde_json::json!({
+      "outgoing": txid.to_string(),
+      "address": fixture.receive().to_string(),
+    }),
+  );
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  assert_eq!(
+    response.text().unwrap(),
+    format!("No inscription found for {txid}")
+  );
+}
+
+#[test]
+fn wallet_api_requires_loopback_address() {
+  let fixture = Fixture::regtest();
+
+  fixture
+    .ord("server --address 0.0.0.0 --http-port 0 --enable-wallet-api")
+    .expected_stderr(
+      "error: `--enable-wallet-api` requires listening on a loopback address, such as `--address 127.0.0.1`, not `0.0.0.0`\n",
+    )
+    .expected_exit_code(1)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn wallet_api_over_unix_socket_requires_opt_in() {
+  let fixture = Fixture::regtest();
+
+  let tempdir = TempDir::new().unwrap();
+  let socket = tempdir.path().join("ord.sock");
+
+  let server = TestServer::spawn_with_server_args(
+    fixture.rpc_server(),
+    &[],
+    &[
+      "--enable-wallet-api",
+      "--listen-unix",
+      socket.to_str().unwrap(),
+    ],
+  );
+
+  let response = server.wallet_api_unix_get(&socket, "/api/wallet/balance");
+  assert!(
+    response.starts_with("HTTP/1.1 403 Forbidden\r\n"),
+    "{response}"
+  );
+  assert!(
+    response.ends_with(
+      "wallet API may only be used over the Unix socket with `--listen-unix-wallet-api`"
+    ),
+    "{response}"
+  );
+
+  drop(server);
+
+  let server = TestServer::spawn_with_server_args(
+    fixture.rpc_server(),
+    &[],
+    &[
+      "--enable-wallet-api",
+      "--listen-unix",
+      socket.to_str().unwrap(),
+      "--listen-unix-wallet-api",
+    ],
+  );
+
+  let response = server.wallet_api_unix_get(&socket, "/api/wallet/balance");
+  assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+}
@@ -0,0 +1,208 @@
+use {
+  super::*,
+  bitcoin::hashes::{hmac, sha256, Hash, HashEngine},
+  std::{
+    io::{BufRead, BufReader, Read, Write},
+    sync::{Arc, Mutex},
+  },
+};
+
+struct Request {
+  body: serde_json::Value,
+  signature: Option<String>,
+}
+
+/// Records the bodies and signatures of requests it receives, and responds
+/// to each with 200.
+struct Sink {
+  port: u16,
+  requests: Arc<Mutex<Vec<(String, Option<String>)>>>,
+}
+
+impl Sink {
+  fn spawn() -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let clone = requests.clone();
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut reader = BufReader::new(stream.unwrap());
+
+        let mut content_length = 0;
+        let mut signature = None;
+        loop {
+          let mut line = String::new();
+          reader.read_line(&mut line).unwrap();
+          let line = line.trim_end();
+          if line.is_empty() {
+            break;
+          }
+          if let Some((name, value)) = line.split_once(": ") {
+            match name.to_lowercase().as_str() {
+              "content-length" => content_length = value.parse().unwrap(),
+              "x-ord-signature" => signature = Some(value.to_string()),
+              _ => {}
+            }
+          }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        clone
+          .lock()
+          .unwrap()
+          .push((String::from_utf8(body).unwrap(), signature));
+
+        reader
+          .get_mut()
+          .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+          .unwrap();
+      }
+    });
+
+    Self { port, requests }
+  }
+
+  fn url(&self) -> String {
+    format!("http://127.0.0.1:{}/webhook", self.port)
+  }
+
+  /// The requests received so far, after checking that each is signed with
+  /// `secret`.
+  fn requests(&self, secret: &str) -> Vec<Request> {
+    self
+      .requests
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|(body, signature)| {
+        let mut engine = hmac::HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+        engine.input(body.as_bytes());
+        assert_eq!(
+          signature.as_deref(),
+          Some(
+            format!(
+              "sha256={}",
+              hex::encode(hmac::Hmac::from_engine(engine).into_inner())
+            )
+            .as_str()
+          )
+        );
+
+        Request {
+          body: serde_json::from_str(body).unwrap(),
+          signature: signature.clone(),
+        }
+      })
+      .collect()
+  }
+}
+
+#[test]
+fn index_posts_each_block_to_webhooks() {
+  let fixture = Fixture::regtest();
+  let outpoint = fixture.fund_wallet();
+  let inscription = fixture.inscribe(outpoint, "hello.txt", "HELLOWORLD");
+  fixture.mine_blocks(1);
+
+  let send = fixture
+    .ord(format!("wallet send {inscription} {}", fixture.receive()))
+    .stdout_regex(".*")
+    .run()
+    .trim()
+    .parse::<Txid>()
+    .unwrap();
+  let blocks = fixture.mine_blocks(1);
+
+  let a = Sink::spawn();
+  let b = Sink::spawn();
+
+  fixture
+    .ord(format!(
+      "index --webhook-url {} --webhook-url {} --webhook-secret foo",
+      a.url(),
+      b.url()
+    ))
+    .run();
+
+  let requests = a.requests("foo");
+
+  assert_eq!(requests.len(), 4);
+  assert_eq!(b.requests("foo").len(), 4);
+
+  for (height, request) in requests.iter().enumerate() {
+    assert!(request.signature.is_some());
+    assert_eq!(request.body["height"], height);
+  }
+
+  assert_eq!(
+    requests[2].body["inscriptions"],
+    serde_json::json!([{
+      "inscription": inscription,
+      "satpoint": format!("{inscription}:0:0"),
+    }])
+  );
+
+  assert_eq!(
+    requests[3].body,
+    serde_json::json!({
+      "height": 3,
+      "hash": blocks[0].block_hash(),
+      "inscriptions": [],
+      "transfers": [{
+        "inscription": inscription,
+        "from": format!("{inscription}:0:0"),
+        "to": format!("{send}:0:0"),
+      }],
+    })
+  );
+}
+
+#[test]
+fn server_posts_new_blocks_to_webhooks() {
+  let fixture = Fixture::regtest();
+  let sink = Sink::spawn();
+
+  let _server = TestServer::spawn_with_server_args(
+    fixture.rpc_server(),
+    &[],
+    &["--webhook-url", &sink.url(), "--webhook-secret", "foo"],
+  );
+
+  let block = fixture.mine_blocks(1).remove(0);
+
+  for i in 0.. {
+    let requests = sink.requests("foo");
+
+    if let Some(request) = requests.get(1) {
+      assert_eq!(
+        request.body,
+        serde_json::json!({
+          "height": 1,
+          "hash": block.block_hash(),
+          "inscriptions": [],
+          "transfers": [],
+        })
+      );
+      break;
+    }
+
+    if i == 400 {
+      panic!("webhook was not called for block 1");
+    }
+
+    thread::sleep(Duration::from_millis(25));
+  }
+}
+
+#[test]
+fn webhook_secret_requires_webhook_url() {
+  Fixture::regtest()
+    .ord("index --webhook-secret foo")
+    .stderr_regex("error: The following required arguments were not provided:.*--webhook-url.*")
+    .expected_exit_code(2)
+    .run();
+}